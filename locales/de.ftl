@@ -0,0 +1,608 @@
+# German text of the user interface. See src/i18n.rs.
+
+## Shared
+
+button-browse = Durchsuchen…
+button-cancel = Abbrechen
+button-choose = Auswählen…
+button-frame-minibrot = Minibrot einpassen
+button-frame-minibrot-hover = Zoomt auf das Minibrot der Periode { $period } am zuletzt gefundenen Zentrum, geschätzt { $size }-mal so groß wie die ganze Menge und um { $angle }° gedreht
+split-position = Position der Teilung
+duration-seconds = { $seconds } s
+duration-minutes = { $minutes } min { $seconds } s
+progress = { $percent } % · { $elapsed } vergangen
+progress-time-left = { $percent } % · { $elapsed } vergangen, noch { $left }
+error-unsupported-file = Dateityp wird nicht unterstützt
+filter-session = Sitzung
+filter-location = Ort
+filter-script = Rhai-Skript
+filter-recording = Aufzeichnung
+filter-text = Text
+
+## Menus
+
+menu-file = Datei
+menu-open-session = Sitzung öffnen…
+menu-save-session = Sitzung speichern…
+menu-import-location = Ort importieren…
+menu-export-location = Ort exportieren…
+menu-save-screenshot = Bildschirmfoto speichern
+menu-export-image = Bild exportieren…
+menu-export-palette = Palette exportieren…
+menu-export-palette-hover = Die Farben der Palette als GIMP- (.gpl) oder Adobe-Swatch-Exchange-Datei (.ase) speichern, für Bildbearbeitungen und andere Fraktalprogramme
+menu-export-svg = Überlagerungen als SVG exportieren…
+menu-batch-render = Stapelberechnung…
+menu-run-script = Skript ausführen…
+menu-copy-image = Bild kopieren
+menu-copy-permalink = Permalink kopieren
+menu-paste-permalink = Permalink einfügen
+menu-settings = Einstellungen…
+menu-diagnostics = Diagnose…
+menu-language = Sprache
+menu-view = Ansicht
+menu-bookmarks = Lesezeichen…
+menu-tour = Geführte Tour…
+menu-landmarks = Wegmarken der reellen Achse
+menu-landmarks-disabled = Nur für z² + c
+menu-annotate-landmarks = Wegmarken beschriften
+menu-annotations = Beschriftungen…
+menu-orbit-traps = Orbit-Fallen…
+menu-layers = Farbebenen…
+menu-riemann-sphere = Riemannsche Zahlenkugel…
+menu-height-map = Höhenkarte…
+menu-julia-morph = Julia-Morphing…
+menu-timeline = Zeitleiste…
+menu-recorder = Aufzeichnung…
+menu-ab-comparison = A/B-Vergleich…
+menu-magnifier = Lupe
+menu-magnifier-hover = Den Bereich unter dem Mauszeiger vergrößert zeigen
+menu-magnification = Vergrößerung
+menu-measure = Messen
+menu-measure-hover = In der Ansicht ziehen, um Abstände zu messen
+menu-distance-circle = Abstandskreis
+menu-distance-circle-hover = Einen Kreis um den Zeiger zeichnen, so groß wie sein geschätzter Abstand zum Rand der Menge. Der wahre Abstand liegt zwischen dem inneren, blasseren Kreis und dem äußeren.
+menu-animate-path = Pfad animieren
+menu-animate-path-hover = Einen Punkt Iteration für Iteration den Pfad entlang bewegen, der eine verblassende Spur hinterlässt. Beschränkte Pfade werden in einer Schleife abgespielt.
+menu-animation-speed = Tempo
+menu-animation-speed-unit = { " " }Iterationen/s
+menu-orbit-values = Orbitwerte
+menu-orbit-values-hover = Die ersten Punkte des Pfads mit ihren Werten beschriften. Zeigt man auf einen Punkt des Pfads, wird sein Wert angezeigt.
+menu-hold-zoom = Zoomen durch Halten
+menu-hold-zoom-hover = Zum Zeiger hineinzoomen, solange die Maustaste oder Z gedrückt ist, und mit zusätzlich gedrückter Umschalttaste oder X heraus, statt Punkte auszuwählen
+menu-hold-zoom-rate = Zoomtempo beim Halten
+menu-hold-zoom-rate-unit = × pro s
+menu-inset-zoom = Zoom des Einsatzes
+menu-inset-zoom-hover = Wie viel tiefer der Einsatz ist als die Ansicht, in der er angeheftet wurde
+menu-go-to-inset = Zum Einsatz gehen
+menu-remove-inset = Einsatz entfernen
+menu-map-to-lambda-plane = Auf λ-Ebene abbilden
+menu-map-to-c-plane = Auf c-Ebene abbilden
+menu-map-plane-hover = Zwischen z → z² + c und der logistischen Abbildung z → λz(1 − z) wechseln und dabei denselben Teil der Menge zeigen: c = λ/2 − λ²/4
+menu-analysis = Analyse
+menu-estimate-area = Fläche schätzen…
+menu-histogram = Iterationshistogramm…
+menu-compare-dem = Mit DEM vergleichen…
+menu-frame-statistics = Bildstatistik
+menu-find-detail = Details finden
+menu-start-autopilot = Autopilot starten
+menu-stop-autopilot = Autopilot anhalten
+menu-export-arrays = Arrays exportieren…
+menu-dimension = Box-Counting-Dimension…
+menu-tile-telemetry = Kachel-Telemetrie…
+context-pin-inset = Einsatz hier anheften
+context-component = Welche Komponente ist das?
+context-component-hover = Die hyperbolische Komponente finden, in der dieser Punkt liegt: ihre Periode, aus der Atomdomäne geschätzt, ihr Zentrum mit dem Newton-Verfahren und den inneren Winkel und Radius des Punktes aus dem Multiplikator seines Zyklus
+
+## Main window
+
+help-click = Klicken, um einen Pfad zu zeigen
+help-snap = Strg-Klick (⌘-Klick auf dem Mac) zeigt den exakten Zyklus des nächsten periodischen Punkts
+help-scroll = Mit dem Mausrad zoomen
+help-hover =
+    Die Mandelbrot-Menge entsteht durch Iteration der Gleichung z = z^2 + c, wobei c die komplexe Koordinate jedes Pixels ist. Punkte, die auch nach vielen Iterationen nicht ins Unendliche entkommen, gehören zur Menge und sind schwarz gefärbt; die übrigen sind danach gefärbt, wie schnell sie entkommen.
+
+    Nach einem Klick zeigt der gelbe Pfad die Folge der komplexen Werte z bei der Iteration für den gewählten Punkt. Verlässt der Pfad den Kreis mit Radius 2, gehört der Punkt nicht zur Mandelbrot-Menge.
+view-description = Mandelbrot-Menge um { $re } { $im }i, { $width } breit. Pfeiltasten verschieben, Plus und Minus zoomen, Eingabe zeigt den Pfad der Mitte
+drop-to-open = Zum Öffnen loslassen
+controls-formula = Formel:
+controls-exponent = Exponent:
+controls-exponent-hover = z → zᵃ⁺ᵇⁱ + c, mit dem Hauptzweig der Potenz
+controls-exponent-pad-hover = Den Exponenten auf einer Fläche der Exponentenebene wählen
+controls-cubic-a-hover = Koeffizient von z in z → z³ + az + c. Die kritischen Punkte sind ±√(−a/3); der Pfad des zweiten wird blau gezeichnet.
+controls-cubic-either = Einer entkommt
+controls-cubic-either-hover = Punkte entkommen, sobald einer der kritischen Orbits entkommt; gezeigt wird, wo beide beschränkt bleiben (der Zusammenhangsort)
+controls-cubic-both = Beide entkommen
+controls-cubic-both-hover = Punkte entkommen erst, wenn beide kritischen Orbits entkommen sind; gezeigt wird, wo einer beschränkt bleibt
+controls-quaternion-offset-hover = Verschiebung des gezeigten Schnitts entlang j und k: seine Punkte sind x + yi + uj + vk
+controls-quaternion-julia-hover = Statt der Mandelbrot-Menge die Julia-Menge eines festen Quaternions c zeigen, ausgehend von jedem Punkt des Schnitts. c beginnt beim ausgewählten Punkt und den Verschiebungen des Schnitts.
+controls-hybrid-pattern = Muster:
+controls-hybrid-pattern-hover = Die abwechselnd angewandten Abbildungen, wiederholt: M z² + c, B Burning Ship, T Tricorn, C Celtic. MMBB etwa macht zwei Schritte mit z² + c, dann zwei mit dem Burning Ship.
+controls-phoenix-p-hover = Gewicht der vorletzten Iterierten: zₙ₊₁ = zₙ² + c + p zₙ₋₁
+controls-nova-relaxation-hover = Relaxation, mit der jeder Newton-Schritt skaliert wird: z → z − R (zᵖ − 1) / (p zᵖ⁻¹) + c
+controls-nova-power-hover = Potenz von zᵖ − 1, dessen Nullstellen das Newton-Verfahren sucht
+controls-nova-julia = Julia
+controls-nova-julia-hover = Statt der Parameterebene die dynamische Ebene für ein festes c zeigen, ausgehend von jedem Punkt. c beginnt beim ausgewählten Punkt.
+controls-palette = Palette:
+controls-palette-layers = Ebenen
+controls-randomize-palette-hover = Eine neue Palette aus einem zufälligen Startwert erzeugen
+controls-palette-seed-hover = Startwert der Zufallspalette; derselbe Startwert ergibt immer dieselbe Palette
+controls-auto-contrast = Autokontrast
+controls-auto-contrast-hover = Die Palette über die Fluchtzeiten im Bild strecken statt bis zur Iterationsgrenze, damit tiefe Zooms alle ihre Farben bekommen
+controls-contrast-clip-hover = Anteil der Fluchtzeiten, der an beiden Enden ausgelassen wird, damit wenige Ausreißer den Rest nicht stauchen; 0 streckt vom kleinsten bis zum größten Wert
+controls-max-iterations = Max. Iterationen:
+controls-path-iterations = Pfad:
+controls-path-iterations-hover = Dem Pfad und seinen Statistiken eine eigene Iterationsgrenze geben, etwa viele Tausend, um einem langsam einschwingenden Orbit zu folgen, während das Bild einige Hundert verwendet
+controls-interior = Inneres:
+controls-interior-hover = Punkte der Menge nach ihrem Abstand zum Rand oder nach der Periode ihres anziehenden Zyklus färben
+controls-renderer = Renderer:
+renderer-auto = Automatisch ({ $name })
+controls-zoom = Zoom:
+controls-zoom-hover = Einen Faktor wie 1000x oder 1e6 eingeben und mit Enter um diesen Faktor in die Mitte hineinzoomen; Faktoren unter 1 zoomen heraus
+controls-surprise = Überrasch mich
+controls-surprise-hover = Zu einer zufälligen Stelle am Rand in zufälliger Tiefe gleiten; Stellen mit wenig Struktur werden übergangen
+controls-surprise-disabled = Nur für z² + c
+controls-field-lines = Feldlinien:
+controls-field-lines-hover = Kurven konstanten äußeren Winkels, gleichmäßig verteilt; 0 für keine
+controls-equipotentials = Äquipotentiallinien
+controls-equipotentials-hover = Kurven, auf denen die geglättete Fluchtzeit ganzzahlig ist
+controls-shading = Hangschattierung
+controls-shading-hover = Beleuchtet die Abstandsschätzung außerhalb der Menge als Relief, sodass Filamente geprägt wirken
+controls-lighting-hover = Richtung, Höhe und Stärke des Lichts
+controls-light-azimuth = Richtung
+controls-light-elevation = Höhe
+controls-light-strength = Stärke
+controls-occlusion = Verdeckung:
+controls-occlusion-hover = Verdunkelt, wo sich die Fluchtzeiten schnell ändern, als bekämen Falten weniger Licht; 0 für keine
+controls-quality = Qualität:
+controls-live-preview = GPU-Livevorschau
+controls-live-preview-hover = Beim Zoomen die Ansicht direkt auf der GPU zeichnen und sie richtig berechnen, sobald du anhältst. Nur für die eingebauten Paletten, ohne Färbung des Inneren oder Overlays, und bei geringer Zoomtiefe.
+controls-sliced = In Scheiben berechnen zu
+controls-sliced-hover = Die Ansicht auf dem Oberflächen-Thread Stück für Stück pro Bild berechnen und dabei schärfer werden lassen, damit das Fenster auch mit einem Kern oder im Web flüssig bleibt
+controls-frame-budget-hover = Zeit, die jedes Bild mit Berechnen verbringen darf. Weniger hält das Fenster flüssiger, mehr wird schneller fertig.
+quality-draft = Entwurf
+quality-normal = Normal
+quality-high = Hoch
+quality-ultra = Ultra
+quality-auto = Automatisch
+quality-auto-hover = Entwurf beim Zoomen oder Verschieben, Hoch, sobald du anhältst
+quality-hover = { $resolution } % Auflösung, { $samples } Abtastwerte pro Pixel, { $limit ->
+        [100] die Iterationsgrenze wie eingestellt
+       *[other] { $limit } % der Iterationsgrenze
+    }
+frame-stats =
+    In der Menge: { $interior } %
+    An max_iter: { $at_limit } %
+frame-stats-escape-time = Fluchtzeit: { $min } / { $median } / { $max } (Min. / Median / Max.)
+frame-stats-unresolved = { $percent } % ungeklärt: max_iter über { $max_iter } erhöhen
+measure-result =
+    Abstand: { $distance }
+    Pixel: { $pixels }
+    Mittelpunkt: { $re } { $im }i
+orbit-value = z{ $n } = { $re } { $im }i
+
+## Status bar
+
+status-bar-view = Mitte: { $re } { $im }i   Breite: { $width }
+cursor-outside = Zeiger: { $re } { $im }i   Abstand zur Menge: { $distance } ({ $pixels } px)
+cursor-inside = Zeiger: { $re } { $im }i   In der Menge, { $distance } ({ $pixels } px) von ihrem Rand
+cursor-unknown = Zeiger: { $re } { $im }i   Abstand zum Rand unbekannt, mehr Iterationen versuchen
+status-detail-found = In Details gezoomt ({ $entropy } Bit Entropie)
+status-snapped = Am Zentrum der Periode { $period } bei { $re } { $im }i eingerastet
+status-no-nucleus = Kein periodischer Punkt in der Nähe des Klicks gefunden
+status-component = In der Komponente der Periode { $period } mit Zentrum { $re } { $im }i, bei innerem Winkel { $angle } Umdrehungen und Radius { $radius }
+status-component-outside = Nicht in einer Komponente; die nächste hat Periode { $period } und Zentrum { $re } { $im }i (hier |λ| = { $radius })
+status-no-component = Keine hyperbolische Komponente für diesen Punkt gefunden
+status-framed-minibrot = Minibrot der Periode { $period } eingepasst; es ist gegenüber der ganzen Menge um { $angle }° gedreht, was die Ansicht nicht ausgleichen kann
+status-no-detail = Keine Details in der Ansicht gefunden
+status-surprise = Überraschung: { $zoom }-fache Vergrößerung ({ $entropy } Bit Entropie)
+status-no-surprise = Keine detailreiche Stelle gefunden; bitte noch einmal versuchen
+status-autopilot-on = Autopilot an: Taste drücken oder klicken zum Anhalten
+status-autopilot-stopped = Autopilot angehalten
+status-autopilot-failed = Autopilot angehalten: { $reason }
+autopilot-no-detail = keine Details mehr in der Ansicht
+autopilot-precision-limit = Grenze der 64-Bit-Genauigkeit erreicht
+status-zoom-factor-invalid = Kein Zoomfaktor: { $text }
+status-moved-to-inset = Zum Einsatz gewechselt
+status-mapped-to-lambda-plane = Zur entsprechenden Stelle der λ-Ebene gewechselt
+status-mapped-to-c-plane = Zur entsprechenden Stelle der c-Ebene gewechselt
+status-render-cancelled = Berechnung abgebrochen
+status-permalink-copied = Permalink in die Zwischenablage kopiert
+status-permalink-opened = Permalink geöffnet
+status-permalink-failed = Permalink konnte nicht geöffnet werden: { $error }
+status-clipboard-failed = Zwischenablage konnte nicht gelesen werden: { $error }
+status-image-copied = Bild in die Zwischenablage kopiert
+status-copy-failed = Bild konnte nicht kopiert werden: { $error }
+status-opened = { $name } geöffnet
+status-open-failed = { $name } konnte nicht geöffnet werden: { $error }
+status-saved = { $name } gespeichert
+status-gpu-failed = Berechnen auf der GPU ist fehlgeschlagen, die Ansicht wird jetzt auf der CPU berechnet ({ $renderer }): { $error }
+status-recovered = Sitzung von vor dem Absturz wiederhergestellt
+status-save-failed = { $name } konnte nicht gespeichert werden: { $error }
+status-exported = { $name } exportiert
+status-export-failed = { $name } konnte nicht exportiert werden: { $error }
+memory-export-refused = Nicht gestartet: Der Export braucht etwa { $needed } MB, aber von der Speichergrenze von { $cap } MB sind nur { $available } MB frei. Kleiner exportieren, auf andere Exporte warten oder die Grenze in den Einstellungen erhöhen.
+status-screenshot-saved = Bildschirmfoto { $path } gespeichert
+status-screenshot-failed = Bildschirmfoto { $path } konnte nicht gespeichert werden: { $error }
+status-script-output = { $name }: { $output }
+status-script-ran = { $name } ausgeführt
+status-script-failed = Skript { $name } fehlgeschlagen: { $error }
+status-plugins-failed = { $count ->
+        [one] Ein Plugin konnte nicht geladen werden, siehe Protokoll
+       *[other] { $count } Plugins konnten nicht geladen werden, siehe Protokoll
+    }
+status-remote-failed = Fernsteuerung konnte nicht gestartet werden: { $error }
+
+## Settings
+
+settings-title = Einstellungen
+settings-language = Sprache:
+settings-screenshot-folder = Ordner für Bildschirmfotos:
+settings-plugin-folder = Plugin-Ordner:
+settings-plugins-note = Plugins werden beim Start geladen.
+settings-remote = Fernsteuerung auf Port
+settings-remote-hover = WebSocket-Befehle von diesem Computer annehmen, siehe src/remote.rs
+settings-cache = Cache-Speicher:
+settings-cache-hover = Berechnete Bilder, die für die Rückkehr zu früheren Ansichten aufbewahrt werden
+settings-memory-cap = Speichergrenze:
+settings-memory-cap-hover = Höchstens so viel Speicher dürfen das gezeigte Bild, zwischengespeicherte Bilder und Exporte zusammen belegen. Der Cache schrumpft, um darunter zu bleiben, und Exporte, die sie überschreiten würden, werden abgelehnt.
+settings-threads = Ein Rechen-Thread pro Kern ({ $cores })
+settings-low-priority = Stapelberechnungen mit niedriger Priorität
+settings-low-priority-hover = Anderen Programmen während der Stapelberechnung den Vortritt lassen, damit ein langer Stapel den Computer nicht verlangsamt (nur Linux)
+settings-zoom-step = Zoomschritt:
+settings-zoom-step-hover = Wie weit eine Raste des Mausrads oder ein Druck auf + oder − hinein- oder herauszoomt
+settings-invert-wheel = Mausrad umkehren
+settings-zoom-about-cursor = Um den Zeiger zoomen
+settings-zoom-about-cursor-hover = Beim Zoomen mit dem Mausrad den Punkt unter dem Zeiger festhalten statt der Mitte der Ansicht
+settings-smooth-zoom = Weiches Zoomen mit dem Mausrad
+settings-smooth-zoom-hover = Im Verhältnis dazu zoomen, wie weit Mausrad oder Touchpad scrollen, und in jeden Schritt hineingleiten, statt bei jeder Raste um einen ganzen Schritt
+
+## Orbit panel
+
+orbit-title = Orbit
+orbit-dock = Andocken
+orbit-dock-hover = Im Hauptfenster zeigen
+orbit-pop-out = Abkoppeln
+orbit-pop-out-hover = In einem eigenen Fenster zeigen
+orbit-escape-time = Fluchtzeit
+orbit-no-escape = keine innerhalb von { $max_iter }
+orbit-converged = keine, konvergiert nach { $n }
+orbit-largest = Größtes |z|
+orbit-last = Letztes z
+orbit-turns = { $turns } Umdrehungen
+orbit-external-angle = Äußerer Winkel
+orbit-external-angle-hover = Winkel des äußeren Strahls durch c, aus der Flucht des Orbits. Am genauesten nahe am Rand.
+orbit-binary = Binär
+orbit-period = Periode
+orbit-period-unsettled = nicht eingeschwungen, mehr Iterationen versuchen
+orbit-multiplier = |Multiplikator|
+orbit-internal-angle = Innerer Winkel
+orbit-rotation-number = Rotationszahl
+orbit-rotation-number-hover = Wie weit die Abbildung den anziehenden Zyklus um den Fixpunkt α dreht. Für eine Knospe an der Hauptkardioide ist es deren innerer Winkel.
+orbit-interior-distance = Innerer Abstand
+orbit-interior-distance-hover = Geschätzter Abstand zum Rand der Menge. Der wahre Abstand liegt zwischen einem Viertel davon und diesem Wert.
+orbit-preperiod = Vorperiode
+orbit-preperiod-value = { $preperiod }, dann Periode { $period }
+orbit-preperiod-hover = Der Pfad wiederholt sich bis auf Rundungsfehler ab dieser Iteration, von z₀ = 0 an gezählt, mit dieser Periode. Er ist bis dahin gelb und ab dort orange gezeichnet. Punkte in der Menge zählen ab dann, wenn sie ihrem anziehenden Zyklus so nahe sind; Misiurewicz-Punkte landen genau auf einem abstoßenden Zyklus.
+orbit-highlight-period = Periode { $period } in der Ansicht hervorheben
+orbit-highlight-period-hover = Die Pixel der Ansicht tönen, deren Zyklus dieselbe Periode hat, sodass die hyperbolischen Komponenten dieser Periode hervortreten
+orbit-sparklines = Re und Im von zₙ
+orbit-sparkline-value = n = { $n }: { $value }
+real-dynamics = Reelle Dynamik
+real-dynamics-map = x → x² + c mit c = { $c }
+real-dynamics-cobweb = Spinnwebdiagramm ab x = 0:
+real-dynamics-bifurcation = Bifurkationsdiagramm, c an der roten Linie:
+
+## Analysis windows
+
+histogram-title = Iterationshistogramm
+histogram-log-scale = Logarithmische Anzahlen
+histogram-interior = In der Menge: { $pixels } Pixel ({ $percent } %)
+histogram-highlighted = Hervorgehoben: Fluchtzeiten { $from }–{ $to }, { $pixels } Pixel
+histogram-bar = Fluchtzeiten { $from }–{ $to }: { $pixels } Pixel
+histogram-hint = Auf einen Balken klicken, um seine Pixel hervorzuheben.
+compare-title = Mit DEM vergleichen
+compare-split = Geteilt
+compare-blend = Überblendet
+compare-opacity = Deckkraft des DEM
+compare-hint = Links oder darunter: Fluchtzeit. Rechts oder darüber: Abstandsschätzung, die auch Filamente dünner als ein Pixel zeigt.
+area-title = Flächenschätzung
+area-current-view = Aktuelle Ansicht
+area-whole-set = Ganze Menge
+area-samples = Stichproben:
+area-seed = Startwert:
+area-stop = Anhalten
+area-estimate = Schätzen
+area-area = Fläche
+area-interval = 95-%-Intervall
+area-in-set = In der Menge
+area-hits = { $hits } von { $samples }
+area-hint = Punkte, die nach { $max_iter } Iterationen noch beschränkt sind, zählen als Teil der Menge, daher ist die Schätzung etwas zu hoch.
+dimension-title = Box-Counting-Dimension
+dimension-resolution = Auflösung:
+dimension-analyze = Ansicht analysieren
+dimension-hint = Zählt die Kästchen, durch die der Rand der Menge in der Ansicht verläuft.
+dimension-result = Dimension ≈ { $dimension }   (R² = { $r_squared })
+dimension-no-boundary = Kein Rand in der Ansicht
+dimension-x-axis = ln(1 / Kästchenseite)
+dimension-y-axis = ln(Kästchen)
+telemetry-title = Kachel-Telemetrie
+telemetry-tile-size = Kachelgröße:
+telemetry-heat-map = Heatmap anzeigen
+telemetry-heat-map-hover = Färbt jede Kachel der Ansicht von Blau (schnell) bis Gelb (langsam) und umrandet die langsamsten
+telemetry-measure = Ansicht messen
+telemetry-measure-hover = Berechnet das gezeigte Bild Kachel für Kachel neu und misst jede Kachel auf einem Thread
+telemetry-hint = Misst, wie lange jeder Teil der Ansicht zum Berechnen braucht.
+telemetry-stale = An einer anderen Ansicht gemessen; erneut messen, um die Heatmap zu sehen.
+telemetry-summary = { $tiles } Kacheln bei { $max_iter } Iterationen: { $total } ms Berechnung, { $elapsed } ms insgesamt
+telemetry-slowest = Langsamste Kacheln:
+telemetry-tile = Kachel bei ({ $x }, { $y })
+
+## View tools
+
+ab-title = A/B-Vergleich
+ab-keep = Aktuelle als A behalten
+ab-keep-hover = Dann Palette, Iterationsgrenze oder Renderer ändern
+ab-a = A (links): { $setup }
+ab-a-none = A (links): noch nicht behalten
+ab-b = B (rechts): { $setup }
+ab-setup = { $palette }, { $max_iter } Iterationen, { $renderer }
+ab-hint = Die Linie in der Ansicht ziehen, um die Teilung zu verschieben.
+bookmarks-title = Lesezeichen
+bookmarks-name = Name:
+bookmarks-add = Aktuelle Ansicht hinzufügen
+bookmarks-import = Liste importieren…
+bookmarks-import-hover = Die Ansichten einer Textdatei mit je einer Zeile „Name re im Breite“ hinzufügen, wie Sammlungen von Orten oft weitergegeben werden
+bookmarks-imported = { $count ->
+        [one] Ein Lesezeichen aus { $name } importiert
+       *[other] { $count } Lesezeichen aus { $name } importiert
+    }
+bookmarks-import-failed = { $name } konnte nicht importiert werden: { $error }
+bookmarks-location = { $re } { $im }i, Breite { $width }
+bookmarks-remove = Entfernen
+bookmarks-remove-one = { $name } entfernen
+bookmarks-remove-all = Alle entfernen
+bookmarks-none = Noch keine Lesezeichen.
+annotations-title = Beschriftungen
+annotations-place = Platzieren:
+annotations-tool-none = Nichts
+annotations-tool-label = Text
+annotations-tool-arrow = Pfeil
+annotations-tool-marker = Markierung
+annotations-text = Text:
+annotations-label-hint = In die Ansicht klicken, um den Text zu platzieren.
+annotations-arrow-hint = In der Ansicht vom Ende des Pfeils zu seiner Spitze ziehen.
+annotations-marker-hint = In die Ansicht klicken, um eine Markierung zu platzieren.
+annotations-place-center = In der Mitte platzieren
+annotations-remove = Entfernen
+annotations-remove-one = { $annotation } entfernen
+annotations-remove-all = Alle entfernen
+annotations-none = Noch keine Beschriftungen.
+annotation-label = Text „{ $text }“ bei { $at }
+annotation-arrow = Pfeil { $from } → { $to }
+annotation-marker = Markierung bei { $at }
+traps-title = Orbit-Fallen
+traps-place = Platzieren:
+traps-tool-none = Nichts
+traps-tool-point = Punkt
+traps-tool-line = Linie
+traps-tool-circle = Kreis
+traps-move-hint = Die Griffe einer Falle in der Ansicht ziehen, um sie zu verschieben.
+traps-point-hint = In die Ansicht klicken, um einen Punkt zu platzieren.
+traps-line-hint = In der Ansicht von einem Ende der Linie zum anderen ziehen.
+traps-circle-hint = In der Ansicht vom Mittelpunkt des Kreises zu seinem Umfang ziehen.
+traps-presets = Vorlagen:
+traps-preset-unit-circle = Einheitskreis
+traps-preset-axes = Achsen
+traps-preset-origin = Ursprung
+traps-preset-gaussian = Gaußsche Zahlen
+traps-preset-gaussian-hover = Ein Gitter von Punkten im Abstand eins: Bahnen werden am nächsten Punkt m + ni gemessen, die klassische Falle der gaußschen Zahlen. Der Abstand lässt sich in der Liste oder durch Ziehen am Griff einstellen.
+traps-spacing-hover = Abstand benachbarter Gitterpunkte
+traps-width = Leuchtbreite
+traps-width-hover = Wie weit ein Orbit an einer Falle vorbeigehen darf, um sein Pixel noch zu tönen
+traps-remove = Entfernen
+traps-remove-one = { $trap } entfernen
+traps-remove-all = Alle entfernen
+traps-none = Noch keine Fallen. Pixel außerhalb der Menge werden danach getönt, wie nah ihr Orbit an den Fallen vorbeigeht.
+layers-title = Farbebenen
+layers-add = Ebene hinzufügen
+layers-remove-all = Alle entfernen
+layers-none = Noch keine Ebenen. Solange eine Ebene sichtbar ist, färben die Ebenen die Ansicht anstelle der Palette und der Tönung durch Orbit-Fallen.
+layers-order-hint = Obere Ebenen werden über die darunter gemischt.
+layers-visible-hover = Ebene anzeigen
+layers-raise = Nach oben
+layers-lower = Nach unten
+layers-remove = Entfernen
+layers-density-hover = Streifen pro Umlauf des Arguments der Iterierten
+layers-opacity-hover = Wie viel von der Ebene zu sehen ist
+sphere-title = Riemannsche Zahlenkugel
+sphere-hint = Zum Drehen ziehen. 0 liegt am Südpol, unendlich am Nordpol.
+sphere-reset = Zurücksetzen
+height-map-title = Höhenkarte
+height-map-height = Höhe
+height-map-reset = Zurücksetzen
+height-map-hint = Zum Drehen ziehen, zum Heranfahren scrollen. Die Höhe ist die geglättete Fluchtzeit auf logarithmischer Skala, mit der Menge obenauf.
+height-map-camera-path = Kamera:
+height-map-turntable = Drehteller
+height-map-flyover = Überflug
+height-map-export = Video exportieren
+height-map-export-hover = Die Kamera als Video rendern, die um die Oberfläche kreist oder auf sie herabfliegt, drei Viertel so hoch wie breit
+
+playback-play = Abspielen
+playback-pause = Pause
+playback-loop = Wiederholen
+
+julia-morph-title = Julia-Morphing
+julia-morph-draw = ✏ Zeichnen
+julia-morph-draw-hover = Auf der Ansicht ziehen, um den Weg zu zeichnen, den c entlangläuft
+julia-morph-add-point = Ausgewählten Punkt hinzufügen
+julia-morph-add-point-hover = Den Punkt, dessen Bahn angezeigt wird, am Ende des Wegs anfügen
+julia-morph-use-path = Bahn des ausgewählten Punkts verwenden
+julia-morph-use-path-hover = c entlang der Iterierten des ausgewählten Punkts laufen lassen
+julia-morph-clear = Leeren
+julia-morph-no-path = Einen Weg auf der Ansicht zeichnen oder Punkte hinzufügen, um die Julia-Menge entlang des Wegs sich verändern zu sehen.
+julia-morph-c = c = { $re } { $im }i
+julia-morph-connected = Zusammenhängend
+julia-morph-dust = Cantor-Staub
+julia-morph-connectivity-hover = Die Julia-Menge ist zusammenhängend, wenn der Orbit des kritischen Punkts 0 beschränkt bleibt, also wenn c in der Mandelbrot-Menge liegt, und zerfällt sonst in überabzählbar viele Punkte. Entschieden mit der Iterationsgrenze, daher brauchen Punkte nahe am Rand eventuell mehr Iterationen.
+julia-morph-show-orbit = Kritischen Orbit zeigen
+julia-morph-show-orbit-hover = c in der Ansicht auswählen, um den entscheidenden Orbit von 0 als Pfad zu zeichnen
+julia-morph-show-orbit-disabled = Nur für die Formel z² + c
+julia-morph-duration = Dauer:
+julia-morph-export = Video exportieren
+
+timeline-title = Zeitleiste
+timeline-add = Schlüsselbild hinzufügen
+timeline-add-hover = Das, was die Ansicht gerade zeigt, mit der Formel und ihren Parametern als Schlüsselbild nach dem letzten hinzufügen
+timeline-remove = Schlüsselbild entfernen
+timeline-go-to = Anzeigen
+timeline-go-to-hover = Dieses Schlüsselbild in der Ansicht anzeigen
+timeline-keyframe = { $formula }, Zoom { $zoom }, { $max_iter } Iterationen
+timeline-none = Noch keine Schlüsselbilder. Die Ansicht so hinzufügen, wie sie ist, sie oder die Parameter der Formel ändern und ein weiteres hinzufügen.
+timeline-export = Video exportieren
+
+landmark-cusp = Spitze (c = 1/4)
+landmark-cusp-explanation = Die Spitze der Hauptkardioide: Der Fixpunkt von x² + c hört auf anzuziehen, und dahinter entkommt jeder Orbit.
+landmark-doubling = Periode { $from } → { $to }
+landmark-doubling-explanation = Periodenverdopplung: Der anziehende Zyklus der Periode { $from } wird instabil und einer der Periode { $to } übernimmt. Die Abstände zwischen den Verdopplungen schrumpfen jeweils etwa um den Faktor 4,669.
+landmark-feigenbaum = Feigenbaum-Punkt
+landmark-feigenbaum-explanation = c ≈ −1,40116, wo sich die Periodenverdopplungen häufen und das Chaos beginnt; die Menge sieht um ihn herum in jeder Vergrößerung gleich aus.
+landmark-period-three = Periode-3-Fenster
+landmark-period-three-explanation = c = −7/4, wo aus dem Chaos ein Zyklus der Periode 3 hervorgeht: die Spitze des größten Mini-Mandelbrots auf der reellen Achse.
+landmark-tip = Ende (c = −2)
+landmark-tip-explanation = Das linke Ende der Menge, wo der Orbit von 0 nach zwei Schritten auf dem abstoßenden Fixpunkt 2 landet.
+
+tour-title = Geführte Tour
+tour-previous = Vorheriger Halt
+tour-next = Nächster Halt
+tour-on-the-way = Unterwegs zu: { $name }…
+tour-whole-set = Die Mandelbrot-Menge
+tour-whole-set-caption = Die Punkte c, für die z → z² + c, von 0 aus gestartet, beschränkt bleibt. Die Herzform ist die Hauptkardioide, in der der Orbit gegen einen Fixpunkt strebt; die Scheibe links davon ist die Periode-2-Knospe.
+tour-seahorse-valley = Seepferdchental
+tour-seahorse-valley-caption = Die Spalte zwischen Hauptkardioide und Periode-2-Knospe, gesäumt von eingerollten Schwänzen. In jeden hineinzuzoomen findet Spiralen in Spiralen.
+tour-elephant-valley = Elefantental
+tour-elephant-valley-caption = Nahe der Spitze der Hauptkardioide reihen sich Knospen mit Rüsseln wie eine Elefantenherde auf, jede kleiner als die vorige.
+tour-minibrot = Mini-Mandelbrot bei −1,75
+tour-minibrot-caption = Eine kleine Kopie der ganzen Menge auf der reellen Achse, um ein Zentrum der Periode 3. Solche Kopien gibt es überall am Rand, jede mit eigener Antenne.
+tour-feigenbaum = Feigenbaum-Punkt
+tour-feigenbaum-caption = Bei c ≈ −1,4012 häufen sich die Knospen der Perioden 2, 4, 8, … entlang der reellen Achse, jede etwa 4,669-mal kleiner als die vorige: die Feigenbaum-Konstante, dieselbe für jede Abbildung mit einem einzigen Buckel.
+tour-spiral = Doppelspirale
+tour-spiral-caption = Tief im Seepferdchental windet sich der Rand in Spiralen um Misiurewicz-Punkte, an denen der Orbit nach wenigen Schritten auf einem abstoßenden Zyklus landet.
+
+recorder-title = Aufzeichnung
+recorder-record = ⏺ Aufzeichnen
+recorder-record-hover = Ab jetzt jede Änderung der Ansicht, der Iterationsgrenze und der Formel aufzeichnen, um sie später abzuspielen
+recorder-stop = ⏹ Stopp
+recorder-recording = Aufzeichnung läuft · { $seconds } s, { $steps } Schritte
+recorder-recorded = { $seconds } s, { $steps } Schritte
+recorder-replay = ▶ Abspielen
+recorder-replay-hover = Die Aufzeichnung in der Ansicht Schritt für Schritt so abspielen, wie sie geschah
+recorder-stop-replay = ⏹ Abspielen beenden
+recorder-none = Noch nichts aufgezeichnet.
+recorder-save = Speichern…
+recorder-open = Öffnen…
+recorder-export = Video exportieren
+recorder-export-hover = Die Aufzeichnung als flüssige Animation rendern, die zwischen den aufgezeichneten Schritten gleitet
+recorder-unsorted = Die Schritte der Aufzeichnung sind nicht zeitlich geordnet
+
+video-fps = Bildrate:
+video-export-hover = Schreibt jedes Bild als PNG in den Ausgabeordner und fügt sie zu video.mp4 zusammen, wenn ffmpeg installiert ist
+video-progress = { $done } / { $total } Bilder · { $progress }
+video-paused = Pausiert, solange die Ansicht benutzt wird
+video-done = { $name } geschrieben
+video-frames-only = { $total } Bilder nach { $folder } geschrieben; mit installiertem ffmpeg würden sie zu einem Video zusammengefügt
+video-cancelled = Nach { $done } Bildern abgebrochen, die in { $folder } liegen
+video-failed = Das Video konnte nicht geschrieben werden: { $error }
+trap-point = Punkt bei { $at }
+trap-line = Linie { $from } – { $to }
+trap-circle = Kreis um { $center }, Radius { $radius }
+trap-lattice = Gitter, Abstand { $spacing }
+
+## Batch rendering
+
+batch-title = Stapelberechnung
+batch-list = Liste der Orte:
+batch-list-hover = Ein Ort pro Zeile: ein Permalink oder `re im scale [max_iter]`. Zeilen, die mit # beginnen, werden ignoriert.
+batch-no-list = (keine)
+batch-output-folder = Ausgabeordner:
+batch-size = Größe:
+batch-progress = { $done } / { $total } · { $progress }
+batch-paused = Pausiert, während die Ansicht benutzt wird
+batch-render = Berechnen
+batch-loaded = { $count ->
+        [one] Ein Ort
+       *[other] { $count } Orte
+    }
+batch-read-failed = { $name } konnte nicht gelesen werden: { $error }
+batch-done = { $done } von { $total } Orten berechnet
+batch-done-failures =
+    { $done } von { $total } Orten berechnet, { $failed } fehlgeschlagen:
+    { $failures }
+
+## Array export
+
+arrays-title = Arrays exportieren
+arrays-iterations = Fluchtzeiten
+arrays-smooth = Glatte Fluchtzeiten
+arrays-distance = Abstandsschätzungen
+arrays-orbit-min = Kleinstes |z| entlang des Orbits
+arrays-orbit-mean = Mittleres |z| entlang des Orbits
+arrays-shape = Arrays aus { $height } × { $width } Werten, das gezeigte Bild, als NumPy-.npy-Dateien
+arrays-export = Exportieren
+arrays-exporting = Wird exportiert…
+arrays-done = { $count } Arrays nach { $folder } exportiert
+arrays-failed = Export fehlgeschlagen: { $error }
+
+## Image export
+
+image-export-title = Bild exportieren
+image-export-format = Format:
+image-format-png = PNG, 8 Bit
+image-format-png16 = PNG, 16 Bit
+image-format-tiff16 = TIFF, 16 Bit
+image-export-linear = Lineares Licht
+image-export-linear-hover = Lichtstärken statt sRGB-Werten speichern, wie es Compositing und andere lineare Arbeitsabläufe erwarten. In gewöhnlichen Bildbetrachtern wirkt das Bild dunkel.
+image-export-dither = Dithering:
+image-export-dither-hover = 8-Bit-PNGs nach der geglätteten Fluchtzeit färben und beim Runden auf 8 Bit dithern, damit lange, feine Verläufe keine Streifen zeigen
+dither-none = Keins
+dither-ordered = Geordnet
+dither-blue-noise = Blaues Rauschen
+image-export-save = Exportieren…
+image-export-rendering = Wird berechnet…
+
+## SVG export
+
+svg-export-title = Überlagerungen als SVG exportieren
+svg-export-size-hint = Die Grafik deckt sich mit einem Bild der Ansicht in dieser Größe, etwa einem Bildschirmfoto.
+svg-export-path = Pfad
+svg-export-annotations = Anmerkungen
+svg-export-axes = Achsen
+svg-export-grid = Gitter
+svg-export-outlines = Kardioide und Periode-2-Kreis
+svg-export-save = Speichern…
+
+## Crash recovery
+
+recovery-title = Sitzung wiederherstellen?
+recovery-text = Der Explorer wurde beim letzten Mal nicht sauber beendet. Seine Sitzung wurde kurz davor gesichert; wiederherstellen?
+recovery-location = Letzte Ansicht: { $re } { $im }i, Breite { $width }
+recovery-restore = Wiederherstellen
+recovery-discard = Verwerfen
+
+## Diagnostics
+
+diagnostics-title = Diagnose
+diagnostics-show = Zeigen:
+diagnostics-clear = Leeren
+diagnostics-caches = Caches
+diagnostics-frames = Bilder
+diagnostics-entries = Einträge
+diagnostics-memory = Speicher
+diagnostics-hits = Treffer
+diagnostics-misses = Fehlschläge
+diagnostics-evictions = Verdrängungen
+diagnostics-memory-use = Speichernutzung
+diagnostics-memory-frame = Gezeigtes Bild
+diagnostics-memory-caches = Caches
+diagnostics-memory-exports = Laufende Exporte
+diagnostics-memory-total = Gesamt
+diagnostics-memory-of-cap = { $used } von { $cap } MB
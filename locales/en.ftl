@@ -0,0 +1,609 @@
+# English text of the user interface, the fallback for other languages.
+# See src/i18n.rs.
+
+## Shared
+
+button-browse = Browse…
+button-cancel = Cancel
+button-choose = Choose…
+button-frame-minibrot = Frame minibrot
+button-frame-minibrot-hover = Zooms to the period { $period } minibrot at the nucleus found last, estimated { $size } times the size of the whole set and turned { $angle }°
+split-position = Split position
+duration-seconds = { $seconds } s
+duration-minutes = { $minutes } min { $seconds } s
+progress = { $percent }% · { $elapsed } elapsed
+progress-time-left = { $percent }% · { $elapsed } elapsed, { $left } left
+error-unsupported-file = unsupported file type
+filter-session = Session
+filter-location = Location
+filter-script = Rhai script
+filter-recording = Recording
+filter-text = Text
+
+## Menus
+
+menu-file = File
+menu-open-session = Open Session…
+menu-save-session = Save Session…
+menu-import-location = Import Location…
+menu-export-location = Export Location…
+menu-save-screenshot = Save Screenshot
+menu-export-image = Export Image…
+menu-export-palette = Export Palette…
+menu-export-palette-hover = Save the palette's colors as a GIMP (.gpl) or Adobe Swatch Exchange (.ase) file, for image editors and other fractal software
+menu-export-svg = Export Overlays as SVG…
+menu-batch-render = Batch Render…
+menu-run-script = Run Script…
+menu-copy-image = Copy Image
+menu-copy-permalink = Copy Permalink
+menu-paste-permalink = Paste Permalink
+menu-settings = Settings…
+menu-diagnostics = Diagnostics…
+menu-language = Language
+menu-view = View
+menu-bookmarks = Bookmarks…
+menu-tour = Guided Tour…
+menu-landmarks = Real-Axis Landmarks
+menu-landmarks-disabled = Only for z² + c
+menu-annotate-landmarks = Label Landmarks
+menu-annotations = Annotations…
+menu-orbit-traps = Orbit Traps…
+menu-layers = Coloring Layers…
+menu-riemann-sphere = Riemann Sphere…
+menu-height-map = Height Map…
+menu-julia-morph = Julia Morph…
+menu-timeline = Timeline…
+menu-recorder = Recorder…
+menu-ab-comparison = A/B Comparison…
+menu-magnifier = Magnifier
+menu-magnifier-hover = Show the area under the cursor zoomed in
+menu-magnification = Magnification
+menu-measure = Measure
+menu-measure-hover = Drag on the view to measure distances
+menu-distance-circle = Distance circle
+menu-distance-circle-hover = Draw a circle around the cursor as wide as its estimated distance to the boundary of the set. The true distance lies between the inner, fainter circle and the outer one.
+menu-animate-path = Animate path
+menu-animate-path-hover = Move a point along the path, one iteration after the other, leaving a fading trail. Paths that stay bounded play in a loop.
+menu-animation-speed = Speed
+menu-animation-speed-unit = { " " }iterations/s
+menu-orbit-values = Orbit values
+menu-orbit-values-hover = Label the first points of the path with their values. Point at any point of the path to see its value.
+menu-hold-zoom = Hold to Zoom
+menu-hold-zoom-hover = Zoom in towards the cursor for as long as the mouse button, or Z, is held down, and out with Shift held too, or X, instead of selecting points
+menu-hold-zoom-rate = Hold zoom rate
+menu-hold-zoom-rate-unit = × per s
+menu-inset-zoom = Inset zoom
+menu-inset-zoom-hover = How much deeper the inset is than the view it was pinned in
+menu-go-to-inset = Go to Inset
+menu-remove-inset = Remove Inset
+menu-map-to-lambda-plane = Map to λ-Plane
+menu-map-to-c-plane = Map to c-Plane
+menu-map-plane-hover = Switch between z → z² + c and the logistic map z → λz(1 − z), keeping the view on the same part of the set: c = λ/2 − λ²/4
+menu-analysis = Analysis
+menu-estimate-area = Estimate Area…
+menu-histogram = Iteration Histogram…
+menu-compare-dem = Compare with DEM…
+menu-frame-statistics = Frame Statistics
+menu-find-detail = Find Detail
+menu-start-autopilot = Start Autopilot
+menu-stop-autopilot = Stop Autopilot
+menu-dimension = Box-Counting Dimension…
+menu-tile-telemetry = Tile Telemetry…
+menu-export-arrays = Export Arrays…
+context-pin-inset = Pin Inset Here
+context-component = What Component Is This?
+context-component-hover = Find the hyperbolic component this point lies in: its period, guessed from the atom domain, its center by Newton's method, and the point's internal angle and radius from the multiplier of its cycle
+
+## Main window
+
+help-click = Click to show a path
+help-snap = Ctrl-click (⌘-click on a Mac) to show the exact cycle of the nearest periodic point
+help-scroll = Scroll wheel to zoom
+help-hover =
+    The Mandelbrot set is computed by iterating the equation z = z^2 + c, where c is the complex coordinate for each pixel. Points that do not escape to infinity after many iterations are part of the set and are colored black; others are colored based on how quickly they escape.
+
+    When you click, the yellow path shows the sequence of complex values z as it is iterated for the selected point. If the path escapes the circle of radius 2, the point is not in the Mandelbrot set.
+view-description = Mandelbrot set centered on { $re } { $im }i, { $width } wide. Arrow keys pan, plus and minus zoom, Enter shows the path of the center
+drop-to-open = Drop to open
+controls-formula = Formula:
+controls-exponent = Exponent:
+controls-exponent-hover = z → zᵃ⁺ᵇⁱ + c, using the principal branch of the power
+controls-exponent-pad-hover = Pick the exponent on a pad of the plane of exponents
+controls-cubic-a-hover = Coefficient of z in z → z³ + az + c. The critical points are ±√(−a/3); the path of the second is drawn in blue.
+controls-cubic-either = Either escapes
+controls-cubic-either-hover = Points escape as soon as either critical orbit does, showing where both stay bounded (the connectedness locus)
+controls-cubic-both = Both escape
+controls-cubic-both-hover = Points escape only once both critical orbits have, showing where either stays bounded
+controls-quaternion-offset-hover = Offset of the slice shown along j and k: its points are x + yi + uj + vk
+controls-quaternion-julia-hover = Show the Julia set of a fixed quaternion c, starting from each point of the slice, instead of the Mandelbrot set. c starts at the selected point and the slice offsets.
+controls-hybrid-pattern = Pattern:
+controls-hybrid-pattern-hover = The maps taken in turn, repeating: M z² + c, B Burning Ship, T Tricorn, C Celtic. For example MMBB takes two steps of z² + c, then two of the Burning Ship.
+controls-phoenix-p-hover = Weight of the iterate before last: zₙ₊₁ = zₙ² + c + p zₙ₋₁
+controls-nova-relaxation-hover = Relaxation, scaling each Newton step: z → z − R (zᵖ − 1) / (p zᵖ⁻¹) + c
+controls-nova-power-hover = Power of zᵖ − 1, whose roots Newton's method looks for
+controls-nova-julia = Julia
+controls-nova-julia-hover = Show the dynamical plane for a fixed c, starting from each point, instead of the parameter plane. c starts at the selected point.
+controls-palette = Palette:
+controls-palette-layers = Layers
+controls-randomize-palette-hover = Make up a new palette from a random seed
+controls-palette-seed-hover = Seed of the random palette; the same seed always gives the same palette
+controls-auto-contrast = Auto contrast
+controls-auto-contrast-hover = Stretch the palette over the escape times in the frame rather than up to the iteration limit, so deep zooms get all its colors
+controls-contrast-clip-hover = Share of escape times left out at either end, so a few outliers don't narrow the rest; 0 stretches from the smallest to the largest
+controls-max-iterations = Max iterations:
+controls-path-iterations = Path:
+controls-path-iterations-hover = Give the path and its statistics an iteration limit of their own, such as many thousands to follow a slowly settling orbit while the image uses a few hundred
+controls-interior = Interior:
+controls-interior-hover = Color points in the set by their distance to the boundary, or by the period of their attracting cycle
+controls-renderer = Renderer:
+renderer-auto = Auto ({ $name })
+controls-zoom = Zoom:
+controls-zoom-hover = Type a factor such as 1000x or 1e6 and press Enter to zoom in by it about the center; factors below 1 zoom out
+controls-surprise = Surprise Me
+controls-surprise-hover = Glide to a random place on the boundary at a random depth, passing over places with little structure
+controls-surprise-disabled = Only for z² + c
+controls-field-lines = Field lines:
+controls-field-lines-hover = Curves of constant external angle, evenly spaced; 0 for none
+controls-equipotentials = Equipotentials
+controls-equipotentials-hover = Curves along which the smooth escape time is a whole number
+controls-shading = Slope shading
+controls-shading-hover = Lights the distance estimate outside the set as relief, so filaments look embossed
+controls-lighting-hover = Direction, height, and strength of the light
+controls-light-azimuth = Direction
+controls-light-elevation = Elevation
+controls-light-strength = Strength
+controls-occlusion = Occlusion:
+controls-occlusion-hover = Darkens where escape times change quickly, as if creases got less light; 0 for none
+controls-quality = Quality:
+controls-live-preview = Live GPU preview
+controls-live-preview-hover = While zooming, draw the view directly on the GPU and render it properly once you stop. Only for the built-in palettes, without interior coloring or overlays, and at shallow zooms.
+controls-sliced = Render in slices of
+controls-sliced-hover = Render the view on the interface thread a slice per frame, sharpening as it goes, so the window stays smooth even on one core or on the web
+controls-frame-budget-hover = Time each frame may spend rendering. Lower keeps the window smoother, higher finishes sooner.
+quality-draft = Draft
+quality-normal = Normal
+quality-high = High
+quality-ultra = Ultra
+quality-auto = Auto
+quality-auto-hover = Draft while zooming or panning, High once you stop
+quality-hover = { $resolution }% resolution, { $samples } samples per pixel, { $limit ->
+        [100] the iteration limit as set
+       *[other] { $limit }% of the iteration limit
+    }
+frame-stats =
+    In the set: { $interior }%
+    At max_iter: { $at_limit }%
+frame-stats-escape-time = Escape time: { $min } / { $median } / { $max } (min / median / max)
+frame-stats-unresolved = { $percent }% unresolved: raise max_iter above { $max_iter }
+measure-result =
+    Distance: { $distance }
+    Pixels: { $pixels }
+    Midpoint: { $re } { $im }i
+orbit-value = z{ $n } = { $re } { $im }i
+
+## Status bar
+
+status-bar-view = Center: { $re } { $im }i   Width: { $width }
+cursor-outside = Cursor: { $re } { $im }i   Distance to the set: { $distance } ({ $pixels } px)
+cursor-inside = Cursor: { $re } { $im }i   In the set, { $distance } ({ $pixels } px) from its boundary
+cursor-unknown = Cursor: { $re } { $im }i   Distance to the boundary unknown, try more iterations
+status-detail-found = Zoomed into detail ({ $entropy } bits of entropy)
+status-no-detail = No detail found in view
+status-snapped = Snapped to the period { $period } nucleus { $re } { $im }i
+status-no-nucleus = No periodic point found near the click
+status-component = In the period { $period } component centered at { $re } { $im }i, at internal angle { $angle } turns and radius { $radius }
+status-component-outside = Not inside a component; the nearest, of period { $period }, is centered at { $re } { $im }i (|λ| = { $radius } here)
+status-no-component = No hyperbolic component found for this point
+status-framed-minibrot = Framed the period { $period } minibrot; it is turned { $angle }° from the whole set, which the view can't rotate to match
+status-surprise = Surprise: { $zoom }× zoom ({ $entropy } bits of entropy)
+status-no-surprise = No detailed place found; try again
+status-autopilot-on = Autopilot on: press a key or click to stop
+status-autopilot-stopped = Autopilot stopped
+status-autopilot-failed = Autopilot stopped: { $reason }
+autopilot-no-detail = no detail left in view
+autopilot-precision-limit = reached the limit of 64-bit precision
+status-zoom-factor-invalid = Not a zoom factor: { $text }
+status-moved-to-inset = Moved to the inset
+status-mapped-to-lambda-plane = Moved to the corresponding place of the λ-plane
+status-mapped-to-c-plane = Moved to the corresponding place of the c-plane
+status-render-cancelled = Render cancelled
+status-permalink-copied = Permalink copied to clipboard
+status-permalink-opened = Opened permalink
+status-permalink-failed = Could not open permalink: { $error }
+status-clipboard-failed = Could not read clipboard: { $error }
+status-image-copied = Image copied to clipboard
+status-copy-failed = Could not copy image: { $error }
+status-opened = Opened { $name }
+status-open-failed = Could not open { $name }: { $error }
+status-saved = Saved { $name }
+status-gpu-failed = GPU rendering failed, so the view is now rendered on the CPU ({ $renderer }): { $error }
+status-recovered = Restored the session from before the crash
+status-save-failed = Could not save { $name }: { $error }
+status-exported = Exported { $name }
+status-export-failed = Could not export { $name }: { $error }
+memory-export-refused = Not started: the export needs about { $needed } MB, but only { $available } MB of the { $cap } MB memory cap is free. Export at a smaller size, wait for other exports to finish, or raise the cap in the settings.
+status-screenshot-saved = Saved screenshot { $path }
+status-screenshot-failed = Could not save screenshot { $path }: { $error }
+status-script-output = { $name }: { $output }
+status-script-ran = Ran { $name }
+status-script-failed = Script { $name } failed: { $error }
+status-plugins-failed = { $count ->
+        [one] One plugin failed to load, see the log
+       *[other] { $count } plugins failed to load, see the log
+    }
+status-remote-failed = Could not start remote control: { $error }
+
+## Settings
+
+settings-title = Settings
+settings-language = Language:
+settings-screenshot-folder = Screenshot folder:
+settings-plugin-folder = Plugin folder:
+settings-plugins-note = Plugins are loaded at startup.
+settings-remote = Remote control on port
+settings-remote-hover = Accept WebSocket commands from this computer, see src/remote.rs
+settings-cache = Cache memory:
+settings-cache-hover = Rendered frames kept for going back to earlier views
+settings-memory-cap = Memory cap:
+settings-memory-cap-hover = Most memory the frame shown, cached frames and exports may take up together. The cache shrinks to stay under it, and exports that would go over it are refused.
+settings-threads = One render thread per core ({ $cores })
+settings-low-priority = Batch renders at low priority
+settings-low-priority-hover = Let other programs go first while batch rendering, so a long batch doesn't slow down the computer (Linux only)
+settings-zoom-step = Zoom step:
+settings-zoom-step-hover = How far a notch of the mouse wheel, or a press of + or −, zooms in or out
+settings-invert-wheel = Invert the mouse wheel
+settings-zoom-about-cursor = Zoom about the cursor
+settings-zoom-about-cursor-hover = Keep the point under the cursor in place while zooming with the wheel, rather than the center of the view
+settings-smooth-zoom = Smooth wheel zoom
+settings-smooth-zoom-hover = Zoom in proportion to how far the wheel or touchpad scrolls, easing into each step, rather than a whole step at once for every notch
+
+## Orbit panel
+
+orbit-title = Orbit
+orbit-dock = Dock
+orbit-dock-hover = Show in the main window
+orbit-pop-out = Pop Out
+orbit-pop-out-hover = Show in a window of its own
+orbit-escape-time = Escape time
+orbit-no-escape = none within { $max_iter }
+orbit-converged = none, converges after { $n }
+orbit-largest = Largest |z|
+orbit-last = Last z
+orbit-turns = { $turns } turns
+orbit-external-angle = External angle
+orbit-external-angle-hover = Angle of the external ray through c, from the orbit's escape. Most accurate close to the boundary.
+orbit-binary = Binary
+orbit-period = Period
+orbit-period-unsettled = not settled, try more iterations
+orbit-multiplier = |Multiplier|
+orbit-internal-angle = Internal angle
+orbit-rotation-number = Rotation number
+orbit-rotation-number-hover = How far the map turns the attracting cycle around the α fixed point. For a bulb on the main cardioid, it's the bulb's internal angle.
+orbit-interior-distance = Interior distance
+orbit-interior-distance-hover = Estimated distance to the boundary of the set. The true distance is between a quarter of this and this.
+orbit-preperiod = Preperiod
+orbit-preperiod-value = { $preperiod }, then period { $period }
+orbit-preperiod-hover = The path repeats to within rounding from this iterate on, counting z₀ = 0, with this period. It is drawn in yellow up to there and in orange from there. Points in the set count from when they are that close to their attracting cycle; Misiurewicz points land on a repelling cycle exactly.
+orbit-highlight-period = Highlight period { $period } in the view
+orbit-highlight-period-hover = Tint the pixels of the view whose cycle has the same period, outlining the hyperbolic components of that period
+orbit-sparklines = Re and Im of zₙ
+orbit-sparkline-value = n = { $n }: { $value }
+real-dynamics = Real dynamics
+real-dynamics-map = x → x² + c with c = { $c }
+real-dynamics-cobweb = Cobweb from x = 0:
+real-dynamics-bifurcation = Bifurcation diagram, c at the red line:
+
+## Analysis windows
+
+histogram-title = Iteration Histogram
+histogram-log-scale = Logarithmic counts
+histogram-interior = In the set: { $pixels } pixels ({ $percent }%)
+histogram-highlighted = Highlighted: escape times { $from }–{ $to }, { $pixels } pixels
+histogram-bar = Escape times { $from }–{ $to }: { $pixels } pixels
+histogram-hint = Click a bar to highlight its pixels.
+compare-title = Compare with DEM
+compare-split = Split
+compare-blend = Blend
+compare-opacity = DEM opacity
+compare-hint = Left or underneath: escape time. Right or on top: distance estimation, which also shows filaments thinner than a pixel.
+area-title = Area Estimate
+area-current-view = Current view
+area-whole-set = Whole set
+area-samples = Samples:
+area-seed = Seed:
+area-stop = Stop
+area-estimate = Estimate
+area-area = Area
+area-interval = 95% interval
+area-in-set = In the set
+area-hits = { $hits } of { $samples }
+area-hint = Points still bounded after { $max_iter } iterations count as in the set, so the estimate is a little high.
+dimension-title = Box-Counting Dimension
+dimension-resolution = Resolution:
+dimension-analyze = Analyze View
+dimension-hint = Counts boxes the boundary of the set in the view passes through.
+dimension-result = Dimension ≈ { $dimension }   (R² = { $r_squared })
+dimension-no-boundary = No boundary in view
+dimension-x-axis = ln(1 / box side)
+dimension-y-axis = ln(boxes)
+telemetry-title = Tile Telemetry
+telemetry-tile-size = Tile size:
+telemetry-heat-map = Show heat map
+telemetry-heat-map-hover = Colors each tile of the view from blue (fast) to yellow (slow) and outlines the slowest
+telemetry-measure = Measure View
+telemetry-measure-hover = Renders the frame shown again tile by tile, timing each tile on one thread
+telemetry-hint = Measures how long each part of the view takes to render.
+telemetry-stale = Measured at another view; measure again to see the heat map.
+telemetry-summary = { $tiles } tiles at { $max_iter } iterations: { $total } ms of rendering, { $elapsed } ms in all
+telemetry-slowest = Slowest tiles:
+telemetry-tile = tile at ({ $x }, { $y })
+
+## View tools
+
+ab-title = A/B Comparison
+ab-keep = Keep Current as A
+ab-keep-hover = Then change the palette, iteration limit, or renderer
+ab-a = A (left): { $setup }
+ab-a-none = A (left): not kept yet
+ab-b = B (right): { $setup }
+ab-setup = { $palette }, { $max_iter } iterations, { $renderer }
+ab-hint = Drag the line in the view to move the split.
+bookmarks-title = Bookmarks
+bookmarks-name = Name:
+bookmarks-add = Add Current View
+bookmarks-import = Import List…
+bookmarks-import-hover = Add the views of a text file with one "name re im width" line each, as location collections are often shared
+bookmarks-imported = { $count ->
+        [one] Imported one bookmark from { $name }
+       *[other] Imported { $count } bookmarks from { $name }
+    }
+bookmarks-import-failed = Could not import { $name }: { $error }
+bookmarks-location = { $re } { $im }i, width { $width }
+bookmarks-remove = Remove
+bookmarks-remove-one = Remove { $name }
+bookmarks-remove-all = Remove All
+bookmarks-none = No bookmarks yet.
+annotations-title = Annotations
+annotations-place = Place:
+annotations-tool-none = Nothing
+annotations-tool-label = Label
+annotations-tool-arrow = Arrow
+annotations-tool-marker = Marker
+annotations-text = Text:
+annotations-label-hint = Click the view to place the label.
+annotations-arrow-hint = Drag on the view from the arrow's tail to its head.
+annotations-marker-hint = Click the view to place a marker.
+annotations-place-center = Place at Center
+annotations-remove = Remove
+annotations-remove-one = Remove { $annotation }
+annotations-remove-all = Remove All
+annotations-none = No annotations yet.
+annotation-label = Label "{ $text }" at { $at }
+annotation-arrow = Arrow { $from } → { $to }
+annotation-marker = Marker at { $at }
+traps-title = Orbit Traps
+traps-place = Place:
+traps-tool-none = Nothing
+traps-tool-point = Point
+traps-tool-line = Line
+traps-tool-circle = Circle
+traps-move-hint = Drag the handles of a trap in the view to move it.
+traps-point-hint = Click the view to place a point.
+traps-line-hint = Drag on the view from one end of the line to the other.
+traps-circle-hint = Drag on the view from the center of the circle to its outline.
+traps-presets = Presets:
+traps-preset-unit-circle = Unit Circle
+traps-preset-axes = Axes
+traps-preset-origin = Origin
+traps-preset-gaussian = Gaussian Integers
+traps-preset-gaussian-hover = A lattice of points one apart: orbits are measured to the nearest point m + ni, the classic Gaussian integer trap. Its spacing can be set in the list or by dragging its handle.
+traps-spacing-hover = Distance between neighbouring points of the lattice
+traps-width = Glow width
+traps-width-hover = How far from a trap an orbit can pass and still tint its pixel
+traps-remove = Remove
+traps-remove-one = Remove { $trap }
+traps-remove-all = Remove All
+traps-none = No traps yet. Pixels outside the set are tinted by how close their orbit passes to the traps.
+layers-title = Coloring Layers
+layers-add = Add Layer
+layers-remove-all = Remove All
+layers-none = No layers yet. While any layer is visible, the layers color the view instead of the palette and the orbit trap tint.
+layers-order-hint = Top layers are blended over the ones below.
+layers-visible-hover = Show the layer
+layers-raise = Move up
+layers-lower = Move down
+layers-remove = Remove
+layers-density-hover = Stripes per turn of the iterates' argument
+layers-opacity-hover = How much of the layer shows
+sphere-title = Riemann Sphere
+sphere-hint = Drag to turn. 0 is at the south pole, infinity at the north pole.
+sphere-reset = Reset
+height-map-title = Height Map
+height-map-height = Height
+height-map-reset = Reset
+height-map-hint = Drag to turn, scroll to move closer. Heights are the smooth escape time on a logarithmic scale, with the set on top.
+height-map-camera-path = Camera:
+height-map-turntable = Turntable
+height-map-flyover = Flyover
+height-map-export = Export Video
+height-map-export-hover = Render the camera going around the surface, or swooping down on it, as a video three quarters as high as wide
+
+playback-play = Play
+playback-pause = Pause
+playback-loop = Loop
+
+julia-morph-title = Julia Morph
+julia-morph-draw = ✏ Draw
+julia-morph-draw-hover = Drag on the view to draw the path c travels along
+julia-morph-add-point = Add Selected Point
+julia-morph-add-point-hover = Add the point whose path is shown to the end of the path
+julia-morph-use-path = Use Path of Selected Point
+julia-morph-use-path-hover = Let c travel along the iterates of the selected point
+julia-morph-clear = Clear
+julia-morph-no-path = Draw a path on the view or add points to it to see the Julia set change along it.
+julia-morph-c = c = { $re } { $im }i
+julia-morph-connected = Connected
+julia-morph-dust = Cantor dust
+julia-morph-connectivity-hover = The Julia set is connected when the orbit of the critical point 0 stays bounded, that is when c is in the Mandelbrot set, and otherwise falls apart into uncountably many points. Decided with the iteration limit, so points close to the boundary may need more iterations.
+julia-morph-show-orbit = Show Critical Orbit
+julia-morph-show-orbit-hover = Select c in the view to draw the orbit of 0 that decides it as the path
+julia-morph-show-orbit-disabled = Only for the formula z² + c
+julia-morph-duration = Duration:
+julia-morph-export = Export Video
+
+timeline-title = Timeline
+timeline-add = Add Keyframe
+timeline-add-hover = Add what the view shows now, with the formula and its parameters, as a keyframe after the last one
+timeline-remove = Remove keyframe
+timeline-go-to = Go To
+timeline-go-to-hover = Show this keyframe in the view
+timeline-keyframe = { $formula }, zoom { $zoom }, { $max_iter } iterations
+timeline-none = No keyframes yet. Add the view as it is now, change it or the formula's parameters, and add another.
+timeline-export = Export Video
+
+landmark-cusp = Cusp (c = 1/4)
+landmark-cusp-explanation = The cusp of the main cardioid: the fixed point of x² + c stops attracting, and beyond it every orbit escapes.
+landmark-doubling = Period { $from } → { $to }
+landmark-doubling-explanation = Period doubling: the attracting cycle of period { $from } becomes unstable and one of period { $to } takes over. The gaps between doublings shrink by a factor of about 4.669.
+landmark-feigenbaum = Feigenbaum Point
+landmark-feigenbaum-explanation = c ≈ −1.40116, where the period doublings pile up and chaos starts; the set looks alike at every zoom around it.
+landmark-period-three = Period 3 Window
+landmark-period-three-explanation = c = −7/4, where a cycle of period 3 appears out of the chaos: the cusp of the largest minibrot on the real axis.
+landmark-tip = Tip (c = −2)
+landmark-tip-explanation = The left end of the set, where the orbit of 0 lands on the repelling fixed point 2 after two steps.
+
+tour-title = Guided Tour
+tour-previous = Previous stop
+tour-next = Next stop
+tour-on-the-way = On the way to { $name }…
+tour-whole-set = The Mandelbrot Set
+tour-whole-set-caption = The points c for which z → z² + c, started from 0, stays bounded. The heart shape is the main cardioid, where the orbit settles on a fixed point; the disk to its left is the period 2 bulb.
+tour-seahorse-valley = Seahorse Valley
+tour-seahorse-valley-caption = The cleft between the main cardioid and the period 2 bulb, lined with curled tails. Zooming into any of them finds spirals within spirals.
+tour-elephant-valley = Elephant Valley
+tour-elephant-valley-caption = Near the cusp of the main cardioid, bulbs with trunks line up like a herd of elephants, each smaller than the one before.
+tour-minibrot = Minibrot at −1.75
+tour-minibrot-caption = A small copy of the whole set on the real axis, around a nucleus of period 3. Copies like it are everywhere along the boundary, each with its own antenna.
+tour-feigenbaum = Feigenbaum Point
+tour-feigenbaum-caption = At c ≈ −1.4012 the bulbs of period 2, 4, 8, … along the real axis pile up, each about 4.669 times smaller than the last: Feigenbaum's constant, the same for every map with a single hump.
+tour-spiral = Double Spiral
+tour-spiral-caption = Deep in Seahorse Valley, the boundary winds into spirals around Misiurewicz points, where the orbit lands on a repelling cycle after a few steps.
+
+recorder-title = Recorder
+recorder-record = ⏺ Record
+recorder-record-hover = Record every change of the view, iteration limit, and formula from now on, to replay later
+recorder-stop = ⏹ Stop
+recorder-recording = Recording · { $seconds } s, { $steps } steps
+recorder-recorded = { $seconds } s, { $steps } steps
+recorder-replay = ▶ Replay
+recorder-replay-hover = Replay the recording in the view, step by step as it happened
+recorder-stop-replay = ⏹ Stop Replay
+recorder-none = Nothing recorded yet.
+recorder-save = Save…
+recorder-open = Open…
+recorder-export = Export Video
+recorder-export-hover = Render the recording as a smooth animation, gliding between the recorded steps
+recorder-unsorted = the steps of the recording aren't in order of time
+
+video-fps = Frame rate:
+video-export-hover = Writes every frame as a PNG to the output folder and joins them into video.mp4 if ffmpeg is installed
+video-progress = { $done } / { $total } frames · { $progress }
+video-paused = Paused while the view is in use
+video-done = Wrote { $name }
+video-frames-only = Wrote { $total } frames to { $folder }; install ffmpeg to have them joined into a video
+video-cancelled = Cancelled after { $done } frames, which are in { $folder }
+video-failed = Could not write the video: { $error }
+trap-point = Point at { $at }
+trap-line = Line { $from } – { $to }
+trap-circle = Circle around { $center }, radius { $radius }
+trap-lattice = Lattice, spacing { $spacing }
+
+## Batch rendering
+
+batch-title = Batch Render
+batch-list = Location list:
+batch-list-hover = One location per line: a permalink, or `re im scale [max_iter]`. Lines starting with # are ignored.
+batch-no-list = (none)
+batch-output-folder = Output folder:
+batch-size = Size:
+batch-progress = { $done } / { $total } · { $progress }
+batch-paused = Paused while the view is in use
+batch-render = Render
+batch-loaded = { $count ->
+        [one] One location
+       *[other] { $count } locations
+    }
+batch-read-failed = Could not read { $name }: { $error }
+batch-done = Rendered { $done } of { $total } locations
+batch-done-failures =
+    Rendered { $done } of { $total } locations, { $failed } failed:
+    { $failures }
+
+## Array export
+
+arrays-title = Export Arrays
+arrays-iterations = Escape times
+arrays-smooth = Smooth escape times
+arrays-distance = Distance estimates
+arrays-orbit-min = Smallest |z| along the orbit
+arrays-orbit-mean = Average |z| along the orbit
+arrays-shape = Arrays of { $height } × { $width } values, the frame shown, as NumPy .npy files
+arrays-export = Export
+arrays-exporting = Exporting…
+arrays-done = Exported { $count } arrays to { $folder }
+arrays-failed = Export failed: { $error }
+
+## Image export
+
+image-export-title = Export Image
+image-export-format = Format:
+image-format-png = PNG, 8-bit
+image-format-png16 = PNG, 16-bit
+image-format-tiff16 = TIFF, 16-bit
+image-export-linear = Linear light
+image-export-linear-hover = Store light intensities rather than sRGB values, as compositing and other linear workflows expect. The image looks dark in ordinary viewers.
+image-export-dither = Dithering:
+image-export-dither-hover = Color 8-bit PNGs by the smooth escape time and dither them when rounding to 8 bits, so long, subtle gradients don't show bands
+dither-none = None
+dither-ordered = Ordered
+dither-blue-noise = Blue noise
+image-export-save = Export…
+image-export-rendering = Rendering…
+
+## SVG export
+
+svg-export-title = Export Overlays as SVG
+svg-export-size-hint = The figure lines up with a render of the view at this size, such as a screenshot.
+svg-export-path = Path
+svg-export-annotations = Annotations
+svg-export-axes = Axes
+svg-export-grid = Grid
+svg-export-outlines = Cardioid and period 2 disk
+svg-export-save = Save…
+
+## Crash recovery
+
+recovery-title = Restore Session?
+recovery-text = The explorer didn't exit cleanly last time. Its session was saved shortly before; restore it?
+recovery-location = Last view: { $re } { $im }i, width { $width }
+recovery-restore = Restore
+recovery-discard = Discard
+
+## Diagnostics
+
+diagnostics-title = Diagnostics
+diagnostics-show = Show:
+diagnostics-clear = Clear
+diagnostics-caches = Caches
+diagnostics-frames = Frames
+diagnostics-entries = Entries
+diagnostics-memory = Memory
+diagnostics-hits = Hits
+diagnostics-misses = Misses
+diagnostics-evictions = Evictions
+diagnostics-memory-use = Memory Use
+diagnostics-memory-frame = Frame shown
+diagnostics-memory-caches = Caches
+diagnostics-memory-exports = Exports running
+diagnostics-memory-total = Total
+diagnostics-memory-of-cap = { $used } of { $cap } MB
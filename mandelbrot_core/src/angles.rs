@@ -0,0 +1,111 @@
+//! External angles of points outside the set and rotation numbers of points in
+//! its bulbs.
+
+use std::f64::consts::TAU;
+
+use crate::{escape_time, interior::Interior};
+
+/// Escape radius for [`external_angle`]. Far out, arg zₙ is close to the
+/// angle of the external ray through zₙ.
+pub(crate) const ANGLE_ESCAPE_RADIUS: f64 = 1e8;
+/// Extra iterations allowed to reach [`ANGLE_ESCAPE_RADIUS`] after the
+/// iteration limit.
+const EXTRA_ITERATIONS: usize = 64;
+
+/// The external angle of a point outside the set.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExternalAngle {
+    /// The angle in turns, from 0 to 1.
+    pub turns: f64,
+    /// Its binary expansion after the point, one digit per iteration. May be
+    /// longer than `turns` can hold.
+    pub bits: String,
+}
+
+/// Estimates the angle of the external ray through c from its escaping orbit.
+///
+/// Far out, arg zₙ / 2π is 2ⁿ times the angle, modulo 1. Each earlier iterate
+/// halves that, leaving a choice of two angles, and the one closer to
+/// arg zₖ / 2π fixes the next binary digit. The angle of z₁ = c is the result.
+/// Returns `None` for points that don't escape within `max_iter` iterations.
+pub fn external_angle(c: (f64, f64), max_iter: usize) -> Option<ExternalAngle> {
+    let mut path = Vec::new();
+    if !escaping_orbit(c, max_iter, &mut path) {
+        return None;
+    }
+    let mut bits = Vec::new();
+    let turns = trace_angle(&path, |bit| bits.push(if bit { '1' } else { '0' }));
+    bits.reverse();
+    Some(ExternalAngle {
+        turns,
+        bits: bits.into_iter().collect(),
+    })
+}
+
+/// Fills `path` with z₁ = c, z₂, … up to the first iterate beyond
+/// [`ANGLE_ESCAPE_RADIUS`]. Returns false if c doesn't escape within
+/// `max_iter` iterations, leaving `path` in an unspecified state.
+pub(crate) fn escaping_orbit(c: (f64, f64), max_iter: usize, path: &mut Vec<(f64, f64)>) -> bool {
+    path.clear();
+    let mut z = (0.0, 0.0);
+    for _ in 0..max_iter + EXTRA_ITERATIONS {
+        z = (z.0 * z.0 - z.1 * z.1 + c.0, 2.0 * z.0 * z.1 + c.1);
+        path.push(z);
+        if z.0 * z.0 + z.1 * z.1 > ANGLE_ESCAPE_RADIUS * ANGLE_ESCAPE_RADIUS {
+            return escape_time(c, max_iter) < max_iter;
+        }
+    }
+    false
+}
+
+/// The external angle in turns from an orbit filled in by [`escaping_orbit`],
+/// passing each binary digit to `on_bit`, last digit first.
+pub(crate) fn trace_angle(path: &[(f64, f64)], mut on_bit: impl FnMut(bool)) -> f64 {
+    let turns_of = |(x, y): (f64, f64)| (y.atan2(x) / TAU).rem_euclid(1.0);
+    let mut turns = turns_of(path[path.len() - 1]);
+    for &z in path.iter().rev().skip(1) {
+        let (low, high) = (turns / 2.0, (turns + 1.0) / 2.0);
+        let target = turns_of(z);
+        let high_is_closer = circular_distance(high, target) < circular_distance(low, target);
+        on_bit(high_is_closer);
+        turns = if high_is_closer { high } else { low };
+    }
+    turns
+}
+
+/// Distance between two angles in turns, going the short way round.
+fn circular_distance(a: f64, b: f64) -> f64 {
+    let d = (a - b).rem_euclid(1.0);
+    d.min(1.0 - d)
+}
+
+/// The rotation number `(q, p)` of the cycle of `interior`, a point of period
+/// p > 1: as the map is applied, each cycle point moves q places around the
+/// α fixed point, ordered by angle. For a point in a bulb attached to the main
+/// cardioid, q/p is that bulb's internal angle at the cardioid.
+pub fn rotation_number(c: (f64, f64), interior: &Interior) -> Option<(usize, usize)> {
+    let p = interior.period;
+    if p < 2 {
+        return None;
+    }
+    // α = (1 - √(1 - 4c)) / 2, the fixed point the bulbs' cycles turn around
+    let (re, im) = (1.0 - 4.0 * c.0, -4.0 * c.1);
+    let r = re.hypot(im).sqrt();
+    let half_arg = im.atan2(re) / 2.0;
+    let alpha = ((1.0 - r * half_arg.cos()) / 2.0, -r * half_arg.sin() / 2.0);
+    let mut cycle = Vec::with_capacity(p);
+    let mut z = interior.cycle_point;
+    for _ in 0..p {
+        cycle.push(z);
+        z = (z.0 * z.0 - z.1 * z.1 + c.0, 2.0 * z.0 * z.1 + c.1);
+    }
+    let angle = |z: (f64, f64)| (z.1 - alpha.1).atan2(z.0 - alpha.0);
+    let mut order: Vec<usize> = (0..p).collect();
+    order.sort_by(|&a, &b| angle(cycle[a]).total_cmp(&angle(cycle[b])));
+    let position = |i: usize| order.iter().position(|&j| j == i).unwrap();
+    let q = (position(1) + p - position(0)) % p;
+    // Every point must move by the same amount for a rotation
+    (1..p)
+        .all(|i| (position((i + 1) % p) + p - position(i)) % p == q)
+        .then_some((q, p))
+}
@@ -0,0 +1,102 @@
+//! Estimating the area of the set by random sampling.
+
+use crate::{View, escape_time, random::SplitMix64};
+
+/// Smallest axis-aligned rectangle containing the whole set, rounded outwards:
+/// `(re_min, re_max, im_min, im_max)`.
+pub const SET_BOUNDS: (f64, f64, f64, f64) = (-2.0, 0.5, -1.15, 1.15);
+
+/// Counts how many uniformly random points of a rectangle are in the set. The
+/// fraction that are, times the rectangle's area, estimates the set's area
+/// within it.
+///
+/// Sampling can be spread over several [`AreaEstimator::sample`] calls, with
+/// the estimate improving as samples accumulate.
+#[derive(Debug, Clone)]
+pub struct AreaEstimator {
+    bounds: (f64, f64, f64, f64),
+    max_iter: usize,
+    rng: SplitMix64,
+    samples: u64,
+    hits: u64,
+}
+
+/// The result of sampling.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AreaEstimate {
+    /// Estimated area of the set within the sampled rectangle.
+    pub area: f64,
+    /// Standard error of `area`.
+    pub std_error: f64,
+    /// Number of points sampled.
+    pub samples: u64,
+    /// Number of sampled points found in the set.
+    pub hits: u64,
+}
+
+impl AreaEstimate {
+    /// The interval `area ± z · std_error`; `z = 1.96` gives 95% confidence.
+    ///
+    /// Points that need more than the iteration limit to escape count as in
+    /// the set, so the interval is for the area at that limit, which is
+    /// somewhat larger than the true area.
+    pub fn confidence_interval(&self, z: f64) -> (f64, f64) {
+        (
+            self.area - z * self.std_error,
+            self.area + z * self.std_error,
+        )
+    }
+}
+
+impl AreaEstimator {
+    /// Samples the rectangle `(re_min, re_max, im_min, im_max)`, taking
+    /// points that don't escape within `max_iter` iterations to be in the set.
+    pub fn new(bounds: (f64, f64, f64, f64), max_iter: usize, seed: u64) -> Self {
+        Self {
+            bounds,
+            max_iter,
+            rng: SplitMix64::new(seed),
+            samples: 0,
+            hits: 0,
+        }
+    }
+
+    /// Samples the area shown by a square image of `view`.
+    pub fn for_view(view: &View, max_iter: usize, seed: u64) -> Self {
+        let half = view.scale / 2.0;
+        let (re, im) = view.center;
+        Self::new((re - half, re + half, im - half, im + half), max_iter, seed)
+    }
+
+    /// Samples the whole set.
+    pub fn whole_set(max_iter: usize, seed: u64) -> Self {
+        Self::new(SET_BOUNDS, max_iter, seed)
+    }
+
+    /// Tests `count` more random points.
+    pub fn sample(&mut self, count: u64) {
+        let (re_min, re_max, im_min, im_max) = self.bounds;
+        for _ in 0..count {
+            let re = re_min + (re_max - re_min) * self.rng.next_f64();
+            let im = im_min + (im_max - im_min) * self.rng.next_f64();
+            if escape_time((re, im), self.max_iter) == self.max_iter {
+                self.hits += 1;
+            }
+        }
+        self.samples += count;
+    }
+
+    /// The estimate from the points sampled so far.
+    pub fn estimate(&self) -> AreaEstimate {
+        let (re_min, re_max, im_min, im_max) = self.bounds;
+        let rectangle = (re_max - re_min) * (im_max - im_min);
+        let n = self.samples.max(1) as f64;
+        let p = self.hits as f64 / n;
+        AreaEstimate {
+            area: rectangle * p,
+            std_error: rectangle * (p * (1.0 - p) / n).sqrt(),
+            samples: self.samples,
+            hits: self.hits,
+        }
+    }
+}
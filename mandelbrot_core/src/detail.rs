@@ -0,0 +1,57 @@
+//! Finding visually interesting parts of a rendered view.
+
+use crate::{IterationBuffer, Tile};
+
+/// A square of a buffer and how much structure it has.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Detail {
+    /// The square, in pixels of the buffer.
+    pub tile: Tile,
+    /// Shannon entropy of the square's escape times in bits. Flat areas, such
+    /// as the inside of the set or far outside it, score close to 0.
+    pub entropy: f64,
+}
+
+/// Shannon entropy in bits of the escape times in `tile` of `buffer`.
+pub fn entropy(buffer: &IterationBuffer, tile: &Tile) -> f64 {
+    let mut values: Vec<u32> = (tile.y..tile.y + tile.height)
+        .flat_map(|y| &buffer.data[y * buffer.width + tile.x..][..tile.width])
+        .copied()
+        .collect();
+    values.sort_unstable();
+    let total = values.len() as f64;
+    values
+        .chunk_by(|a, b| a == b)
+        .map(|run| {
+            let p = run.len() as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// The `cell`×`cell` square of `buffer` with the highest [`entropy`], trying
+/// squares at steps of half a cell. `None` if the buffer is smaller than a
+/// cell.
+pub fn find_detail(buffer: &IterationBuffer, cell: usize) -> Option<Detail> {
+    let cell = cell.max(2);
+    if buffer.width < cell || buffer.height < cell {
+        return None;
+    }
+    let step = cell / 2;
+    let xs = (0..=buffer.width - cell).step_by(step);
+    let ys = (0..=buffer.height - cell).step_by(step);
+    ys.flat_map(|y| xs.clone().map(move |x| (x, y)))
+        .map(|(x, y)| {
+            let tile = Tile {
+                x,
+                y,
+                width: cell,
+                height: cell,
+            };
+            Detail {
+                tile,
+                entropy: entropy(buffer, &tile),
+            }
+        })
+        .max_by(|a, b| a.entropy.total_cmp(&b.entropy))
+}
@@ -0,0 +1,113 @@
+//! Box-counting estimates of the fractal dimension of the set's boundary.
+
+use crate::IterationBuffer;
+
+/// A least-squares line `y = slope · x + intercept`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineFit {
+    /// Slope of the line.
+    pub slope: f64,
+    /// Value at x = 0.
+    pub intercept: f64,
+    /// Coefficient of determination; 1 when all points lie on the line.
+    pub r_squared: f64,
+}
+
+/// Fits a line through `points` by least squares. Needs at least two distinct
+/// x values.
+pub fn fit_line(points: &[(f64, f64)]) -> Option<LineFit> {
+    if points.len() < 2 {
+        return None;
+    }
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let (mut sxx, mut sxy, mut syy) = (0.0, 0.0, 0.0);
+    for &(x, y) in points {
+        sxx += (x - mean_x) * (x - mean_x);
+        sxy += (x - mean_x) * (y - mean_y);
+        syy += (y - mean_y) * (y - mean_y);
+    }
+    if sxx == 0.0 {
+        return None;
+    }
+    let slope = sxy / sxx;
+    Some(LineFit {
+        slope,
+        intercept: mean_y - slope * mean_x,
+        r_squared: if syy == 0.0 {
+            1.0
+        } else {
+            sxy * sxy / (sxx * syy)
+        },
+    })
+}
+
+/// Result of [`box_counting`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoxCounting {
+    /// Box side in pixels and the number of boxes containing boundary pixels,
+    /// from the smallest boxes to the largest.
+    pub counts: Vec<(usize, usize)>,
+    /// Line through `(ln(1 / side), ln(count))`; its slope is the dimension
+    /// estimate.
+    pub fit: Option<LineFit>,
+}
+
+impl BoxCounting {
+    /// The estimated dimension, the slope of the log-log fit.
+    pub fn dimension(&self) -> Option<f64> {
+        self.fit.map(|fit| fit.slope)
+    }
+
+    /// The counts as `(ln(1 / side), ln(count))`, the points the line is
+    /// fitted through.
+    pub fn log_points(&self) -> Vec<(f64, f64)> {
+        self.counts
+            .iter()
+            .filter(|&&(_, count)| count > 0)
+            .map(|&(side, count)| (-(side as f64).ln(), (count as f64).ln()))
+            .collect()
+    }
+}
+
+/// Whether each pixel of `buffer` is on the boundary: in the set with a
+/// neighbor outside it.
+pub fn boundary(buffer: &IterationBuffer) -> Vec<bool> {
+    let (width, height) = (buffer.width, buffer.height);
+    let inside = |x: usize, y: usize| buffer.is_interior(buffer.data[y * width + x] as usize);
+    let mut edge = vec![false; width * height];
+    for y in 0..height {
+        for x in 0..width {
+            edge[y * width + x] = inside(x, y)
+                && ((x > 0 && !inside(x - 1, y))
+                    || (x + 1 < width && !inside(x + 1, y))
+                    || (y > 0 && !inside(x, y - 1))
+                    || (y + 1 < height && !inside(x, y + 1)));
+        }
+    }
+    edge
+}
+
+/// Counts the boxes of side 1, 2, 4, … pixels that the boundary in `buffer`
+/// passes through, up to an eighth of the smaller image side, and fits a line
+/// to the counts on a log-log scale.
+pub fn box_counting(buffer: &IterationBuffer) -> BoxCounting {
+    let edge = boundary(buffer);
+    let largest = (buffer.width.min(buffer.height) / 8).max(1);
+    let mut counts = Vec::new();
+    let mut side = 1;
+    while side <= largest {
+        let columns = buffer.width.div_ceil(side);
+        let mut occupied = vec![false; columns * buffer.height.div_ceil(side)];
+        for (i, _) in edge.iter().enumerate().filter(|&(_, &edge)| edge) {
+            let (x, y) = (i % buffer.width, i / buffer.width);
+            occupied[(y / side) * columns + x / side] = true;
+        }
+        counts.push((side, occupied.iter().filter(|&&b| b).count()));
+        side *= 2;
+    }
+    let mut result = BoxCounting { counts, fit: None };
+    result.fit = fit_line(&result.log_points());
+    result
+}
@@ -0,0 +1,55 @@
+//! Exterior distance estimation (DEM): how far points outside the set are
+//! from it, which shows filaments too thin to hit any pixel's center.
+
+use crate::{Image, IterationBuffer, View};
+
+/// Escape radius for distance estimates. The estimate is better the further
+/// out the orbit gets.
+const DISTANCE_ESCAPE_RADIUS: f64 = 1000.0;
+
+/// Estimated distance from c to the set, or `None` if c doesn't escape within
+/// `max_iter` iterations. The true distance lies between a quarter of this
+/// and this.
+pub fn exterior_distance(c: (f64, f64), max_iter: usize) -> Option<f64> {
+    let (mut x, mut y) = (0.0f64, 0.0f64);
+    // dz/dc
+    let (mut dx, mut dy) = (0.0f64, 0.0f64);
+    for _ in 0..max_iter {
+        let (new_dx, new_dy) = (2.0 * (x * dx - y * dy) + 1.0, 2.0 * (x * dy + y * dx));
+        (dx, dy) = (new_dx, new_dy);
+        (x, y) = (x * x - y * y + c.0, 2.0 * x * y + c.1);
+        let abs = x.hypot(y);
+        if abs > DISTANCE_ESCAPE_RADIUS {
+            return Some(2.0 * abs * abs.ln() / dx.hypot(dy));
+        }
+    }
+    None
+}
+
+/// Colors `view`, rendered into `buffer`, by distance to the set: black in
+/// the set and at the boundary, fading to white a few pixels away. Points
+/// that escape from the buffer's iteration limit but not the larger distance
+/// radius in time count as on the boundary.
+pub fn render_distance(buffer: &IterationBuffer, view: &View) -> Image {
+    let pixel = view.pixel_size(buffer.width);
+    let pixels = buffer
+        .data
+        .iter()
+        .enumerate()
+        .map(|(i, &iter)| {
+            if buffer.is_interior(iter as usize) {
+                return [0, 0, 0, 255];
+            }
+            let (x, y) = ((i % buffer.width) as f64, (i / buffer.width) as f64);
+            let c = view.pixel_to_complex(x, y, buffer.width, buffer.height);
+            let distance = exterior_distance(c, buffer.max_iter).unwrap_or(0.0);
+            let v = ((distance / (4.0 * pixel)).sqrt().min(1.0) * 255.0) as u8;
+            [v, v, v, 255]
+        })
+        .collect();
+    Image {
+        width: buffer.width,
+        height: buffer.height,
+        pixels,
+    }
+}
@@ -0,0 +1,693 @@
+//! The maps iterated over the parameter plane: z → z² + c, the Mandelbrot
+//! set proper, and variations on it, some of which can also be shown over
+//! the dynamical plane. Everything else in this crate that doesn't take a
+//! [`Formula`] is about z → z² + c.
+
+use std::hash::{Hash, Hasher};
+
+use crate::{
+    hybrid::Pattern,
+    iterate::{ESCAPE_RADIUS, escape_time},
+    render::CONVERGED,
+};
+
+/// Parameter p of the Phoenix fractal when it is first chosen.
+pub const PHOENIX_P: (f64, f64) = (-0.5, 0.0);
+
+/// Exponent of the Multibrot formula when it is first chosen.
+pub const MULTIBROT_EXPONENT: (f64, f64) = (3.0, 0.0);
+/// Relaxation of the Nova fractal when it is first chosen.
+pub const NOVA_RELAXATION: (f64, f64) = (1.0, 0.0);
+/// Power of the Nova fractal when it is first chosen.
+pub const NOVA_POWER: u32 = 3;
+
+/// Bailout radius of the cubic family, enough that orbits beyond it escape
+/// for |a| + |c| up to about 100.
+const CUBIC_ESCAPE_RADIUS: f64 = 10.0;
+/// Bailout radius of formulas whose orbits can converge. Their iterates grow
+/// slowly enough near the set that 2 would cut off much of its outside
+/// structure.
+const CONVERGING_ESCAPE_RADIUS: f64 = 100.0;
+/// How close to 1 iterates of the Magnet formulas, and to the iterate before
+/// them those of Nova, must come to count as converged.
+const CONVERGENCE_TOLERANCE: f64 = 1e-6;
+
+/// A map iterated from a critical point for each parameter c, or for Nova
+/// optionally from each point of the dynamical plane for a fixed c.
+#[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Formula {
+    /// z → z² + c.
+    #[default]
+    Mandelbrot,
+    /// z → zᵃ⁺ᵇⁱ + c, using the principal branch of the power for
+    /// exponents that aren't integers, so the plane is cut along the
+    /// negative real axis of z. 0 to any power is taken to be 0.
+    Multibrot {
+        /// The exponent a + bi.
+        exponent: (f64, f64),
+    },
+    /// The cubic family z → z³ + az + c over c for a fixed a, whose two
+    /// critical points ±√(−a/3) each have an orbit. With a = 0 this is the
+    /// cubic Multibrot z³ + c.
+    Cubic {
+        /// Coefficient a of z.
+        a: (f64, f64),
+        /// Which escaping critical orbits make a point escape.
+        escape: CubicEscape,
+    },
+    /// q → q² + c over the quaternions, shown as the slice of points
+    /// x + yi + uj + vk with fixed offsets u and v. Paths show the 1 and i
+    /// parts of the iterates.
+    Quaternion {
+        /// Offsets (u, v) of the slice along j and k.
+        offset: (f64, f64),
+        /// For a Julia set, the fixed c as its 1, i, j, and k parts, with
+        /// the slice's points as q₀ instead. `None` for the Mandelbrot set.
+        julia: Option<[f64; 4]>,
+    },
+    /// A hybrid, taking each step with the next map of a repeating
+    /// [`Pattern`].
+    Hybrid {
+        /// The maps stepped with, in order.
+        pattern: Pattern,
+    },
+    /// Ushiki's Phoenix fractal, zₙ₊₁ = zₙ² + c + p zₙ₋₁, whose next iterate
+    /// depends on the one before as well.
+    Phoenix {
+        /// Weight of the iterate before last.
+        p: (f64, f64),
+    },
+    /// The first Magnet model, z → ((z² + c − 1) / (2z + c − 2))², from the
+    /// renormalization of the Ising model. Orbits either escape or converge
+    /// to the fixed point 1.
+    MagnetI,
+    /// The second Magnet model, z → ((z³ + 3(c − 1)z + (c − 1)(c − 2)) /
+    /// (3z² + 3(c − 2)z + (c − 1)(c − 2) + 1))², which also fixes 1.
+    MagnetII,
+    /// Newton's method for zᵖ − 1 with relaxation R, plus c:
+    /// z → z − R (zᵖ − 1) / (p zᵖ⁻¹) + c, iterated from the critical point
+    /// 1. Orbits mostly converge, to wherever c moved the roots.
+    Nova {
+        /// Relaxation R, scaling each Newton step.
+        relaxation: (f64, f64),
+        /// Power p, at least 2.
+        power: u32,
+        /// The fixed c when showing the dynamical plane, whose points are
+        /// then the starting iterates z₀. `None` for the parameter plane.
+        julia: Option<(f64, f64)>,
+    },
+    /// The logistic map z → λz(1 − z) over the λ-plane, iterated from its
+    /// critical point ½. Conjugate to z → z² + c for c = [`lambda_to_c`]`(λ)`,
+    /// so the plane shows two copies of the Mandelbrot set, mirrored about
+    /// λ = 1.
+    Lambda,
+}
+
+/// When a parameter of the [cubic family](Formula::Cubic) counts as
+/// escaping, given the orbits of its two critical points.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CubicEscape {
+    /// As soon as either orbit escapes: the set is the intersection of where
+    /// each orbit stays bounded, the connectedness locus.
+    #[default]
+    Either,
+    /// Once both orbits have: the set is the union of where each stays
+    /// bounded.
+    Both,
+}
+
+/// How an orbit stopped before the iteration limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bailout {
+    /// After this many iterations, the iterate left the escape radius.
+    Escaped(usize),
+    /// After this many iterations, the iterate came within tolerance of a
+    /// fixed point: 1 for the Magnet formulas, wherever it settles for Nova.
+    Converged(usize),
+}
+
+// Parameters are compared by their bits, but for the sign of zero, so that
+// formulas loaded with NaN parameters still equal themselves and hash alike,
+// as frame cache keys need.
+impl PartialEq for Formula {
+    fn eq(&self, other: &Self) -> bool {
+        let bits = parameter_bits;
+        let pair = |(x, y): (f64, f64)| (bits(x), bits(y));
+        match (*self, *other) {
+            (Formula::Mandelbrot, Formula::Mandelbrot)
+            | (Formula::MagnetI, Formula::MagnetI)
+            | (Formula::MagnetII, Formula::MagnetII)
+            | (Formula::Lambda, Formula::Lambda) => true,
+            (Formula::Phoenix { p }, Formula::Phoenix { p: q }) => pair(p) == pair(q),
+            (Formula::Multibrot { exponent: a }, Formula::Multibrot { exponent: b }) => {
+                pair(a) == pair(b)
+            }
+            (Formula::Hybrid { pattern: a }, Formula::Hybrid { pattern: b }) => a == b,
+            (
+                Formula::Cubic { a, escape },
+                Formula::Cubic {
+                    a: b,
+                    escape: other,
+                },
+            ) => pair(a) == pair(b) && escape == other,
+            (
+                Formula::Quaternion { offset, julia },
+                Formula::Quaternion {
+                    offset: other_offset,
+                    julia: other_julia,
+                },
+            ) => {
+                pair(offset) == pair(other_offset)
+                    && julia.map(|c| c.map(bits)) == other_julia.map(|c| c.map(bits))
+            }
+            (
+                Formula::Nova {
+                    relaxation,
+                    power,
+                    julia,
+                },
+                Formula::Nova {
+                    relaxation: other_relaxation,
+                    power: other_power,
+                    julia: other_julia,
+                },
+            ) => {
+                pair(relaxation) == pair(other_relaxation)
+                    && power == other_power
+                    && julia.map(pair) == other_julia.map(pair)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Formula {}
+
+impl Hash for Formula {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let bits = parameter_bits;
+        std::mem::discriminant(self).hash(state);
+        match *self {
+            Formula::Mandelbrot | Formula::MagnetI | Formula::MagnetII | Formula::Lambda => {}
+            Formula::Phoenix { p } => (bits(p.0), bits(p.1)).hash(state),
+            Formula::Multibrot { exponent } => (bits(exponent.0), bits(exponent.1)).hash(state),
+            Formula::Hybrid { pattern } => pattern.hash(state),
+            Formula::Cubic { a, escape } => (bits(a.0), bits(a.1), escape).hash(state),
+            Formula::Quaternion { offset, julia } => {
+                (bits(offset.0), bits(offset.1)).hash(state);
+                julia.map(|c| c.map(bits)).hash(state);
+            }
+            Formula::Nova {
+                relaxation,
+                power,
+                julia,
+            } => {
+                (bits(relaxation.0), bits(relaxation.1), power).hash(state);
+                julia.map(|c| (bits(c.0), bits(c.1))).hash(state);
+            }
+        }
+    }
+}
+
+impl Formula {
+    /// Every formula, with its parameters as first chosen.
+    pub const ALL: [Formula; 10] = [
+        Formula::Mandelbrot,
+        Formula::Multibrot {
+            exponent: MULTIBROT_EXPONENT,
+        },
+        Formula::Cubic {
+            a: (0.0, 0.0),
+            escape: CubicEscape::Either,
+        },
+        Formula::Hybrid {
+            pattern: Pattern::DEFAULT,
+        },
+        Formula::Quaternion {
+            offset: (0.0, 0.0),
+            julia: None,
+        },
+        Formula::Phoenix { p: PHOENIX_P },
+        Formula::MagnetI,
+        Formula::MagnetII,
+        Formula::Nova {
+            relaxation: NOVA_RELAXATION,
+            power: NOVA_POWER,
+            julia: None,
+        },
+        Formula::Lambda,
+    ];
+
+    /// Human-readable name.
+    pub fn name(self) -> &'static str {
+        match self {
+            Formula::Mandelbrot => "Mandelbrot",
+            Formula::Multibrot { .. } => "Multibrot",
+            Formula::Cubic { .. } => "Cubic",
+            Formula::Hybrid { .. } => "Hybrid",
+            Formula::Quaternion { .. } => "Quaternion",
+            Formula::Phoenix { .. } => "Phoenix",
+            Formula::MagnetI => "Magnet I",
+            Formula::MagnetII => "Magnet II",
+            Formula::Nova { .. } => "Nova",
+            Formula::Lambda => "Lambda",
+        }
+    }
+
+    /// Whether orbits can stop by converging to a fixed point as well as by
+    /// escaping.
+    pub fn converges(self) -> bool {
+        matches!(
+            self,
+            Formula::MagnetI | Formula::MagnetII | Formula::Nova { .. }
+        )
+    }
+
+    /// Radius iterates escape beyond: [`ESCAPE_RADIUS`] but for formulas
+    /// whose orbits can converge.
+    pub fn escape_radius(self) -> f64 {
+        if self.converges() {
+            CONVERGING_ESCAPE_RADIUS
+        } else if let Formula::Cubic { .. } = self {
+            CUBIC_ESCAPE_RADIUS
+        } else {
+            ESCAPE_RADIUS
+        }
+    }
+
+    /// Whether this is another formula with different parameters, or the
+    /// same one.
+    pub fn same_kind(self, other: Formula) -> bool {
+        std::mem::discriminant(&self) == std::mem::discriminant(&other)
+    }
+
+    /// The formula a fraction `t`, from 0 to 1, of the way from this one to
+    /// `to`, for animating parameters: with the parameters of both in
+    /// between where they vary continuously, and those of `to` from halfway
+    /// for the rest, such as Nova's power, or when the formulas differ.
+    pub fn interpolate(self, to: Formula, t: f64) -> Formula {
+        let lerp = |a: f64, b: f64| a + t * (b - a);
+        let lerp2 = |a: (f64, f64), b: (f64, f64)| (lerp(a.0, b.0), lerp(a.1, b.1));
+        let step = if t < 0.5 { self } else { to };
+        match (self, to, step) {
+            (Formula::Multibrot { exponent: a }, Formula::Multibrot { exponent: b }, _) => {
+                Formula::Multibrot {
+                    exponent: lerp2(a, b),
+                }
+            }
+            (
+                Formula::Cubic { a, .. },
+                Formula::Cubic { a: b, .. },
+                Formula::Cubic { escape, .. },
+            ) => Formula::Cubic {
+                a: lerp2(a, b),
+                escape,
+            },
+            (
+                Formula::Quaternion {
+                    offset: a,
+                    julia: c,
+                },
+                Formula::Quaternion {
+                    offset: b,
+                    julia: d,
+                },
+                Formula::Quaternion { julia, .. },
+            ) => Formula::Quaternion {
+                offset: lerp2(a, b),
+                julia: match (c, d) {
+                    (Some(c), Some(d)) => Some(std::array::from_fn(|i| lerp(c[i], d[i]))),
+                    _ => julia,
+                },
+            },
+            (Formula::Phoenix { p: a }, Formula::Phoenix { p: b }, _) => {
+                Formula::Phoenix { p: lerp2(a, b) }
+            }
+            (
+                Formula::Nova {
+                    relaxation: a,
+                    julia: c,
+                    ..
+                },
+                Formula::Nova {
+                    relaxation: b,
+                    julia: d,
+                    ..
+                },
+                Formula::Nova { power, julia, .. },
+            ) => Formula::Nova {
+                relaxation: lerp2(a, b),
+                power,
+                julia: match (c, d) {
+                    (Some(c), Some(d)) => Some(lerp2(c, d)),
+                    _ => julia,
+                },
+            },
+            _ => step,
+        }
+    }
+
+    /// The iterates z₀, z₁, z₂, … of the point c of the plane shown, without
+    /// end: from the critical point (0, 1 for Nova, ½ for Lambda, √(−a/3) for
+    /// the cubic family) under parameter c, or in the dynamical plane from
+    /// z₀ = c. For quaternions, the iterates' 1 and i parts.
+    ///
+    /// The Magnet formulas keep the conventional z₀ = 0, which isn't a
+    /// critical point but the critical value of the points where the
+    /// fraction vanishes, ±√(1 − c) for Magnet I and the roots of the cubic
+    /// numerator for Magnet II. Their critical orbit is followed one step
+    /// on, and the pictures match the published ones. Of the other critical
+    /// points of Magnet I, 1 is fixed and the orbit of 1 − c isn't followed.
+    pub fn iterates(self, c: (f64, f64)) -> Iterates {
+        if let Formula::Quaternion { offset, julia } = self {
+            let point = [c.0, c.1, offset.0, offset.1];
+            let (c, q) = match julia {
+                Some(julia) => (julia, point),
+                None => (point, [0.0; 4]),
+            };
+            return Iterates {
+                formula: self,
+                c: (c[0], c[1]),
+                z: (q[0], q[1]),
+                previous: (q[0], q[1]),
+                n: 0,
+                jk: [q[2], q[3], c[2], c[3]],
+                hidden: 0.0,
+            };
+        }
+        let (c, z) = match self {
+            Formula::Lambda => (c, (0.5, 0.0)),
+            Formula::Cubic { a, .. } => (c, cubic_critical_point(a)),
+            Formula::Nova {
+                julia: Some(julia), ..
+            } => (julia, c),
+            Formula::Nova { julia: None, .. } => (c, (1.0, 0.0)),
+            _ => (c, (0.0, 0.0)),
+        };
+        Iterates {
+            formula: self,
+            c,
+            z,
+            previous: z,
+            n: 0,
+            jk: [0.0; 4],
+            hidden: 0.0,
+        }
+    }
+
+    /// The iterates from the second critical point, −√(−a/3), for the cubic
+    /// family, the only formula with two.
+    fn other_iterates(self, c: (f64, f64)) -> Option<Iterates> {
+        let Formula::Cubic { a, .. } = self else {
+            return None;
+        };
+        let (x, y) = cubic_critical_point(a);
+        Some(Iterates {
+            z: (-x, -y),
+            previous: (-x, -y),
+            ..self.iterates(c)
+        })
+    }
+
+    /// The orbit of the second critical point, for the cubic family, which
+    /// has two, as [`orbit`](Self::orbit) gives that of the first.
+    pub fn other_orbit(self, c: (f64, f64), max_iter: usize) -> Option<Vec<(f64, f64)>> {
+        Some(self.orbit_of(self.other_iterates(c)?, max_iter))
+    }
+
+    /// How the orbit of c stops within `max_iter` iterations, if it does.
+    pub fn bailout(self, c: (f64, f64), max_iter: usize) -> Option<Bailout> {
+        match self {
+            Formula::Mandelbrot => {
+                let iter = escape_time(c, max_iter);
+                return (iter < max_iter).then_some(Bailout::Escaped(iter));
+            }
+            // The conjugacy maps escape radius 2 to a circle around ½ that
+            // depends on λ, so test the corresponding orbit of z² + c instead
+            Formula::Lambda => return Formula::Mandelbrot.bailout(lambda_to_c(c), max_iter),
+            Formula::Cubic { escape, .. } => {
+                // Cubic orbits only stop by escaping
+                let escaped = |iterates| match self.bailout_of(iterates, max_iter) {
+                    Some(Bailout::Escaped(n)) => Some(n),
+                    _ => None,
+                };
+                let first = escaped(self.iterates(c));
+                let second = self.other_iterates(c).and_then(escaped);
+                let n = match escape {
+                    CubicEscape::Either => first.into_iter().chain(second).min(),
+                    CubicEscape::Both => first.zip(second).map(|(a, b)| a.max(b)),
+                };
+                return n.map(Bailout::Escaped);
+            }
+            _ => {}
+        }
+        self.bailout_of(self.iterates(c), max_iter)
+    }
+
+    fn bailout_of(self, mut iterates: Iterates, max_iter: usize) -> Option<Bailout> {
+        let mut previous = None;
+        for n in 0..max_iter {
+            let z = iterates.next()?;
+            if let Some(stop) = self.stop(z, iterates.hidden, previous) {
+                return Some(stop(n));
+            }
+            previous = Some(z);
+        }
+        None
+    }
+
+    /// Number of iterations before |z| reaches the
+    /// [escape radius](Self::escape_radius), as [`escape_time`] for
+    /// z → z² + c. Points whose orbit converges count as in the set.
+    pub fn escape_time(self, c: (f64, f64), max_iter: usize) -> usize {
+        match self.bailout(c, max_iter) {
+            Some(Bailout::Escaped(n)) => n,
+            _ => max_iter,
+        }
+    }
+
+    /// The escape time of c interpolated between iterations from how far the
+    /// iterate that escaped overshot, like
+    /// [`EscapeData::smooth_iter`](crate::overlay::EscapeData::smooth_iter)
+    /// for z → z² + c, so that its level sets are smooth curves. Exact for
+    /// quadratic maps and an approximation for the others. `None` for points
+    /// that don't escape within `max_iter` iterations.
+    pub fn smooth_escape_time(self, c: (f64, f64), max_iter: usize) -> Option<f64> {
+        if self == Formula::Lambda {
+            return Formula::Mandelbrot.smooth_escape_time(lambda_to_c(c), max_iter);
+        }
+        let Some(Bailout::Escaped(n)) = self.bailout(c, max_iter) else {
+            return None;
+        };
+        // The cubic family's escape time can be that of its second critical
+        // orbit
+        let mut iterates = self.iterates(c);
+        if let Some(other) = self.other_iterates(c)
+            && self.bailout_of(iterates.clone(), max_iter) != Some(Bailout::Escaped(n))
+        {
+            iterates = other;
+        }
+        let (x, y) = iterates.nth(n)?;
+        Some(n as f64 + 1.0 - x.hypot(y).ln().log2())
+    }
+
+    /// The value stored for c in an
+    /// [`IterationBuffer`](crate::IterationBuffer): its escape time, or for
+    /// orbits that converge the iterations they took with [`CONVERGED`] set.
+    pub fn buffer_value(self, c: (f64, f64), max_iter: usize) -> u32 {
+        match self.bailout(c, max_iter) {
+            Some(Bailout::Escaped(n)) => n as u32,
+            Some(Bailout::Converged(n)) => CONVERGED | n as u32,
+            None => max_iter as u32,
+        }
+    }
+
+    /// The iterates of c up to and including the first one outside the
+    /// escape radius or converged, and at most `max_iter` of them, as
+    /// [`orbit`](crate::orbit) for z → z² + c.
+    pub fn orbit(self, c: (f64, f64), max_iter: usize) -> Vec<(f64, f64)> {
+        if self == Formula::Lambda {
+            let len = Formula::Mandelbrot.orbit(lambda_to_c(c), max_iter).len();
+            return self.iterates(c).take(len).collect();
+        }
+        self.orbit_of(self.iterates(c), max_iter)
+    }
+
+    fn orbit_of(self, mut iterates: Iterates, max_iter: usize) -> Vec<(f64, f64)> {
+        let mut path: Vec<(f64, f64)> = Vec::new();
+        while path.len() < max_iter
+            && let Some(z) = iterates.next()
+        {
+            let stop = self.stop(z, iterates.hidden, path.last().copied());
+            path.push(z);
+            if stop.is_some() {
+                break;
+            }
+        }
+        path
+    }
+
+    /// Whether the orbit stops at iterate z, which followed `previous`, and
+    /// how. `hidden` is the squared norm of the iterate's parts besides z.
+    fn stop(
+        self,
+        z: (f64, f64),
+        hidden: f64,
+        previous: Option<(f64, f64)>,
+    ) -> Option<fn(usize) -> Bailout> {
+        let radius = self.escape_radius();
+        if z.0 * z.0 + z.1 * z.1 + hidden >= radius * radius {
+            return Some(Bailout::Escaped);
+        }
+        let target = match self {
+            Formula::MagnetI | Formula::MagnetII => (1.0, 0.0),
+            Formula::Nova { .. } => previous?,
+            _ => return None,
+        };
+        let (dx, dy) = (z.0 - target.0, z.1 - target.1);
+        (dx * dx + dy * dy < CONVERGENCE_TOLERANCE * CONVERGENCE_TOLERANCE)
+            .then_some(Bailout::Converged)
+    }
+}
+
+/// The orbit of a point, from [`Formula::iterates`]. Keeps the iterate before
+/// the current one for formulas that depend on it, and counts iterates for
+/// hybrids.
+#[derive(Debug, Clone)]
+pub struct Iterates {
+    formula: Formula,
+    c: (f64, f64),
+    z: (f64, f64),
+    previous: (f64, f64),
+    n: usize,     // index of z
+    jk: [f64; 4], // j and k parts of z and of c, for quaternions
+    hidden: f64,  // squared norm of the j and k parts of the last iterate
+}
+
+impl Iterator for Iterates {
+    type Item = (f64, f64);
+
+    fn next(&mut self) -> Option<(f64, f64)> {
+        let (c, z) = (self.c, self.z);
+        let square = (z.0 * z.0 - z.1 * z.1 + c.0, 2.0 * z.0 * z.1 + c.1);
+        let next = match self.formula {
+            Formula::Mandelbrot => square,
+            Formula::Multibrot { exponent } => add(pow(z, exponent), c),
+            Formula::Hybrid { pattern } => pattern.step(self.n).apply(z, c),
+            Formula::Phoenix { p } => {
+                let w = self.previous;
+                (
+                    square.0 + p.0 * w.0 - p.1 * w.1,
+                    square.1 + p.0 * w.1 + p.1 * w.0,
+                )
+            }
+            Formula::MagnetI => {
+                let numerator = add(mul(z, z), (c.0 - 1.0, c.1));
+                let denominator = (2.0 * z.0 + c.0 - 2.0, 2.0 * z.1 + c.1);
+                let q = div(numerator, denominator);
+                mul(q, q)
+            }
+            Formula::MagnetII => {
+                let (c1, c2) = ((c.0 - 1.0, c.1), (c.0 - 2.0, c.1));
+                let c1c2 = mul(c1, c2);
+                let z2 = mul(z, z);
+                let numerator = add(add(mul(z2, z), scale(mul(c1, z), 3.0)), c1c2);
+                let denominator = add(
+                    add(scale(z2, 3.0), scale(mul(c2, z), 3.0)),
+                    (c1c2.0 + 1.0, c1c2.1),
+                );
+                let q = div(numerator, denominator);
+                mul(q, q)
+            }
+            Formula::Nova {
+                relaxation, power, ..
+            } => {
+                let below = (1..power.saturating_sub(1)).fold(z, |w, _| mul(w, z));
+                let numerator = add(mul(below, z), (-1.0, 0.0));
+                let step = mul(relaxation, div(numerator, scale(below, power as f64)));
+                add((z.0 - step.0, z.1 - step.1), c)
+            }
+            Formula::Lambda => mul(c, mul(z, (1.0 - z.0, -z.1))),
+            Formula::Cubic { a, .. } => add(mul(add(mul(z, z), a), z), c),
+            Formula::Quaternion { .. } => {
+                let [u, v, cu, cv] = self.jk;
+                self.hidden = u * u + v * v;
+                self.jk = [2.0 * z.0 * u + cu, 2.0 * z.0 * v + cv, cu, cv];
+                (square.0 - u * u - v * v, square.1)
+            }
+        };
+        (self.previous, self.z) = (z, next);
+        self.n += 1;
+        Some(z)
+    }
+}
+
+/// The c whose Mandelbrot orbit is conjugate to the Lambda orbit of λ:
+/// c = λ/2 − λ²/4.
+pub fn lambda_to_c(lambda: (f64, f64)) -> (f64, f64) {
+    let (half, square) = (scale(lambda, 0.5), mul(lambda, lambda));
+    (half.0 - square.0 / 4.0, half.1 - square.1 / 4.0)
+}
+
+/// The λ of the copy of the Mandelbrot set containing λ = 0 that
+/// [`lambda_to_c`] maps to c: λ = 1 − √(1 − 4c).
+pub fn c_to_lambda(c: (f64, f64)) -> (f64, f64) {
+    let root = sqrt((1.0 - 4.0 * c.0, -4.0 * c.1));
+    (1.0 - root.0, -root.1)
+}
+
+/// How much [`lambda_to_c`] scales lengths near λ: |dc/dλ| = |1 − λ| / 2.
+pub fn lambda_scale(lambda: (f64, f64)) -> f64 {
+    (1.0 - lambda.0).hypot(lambda.1) / 2.0
+}
+
+/// The bits of a formula parameter, with −0 taken for 0.
+fn parameter_bits(x: f64) -> u64 {
+    (x + 0.0).to_bits()
+}
+
+/// The critical point √(−a/3) of z → z³ + az + c; the other one is its
+/// negative.
+fn cubic_critical_point(a: (f64, f64)) -> (f64, f64) {
+    sqrt((-a.0 / 3.0, -a.1 / 3.0))
+}
+
+/// The principal value of aʷ, exp(w log a), or 0 for a = 0.
+fn pow(a: (f64, f64), w: (f64, f64)) -> (f64, f64) {
+    if a == (0.0, 0.0) {
+        return a;
+    }
+    let log = (a.0.hypot(a.1).ln(), a.1.atan2(a.0));
+    let (re, im) = mul(w, log);
+    let r = re.exp();
+    (r * im.cos(), r * im.sin())
+}
+
+fn sqrt(a: (f64, f64)) -> (f64, f64) {
+    let r = a.0.hypot(a.1);
+    let re = ((r + a.0) / 2.0).sqrt();
+    let im = ((r - a.0) / 2.0).sqrt();
+    (re, if a.1 < 0.0 { -im } else { im })
+}
+
+fn add(a: (f64, f64), b: (f64, f64)) -> (f64, f64) {
+    (a.0 + b.0, a.1 + b.1)
+}
+
+fn scale(a: (f64, f64), k: f64) -> (f64, f64) {
+    (a.0 * k, a.1 * k)
+}
+
+fn mul(a: (f64, f64), b: (f64, f64)) -> (f64, f64) {
+    (a.0 * b.0 - a.1 * b.1, a.0 * b.1 + a.1 * b.0)
+}
+
+fn div(a: (f64, f64), b: (f64, f64)) -> (f64, f64) {
+    let norm = b.0 * b.0 + b.1 * b.1;
+    (
+        (a.0 * b.0 + a.1 * b.1) / norm,
+        (a.1 * b.0 - a.0 * b.1) / norm,
+    )
+}
@@ -0,0 +1,115 @@
+//! Reproducible renders for golden-image tests.
+//!
+//! [`render_reference`] is a pure function of its arguments: it uses no
+//! randomness, clock, or thread scheduling, and only `f64` arithmetic as
+//! specified by IEEE 754 (Rust never fuses or reorders floating-point
+//! operations on its own). The same arguments give bit-identical images on
+//! every platform, so their [`image_hash`] can be stored and compared in tests.
+//! Changes to the iteration or palettes that alter images are breaking changes
+//! for such tests and are called out in release notes.
+//!
+//! ```
+//! use mandelbrot_core::{Formula, Palette, RenderSettings, View, golden};
+//!
+//! let settings = RenderSettings {
+//!     width: 32,
+//!     height: 32,
+//!     max_iter: 50,
+//!     formula: Formula::Mandelbrot,
+//! };
+//! let a = golden::render_reference(&View::default(), &settings, Palette::Fire);
+//! let b = golden::render_reference(&View::default(), &settings, Palette::Fire);
+//! assert_eq!(golden::image_hash(&a), golden::image_hash(&b));
+//! assert!(golden::compare(&a, &b).unwrap().is_identical());
+//! ```
+
+use crate::{
+    Image, IterationBuffer, Palette, RenderSettings, Renderer, ScalarRenderer, View, colorize,
+};
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Renders on the calling thread with [`ScalarRenderer`].
+///
+/// Every renderer in this crate computes the same image; this one is the
+/// reference the others are checked against.
+pub fn render_reference(view: &View, settings: &RenderSettings, palette: Palette) -> Image {
+    colorize(&ScalarRenderer.render(view, settings), palette)
+}
+
+/// 64-bit FNV-1a hash of an image's size and pixels. Unlike
+/// [`std::hash::Hash`], it is fixed across Rust versions and platforms.
+pub fn image_hash(image: &Image) -> u64 {
+    let mut hash = Fnv::new();
+    hash.write_size(image.width, image.height);
+    hash.write(image.as_bytes());
+    hash.0
+}
+
+/// 64-bit FNV-1a hash of an iteration buffer's size, limit, and escape times,
+/// for comparing renderers independently of coloring.
+pub fn iteration_hash(buffer: &IterationBuffer) -> u64 {
+    let mut hash = Fnv::new();
+    hash.write_size(buffer.width, buffer.height);
+    hash.write(&(buffer.max_iter as u64).to_le_bytes());
+    for iter in &buffer.data {
+        hash.write(&iter.to_le_bytes());
+    }
+    hash.0
+}
+
+/// How two images of the same size differ.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageDiff {
+    /// Number of pixels with at least one differing channel.
+    pub differing_pixels: usize,
+    /// Largest difference of any channel of any pixel.
+    pub max_channel_delta: u8,
+}
+
+impl ImageDiff {
+    /// Whether the images are equal.
+    pub fn is_identical(&self) -> bool {
+        self.differing_pixels == 0
+    }
+}
+
+/// Compares two images pixel by pixel. Returns `None` if their sizes differ.
+pub fn compare(a: &Image, b: &Image) -> Option<ImageDiff> {
+    if (a.width, a.height) != (b.width, b.height) {
+        return None;
+    }
+    let mut diff = ImageDiff {
+        differing_pixels: 0,
+        max_channel_delta: 0,
+    };
+    for (pa, pb) in a.pixels.iter().zip(&b.pixels) {
+        if pa != pb {
+            diff.differing_pixels += 1;
+            for (ca, cb) in pa.iter().zip(pb) {
+                diff.max_channel_delta = diff.max_channel_delta.max(ca.abs_diff(*cb));
+            }
+        }
+    }
+    Some(diff)
+}
+
+struct Fnv(u64);
+
+impl Fnv {
+    fn new() -> Self {
+        Self(FNV_OFFSET)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ byte as u64).wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn write_size(&mut self, width: usize, height: usize) {
+        self.write(&(width as u64).to_le_bytes());
+        self.write(&(height as u64).to_le_bytes());
+    }
+}
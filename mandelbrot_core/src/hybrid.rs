@@ -0,0 +1,148 @@
+//! Hybrid fractals: orbits that take each step with a different quadratic
+//! map, cycling through a repeating pattern such as `MMBB`, two steps of
+//! z → z² + c followed by two of the Burning Ship.
+
+use std::fmt;
+
+/// Longest pattern of steps.
+pub const MAX_STEPS: usize = 16;
+
+/// One of the maps a hybrid pattern steps with, each written as a letter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Step {
+    /// `M`: z → z² + c.
+    Mandelbrot,
+    /// `B`: the Burning Ship, z → (|Re z| + i|Im z|)² + c.
+    BurningShip,
+    /// `T`: the Tricorn, z → z̄² + c.
+    Tricorn,
+    /// `C`: the Celtic, z → |Re z²| + i Im z² + c.
+    Celtic,
+}
+
+impl Step {
+    /// Every step.
+    pub const ALL: [Step; 4] = [
+        Step::Mandelbrot,
+        Step::BurningShip,
+        Step::Tricorn,
+        Step::Celtic,
+    ];
+
+    /// The letter standing for the step in patterns.
+    pub fn letter(self) -> char {
+        match self {
+            Step::Mandelbrot => 'M',
+            Step::BurningShip => 'B',
+            Step::Tricorn => 'T',
+            Step::Celtic => 'C',
+        }
+    }
+
+    /// Human-readable name.
+    pub fn name(self) -> &'static str {
+        match self {
+            Step::Mandelbrot => "Mandelbrot",
+            Step::BurningShip => "Burning Ship",
+            Step::Tricorn => "Tricorn",
+            Step::Celtic => "Celtic",
+        }
+    }
+
+    /// The step for `letter`, in either case.
+    pub fn from_letter(letter: char) -> Option<Step> {
+        Step::ALL
+            .into_iter()
+            .find(|step| step.letter() == letter.to_ascii_uppercase())
+    }
+
+    /// The iterate after z for parameter c.
+    pub fn apply(self, z: (f64, f64), c: (f64, f64)) -> (f64, f64) {
+        let z = match self {
+            Step::BurningShip => (z.0.abs(), z.1.abs()),
+            Step::Tricorn => (z.0, -z.1),
+            Step::Mandelbrot | Step::Celtic => z,
+        };
+        let mut square = (z.0 * z.0 - z.1 * z.1, 2.0 * z.0 * z.1);
+        if self == Step::Celtic {
+            square.0 = square.0.abs();
+        }
+        (square.0 + c.0, square.1 + c.1)
+    }
+}
+
+/// The repeating pattern of steps of a hybrid, of 1 to [`MAX_STEPS`] steps.
+/// Serialized as its letters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "String", into = "String")
+)]
+pub struct Pattern {
+    steps: [Step; MAX_STEPS],
+    len: u8,
+}
+
+impl Default for Pattern {
+    fn default() -> Self {
+        Pattern::DEFAULT
+    }
+}
+
+impl Pattern {
+    /// `MMBB`, the pattern hybrids start with.
+    pub const DEFAULT: Pattern = {
+        let mut steps = [Step::Mandelbrot; MAX_STEPS];
+        (steps[2], steps[3]) = (Step::BurningShip, Step::BurningShip);
+        Pattern { steps, len: 4 }
+    };
+
+    /// The pattern written as `letters`, such as `MMBB`, ignoring spaces.
+    /// `None` if a letter stands for no [`Step`] or there are none or more
+    /// than [`MAX_STEPS`].
+    pub fn parse(letters: &str) -> Option<Pattern> {
+        let mut pattern = Pattern {
+            steps: [Step::Mandelbrot; MAX_STEPS],
+            len: 0,
+        };
+        for letter in letters.chars().filter(|c| !c.is_whitespace()) {
+            let slot = pattern.steps.get_mut(pattern.len as usize)?;
+            *slot = Step::from_letter(letter)?;
+            pattern.len += 1;
+        }
+        (pattern.len > 0).then_some(pattern)
+    }
+
+    /// The steps of one repetition.
+    pub fn steps(&self) -> &[Step] {
+        &self.steps[..self.len as usize]
+    }
+
+    /// The step taken from the `n`th iterate to the next.
+    pub fn step(&self, n: usize) -> Step {
+        self.steps[n % self.len as usize]
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.steps()
+            .iter()
+            .try_for_each(|step| write!(f, "{}", step.letter()))
+    }
+}
+
+impl TryFrom<String> for Pattern {
+    type Error = String;
+
+    fn try_from(letters: String) -> Result<Self, String> {
+        Pattern::parse(&letters).ok_or_else(|| format!("invalid hybrid pattern {letters:?}"))
+    }
+}
+
+impl From<Pattern> for String {
+    fn from(pattern: Pattern) -> String {
+        pattern.to_string()
+    }
+}
@@ -0,0 +1,169 @@
+//! Plain RGBA images, at 8 and 16 bits per channel.
+
+/// An 8-bit RGBA image stored row by row from the top-left corner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    /// Width in pixels.
+    pub width: usize,
+    /// Height in pixels.
+    pub height: usize,
+    /// `width * height` pixels as `[r, g, b, a]`.
+    pub pixels: Vec<[u8; 4]>,
+}
+
+impl Image {
+    /// An image filled with a single color.
+    pub fn new(width: usize, height: usize, color: [u8; 4]) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![color; width * height],
+        }
+    }
+
+    /// The image `factor` times smaller in each direction, each pixel the
+    /// average of the `factor`×`factor` block it covers, as for antialiasing
+    /// a supersampled render. Blocks cut off at the right and bottom edges
+    /// are left out.
+    pub fn downsample(&self, factor: usize) -> Image {
+        let factor = factor.max(1);
+        let (width, height) = (self.width / factor, self.height / factor);
+        let samples = (factor * factor) as u32;
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let mut sum = [0u32; 4];
+                for row in y * factor..(y + 1) * factor {
+                    let start = row * self.width + x * factor;
+                    for pixel in &self.pixels[start..start + factor] {
+                        for (total, channel) in sum.iter_mut().zip(pixel) {
+                            *total += u32::from(*channel);
+                        }
+                    }
+                }
+                pixels.push(sum.map(|total| ((total + samples / 2) / samples) as u8));
+            }
+        }
+        Image {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// The pixel data as a flat `r, g, b, a, r, g, b, a, …` byte slice.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.pixels.as_flattened()
+    }
+}
+
+/// A 16-bit RGBA image stored row by row from the top-left corner, for
+/// exports whose gradients should survive editing without banding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image16 {
+    /// Width in pixels.
+    pub width: usize,
+    /// Height in pixels.
+    pub height: usize,
+    /// `width * height` pixels as `[r, g, b, a]`, sRGB-encoded like [`Image`].
+    pub pixels: Vec<[u16; 4]>,
+}
+
+impl Image16 {
+    /// The image with its colors decoded from sRGB to linear light, in which
+    /// values are proportional to light intensity, as compositing expects.
+    /// Alpha stays as it is.
+    pub fn to_linear(&self) -> Image16 {
+        let decode = |channel: u16| {
+            let v = f64::from(channel) / 65535.0;
+            let linear = if v <= 0.04045 {
+                v / 12.92
+            } else {
+                ((v + 0.055) / 1.055).powf(2.4)
+            };
+            (linear * 65535.0).round() as u16
+        };
+        let pixels = (self.pixels.iter())
+            .map(|&[r, g, b, a]| [decode(r), decode(g), decode(b), a])
+            .collect();
+        Image16 {
+            width: self.width,
+            height: self.height,
+            pixels,
+        }
+    }
+
+    /// The pixel data as a flat `r, g, b, a, r, g, b, a, …` slice.
+    pub fn as_samples(&self) -> &[u16] {
+        self.pixels.as_flattened()
+    }
+
+    /// The image at 8 bits per channel, its colors rounded with `dither`.
+    /// Alpha is rounded to nearest.
+    pub fn to_8bit(&self, dither: Dither) -> Image {
+        let pixels = (self.pixels.iter().enumerate())
+            .map(|(i, &[r, g, b, a])| {
+                let threshold = dither.threshold(i % self.width, i / self.width);
+                let quantize = |channel: u16, threshold: f64| {
+                    (f64::from(channel) / 257.0 + threshold).floor().min(255.0) as u8
+                };
+                [
+                    quantize(r, threshold),
+                    quantize(g, threshold),
+                    quantize(b, threshold),
+                    quantize(a, 0.5),
+                ]
+            })
+            .collect();
+        Image {
+            width: self.width,
+            height: self.height,
+            pixels,
+        }
+    }
+}
+
+/// How colors are rounded to fewer bits. Rounding each pixel to nearest
+/// turns long, subtle gradients into visible bands; dithering rounds up or
+/// down by a threshold that varies from pixel to pixel instead, so the bands
+/// dissolve into fine noise that averages out to the true color.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Dither {
+    /// Round each pixel to nearest.
+    #[default]
+    None,
+    /// Thresholds from an 8×8 Bayer matrix: a regular crosshatch pattern.
+    Ordered,
+    /// Thresholds from the R2 low-discrepancy sequence, whose noise is close
+    /// to blue noise: even, without visible pattern or clumps.
+    BlueNoise,
+}
+
+impl Dither {
+    /// Every way of rounding.
+    pub const ALL: [Dither; 3] = [Dither::None, Dither::Ordered, Dither::BlueNoise];
+
+    /// Amount added before rounding down at pixel (`x`, `y`), from 0 to 1.
+    fn threshold(self, x: usize, y: usize) -> f64 {
+        match self {
+            Dither::None => 0.5,
+            Dither::Ordered => {
+                // The bits of x ^ y and y interleaved and reversed
+                let (x, y) = (x & 7, y & 7);
+                let xy = x ^ y;
+                let index = ((xy & 1) << 5)
+                    | ((y & 1) << 4)
+                    | ((xy & 2) << 2)
+                    | ((y & 2) << 1)
+                    | ((xy & 4) >> 1)
+                    | ((y & 4) >> 2);
+                (index as f64 + 0.5) / 64.0
+            }
+            Dither::BlueNoise => {
+                // 1/g and 1/g² for g the plastic number, g³ = g + 1
+                const A: (f64, f64) = (0.754_877_666_246_692_8, 0.569_840_290_998_053_3);
+                (0.5 + A.0 * x as f64 + A.1 * y as f64).fract()
+            }
+        }
+    }
+}
@@ -0,0 +1,436 @@
+//! Attracting cycles and interior distance estimates for points in the set,
+//! and the nuclei of hyperbolic components.
+
+use std::f64::consts::TAU;
+
+use crate::{Image, IterationBuffer, View, iterate::ESCAPE_RADIUS, palette::hsv_to_rgb};
+
+/// Longest cycle searched for.
+pub const MAX_PERIOD: usize = 4096;
+/// How close an iterate must return to count as a cycle, before refinement.
+const CYCLE_TOLERANCE: f64 = 1e-6;
+/// Newton steps [`nucleus`] takes at most.
+const NUCLEUS_STEPS: usize = 64;
+/// Newton steps [`component`] takes at most to find the cycle at c.
+const CYCLE_STEPS: usize = 64;
+
+/// The attracting cycle of an interior point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Interior {
+    /// Length of the cycle, i.e. the period of the hyperbolic component the
+    /// point lies in.
+    pub period: usize,
+    /// A point of the cycle.
+    pub cycle_point: (f64, f64),
+    /// |λ|, the magnitude of the cycle's multiplier. It is 0 at the
+    /// component's center and 1 on its boundary.
+    pub multiplier: f64,
+    /// arg λ in turns, from 0 to 1: the internal angle of c within its
+    /// component.
+    pub internal_angle: f64,
+    /// Estimated distance from c to the boundary of the set. The true distance
+    /// is between a quarter of this and this.
+    pub distance: f64,
+}
+
+/// Finds the attracting cycle of z → z² + c after iterating `max_iter` times,
+/// and estimates the interior distance from it.
+///
+/// Returns `None` if the orbit escapes, or hasn't settled on a cycle of at
+/// most [`MAX_PERIOD`] points by then, as happens close to the boundary.
+pub fn interior(c: (f64, f64), max_iter: usize) -> Option<Interior> {
+    let step = |(x, y): (f64, f64)| (x * x - y * y + c.0, 2.0 * x * y + c.1);
+    let mut z = (0.0, 0.0);
+    for _ in 0..max_iter {
+        z = step(z);
+        if abs2(z) >= ESCAPE_RADIUS * ESCAPE_RADIUS {
+            return None;
+        }
+    }
+    let start = z;
+    let mut w = start;
+    let period = (1..=MAX_PERIOD.min(max_iter.max(1))).find(|_| {
+        w = step(w);
+        abs2(sub(w, start)) < CYCLE_TOLERANCE * CYCLE_TOLERANCE
+    })?;
+
+    // Newton's method on f^p(z) = z pins down the cycle point
+    let mut z0 = start;
+    let converged = (0..16).any(|_| {
+        let (fz, dz) = iterate_with_derivative(z0, c, period);
+        let delta = div(sub(fz, z0), sub(dz, (1.0, 0.0)));
+        z0 = sub(z0, delta);
+        abs2(delta) < 1e-28
+    });
+    if !converged {
+        return None;
+    }
+
+    let (distance, multiplier) = distance_estimate(z0, c, period);
+    (abs2(multiplier) < 1.0).then_some(Interior {
+        period,
+        cycle_point: z0,
+        multiplier: abs2(multiplier).sqrt(),
+        // abs() turns -0 into 0
+        internal_angle: (multiplier.1.atan2(multiplier.0) / TAU)
+            .rem_euclid(1.0)
+            .abs(),
+        distance,
+    })
+}
+
+/// The nucleus of a hyperbolic component of period `period` near c: the
+/// parameter whose critical orbit is a superattracting cycle through 0, found
+/// with Newton's method on f^p(0) = 0 starting from c.
+///
+/// Returns the nucleus with its exact period, which divides `period`, or
+/// `None` if Newton's method doesn't converge.
+pub fn nucleus(c: (f64, f64), period: usize) -> Option<(usize, (f64, f64))> {
+    let period = period.max(1);
+    let mut c = c;
+    let converged = (0..NUCLEUS_STEPS).any(|_| {
+        // f^p(0) and its derivative with respect to c
+        let mut z = (0.0, 0.0);
+        let mut dc = (0.0, 0.0);
+        for _ in 0..period {
+            dc = add(scale(mul(z, dc), 2.0), (1.0, 0.0));
+            z = add(mul(z, z), c);
+        }
+        let delta = div(z, dc);
+        c = sub(c, delta);
+        abs2(delta) <= 1e-30 * abs2(c).max(1.0)
+    });
+    // NaN steps never count as converged
+    if !converged {
+        return None;
+    }
+    let mut z = (0.0, 0.0);
+    let exact = (1..=period).find(|_| {
+        z = add(mul(z, z), c);
+        abs2(z) < 1e-20
+    })?;
+    Some((exact, c))
+}
+
+/// The nucleus [`nucleus`] finds from c, for the period of c's attracting
+/// cycle if it is in the set, or otherwise the iteration at which its orbit
+/// comes closest to 0 (the period of the atom domain c is in).
+pub fn nearest_nucleus(c: (f64, f64), max_iter: usize) -> Option<(usize, (f64, f64))> {
+    let period = match interior(c, max_iter) {
+        Some(interior) => interior.period,
+        None => atom_domain(c, max_iter),
+    };
+    nucleus(c, period)
+}
+
+/// The period of the atom domain c is in: the iteration, within `max_iter`
+/// and before the orbit escapes, at which it comes closest to 0. The
+/// hyperbolic component of that period lies inside the domain. 0 if the
+/// orbit escapes at once.
+fn atom_domain(c: (f64, f64), max_iter: usize) -> usize {
+    let mut z = (0.0, 0.0);
+    let mut closest = (0, f64::INFINITY);
+    for n in 1..=MAX_PERIOD.min(max_iter) {
+        z = add(mul(z, z), c);
+        if abs2(z) >= ESCAPE_RADIUS * ESCAPE_RADIUS {
+            break;
+        }
+        if abs2(z) < closest.1 {
+            closest = (n, abs2(z));
+        }
+    }
+    closest.0
+}
+
+/// The hyperbolic component a point lies in, or the one it is nearest to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Component {
+    /// Its period.
+    pub period: usize,
+    /// Its center, where the cycle is superattracting.
+    pub nucleus: (f64, f64),
+    /// |λ| for the multiplier λ of the cycle of this period at the point:
+    /// below 1 inside the component, 0 at its nucleus and 1 on its boundary.
+    pub internal_radius: f64,
+    /// arg λ in turns, from 0 to 1: the internal angle of the point.
+    pub internal_angle: f64,
+}
+
+impl Component {
+    /// Whether the point lies inside the component.
+    pub fn contains(&self) -> bool {
+        self.internal_radius < 1.0
+    }
+}
+
+/// The hyperbolic component c lies in, or failing that the one whose atom
+/// domain it is in. The period is guessed from the atom domain, the nucleus
+/// of that period found with [`nucleus`], and the cycle of that period at c
+/// with Newton's method on f^p(z) = z from 0, giving its multiplier. If that
+/// cycle doesn't attract but c has an attracting cycle of another period
+/// within `max_iter` iterations, that period is taken instead.
+///
+/// Returns `None` if c escapes at once or Newton's method doesn't converge.
+pub fn component(c: (f64, f64), max_iter: usize) -> Option<Component> {
+    let found = component_of_period(c, atom_domain(c, max_iter));
+    if found.is_some_and(|found| found.contains()) {
+        return found;
+    }
+    match interior(c, max_iter) {
+        Some(interior) if found.is_none_or(|found| found.period != interior.period) => {
+            component_of_period(c, interior.period).or(found)
+        }
+        _ => found,
+    }
+}
+
+/// The component of period `period` near c, as [`component`] finds it.
+fn component_of_period(c: (f64, f64), period: usize) -> Option<Component> {
+    if period == 0 {
+        return None;
+    }
+    let (period, nucleus) = nucleus(c, period)?;
+    let mut z = (0.0, 0.0);
+    let converged = (0..CYCLE_STEPS).any(|_| {
+        let (fz, dz) = iterate_with_derivative(z, c, period);
+        let delta = div(sub(fz, z), sub(dz, (1.0, 0.0)));
+        z = sub(z, delta);
+        abs2(delta) <= 1e-28 * abs2(z).max(1.0)
+    });
+    if !converged {
+        return None;
+    }
+    let (_, multiplier) = iterate_with_derivative(z, c, period);
+    Some(Component {
+        period,
+        nucleus,
+        internal_radius: abs2(multiplier).sqrt(),
+        // abs() turns -0 into 0
+        internal_angle: (multiplier.1.atan2(multiplier.0) / TAU)
+            .rem_euclid(1.0)
+            .abs(),
+    })
+}
+
+/// The small copy of the set around a nucleus, as estimated from the
+/// nucleus alone.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Minibrot {
+    /// Period of the nucleus, the period of the minibrot's cardioid.
+    pub period: usize,
+    /// The nucleus, where the minibrot's 0 is.
+    pub nucleus: (f64, f64),
+    /// Its size relative to the whole set.
+    pub size: f64,
+    /// The angle the minibrot is turned by relative to the whole set, in
+    /// turns from 0 to 1.
+    pub orientation: f64,
+}
+
+impl Minibrot {
+    /// The standard estimate for the minibrot at `nucleus` of period
+    /// `period`. With λ the derivative with respect to z of the first p − 1
+    /// iterates of 0, and b one plus the sum of the reciprocals of its values
+    /// along the way, s = 1 / (b λ²) maps the whole set approximately onto
+    /// the minibrot: |s| is its size and arg s its orientation.
+    pub fn new(nucleus: (f64, f64), period: usize) -> Self {
+        let (mut z, mut l, mut b) = ((0.0, 0.0), (1.0, 0.0), (1.0, 0.0));
+        for _ in 1..period.max(1) {
+            z = add(mul(z, z), nucleus);
+            l = scale(mul(z, l), 2.0);
+            b = add(b, div((1.0, 0.0), l));
+        }
+        let factor = div((1.0, 0.0), mul(b, mul(l, l)));
+        Self {
+            period,
+            nucleus,
+            size: abs2(factor).sqrt(),
+            // abs() turns -0 into 0
+            orientation: (factor.1.atan2(factor.0) / TAU).rem_euclid(1.0).abs(),
+        }
+    }
+
+    /// The view that shows the minibrot as [`View::default`] shows the
+    /// whole set, apart from its orientation.
+    pub fn view(&self) -> View {
+        let whole = View::default();
+        let angle = self.orientation * TAU;
+        let factor = (self.size * angle.cos(), self.size * angle.sin());
+        View {
+            center: add(self.nucleus, mul(factor, whole.center)),
+            scale: whole.scale * self.size,
+        }
+    }
+}
+
+/// How points in the set are colored.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InteriorColoring {
+    /// Plain [`INTERIOR_COLOR`](crate::render::INTERIOR_COLOR).
+    #[default]
+    Black,
+    /// Dark at the boundary to light deep inside, by interior distance in
+    /// pixels.
+    Distance,
+    /// A color per period of the attracting cycle, showing the hyperbolic
+    /// components.
+    Period,
+}
+
+impl InteriorColoring {
+    /// Every interior coloring.
+    pub const ALL: [InteriorColoring; 3] = [
+        InteriorColoring::Black,
+        InteriorColoring::Distance,
+        InteriorColoring::Period,
+    ];
+
+    /// Human-readable name.
+    pub fn name(self) -> &'static str {
+        match self {
+            InteriorColoring::Black => "Black",
+            InteriorColoring::Distance => "Distance",
+            InteriorColoring::Period => "Period",
+        }
+    }
+}
+
+/// Repaints the pixels of `image` that `buffer` has in the set with
+/// `coloring`. Pixels whose cycle can't be found keep their color.
+pub fn color_interior(
+    image: &mut Image,
+    buffer: &IterationBuffer,
+    view: &View,
+    coloring: InteriorColoring,
+) {
+    if coloring == InteriorColoring::Black {
+        return;
+    }
+    let pixel = view.pixel_size(buffer.width);
+    for (i, &iter) in buffer.data.iter().enumerate() {
+        if !buffer.is_interior(iter as usize) {
+            continue;
+        }
+        let (x, y) = ((i % buffer.width) as f64, (i / buffer.width) as f64);
+        let c = view.pixel_to_complex(x, y, buffer.width, buffer.height);
+        let Some(interior) = interior(c, buffer.max_iter) else {
+            continue;
+        };
+        image.pixels[i] = match coloring {
+            InteriorColoring::Black => unreachable!(),
+            InteriorColoring::Distance => {
+                // Saturates a few dozen pixels from the boundary
+                let level = 1.0 - (-interior.distance / (32.0 * pixel)).exp();
+                let channel = |max: f64| (level * max) as u8;
+                [channel(128.0), channel(192.0), channel(255.0), 255]
+            }
+            InteriorColoring::Period => period_color(interior.period),
+        };
+    }
+}
+
+/// Which pixels of `buffer`, rendered from `view`, are in the set with an
+/// attracting cycle of period `period`: the hyperbolic components of that
+/// period in view.
+pub fn period_mask(buffer: &IterationBuffer, view: &View, period: usize) -> Vec<bool> {
+    (buffer.data.iter().enumerate())
+        .map(|(i, &iter)| {
+            if !buffer.is_interior(iter as usize) {
+                return false;
+            }
+            let (x, y) = ((i % buffer.width) as f64, (i / buffer.width) as f64);
+            let c = view.pixel_to_complex(x, y, buffer.width, buffer.height);
+            interior(c, buffer.max_iter).is_some_and(|interior| interior.period == period)
+        })
+        .collect()
+}
+
+/// A color for period `period`. Hues step by the golden angle, so nearby
+/// periods get clearly different colors.
+pub fn period_color(period: usize) -> [u8; 4] {
+    let hue = (period as f32 * 137.507_77) % 360.0;
+    let (r, g, b) = hsv_to_rgb(hue, 0.65, 0.9);
+    [r, g, b, 255]
+}
+
+/// `samples` points evenly spaced in internal angle around the boundary of
+/// the main cardioid, where the fixed point has multiplier e^(2πit): the
+/// points c = e^(2πit)/2 − e^(4πit)/4. The first point is repeated at the end
+/// to close the curve.
+pub fn main_cardioid(samples: usize) -> Vec<(f64, f64)> {
+    (0..=samples)
+        .map(|i| {
+            let (sin, cos) = (i as f64 / samples as f64 * TAU).sin_cos();
+            let (sin2, cos2) = (2.0 * i as f64 / samples as f64 * TAU).sin_cos();
+            (cos / 2.0 - cos2 / 4.0, sin / 2.0 - sin2 / 4.0)
+        })
+        .collect()
+}
+
+/// `samples` points around the boundary of the period 2 disk, the circle of
+/// radius 1/4 around −1, closed like [`main_cardioid`].
+pub fn period_two_disk(samples: usize) -> Vec<(f64, f64)> {
+    (0..=samples)
+        .map(|i| {
+            let (sin, cos) = (i as f64 / samples as f64 * TAU).sin_cos();
+            (-1.0 + cos / 4.0, sin / 4.0)
+        })
+        .collect()
+}
+
+/// f^p(z) and its derivative with respect to z.
+fn iterate_with_derivative(mut z: (f64, f64), c: (f64, f64), p: usize) -> ((f64, f64), (f64, f64)) {
+    let mut dz = (1.0, 0.0);
+    for _ in 0..p {
+        dz = scale(mul(z, dz), 2.0);
+        z = add(mul(z, z), c);
+    }
+    (z, dz)
+}
+
+/// Interior distance estimate for cycle point `z0` of period `p`, and the
+/// multiplier λ.
+fn distance_estimate(z0: (f64, f64), c: (f64, f64), p: usize) -> (f64, (f64, f64)) {
+    // Derivatives of f^p with respect to z and c, and their mixed and second
+    // z derivatives, as in the standard interior distance formula
+    let mut z = z0;
+    let mut dz = (1.0, 0.0);
+    let mut dc = (0.0, 0.0);
+    let mut dzdz = (0.0, 0.0);
+    let mut dcdz = (0.0, 0.0);
+    for _ in 0..p {
+        dcdz = scale(add(mul(z, dcdz), mul(dc, dz)), 2.0);
+        dzdz = scale(add(mul(dz, dz), mul(z, dzdz)), 2.0);
+        dc = add(scale(mul(z, dc), 2.0), (1.0, 0.0));
+        dz = scale(mul(z, dz), 2.0);
+        z = add(mul(z, z), c);
+    }
+    let denominator = add(dcdz, div(mul(dzdz, dc), sub((1.0, 0.0), dz)));
+    ((1.0 - abs2(dz)) / abs2(denominator).sqrt(), dz)
+}
+
+fn add(a: (f64, f64), b: (f64, f64)) -> (f64, f64) {
+    (a.0 + b.0, a.1 + b.1)
+}
+
+fn sub(a: (f64, f64), b: (f64, f64)) -> (f64, f64) {
+    (a.0 - b.0, a.1 - b.1)
+}
+
+fn mul(a: (f64, f64), b: (f64, f64)) -> (f64, f64) {
+    (a.0 * b.0 - a.1 * b.1, a.0 * b.1 + a.1 * b.0)
+}
+
+fn div(a: (f64, f64), b: (f64, f64)) -> (f64, f64) {
+    let d = abs2(b);
+    ((a.0 * b.0 + a.1 * b.1) / d, (a.1 * b.0 - a.0 * b.1) / d)
+}
+
+fn scale(a: (f64, f64), k: f64) -> (f64, f64) {
+    (a.0 * k, a.1 * k)
+}
+
+fn abs2(a: (f64, f64)) -> f64 {
+    a.0 * a.0 + a.1 * a.1
+}
@@ -0,0 +1,68 @@
+//! Adam7 interlacing: an order for computing pixels that covers the whole
+//! image coarsely first and then refines it evenly, as interlaced PNGs load.
+//!
+//! Each of the seven passes computes every pixel at some offset and spacing
+//! in 8×8 blocks. Once the first passes are done, every pixel can be shown
+//! with the color of a computed pixel at most [`block_size`] away.
+
+use std::{iter::StepBy, ops::Range};
+
+/// The pixels at `x0 + i·dx`, `y0 + j·dy`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pass {
+    /// Column of the first pixel.
+    pub x0: usize,
+    /// Row of the first pixel.
+    pub y0: usize,
+    /// Spacing between columns.
+    pub dx: usize,
+    /// Spacing between rows.
+    pub dy: usize,
+}
+
+/// The passes in order. Together they cover every pixel exactly once.
+pub const ADAM7: [Pass; 7] = [
+    Pass::new(0, 0, 8, 8),
+    Pass::new(4, 0, 8, 8),
+    Pass::new(0, 4, 4, 8),
+    Pass::new(2, 0, 4, 4),
+    Pass::new(0, 2, 2, 4),
+    Pass::new(1, 0, 2, 2),
+    Pass::new(0, 1, 1, 2),
+];
+
+impl Pass {
+    const fn new(x0: usize, y0: usize, dx: usize, dy: usize) -> Self {
+        Self { x0, y0, dx, dy }
+    }
+
+    /// Rows of a `height`-pixel image with pixels in this pass.
+    pub fn rows(&self, height: usize) -> StepBy<Range<usize>> {
+        (self.y0..height).step_by(self.dy)
+    }
+
+    /// Columns of a `width`-pixel image with pixels in this pass.
+    pub fn columns(&self, width: usize) -> StepBy<Range<usize>> {
+        (self.x0..width).step_by(self.dx)
+    }
+}
+
+/// Width and height of the blocks the computed pixels stand for once the
+/// first `passes` passes (at least one) are done: the pixel at the top-left
+/// corner of each block is computed.
+pub fn block_size(passes: usize) -> (usize, usize) {
+    const SIZES: [(usize, usize); 7] = [(8, 8), (4, 8), (4, 4), (2, 4), (2, 2), (1, 2), (1, 1)];
+    SIZES[passes.clamp(1, 7) - 1]
+}
+
+/// Copies the top-left pixel of each block over the rest of the block, in an
+/// image `width` pixels wide of which the first `passes` passes are done.
+pub fn fill_blocks(data: &mut [u32], width: usize, passes: usize) {
+    let (block_width, block_height) = block_size(passes);
+    for y in 0..data.len() / width.max(1) {
+        let source_row = y - y % block_height;
+        for x in 0..width {
+            data[y * width + x] = data[source_row * width + x - x % block_width];
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! Iteration of z → z² + c for a single point.
+
+use std::collections::HashMap;
+
+/// Iterates are considered escaped once |z| reaches this radius.
+pub const ESCAPE_RADIUS: f64 = 2.0;
+
+/// Longest cycle [`preperiodic`] looks for.
+pub const MAX_CYCLE: usize = 1024;
+/// How close iterates one period apart must be to count as the same point.
+const CYCLE_TOLERANCE: f64 = 1e-9;
+/// Times the cycle must go round, within tolerance, to count. Rounding errors
+/// grow along repelling cycles, so orbits that land on one, as at Misiurewicz
+/// points, stay on it for only so long.
+const CYCLE_REPEATS: usize = 3;
+
+/// Number of iterations of z → z² + c, starting from z = 0, before |z|
+/// reaches [`ESCAPE_RADIUS`]. Returns `max_iter` for points that never escape,
+/// which are taken to be in the set.
+pub fn escape_time(c: (f64, f64), max_iter: usize) -> usize {
+    let (cx, cy) = c;
+    let mut zx = 0.0;
+    let mut zy = 0.0;
+    let mut iter = 0;
+    while zx * zx + zy * zy < ESCAPE_RADIUS * ESCAPE_RADIUS && iter < max_iter {
+        let tmp = zx * zx - zy * zy + cx;
+        zy = 2.0 * zx * zy + cy;
+        zx = tmp;
+        iter += 1;
+    }
+    iter
+}
+
+/// Number of points [`escape_times_f32`] iterates at once.
+pub const LANES: usize = 8;
+
+/// [`escape_time`] of `LANES` points at once in single precision, written so
+/// the compiler can keep the lanes in SIMD registers. Points escape at the
+/// same iteration as in double precision except close to the boundary, where
+/// rounding errors add up; only use it where `f32` can tell the points apart
+/// (see [`View::fits_f32`](crate::View::fits_f32)).
+pub fn escape_times_f32(cx: [f32; LANES], cy: [f32; LANES], max_iter: usize) -> [u32; LANES] {
+    let radius = (ESCAPE_RADIUS * ESCAPE_RADIUS) as f32;
+    let mut zx = [0.0f32; LANES];
+    let mut zy = [0.0f32; LANES];
+    let mut iters = [0u32; LANES];
+    for _ in 0..max_iter {
+        let mut any_inside = false;
+        for i in 0..LANES {
+            let (x2, y2) = (zx[i] * zx[i], zy[i] * zy[i]);
+            let inside = x2 + y2 < radius;
+            // Escaped lanes keep their last z, so they stay escaped
+            let new_zx = x2 - y2 + cx[i];
+            let new_zy = 2.0 * zx[i] * zy[i] + cy[i];
+            zx[i] = if inside { new_zx } else { zx[i] };
+            zy[i] = if inside { new_zy } else { zy[i] };
+            iters[i] += inside as u32;
+            any_inside |= inside;
+        }
+        if !any_inside {
+            break;
+        }
+    }
+    iters
+}
+
+/// The iterates z₀ = 0, z₁, z₂, … of z → z² + c, up to and including the first
+/// one outside [`ESCAPE_RADIUS`], and at most `max_iter` of them.
+pub fn orbit(c: (f64, f64), max_iter: usize) -> Vec<(f64, f64)> {
+    let (cx, cy) = c;
+    let mut path = Vec::new();
+    let mut zx = 0.0;
+    let mut zy = 0.0;
+    for _ in 0..max_iter {
+        path.push((zx, zy));
+        if zx * zx + zy * zy >= ESCAPE_RADIUS * ESCAPE_RADIUS {
+            break;
+        }
+        let tmp = zx * zx - zy * zy + cx;
+        zy = 2.0 * zx * zy + cy;
+        zx = tmp;
+    }
+    path
+}
+
+/// Where an orbit falls into a cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Preperiodic {
+    /// Index of the first iterate on the cycle, counting z₀ = 0, so 0 for
+    /// orbits that are periodic from the start.
+    pub preperiod: usize,
+    /// Length of the cycle.
+    pub period: usize,
+}
+
+/// The smallest preperiod, and then the smallest period, after which the
+/// iterates in `path` (as from [`orbit`], or
+/// [`Formula::orbit`](crate::Formula::orbit)) repeat to within rounding, if
+/// they do within the path and with a period of at most [`MAX_CYCLE`]. Orbits
+/// attracted to a cycle count from when they are within rounding of it, and
+/// escaping orbits never do.
+///
+/// Candidates come from the iterates that return close to an earlier one,
+/// found by sorting the iterates into cells the size of the tolerance, so
+/// orbits that don't settle take time linear in their length.
+pub fn preperiodic(path: &[(f64, f64)]) -> Option<Preperiodic> {
+    let close = |a: (f64, f64), b: (f64, f64)| {
+        let (dx, dy) = (a.0 - b.0, a.1 - b.1);
+        dx * dx + dy * dy < CYCLE_TOLERANCE * CYCLE_TOLERANCE
+    };
+    let repeats = |preperiod: usize, period: usize| {
+        let end = preperiod + (CYCLE_REPEATS - 1) * period;
+        end + period <= path.len() && (preperiod..end).all(|n| close(path[n], path[n + period]))
+    };
+    let cell = |(x, y): (f64, f64)| {
+        (
+            (x / CYCLE_TOLERANCE).floor() as i64,
+            (y / CYCLE_TOLERANCE).floor() as i64,
+        )
+    };
+    // The last iterate in each cell
+    let mut last = HashMap::new();
+    let mut best: Option<Preperiodic> = None;
+    for (n, &z) in path.iter().enumerate() {
+        // Later returns can't start their cycle any earlier
+        if best.is_some_and(|best| n > best.preperiod + MAX_CYCLE) {
+            break;
+        }
+        let (x, y) = cell(z);
+        for neighbor in (x - 1..=x + 1).flat_map(|x| (y - 1..=y + 1).map(move |y| (x, y))) {
+            let Some(&earlier) = last.get(&neighbor) else {
+                continue;
+            };
+            let found = Preperiodic {
+                preperiod: earlier,
+                period: n - earlier,
+            };
+            if found.period <= MAX_CYCLE
+                && close(path[earlier], z)
+                && best.is_none_or(|best| {
+                    (found.preperiod, found.period) < (best.preperiod, best.period)
+                })
+                && repeats(found.preperiod, found.period)
+            {
+                best = Some(found);
+            }
+        }
+        last.insert((x, y), n);
+    }
+    best
+}
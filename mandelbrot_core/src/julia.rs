@@ -0,0 +1,68 @@
+//! Julia sets of z → z² + c: the dynamical plane for a fixed parameter c,
+//! iterated from each of its points rather than from the critical point, and
+//! paths of parameters to carry c along.
+
+use crate::{IterationBuffer, ParallelRenderer, RenderSettings, View, iterate::ESCAPE_RADIUS};
+
+/// Number of iterations of z → z² + c, starting from `z`, before |z| reaches
+/// [`ESCAPE_RADIUS`]. Returns `max_iter` for points that never escape, which
+/// are taken to be in the filled Julia set.
+pub fn escape_time(z: (f64, f64), c: (f64, f64), max_iter: usize) -> usize {
+    let (mut zx, mut zy) = z;
+    let mut iter = 0;
+    while zx * zx + zy * zy < ESCAPE_RADIUS * ESCAPE_RADIUS && iter < max_iter {
+        (zx, zy) = (zx * zx - zy * zy + c.0, 2.0 * zx * zy + c.1);
+        iter += 1;
+    }
+    iter
+}
+
+/// Whether the Julia set of `c` is connected, as far as `max_iter`
+/// iterations tell. It is exactly when the critical orbit, of 0, stays
+/// bounded, that is when c is in the Mandelbrot set, and is a Cantor dust of
+/// points otherwise.
+pub fn is_connected(c: (f64, f64), max_iter: usize) -> bool {
+    crate::escape_time(c, max_iter) == max_iter
+}
+
+/// Escape times of the Julia set for `c` over `view`, with the size and
+/// iteration limit `settings` give. Their formula is ignored.
+pub fn render_julia(
+    renderer: &ParallelRenderer,
+    view: &View,
+    settings: &RenderSettings,
+    c: (f64, f64),
+) -> IterationBuffer {
+    renderer.render_with(settings, |x, y| {
+        let z = view.pixel_to_complex(x as f64, y as f64, settings.width, settings.height);
+        escape_time(z, c, settings.max_iter) as u32
+    })
+}
+
+/// Length of the polyline through `points`.
+pub fn path_length(points: &[(f64, f64)]) -> f64 {
+    points.windows(2).map(|w| distance(w[0], w[1])).sum()
+}
+
+/// The point a fraction `t`, from 0 to 1, of the way along the polyline
+/// through `points`, measured by length so it moves at an even speed.
+/// `None` if there are no points.
+pub fn point_along(points: &[(f64, f64)], t: f64) -> Option<(f64, f64)> {
+    let mut left = t.clamp(0.0, 1.0) * path_length(points);
+    for w in points.windows(2) {
+        let length = distance(w[0], w[1]);
+        if left <= length && length > 0.0 {
+            let f = left / length;
+            return Some((
+                w[0].0 + f * (w[1].0 - w[0].0),
+                w[0].1 + f * (w[1].1 - w[0].1),
+            ));
+        }
+        left -= length;
+    }
+    points.last().copied()
+}
+
+fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (b.0 - a.0).hypot(b.1 - a.1)
+}
@@ -0,0 +1,237 @@
+//! Coloring in layers: each layer colors the view its own way, by escape
+//! time, smooth escape time, stripe average, or orbit traps, and the layers
+//! are blended over one another from the bottom up with a blend mode and
+//! opacity each.
+
+use crate::{
+    Formula, Image, IterationBuffer, Palette, View, colorize,
+    trap::{Trap, trap_color, trap_distance},
+};
+
+/// Stripe density of a stripe average layer when it is first added.
+pub const DEFAULT_STRIPE_DENSITY: f64 = 5.0;
+
+/// How a layer colors the view.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LayerSource {
+    /// Whole escape times through a palette, as [`colorize`] colors them,
+    /// points in the set included.
+    EscapeTime(Palette),
+    /// The smooth escape time through a palette.
+    Smooth(Palette),
+    /// The stripe average: the mean of ½ + ½ sin(density · arg z) over the
+    /// orbit, blended between its last two values by the fraction of the
+    /// smooth escape time so it doesn't band, through a palette.
+    Stripes {
+        /// Palette the average from 0 to 1 is colored with.
+        palette: Palette,
+        /// Stripes per turn of arg z.
+        density: f64,
+    },
+    /// The color of the orbit trap the orbit passes closest to, opaque on
+    /// it and fading out over the trap width away.
+    Traps,
+}
+
+impl LayerSource {
+    /// Human-readable name of the kind of source.
+    pub fn name(&self) -> &'static str {
+        match self {
+            LayerSource::EscapeTime(_) => "Escape time",
+            LayerSource::Smooth(_) => "Smooth",
+            LayerSource::Stripes { .. } => "Stripe average",
+            LayerSource::Traps => "Orbit traps",
+        }
+    }
+
+    /// The palette the layer is colored through, if it has one.
+    pub fn palette_mut(&mut self) -> Option<&mut Palette> {
+        match self {
+            LayerSource::EscapeTime(palette)
+            | LayerSource::Smooth(palette)
+            | LayerSource::Stripes { palette, .. } => Some(palette),
+            LayerSource::Traps => None,
+        }
+    }
+}
+
+/// How a layer's colors combine with those of the layers below.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BlendMode {
+    /// The layer's color.
+    #[default]
+    Normal,
+    /// The product of both, which only darkens.
+    Multiply,
+    /// The inverse of the product of the inverses, which only lightens.
+    Screen,
+    /// Multiply where the colors below are dark and screen where they are
+    /// light, which adds contrast.
+    Overlay,
+}
+
+impl BlendMode {
+    /// Every blend mode.
+    pub const ALL: [BlendMode; 4] = [
+        BlendMode::Normal,
+        BlendMode::Multiply,
+        BlendMode::Screen,
+        BlendMode::Overlay,
+    ];
+
+    /// Human-readable name.
+    pub fn name(self) -> &'static str {
+        match self {
+            BlendMode::Normal => "Normal",
+            BlendMode::Multiply => "Multiply",
+            BlendMode::Screen => "Screen",
+            BlendMode::Overlay => "Overlay",
+        }
+    }
+
+    /// Channel `top`, from 0 to 1, blended onto `below`.
+    fn blend(self, below: f32, top: f32) -> f32 {
+        match self {
+            BlendMode::Normal => top,
+            BlendMode::Multiply => below * top,
+            BlendMode::Screen => 1.0 - (1.0 - below) * (1.0 - top),
+            BlendMode::Overlay if below < 0.5 => 2.0 * below * top,
+            BlendMode::Overlay => 1.0 - 2.0 * (1.0 - below) * (1.0 - top),
+        }
+    }
+}
+
+/// One layer of the coloring.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Layer {
+    /// How it colors the view.
+    pub source: LayerSource,
+    /// How it combines with the layers below.
+    pub mode: BlendMode,
+    /// How much of it shows, from 0 to 1.
+    pub opacity: f32,
+    /// Whether it is drawn at all.
+    pub visible: bool,
+}
+
+impl Layer {
+    /// A fully opaque, visible layer blended normally.
+    pub fn new(source: LayerSource) -> Self {
+        Self {
+            source,
+            mode: BlendMode::Normal,
+            opacity: 1.0,
+            visible: true,
+        }
+    }
+
+    /// The layer's colors for `view` as rendered into `buffer` with
+    /// `formula`. Layers other than escape time leave points that don't
+    /// escape transparent.
+    fn image(
+        &self,
+        buffer: &IterationBuffer,
+        view: &View,
+        formula: Formula,
+        traps: &[Trap],
+        trap_width: f64,
+    ) -> Image {
+        if let LayerSource::EscapeTime(palette) = self.source {
+            return colorize(buffer, palette);
+        }
+        let max_iter = buffer.max_iter;
+        let pixels = (buffer.data.iter().enumerate())
+            .map(|(i, &iter)| {
+                const CLEAR: [u8; 4] = [0; 4];
+                if buffer.is_interior(iter as usize) {
+                    return CLEAR;
+                }
+                let (x, y) = ((i % buffer.width) as f64, (i / buffer.width) as f64);
+                let c = view.pixel_to_complex(x, y, buffer.width, buffer.height);
+                match self.source {
+                    LayerSource::EscapeTime(_) => unreachable!(),
+                    LayerSource::Smooth(palette) => formula
+                        .smooth_escape_time(c, max_iter)
+                        .map_or(CLEAR, |smooth| palette.color_at(smooth / max_iter as f64)),
+                    LayerSource::Stripes { palette, density } => {
+                        stripe_average(formula, c, max_iter, density)
+                            .map_or(CLEAR, |average| palette.color_at(average))
+                    }
+                    LayerSource::Traps => {
+                        let Some((trap, distance)) =
+                            trap_distance(c, formula, iter as usize, traps)
+                        else {
+                            return CLEAR;
+                        };
+                        let [r, g, b, _] = trap_color(trap);
+                        let glow = (-distance / trap_width).exp();
+                        [r, g, b, (glow * 255.0).round() as u8]
+                    }
+                }
+            })
+            .collect();
+        Image {
+            width: buffer.width,
+            height: buffer.height,
+            pixels,
+        }
+    }
+}
+
+/// The stripe average of c's orbit under `formula`, from 0 to 1, or `None`
+/// if it doesn't escape within `max_iter` iterations.
+pub fn stripe_average(
+    formula: Formula,
+    c: (f64, f64),
+    max_iter: usize,
+    density: f64,
+) -> Option<f64> {
+    let smooth = formula.smooth_escape_time(c, max_iter)?;
+    let escaped = formula.escape_time(c, max_iter).max(1);
+    let (mut sum, mut last) = (0.0, 0.0);
+    for (x, y) in formula.iterates(c).skip(1).take(escaped) {
+        last = 0.5 + 0.5 * (density * y.atan2(x)).sin();
+        sum += last;
+    }
+    let count = escaped as f64;
+    let average = sum / count;
+    let before = if escaped > 1 {
+        (sum - last) / (count - 1.0)
+    } else {
+        average
+    };
+    let fraction = (smooth - count).clamp(0.0, 1.0);
+    Some(before + (average - before) * fraction)
+}
+
+/// Colors `view`, rendered into `buffer` with `formula`, with the visible
+/// `layers` blended from the first up over black. Trap layers use `traps`,
+/// fading over `trap_width`.
+pub fn composite(
+    layers: &[Layer],
+    buffer: &IterationBuffer,
+    view: &View,
+    formula: Formula,
+    traps: &[Trap],
+    trap_width: f64,
+) -> Image {
+    let mut image = Image::new(buffer.width, buffer.height, [0, 0, 0, 255]);
+    for layer in layers.iter().filter(|layer| layer.visible) {
+        let top = layer.image(buffer, view, formula, traps, trap_width);
+        for (pixel, over) in image.pixels.iter_mut().zip(&top.pixels) {
+            let alpha = layer.opacity.clamp(0.0, 1.0) * f32::from(over[3]) / 255.0;
+            if alpha == 0.0 {
+                continue;
+            }
+            for (channel, &top) in pixel[..3].iter_mut().zip(&over[..3]) {
+                let below = f32::from(*channel) / 255.0;
+                let blended = layer.mode.blend(below, f32::from(top) / 255.0);
+                *channel = ((below + (blended - below) * alpha) * 255.0).round() as u8;
+            }
+        }
+    }
+    image
+}
@@ -0,0 +1,76 @@
+//! Math and rendering for exploring the Mandelbrot set, independent of any GUI.
+//!
+//! A [`View`] maps between pixels and points of the complex plane, a
+//! [`Renderer`] computes escape times for every pixel of a view, and
+//! [`colorize`] turns those into an RGBA [`Image`] using a [`Palette`], or
+//! [`colorize_smooth`] renders a smoothly colored 16-bit [`Image16`].
+//! [`orbit`] gives the sequence of iterates for a single point, and
+//! [`iterate::preperiodic`] where it falls into a cycle. [`formula`] has
+//! other maps to iterate than z → z² + c, [`hybrid`] the patterns of maps
+//! hybrid fractals alternate between, and [`golden`] has reproducible renders
+//! and hashes for image comparison tests.
+//! [`interior`] finds the attracting cycle of points in the set and estimates
+//! their distance to the boundary, as well as the nuclei of components,
+//! [`distance`] estimates the distance for points outside it, [`area`]
+//! estimates the set's area by sampling, and [`dimension`] the boundary's
+//! fractal dimension by box counting.
+//! [`real`] covers the one-dimensional dynamics along the real axis, and
+//! [`angles`] external angles and rotation numbers. [`overlay`] draws field
+//! lines and equipotentials over rendered images, [`shading`] lights them as
+//! relief and darkens their creases, [`trap`] tints them by how close orbits
+//! pass to shapes in the plane, [`layers`] blends several colorings, and
+//! [`detail`] finds their most structured parts. [`stats`] summarizes a
+//! view's escape times, and [`interlace`] orders pixels for progressive
+//! display. [`sphere`] maps the plane onto the Riemann sphere, and [`julia`]
+//! renders the Julia sets of parameters. [`timeline`] animates the view and
+//! the formula's parameters between keyframes, and [`svg`] writes figures
+//! drawn over a view as SVG.
+//!
+//! ```
+//! use mandelbrot_core::{Palette, View, render};
+//!
+//! let image = render(&View::default(), 64, 64, 100, Palette::Rainbow);
+//! assert_eq!(image.pixels.len(), 64 * 64);
+//! ```
+
+#![warn(missing_docs)]
+
+pub mod angles;
+pub mod area;
+pub mod detail;
+pub mod dimension;
+pub mod distance;
+pub mod formula;
+pub mod golden;
+pub mod hybrid;
+pub mod image;
+pub mod interior;
+pub mod interlace;
+pub mod iterate;
+pub mod julia;
+pub mod layers;
+pub mod overlay;
+pub mod palette;
+pub mod random;
+pub mod real;
+pub mod render;
+pub mod shading;
+pub mod sphere;
+pub mod stats;
+pub mod svg;
+pub mod timeline;
+pub mod trap;
+pub mod view;
+
+pub use formula::Formula;
+pub use image::{Dither, Image, Image16};
+pub use iterate::{escape_time, orbit};
+pub use palette::Palette;
+pub use render::{
+    FastRenderer, IterationBuffer, ParallelRenderer, RenderSettings, Renderer, ScalarRenderer,
+    Tile, colorize, colorize_smooth, colorize_stretched, render,
+};
+pub use view::View;
+
+/// Iteration limit used when none is specified.
+pub const DEFAULT_MAX_ITER: usize = 100;
@@ -0,0 +1,123 @@
+//! Field lines and equipotential curves drawn over a rendered image.
+
+use crate::{
+    Image, IterationBuffer, View,
+    angles::{escaping_orbit, trace_angle},
+};
+
+/// Color the curves are blended towards.
+pub const OVERLAY_COLOR: [u8; 4] = [255, 255, 255, 255];
+/// How far curve pixels are blended towards [`OVERLAY_COLOR`], 0 to 1.
+const OVERLAY_OPACITY: f32 = 0.7;
+
+/// Which curves to draw outside the set.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Overlay {
+    /// Number of field lines, curves of constant external angle k / n, evenly
+    /// spaced in angle. 0 draws none.
+    pub field_lines: usize,
+    /// Whether to draw equipotential curves, one wherever the smooth escape
+    /// time passes an integer.
+    pub equipotentials: bool,
+}
+
+impl Overlay {
+    /// Whether nothing is drawn.
+    pub fn is_empty(&self) -> bool {
+        self.field_lines == 0 && !self.equipotentials
+    }
+}
+
+/// Smooth escape time and external angle of a point outside the set.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EscapeData {
+    /// Escape time interpolated between iterations from how far the last
+    /// iterate overshot, so that its level sets are smooth curves.
+    pub smooth_iter: f64,
+    /// Angle of the external ray through the point, in turns.
+    pub angle: f64,
+}
+
+/// Smooth escape time and external angle of c, or `None` if it doesn't
+/// escape within `max_iter` iterations.
+pub fn escape_data(c: (f64, f64), max_iter: usize) -> Option<EscapeData> {
+    escape_data_with(c, max_iter, &mut Vec::new())
+}
+
+/// [`escape_data`] with a reusable orbit buffer.
+fn escape_data_with(
+    c: (f64, f64),
+    max_iter: usize,
+    path: &mut Vec<(f64, f64)>,
+) -> Option<EscapeData> {
+    if !escaping_orbit(c, max_iter, path) {
+        return None;
+    }
+    let (x, y) = path[path.len() - 1];
+    let smooth_iter = path.len() as f64 + 1.0 - x.hypot(y).ln().log2();
+    Some(EscapeData {
+        smooth_iter,
+        angle: trace_angle(path, |_| {}),
+    })
+}
+
+/// Draws `overlay` onto `image`, which shows `view` as rendered into
+/// `buffer`. Curves are edges between neighboring pixels on different sides
+/// of a level, skipped where levels are closer together than pixels.
+pub fn draw_overlay(image: &mut Image, buffer: &IterationBuffer, view: &View, overlay: Overlay) {
+    if overlay.is_empty() {
+        return;
+    }
+    let (width, height) = (buffer.width, buffer.height);
+    let mut path = Vec::new();
+    let data: Vec<Option<EscapeData>> = buffer
+        .data
+        .iter()
+        .enumerate()
+        .map(|(i, &iter)| {
+            if buffer.is_interior(iter as usize) {
+                return None;
+            }
+            let (x, y) = ((i % width) as f64, (i / width) as f64);
+            let c = view.pixel_to_complex(x, y, width, height);
+            escape_data_with(c, buffer.max_iter, &mut path)
+        })
+        .collect();
+    let lines = overlay.field_lines as f64;
+    let crosses = |a: &EscapeData, b: &EscapeData| {
+        let field_line = overlay.field_lines > 0 && {
+            let (a, b) = (a.angle * lines, b.angle * lines);
+            // Shortest way round, so the angle wrapping from 1 to 0 counts
+            let delta = (b - a + lines / 2.0).rem_euclid(lines) - lines / 2.0;
+            delta.abs() < 1.0 && (a + delta).floor() != a.floor()
+        };
+        let equipotential = overlay.equipotentials && {
+            let delta = b.smooth_iter - a.smooth_iter;
+            delta.abs() < 1.0 && b.smooth_iter.floor() != a.smooth_iter.floor()
+        };
+        field_line || equipotential
+    };
+    for y in 0..height {
+        for x in 0..width {
+            let Some(here) = &data[y * width + x] else {
+                continue;
+            };
+            let right = (x + 1 < width)
+                .then(|| data[y * width + x + 1].as_ref())
+                .flatten();
+            let below = (y + 1 < height)
+                .then(|| data[(y + 1) * width + x].as_ref())
+                .flatten();
+            if right.is_some_and(|right| crosses(here, right))
+                || below.is_some_and(|below| crosses(here, below))
+            {
+                let pixel = &mut image.pixels[y * width + x];
+                for (channel, target) in pixel.iter_mut().zip(OVERLAY_COLOR) {
+                    let value =
+                        *channel as f32 + (target as f32 - *channel as f32) * OVERLAY_OPACITY;
+                    *channel = value.round() as u8;
+                }
+            }
+        }
+    }
+}
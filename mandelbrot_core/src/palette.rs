@@ -0,0 +1,168 @@
+//! Color palettes for points outside the set.
+
+use crate::random::SplitMix64;
+
+/// Color stops of a [`Palette::Random`] gradient.
+const RANDOM_STOPS: usize = 5;
+
+/// Maps escape times of points outside the set to colors.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Palette {
+    /// Full hue circle, from red for slow escapes through to red again.
+    #[default]
+    Rainbow,
+    /// Black through red and orange to pale yellow.
+    Fire,
+    /// Deep blue through cyan to white.
+    Ocean,
+    /// Black to white.
+    Grayscale,
+    /// A gradient made up from `seed`, the same for the same seed: from dark
+    /// to light through hues a little apart, so escape times still read in
+    /// order.
+    Random {
+        /// What the gradient is made from.
+        seed: u32,
+    },
+}
+
+impl Palette {
+    /// Every built-in palette, in the order of their [`Palette::index`].
+    pub const ALL: [Palette; 4] = [
+        Palette::Rainbow,
+        Palette::Fire,
+        Palette::Ocean,
+        Palette::Grayscale,
+    ];
+
+    /// The [`Palette::index`] of every random palette.
+    pub const RANDOM_INDEX: u8 = 4;
+
+    /// Human-readable name.
+    pub fn name(self) -> &'static str {
+        match self {
+            Palette::Rainbow => "Rainbow",
+            Palette::Fire => "Fire",
+            Palette::Ocean => "Ocean",
+            Palette::Grayscale => "Grayscale",
+            Palette::Random { .. } => "Random",
+        }
+    }
+
+    /// Stable numeric id, used by compact encodings such as permalinks.
+    /// Random palettes share [`Palette::RANDOM_INDEX`] and need their seed
+    /// stored beside it.
+    pub fn index(self) -> u8 {
+        match self {
+            Palette::Rainbow => 0,
+            Palette::Fire => 1,
+            Palette::Ocean => 2,
+            Palette::Grayscale => 3,
+            Palette::Random { .. } => Self::RANDOM_INDEX,
+        }
+    }
+
+    /// The built-in palette with the given [`Palette::index`], if any.
+    pub fn from_index(index: u8) -> Option<Self> {
+        Self::ALL.get(index as usize).copied()
+    }
+
+    /// Color of a point that escaped after `iter` of `max_iter` iterations.
+    pub fn color(self, iter: usize, max_iter: usize) -> [u8; 4] {
+        let [r, g, b] = self.channels(iter as f32 / max_iter as f32);
+        [r, g, b, 255.0].map(|channel| channel.round() as u8)
+    }
+
+    /// Color a fraction `t` from 0 to 1 of the way through the palette, for
+    /// values other than escape times.
+    pub fn color_at(self, t: f64) -> [u8; 4] {
+        let [r, g, b] = self.channels(t as f32);
+        [r, g, b, 255.0].map(|channel| channel.round() as u8)
+    }
+
+    /// Color of a point that escaped after a fractional `iter` of `max_iter`
+    /// iterations, such as a smooth escape time, at 16 bits per channel so
+    /// that gradients between iterations don't band.
+    pub fn color16(self, iter: f64, max_iter: usize) -> [u16; 4] {
+        let [r, g, b] = self.channels((iter / max_iter as f64) as f32);
+        // 65535 = 255 × 257, so the 8-bit colors keep their exact values
+        [r, g, b, 255.0].map(|channel| (channel * 257.0).round() as u16)
+    }
+
+    /// Red, green, and blue from 0 to 255, unrounded, a fraction `t` of the
+    /// way through the palette.
+    fn channels(self, t: f32) -> [f32; 3] {
+        match self {
+            Palette::Rainbow => {
+                // Map t to hue (0..360) for a rainbow spectrum
+                let hue = (1.0 - t) * 360.0;
+                hsv_channels(hue, 1.0, 1.0)
+            }
+            Palette::Fire => gradient(
+                &[(0, 0, 0), (180, 20, 0), (255, 160, 0), (255, 255, 200)],
+                t.sqrt(),
+            ),
+            Palette::Ocean => gradient(
+                &[(0, 7, 40), (0, 80, 160), (40, 200, 220), (240, 255, 255)],
+                t.sqrt(),
+            ),
+            Palette::Grayscale => gradient(&[(0, 0, 0), (255, 255, 255)], t.sqrt()),
+            Palette::Random { seed } => gradient(&random_stops(seed), t.sqrt()),
+        }
+    }
+}
+
+/// The color stops of the random palette made from `seed`. Brightness rises
+/// steadily from near black to near white, hues turn one way by 20° to 70° a
+/// stop from a random start, and saturation fades towards the last stop, so
+/// that any seed gives a gradient in the manner of the built-in ones.
+fn random_stops(seed: u32) -> [(u8, u8, u8); RANDOM_STOPS] {
+    let mut rng = SplitMix64::new(seed.into());
+    let mut random = |low: f32, high: f32| low + (high - low) * rng.next_f64() as f32;
+    let mut hue = random(0.0, 360.0);
+    let turn = random(20.0, 70.0) * if random(0.0, 1.0) < 0.5 { -1.0 } else { 1.0 };
+    let (dark, light) = (random(0.0, 0.15), random(0.85, 1.0));
+    let saturation = random(0.6, 1.0);
+    std::array::from_fn(|i| {
+        let f = i as f32 / (RANDOM_STOPS - 1) as f32;
+        let value = dark + (light - dark) * f;
+        let stop_hue = hue.rem_euclid(360.0);
+        hue += turn;
+        let (r, g, b) = hsv_to_rgb(stop_hue, saturation * (1.0 - 0.7 * f * f), value);
+        (r, g, b)
+    })
+}
+
+/// Linearly interpolates between evenly spaced color stops, `t` in 0..=1.
+fn gradient(stops: &[(u8, u8, u8)], t: f32) -> [f32; 3] {
+    let pos = t.clamp(0.0, 1.0) * (stops.len() - 1) as f32;
+    let i = (pos as usize).min(stops.len() - 2);
+    let f = pos - i as f32;
+    let lerp = |a: u8, b: u8| a as f32 + (b as f32 - a as f32) * f;
+    let (a, b) = (stops[i], stops[i + 1]);
+    [lerp(a.0, b.0), lerp(a.1, b.1), lerp(a.2, b.2)]
+}
+
+pub(crate) fn hsv_to_rgb(h: f32, s: f32, v: f32) -> (u8, u8, u8) {
+    let [r, g, b] = hsv_channels(h, s, v).map(|channel| channel.round() as u8);
+    (r, g, b)
+}
+
+/// Red, green, and blue from 0 to 255, unrounded, of a color given by hue in
+/// degrees, saturation, and value.
+fn hsv_channels(h: f32, s: f32, v: f32) -> [f32; 3] {
+    let c = v * s;
+    let x = c * (1.0 - ((h / 60.0) % 2.0 - 1.0).abs());
+    let m = v - c;
+    let (r1, g1, b1) = match h as u32 {
+        0..=59 => (c, x, 0.0),
+        60..=119 => (x, c, 0.0),
+        120..=179 => (0.0, c, x),
+        180..=239 => (0.0, x, c),
+        240..=299 => (x, 0.0, c),
+        300..=359 => (c, 0.0, x),
+        _ => (0.0, 0.0, 0.0),
+    };
+    [r1 + m, g1 + m, b1 + m].map(|channel| channel * 255.0)
+}
@@ -0,0 +1,65 @@
+//! Dynamics of x → x² + c for real c: cobweb diagrams and the bifurcation
+//! diagram, the one-dimensional picture of the set's real axis.
+
+use crate::{Image, iterate::ESCAPE_RADIUS};
+
+/// Range of c shown by [`bifurcation_diagram`], the real axis of the set.
+pub const BIFURCATION_RANGE: (f64, f64) = (-2.0, 0.25);
+
+/// Corners of the cobweb diagram of x → x² + c from x₀ = 0: each step goes
+/// vertically from (x, x) to the graph at (x, f(x)), then horizontally back to
+/// the diagonal at (f(x), f(x)). Starts at (0, 0) and takes at most `steps`
+/// steps, ending after the first x outside [`ESCAPE_RADIUS`].
+pub fn cobweb(c: f64, steps: usize) -> Vec<(f64, f64)> {
+    let mut x: f64 = 0.0;
+    let mut path = vec![(x, x)];
+    for _ in 0..steps {
+        let next = x * x + c;
+        path.push((x, next));
+        path.push((next, next));
+        if next.abs() > ESCAPE_RADIUS {
+            break;
+        }
+        x = next;
+    }
+    path
+}
+
+/// The bifurcation diagram: c across [`BIFURCATION_RANGE`] from left to
+/// right and x from 2 at the top to -2 at the bottom. For each column's c,
+/// the orbit of 0 is iterated `warmup` times and the next `samples` values
+/// are plotted, brighter where they fall more often. Unplotted pixels are
+/// transparent.
+pub fn bifurcation_diagram(width: usize, height: usize, warmup: usize, samples: usize) -> Image {
+    let mut counts = vec![0u32; width * height];
+    let (c_min, c_max) = BIFURCATION_RANGE;
+    for column in 0..width {
+        let c = c_min + (c_max - c_min) * (column as f64 + 0.5) / width as f64;
+        let mut x: f64 = 0.0;
+        for _ in 0..warmup {
+            x = x * x + c;
+        }
+        for _ in 0..samples {
+            x = x * x + c;
+            let row = ((ESCAPE_RADIUS - x) / (2.0 * ESCAPE_RADIUS) * height as f64).floor();
+            if (0.0..height as f64).contains(&row) {
+                counts[row as usize * width + column] += 1;
+            }
+        }
+    }
+    // Attracting cycles put all samples into a few pixels; chaotic orbits
+    // spread them, so scale brightness by a fraction of the samples
+    let full = (samples / 20).max(1) as f32;
+    let pixels = counts
+        .into_iter()
+        .map(|count| {
+            let alpha = (count as f32 / full).sqrt().min(1.0);
+            [255, 255, 255, (alpha * 255.0) as u8]
+        })
+        .collect();
+    Image {
+        width,
+        height,
+        pixels,
+    }
+}
@@ -0,0 +1,434 @@
+//! Rendering whole views: escape times per pixel, then colors.
+
+use std::{num::NonZeroUsize, ops::RangeInclusive, sync::Mutex, thread};
+
+use tracing::{debug_span, trace_span};
+
+use crate::{
+    Formula, Image, Image16, Palette, View,
+    iterate::{LANES, escape_times_f32},
+};
+
+/// Color of points in the set.
+pub const INTERIOR_COLOR: [u8; 4] = [0, 0, 0, 255];
+
+/// Flag on [`IterationBuffer`] values of points whose orbit converged to a
+/// fixed point instead of escaping, over the iterations it took. Flagged
+/// values are at least the iteration limit, so anything that doesn't look for
+/// the flag takes these points to be in the set.
+pub const CONVERGED: u32 = 1 << 31;
+
+/// What to render, apart from where: image size, iteration limit, and
+/// formula.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RenderSettings {
+    /// Image width in pixels.
+    pub width: usize,
+    /// Image height in pixels.
+    pub height: usize,
+    /// Iteration limit; points that haven't escaped by then are in the set.
+    pub max_iter: usize,
+    /// The map iterated.
+    pub formula: Formula,
+}
+
+/// A rectangle of pixels within an image, for rendering part of a view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tile {
+    /// Left edge in pixels.
+    pub x: usize,
+    /// Top edge in pixels.
+    pub y: usize,
+    /// Width in pixels.
+    pub width: usize,
+    /// Height in pixels.
+    pub height: usize,
+}
+
+impl Tile {
+    /// The whole image.
+    pub fn full(settings: &RenderSettings) -> Self {
+        Self {
+            x: 0,
+            y: 0,
+            width: settings.width,
+            height: settings.height,
+        }
+    }
+}
+
+/// Escape time of every pixel of a view, stored row by row from the top-left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IterationBuffer {
+    /// Width in pixels.
+    pub width: usize,
+    /// Height in pixels.
+    pub height: usize,
+    /// Iteration limit the buffer was computed with. Pixels with this count
+    /// never escaped.
+    pub max_iter: usize,
+    /// `width * height` escape times as returned by
+    /// [`Formula::buffer_value`].
+    pub data: Vec<u32>,
+}
+
+impl IterationBuffer {
+    /// Escape time of pixel `(x, y)`.
+    pub fn get(&self, x: usize, y: usize) -> usize {
+        self.data[y * self.width + x] as usize
+    }
+
+    /// Whether an escape time from this buffer belongs to a point in the set.
+    pub fn is_interior(&self, iter: usize) -> bool {
+        iter >= self.max_iter
+    }
+
+    /// The iterations a point with value `iter` took to converge, if its
+    /// orbit converged.
+    pub fn converged(&self, iter: usize) -> Option<usize> {
+        (iter as u32 & CONVERGED != 0).then_some(iter & !(CONVERGED as usize))
+    }
+}
+
+/// A way of computing iteration buffers.
+///
+/// Implementations must agree on what they compute, but may differ in speed
+/// and precision. They record a `render` [`tracing`] span per call.
+pub trait Renderer {
+    /// Short human-readable name of the backend.
+    fn name(&self) -> &'static str;
+
+    /// Computes the escape time of every pixel of `view`.
+    fn render(&self, view: &View, settings: &RenderSettings) -> IterationBuffer;
+}
+
+/// Renders on the calling thread, one pixel at a time.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ScalarRenderer;
+
+impl Renderer for ScalarRenderer {
+    fn name(&self) -> &'static str {
+        "CPU"
+    }
+
+    fn render(&self, view: &View, settings: &RenderSettings) -> IterationBuffer {
+        let _span = render_span(self, settings).entered();
+        let mut data = vec![0; settings.width * settings.height];
+        for (y, row) in data.chunks_mut(settings.width.max(1)).enumerate() {
+            render_row(view, settings, 0, y, row);
+        }
+        buffer(settings, data)
+    }
+}
+
+/// Renders rows on several threads, which take the next unrendered row as they
+/// become free. With a single thread it renders on the calling thread, so it
+/// also works on targets without threads such as `wasm32-unknown-unknown`.
+#[derive(Debug, Clone, Copy)]
+pub struct ParallelRenderer {
+    /// Number of worker threads.
+    pub threads: NonZeroUsize,
+}
+
+impl Default for ParallelRenderer {
+    /// One thread per available core, or one if that can't be determined.
+    fn default() -> Self {
+        Self {
+            threads: thread::available_parallelism().unwrap_or(NonZeroUsize::MIN),
+        }
+    }
+}
+
+impl Renderer for ParallelRenderer {
+    fn name(&self) -> &'static str {
+        "CPU (parallel)"
+    }
+
+    fn render(&self, view: &View, settings: &RenderSettings) -> IterationBuffer {
+        if self.threads.get() == 1 {
+            return ScalarRenderer.render(view, settings);
+        }
+        let _span = render_span(self, settings).entered();
+        buffer(
+            settings,
+            self.render_tile(view, settings, &Tile::full(settings)),
+        )
+    }
+}
+
+impl ParallelRenderer {
+    /// Computes the escape times of the pixels in `tile` of the image
+    /// described by `settings`, row by row. They equal the corresponding
+    /// pixels of a full render.
+    pub fn render_tile(&self, view: &View, settings: &RenderSettings, tile: &Tile) -> Vec<u32> {
+        self.render_rows(view, settings, tile, render_row)
+    }
+
+    /// Renders an image the size `settings` give whose pixels aren't points
+    /// of a view, such as a projection of the plane, computing each pixel's
+    /// value with `pixel(x, y)`.
+    pub fn render_with(
+        &self,
+        settings: &RenderSettings,
+        pixel: impl Fn(usize, usize) -> u32 + Sync,
+    ) -> IterationBuffer {
+        let _span = render_span(self, settings).entered();
+        let render_row = |_: &View, _: &RenderSettings, x0: usize, y: usize, row: &mut [u32]| {
+            for (i, value) in row.iter_mut().enumerate() {
+                *value = pixel(x0 + i, y);
+            }
+        };
+        let data = self.render_rows(
+            &View::default(),
+            settings,
+            &Tile::full(settings),
+            render_row,
+        );
+        buffer(settings, data)
+    }
+
+    /// Computes `value(c)` for the point c at the center of every pixel of
+    /// `view` rendered as `settings` say, row by row, such as a smooth escape
+    /// time or a distance estimate. Points without a value get NaN.
+    pub fn render_values(
+        &self,
+        view: &View,
+        settings: &RenderSettings,
+        value: impl Fn((f64, f64)) -> Option<f32> + Sync,
+    ) -> Vec<f32> {
+        // Values as the bits of an f32
+        let buffer = self.render_with(settings, |x, y| {
+            let c = view.pixel_to_complex(x as f64, y as f64, settings.width, settings.height);
+            value(c).unwrap_or(f32::NAN).to_bits()
+        });
+        buffer.data.into_iter().map(f32::from_bits).collect()
+    }
+
+    /// Renders the rows of `tile` with `render_row`, spread over the threads.
+    fn render_rows(
+        &self,
+        view: &View,
+        settings: &RenderSettings,
+        tile: &Tile,
+        render_row: impl Fn(&View, &RenderSettings, usize, usize, &mut [u32]) + Sync,
+    ) -> Vec<u32> {
+        let mut data = vec![0; tile.width * tile.height];
+        if self.threads.get() == 1 {
+            for (i, row) in data.chunks_mut(tile.width.max(1)).enumerate() {
+                render_row(view, settings, tile.x, tile.y + i, row);
+            }
+            return data;
+        }
+        let rows = Mutex::new(data.chunks_mut(tile.width.max(1)).enumerate());
+        let span = tracing::Span::current();
+        thread::scope(|scope| {
+            for _ in 0..self.threads.get() {
+                scope.spawn(|| {
+                    let _span = trace_span!(parent: &span, "worker").entered();
+                    loop {
+                        let Some((i, row)) = rows.lock().unwrap().next() else {
+                            break;
+                        };
+                        render_row(view, settings, tile.x, tile.y + i, row);
+                    }
+                });
+            }
+        });
+        data
+    }
+}
+
+/// Renders in single precision, several pixels at a time on each of the
+/// [`ParallelRenderer`]'s threads, at shallow zooms, and like the
+/// `ParallelRenderer` beyond and for formulas other than z → z² + c. About
+/// twice as fast at overview zooms, but escape times differ from the other
+/// renderers at some pixels near the boundary, so it's not for reproducible
+/// renders.
+///
+/// Rounding errors grow with every iteration, so the zoom at which it switches
+/// to double precision depends on the iteration limit too: pixels must be
+/// about `max_iter` times larger than the precision of `f32`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FastRenderer {
+    /// Threads to render on, and the renderer for deep zooms.
+    pub parallel: ParallelRenderer,
+}
+
+impl Renderer for FastRenderer {
+    fn name(&self) -> &'static str {
+        "CPU (fast)"
+    }
+
+    fn render(&self, view: &View, settings: &RenderSettings) -> IterationBuffer {
+        if settings.formula != Formula::Mandelbrot {
+            return self.parallel.render(view, settings);
+        }
+        let magnitude = view.center.0.abs().max(view.center.1.abs()).max(1.0);
+        let precision = magnitude * f32::EPSILON as f64 * settings.max_iter as f64;
+        if view.pixel_size(settings.width) < precision {
+            return self.parallel.render(view, settings);
+        }
+        let _span = render_span(self, settings).entered();
+        let tile = Tile::full(settings);
+        buffer(
+            settings,
+            self.parallel
+                .render_rows(view, settings, &tile, render_row_f32),
+        )
+    }
+}
+
+/// The `render` span for `renderer`, with the settings as fields.
+pub fn render_span(renderer: &dyn Renderer, settings: &RenderSettings) -> tracing::Span {
+    debug_span!(
+        "render",
+        renderer = renderer.name(),
+        width = settings.width,
+        height = settings.height,
+        max_iter = settings.max_iter,
+        formula = settings.formula.name(),
+    )
+}
+
+/// Renders the pixels of row `y` starting at column `x0`.
+fn render_row(view: &View, settings: &RenderSettings, x0: usize, y: usize, row: &mut [u32]) {
+    let _span = trace_span!("row", y).entered();
+    for (i, iter) in row.iter_mut().enumerate() {
+        let x = (x0 + i) as f64;
+        let c = view.pixel_to_complex(x, y as f64, settings.width, settings.height);
+        *iter = settings.formula.buffer_value(c, settings.max_iter);
+    }
+}
+
+/// [`render_row`] in single precision, [`LANES`] pixels at a time.
+fn render_row_f32(view: &View, settings: &RenderSettings, x0: usize, y: usize, row: &mut [u32]) {
+    let _span = trace_span!("row", y).entered();
+    for (chunk_index, chunk) in row.chunks_mut(LANES).enumerate() {
+        let mut cx = [0.0; LANES];
+        let mut cy = [0.0; LANES];
+        for i in 0..LANES {
+            // The last chunk's spare lanes compute pixels past the row's end
+            let x = (x0 + chunk_index * LANES + i) as f64;
+            let c = view.pixel_to_complex(x, y as f64, settings.width, settings.height);
+            (cx[i], cy[i]) = (c.0 as f32, c.1 as f32);
+        }
+        let iters = escape_times_f32(cx, cy, settings.max_iter);
+        chunk.copy_from_slice(&iters[..chunk.len()]);
+    }
+}
+
+fn buffer(settings: &RenderSettings, data: Vec<u32>) -> IterationBuffer {
+    IterationBuffer {
+        width: settings.width,
+        height: settings.height,
+        max_iter: settings.max_iter,
+        data,
+    }
+}
+
+/// Colors escape times with `palette`, painting points in the set with
+/// [`INTERIOR_COLOR`]. Points whose orbit [converged](CONVERGED) are colored
+/// by how long it took, at half brightness to set them apart from escaping
+/// ones.
+pub fn colorize(buffer: &IterationBuffer, palette: Palette) -> Image {
+    colorize_escaped(buffer, palette, |iter| palette.color(iter, buffer.max_iter))
+}
+
+/// Colors escape times like [`colorize`], but with the palette stretched
+/// over `range` rather than `0..max_iter`, so that a frame whose escape times
+/// fall in a narrow band still gets all of its colors. Escape times outside
+/// the range get the colors of its ends.
+pub fn colorize_stretched(
+    buffer: &IterationBuffer,
+    palette: Palette,
+    range: RangeInclusive<usize>,
+) -> Image {
+    let (low, high) = (*range.start(), *range.end());
+    let span = high.saturating_sub(low).max(1) as f64;
+    colorize_escaped(buffer, palette, |iter| {
+        palette.color_at((iter.clamp(low, high) - low) as f64 / span)
+    })
+}
+
+/// Colors escape times with `escaped` for points that escaped, and the rest
+/// as [`colorize`] does.
+fn colorize_escaped(
+    buffer: &IterationBuffer,
+    palette: Palette,
+    escaped: impl Fn(usize) -> [u8; 4],
+) -> Image {
+    let pixels = buffer
+        .data
+        .iter()
+        .map(|&iter| {
+            let iter = iter as usize;
+            if let Some(iter) = buffer.converged(iter) {
+                let [r, g, b, a] = palette.color(iter, buffer.max_iter);
+                [r / 2, g / 2, b / 2, a]
+            } else if buffer.is_interior(iter) {
+                INTERIOR_COLOR
+            } else {
+                escaped(iter)
+            }
+        })
+        .collect();
+    Image {
+        width: buffer.width,
+        height: buffer.height,
+        pixels,
+    }
+}
+
+/// Renders `view` as `settings` say, colored with `palette` by the smooth
+/// escape time rather than the whole number of iterations, at 16 bits per
+/// channel, so colors change continuously across the image. Points that
+/// don't escape, including those whose orbit converged, get
+/// [`INTERIOR_COLOR`].
+pub fn colorize_smooth(
+    renderer: &ParallelRenderer,
+    view: &View,
+    settings: &RenderSettings,
+    palette: Palette,
+) -> Image16 {
+    let smooth = renderer.render_values(view, settings, |c| {
+        let smooth = settings.formula.smooth_escape_time(c, settings.max_iter);
+        smooth.map(|smooth| smooth as f32)
+    });
+    let interior = INTERIOR_COLOR.map(|channel| u16::from(channel) * 257);
+    let pixels = (smooth.into_iter())
+        .map(|smooth| {
+            if smooth.is_nan() {
+                interior
+            } else {
+                let smooth = f64::from(smooth).clamp(0.0, settings.max_iter as f64);
+                palette.color16(smooth, settings.max_iter)
+            }
+        })
+        .collect();
+    Image16 {
+        width: settings.width,
+        height: settings.height,
+        pixels,
+    }
+}
+
+/// Renders `view` of the Mandelbrot set to a `width`×`height` image in one
+/// step, using all cores.
+pub fn render(
+    view: &View,
+    width: usize,
+    height: usize,
+    max_iter: usize,
+    palette: Palette,
+) -> Image {
+    let settings = RenderSettings {
+        width,
+        height,
+        max_iter,
+        formula: Formula::Mandelbrot,
+    };
+    colorize(
+        &ParallelRenderer::default().render(view, &settings),
+        palette,
+    )
+}
@@ -0,0 +1,187 @@
+//! Lighting effects that give rendered images depth. Slope shading takes the
+//! exterior distance estimate as a height field, highest at the set, and
+//! lights its slopes like a hillshaded map, so that filaments stand out
+//! embossed. Ambient occlusion darkens where escape times change quickly, as
+//! if those creases got less light.
+
+use crate::{Image, IterationBuffer, View, distance::exterior_distance};
+
+/// How quickly the occlusion grows with the gradient of the logarithm of
+/// the escape time, per pixel.
+const OCCLUSION_SENSITIVITY: f64 = 4.0;
+/// Radius in pixels of the box the occlusion is averaged over, to soften
+/// the steps between whole escape times.
+const OCCLUSION_RADIUS: usize = 2;
+
+/// Light shining on the height field, as for a hillshaded map.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Shading {
+    /// Whether to shade at all.
+    pub enabled: bool,
+    /// Direction the light comes from, in degrees clockwise from the top of
+    /// the image, as on a map. The default 315° lights from the top left.
+    pub azimuth: f64,
+    /// Angle of the light above the plane, in degrees from 0 (grazing) to 90
+    /// (straight down).
+    pub elevation: f64,
+    /// How far colors are darkened and lightened, from 0 (not at all) to 1
+    /// (slopes facing away from the light go black).
+    pub strength: f64,
+}
+
+impl Default for Shading {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            azimuth: 315.0,
+            elevation: 45.0,
+            strength: 0.6,
+        }
+    }
+}
+
+impl Shading {
+    /// Unit vector towards the light, in image coordinates: x to the right,
+    /// y down, and z up out of the image.
+    fn light(&self) -> [f64; 3] {
+        let (azimuth, elevation) = (self.azimuth.to_radians(), self.elevation.to_radians());
+        [
+            elevation.cos() * azimuth.sin(),
+            -elevation.cos() * azimuth.cos(),
+            elevation.sin(),
+        ]
+    }
+}
+
+/// Height of the field where the distance estimate is `distance` in units of
+/// pixels: minus its logarithm, so that slopes are steep near the set and
+/// flatten out away from it.
+fn height(distance: f64) -> f64 {
+    -distance.max(f64::MIN_POSITIVE).ln()
+}
+
+/// Shades `image`, which shows `view` as rendered into `buffer`, with
+/// `shading`. Normals come from central differences of the height field,
+/// one-sided next to the set and at the edges. Pixels in the set, and those
+/// whose distance isn't known, are left as they are.
+pub fn shade(image: &mut Image, buffer: &IterationBuffer, view: &View, shading: Shading) {
+    if !shading.enabled || shading.strength <= 0.0 {
+        return;
+    }
+    let (width, height_px) = (buffer.width, buffer.height);
+    let pixel = view.pixel_size(width);
+    let heights: Vec<Option<f64>> = buffer
+        .data
+        .iter()
+        .enumerate()
+        .map(|(i, &iter)| {
+            if buffer.is_interior(iter as usize) {
+                return None;
+            }
+            let (x, y) = ((i % width) as f64, (i / width) as f64);
+            let c = view.pixel_to_complex(x, y, width, height_px);
+            exterior_distance(c, buffer.max_iter).map(|distance| height(distance / pixel))
+        })
+        .collect();
+    // Slope along one axis from the heights before and after a pixel
+    let slope = |here: f64, before: Option<f64>, after: Option<f64>| match (before, after) {
+        (Some(before), Some(after)) => (after - before) / 2.0,
+        (Some(before), None) => here - before,
+        (None, Some(after)) => after - here,
+        (None, None) => 0.0,
+    };
+    let light = shading.light();
+    // Flat ground keeps its color, so only slopes change
+    let flat = light[2].max(f64::EPSILON);
+    for y in 0..height_px {
+        for x in 0..width {
+            let Some(here) = heights[y * width + x] else {
+                continue;
+            };
+            let at = |x: usize, y: usize| heights[y * width + x];
+            let dx = slope(
+                here,
+                (x > 0).then(|| at(x - 1, y)).flatten(),
+                (x + 1 < width).then(|| at(x + 1, y)).flatten(),
+            );
+            let dy = slope(
+                here,
+                (y > 0).then(|| at(x, y - 1)).flatten(),
+                (y + 1 < height_px).then(|| at(x, y + 1)).flatten(),
+            );
+            let length = (dx * dx + dy * dy + 1.0).sqrt();
+            let lit = (-dx * light[0] - dy * light[1] + light[2]) / length;
+            let change = ((lit.max(0.0) / flat - 1.0) * shading.strength).clamp(-1.0, 1.0);
+            let pixel = &mut image.pixels[y * width + x];
+            for channel in &mut pixel[..3] {
+                let value = *channel as f64;
+                let value = if change < 0.0 {
+                    value * (1.0 + change)
+                } else {
+                    value + (255.0 - value) * change
+                };
+                *channel = value.round() as u8;
+            }
+        }
+    }
+}
+
+/// Darkens `image`, colored from `buffer`, by up to `intensity`, from 0 (not
+/// at all) to 1 (black), where the logarithm of the escape time is steep.
+/// Works from the escape times alone, so for any formula. Pixels that
+/// neither escaped nor converged are left as they are.
+pub fn occlude(image: &mut Image, buffer: &IterationBuffer, intensity: f64) {
+    if intensity <= 0.0 {
+        return;
+    }
+    let (width, height) = (buffer.width, buffer.height);
+    let levels: Vec<f64> = (buffer.data.iter())
+        .map(|&iter| {
+            let iter = iter as usize;
+            let n = buffer.converged(iter).unwrap_or(iter.min(buffer.max_iter));
+            (n as f64 + 1.0).ln()
+        })
+        .collect();
+    let at = |x: usize, y: usize| levels[y * width + x];
+    let mut occlusion: Vec<f64> = (0..width * height)
+        .map(|i| {
+            let (x, y) = (i % width, i / width);
+            let (left, right) = (x.saturating_sub(1), (x + 1).min(width - 1));
+            let (up, down) = (y.saturating_sub(1), (y + 1).min(height - 1));
+            let dx = (at(right, y) - at(left, y)) / (right - left).max(1) as f64;
+            let dy = (at(x, down) - at(x, up)) / (down - up).max(1) as f64;
+            1.0 - (-dx.hypot(dy) * OCCLUSION_SENSITIVITY).exp()
+        })
+        .collect();
+    box_blur(&mut occlusion, width, height, OCCLUSION_RADIUS);
+    for ((pixel, &iter), occlusion) in image.pixels.iter_mut().zip(&buffer.data).zip(occlusion) {
+        let iter = iter as usize;
+        if buffer.is_interior(iter) && buffer.converged(iter).is_none() {
+            continue;
+        }
+        let factor = 1.0 - (intensity * occlusion).clamp(0.0, 1.0);
+        for channel in &mut pixel[..3] {
+            *channel = (*channel as f64 * factor).round() as u8;
+        }
+    }
+}
+
+/// Averages each of the `width`×`height` `values` over the box of `radius`
+/// around it, cut off at the edges.
+fn box_blur(values: &mut [f64], width: usize, height: usize, radius: usize) {
+    let mut blurred = vec![0.0; values.len()];
+    for (stride, count, lines, line_stride) in
+        [(1, width, height, width), (width, height, width, 1)]
+    {
+        for line in 0..lines {
+            let start = line * line_stride;
+            let value = |i: usize| values[start + i * stride];
+            for i in 0..count {
+                let (from, to) = (i.saturating_sub(radius), (i + radius).min(count - 1));
+                let sum: f64 = (from..=to).map(value).sum();
+                blurred[start + i * stride] = sum / (to - from + 1) as f64;
+            }
+        }
+        values.copy_from_slice(&blurred);
+    }
+}
@@ -0,0 +1,60 @@
+//! The Riemann sphere: the complex plane with a point at infinity, wrapped
+//! around the unit sphere by stereographic projection, so the whole plane,
+//! including how orbits behave near infinity, can be seen at once.
+//!
+//! Projecting from the north pole, which is infinity, the south pole is 0
+//! and the equator the unit circle. The imaginary axis runs along −y, so
+//! the plane looks the right way round seen from outside the sphere below
+//! the south pole.
+
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use crate::{Formula, IterationBuffer, ParallelRenderer, RenderSettings};
+
+/// The point of the plane at `p` on the unit sphere, or `None` for the north
+/// pole, infinity.
+pub fn to_plane(p: [f64; 3]) -> Option<(f64, f64)> {
+    let [x, y, z] = p;
+    (z < 1.0).then(|| (x / (1.0 - z), -y / (1.0 - z)))
+}
+
+/// The point of the unit sphere at c.
+pub fn to_sphere(c: (f64, f64)) -> [f64; 3] {
+    let r2 = c.0 * c.0 + c.1 * c.1;
+    [2.0 * c.0, -2.0 * c.1, r2 - 1.0].map(|v| v / (r2 + 1.0))
+}
+
+/// The point of the unit sphere at longitude `lon` and latitude `lat` in
+/// radians, with longitude 0 on the positive real axis and π/2 on the
+/// negative imaginary one.
+pub fn from_angles(lon: f64, lat: f64) -> [f64; 3] {
+    [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()]
+}
+
+/// Escape times over the whole sphere as an equirectangular map `width`
+/// pixels wide and half that high: longitude −π to π from left to right,
+/// and latitude from the north pole, infinity, at the top to the south
+/// pole, 0, at the bottom.
+pub fn render_sphere(
+    renderer: &ParallelRenderer,
+    formula: Formula,
+    width: usize,
+    max_iter: usize,
+) -> IterationBuffer {
+    let settings = RenderSettings {
+        width,
+        height: width / 2,
+        max_iter,
+        formula,
+    };
+    renderer.render_with(&settings, |x, y| {
+        let lon = (x as f64 + 0.5) / settings.width as f64 * 2.0 * PI - PI;
+        let lat = FRAC_PI_2 - (y as f64 + 0.5) / settings.height as f64 * PI;
+        // Pixel centers are never at the pole, but close to it rounding can
+        // land there; infinity escapes at once
+        match to_plane(from_angles(lon, lat)) {
+            Some(c) => formula.buffer_value(c, max_iter),
+            None => 0,
+        }
+    })
+}
@@ -0,0 +1,115 @@
+//! Statistics about the escape times of a rendered view.
+
+use std::ops::{Range, RangeInclusive};
+
+use crate::{IterationBuffer, View, interior::interior};
+
+/// Counts of escape times in equal-width bins, for pixels outside the set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Histogram {
+    /// Number of pixels per bin, covering escape times `0..max_iter`.
+    pub bins: Vec<usize>,
+    /// Iteration limit of the buffer counted.
+    pub max_iter: usize,
+    /// Number of pixels in the set, which aren't in any bin.
+    pub interior: usize,
+}
+
+impl Histogram {
+    /// Escape times counted in bin `index`.
+    pub fn bin_range(&self, index: usize) -> Range<usize> {
+        let bins = self.bins.len();
+        (index * self.max_iter).div_ceil(bins)..((index + 1) * self.max_iter).div_ceil(bins)
+    }
+
+    /// The bin escape time `iter` is counted in, or `None` for points in the
+    /// set.
+    pub fn bin_of(&self, iter: usize) -> Option<usize> {
+        (iter < self.max_iter).then(|| iter * self.bins.len() / self.max_iter)
+    }
+}
+
+/// Counts the escape times in `buffer` into `bins` bins, or fewer if the
+/// iteration limit is smaller.
+pub fn histogram(buffer: &IterationBuffer, bins: usize) -> Histogram {
+    let mut histogram = Histogram {
+        bins: vec![0; bins.clamp(1, buffer.max_iter.max(1))],
+        max_iter: buffer.max_iter.max(1),
+        interior: 0,
+    };
+    for &iter in &buffer.data {
+        match histogram.bin_of(iter as usize) {
+            Some(bin) => histogram.bins[bin] += 1,
+            None => histogram.interior += 1,
+        }
+    }
+    histogram
+}
+
+/// The escape times in `buffer` from the fraction `clip` of the way through
+/// them in order to `1 - clip` of the way, for `clip` from 0 (the smallest
+/// to the largest) to ½, leaving out rare outliers at either end. `None` if
+/// no point escaped.
+pub fn escape_range(buffer: &IterationBuffer, clip: f64) -> Option<RangeInclusive<usize>> {
+    let mut escaped: Vec<u32> = (buffer.data.iter().copied())
+        .filter(|&iter| !buffer.is_interior(iter as usize))
+        .collect();
+    let last = escaped.len().checked_sub(1)?;
+    let at = |fraction: f64| ((last as f64 * fraction).round() as usize).min(last);
+    let clip = clip.clamp(0.0, 0.5);
+    let (from, to) = (at(clip), at(1.0 - clip));
+    let (_, &mut low, above) = escaped.select_nth_unstable(from);
+    let high = match to.checked_sub(from + 1) {
+        Some(index) => *above.select_nth_unstable(index).1,
+        None => low,
+    };
+    Some(low as usize..=high as usize)
+}
+
+/// A summary of a frame, for judging whether its iteration limit is high
+/// enough.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameStats {
+    /// Number of pixels.
+    pub pixels: usize,
+    /// Pixels that reached the iteration limit without escaping.
+    pub at_limit: usize,
+    /// Pixels that reached the limit and have an attracting cycle, so are
+    /// certainly in the set. The rest of `at_limit` may just need more
+    /// iterations.
+    pub interior: usize,
+    /// Smallest, median, and largest escape time of the pixels that escaped,
+    /// if any did.
+    pub escaped: Option<(usize, usize, usize)>,
+}
+
+/// Summarizes `buffer`, a render of `view`.
+pub fn frame_stats(buffer: &IterationBuffer, view: &View) -> FrameStats {
+    let mut escaped = Vec::with_capacity(buffer.data.len());
+    let mut interior_pixels = 0;
+    for (i, &iter) in buffer.data.iter().enumerate() {
+        if !buffer.is_interior(iter as usize) {
+            escaped.push(iter as usize);
+            continue;
+        }
+        let (x, y) = ((i % buffer.width) as f64, (i / buffer.width) as f64);
+        let c = view.pixel_to_complex(x, y, buffer.width, buffer.height);
+        if interior(c, buffer.max_iter).is_some() {
+            interior_pixels += 1;
+        }
+    }
+    let at_limit = buffer.data.len() - escaped.len();
+    escaped.sort_unstable();
+    FrameStats {
+        pixels: buffer.data.len(),
+        at_limit,
+        interior: interior_pixels,
+        escaped: (!escaped.is_empty()).then(|| {
+            (
+                escaped[0],
+                escaped[escaped.len() / 2],
+                escaped[escaped.len() - 1],
+            )
+        }),
+    }
+}
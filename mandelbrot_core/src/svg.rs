@@ -0,0 +1,240 @@
+//! Vector figures: curves, circles, and labels at points of the complex
+//! plane written as SVG, aligned pixel for pixel with a render of the same
+//! view at the same size, so they can be finished in a vector editor on top
+//! of the rendered image.
+
+use std::fmt::Write;
+
+use crate::View;
+
+/// Lines across the view [`grid_spacing`] aims for at least.
+const GRID_LINES: f64 = 8.0;
+
+/// How a curve is outlined.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stroke {
+    /// RGBA color.
+    pub color: [u8; 4],
+    /// Width in pixels.
+    pub width: f64,
+}
+
+impl Stroke {
+    /// A stroke `width` pixels wide in `color`.
+    pub fn new(width: f64, color: [u8; 4]) -> Self {
+        Self { color, width }
+    }
+
+    fn attributes(self) -> String {
+        let [r, g, b, a] = self.color;
+        let mut attributes = format!(
+            r#"fill="none" stroke="rgb({r},{g},{b})" stroke-width="{}" stroke-linecap="round" stroke-linejoin="round""#,
+            self.width
+        );
+        if a < 255 {
+            write!(
+                attributes,
+                r#" stroke-opacity="{:.3}""#,
+                f64::from(a) / 255.0
+            )
+            .unwrap();
+        }
+        attributes
+    }
+}
+
+/// An SVG document of a `width`×`height` image of a view, built up layer by
+/// layer.
+#[derive(Debug, Clone)]
+pub struct Svg {
+    view: View,
+    width: usize,
+    height: usize,
+    body: String,
+}
+
+impl Svg {
+    /// An empty figure over `view` rendered at `width`×`height` pixels.
+    pub fn new(view: &View, width: usize, height: usize) -> Self {
+        Self {
+            view: *view,
+            width,
+            height,
+            body: String::new(),
+        }
+    }
+
+    /// Pixel position of complex coordinate `c` in the figure.
+    fn position(&self, (re, im): (f64, f64)) -> (f64, f64) {
+        self.view.complex_to_pixel(re, im, self.width, self.height)
+    }
+
+    /// Starts a named group, such as a layer; shapes added until
+    /// [`Svg::end_group`] belong to it. Inkscape shows groups with an
+    /// `inkscape:label` as layers.
+    pub fn begin_group(&mut self, name: &str) {
+        let name = escape(name);
+        writeln!(
+            self.body,
+            r#"<g id="{}" inkscape:groupmode="layer" inkscape:label="{name}">"#,
+            name.to_lowercase().replace(' ', "-")
+        )
+        .unwrap();
+    }
+
+    /// Ends the group started last.
+    pub fn end_group(&mut self) {
+        self.body.push_str("</g>\n");
+    }
+
+    /// A line through `points` of the plane. Points that aren't finite, as
+    /// escaping orbits end with, are left out.
+    pub fn polyline(&mut self, points: &[(f64, f64)], stroke: Stroke) {
+        let mut coordinates = String::new();
+        for &c in points
+            .iter()
+            .filter(|(re, im)| re.is_finite() && im.is_finite())
+        {
+            let (x, y) = self.position(c);
+            write!(coordinates, "{x:.2},{y:.2} ").unwrap();
+        }
+        if coordinates.is_empty() {
+            return;
+        }
+        writeln!(
+            self.body,
+            r#"<polyline points="{}" {}/>"#,
+            coordinates.trim_end(),
+            stroke.attributes()
+        )
+        .unwrap();
+    }
+
+    /// A straight line between two points of the plane.
+    pub fn line(&mut self, from: (f64, f64), to: (f64, f64), stroke: Stroke) {
+        self.polyline(&[from, to], stroke);
+    }
+
+    /// An arrow from one point of the plane to another, with a head a quarter
+    /// of its length.
+    pub fn arrow(&mut self, from: (f64, f64), to: (f64, f64), stroke: Stroke) {
+        let ((x0, y0), (x1, y1)) = (self.position(from), self.position(to));
+        let (dx, dy) = ((x1 - x0) / 4.0, (y1 - y0) / 4.0);
+        let (sin, cos) = std::f64::consts::FRAC_PI_6.sin_cos();
+        let barb = |sin: f64| (x1 - (dx * cos - dy * sin), y1 - (dx * sin + dy * cos));
+        let ((lx, ly), (rx, ry)) = (barb(sin), barb(-sin));
+        writeln!(
+            self.body,
+            r#"<path d="M {x0:.2} {y0:.2} L {x1:.2} {y1:.2} M {lx:.2} {ly:.2} L {x1:.2} {y1:.2} L {rx:.2} {ry:.2}" {}/>"#,
+            stroke.attributes()
+        )
+        .unwrap();
+    }
+
+    /// A circle with a radius of `radius` pixels around a point of the plane.
+    pub fn circle(&mut self, center: (f64, f64), radius: f64, stroke: Stroke) {
+        let (x, y) = self.position(center);
+        writeln!(
+            self.body,
+            r#"<circle cx="{x:.2}" cy="{y:.2}" r="{radius}" {}/>"#,
+            stroke.attributes()
+        )
+        .unwrap();
+    }
+
+    /// `text` centered on a point of the plane, `size` pixels high, in
+    /// `color` and outlined by `outline` so it stands out on any background.
+    pub fn label(
+        &mut self,
+        at: (f64, f64),
+        text: &str,
+        size: f64,
+        color: [u8; 4],
+        outline: Stroke,
+    ) {
+        let (x, y) = self.position(at);
+        let [r, g, b, _] = color;
+        writeln!(
+            self.body,
+            r#"<text x="{x:.2}" y="{y:.2}" font-family="sans-serif" font-size="{size}" text-anchor="middle" dominant-baseline="central" fill="rgb({r},{g},{b})" paint-order="stroke" {}>{}</text>"#,
+            stroke_only(outline),
+            escape(text)
+        )
+        .unwrap();
+    }
+
+    /// The real and imaginary axes, where they cross the figure.
+    pub fn axes(&mut self, stroke: Stroke) {
+        let (left, top) = self
+            .view
+            .pixel_to_complex(0.0, 0.0, self.width, self.height);
+        let (right, bottom) = self.corner();
+        self.line((left, 0.0), (right, 0.0), stroke);
+        self.line((0.0, top), (0.0, bottom), stroke);
+    }
+
+    /// Lines of constant real and imaginary part, [`grid_spacing`] apart.
+    pub fn grid(&mut self, stroke: Stroke) {
+        let spacing = grid_spacing(&self.view);
+        let (left, top) = self
+            .view
+            .pixel_to_complex(0.0, 0.0, self.width, self.height);
+        let (right, bottom) = self.corner();
+        let steps = |from: f64, to: f64| {
+            ((from / spacing).ceil() as i64..=(to / spacing).floor() as i64)
+                .map(move |k| k as f64 * spacing)
+        };
+        for re in steps(left, right) {
+            self.line((re, top), (re, bottom), stroke);
+        }
+        for im in steps(top, bottom) {
+            self.line((left, im), (right, im), stroke);
+        }
+    }
+
+    /// The point of the plane at the bottom-right corner of the figure.
+    fn corner(&self) -> (f64, f64) {
+        let (width, height) = (self.width as f64, self.height as f64);
+        (self.view).pixel_to_complex(width, height, self.width, self.height)
+    }
+
+    /// The finished document.
+    pub fn finish(self) -> String {
+        format!(
+            concat!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+                "\n",
+                r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:inkscape="http://www.inkscape.org/namespaces/inkscape" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+                "\n{body}</svg>\n"
+            ),
+            w = self.width,
+            h = self.height,
+            body = self.body
+        )
+    }
+}
+
+/// Distance between grid lines for `view`: the largest 1, 2, or 5 times a
+/// power of ten that leaves at least [`GRID_LINES`] across it.
+pub fn grid_spacing(view: &View) -> f64 {
+    let span = view.scale / GRID_LINES;
+    let power = 10f64.powf(span.log10().floor());
+    [5.0, 2.0, 1.0]
+        .into_iter()
+        .map(|step| step * power)
+        .find(|&spacing| spacing <= span)
+        .unwrap_or(power)
+}
+
+/// The stroke attributes of `stroke`, without the `fill="none"` of curves.
+fn stroke_only(stroke: Stroke) -> String {
+    stroke.attributes().replacen(r#"fill="none" "#, "", 1)
+}
+
+/// `text` with the characters that are special in XML replaced by entities.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
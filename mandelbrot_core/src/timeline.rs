@@ -0,0 +1,55 @@
+//! Animations of the view and of the formula's parameters, such as the
+//! Multibrot exponent or the c of a Julia set: keyframes at points in time,
+//! and the frames in between.
+
+use crate::{Formula, View};
+
+/// What is shown at one point in time of an animation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Keyframe {
+    /// Seconds from the start of the animation.
+    pub time: f64,
+    /// The part of the plane shown.
+    pub view: View,
+    /// Iteration limit.
+    pub max_iter: usize,
+    /// The map iterated, with its parameters.
+    pub formula: Formula,
+}
+
+/// The frame at `time` of the animation through `keyframes`, which must be
+/// sorted by time, or `None` if there are none. Before the first keyframe
+/// and after the last it holds still.
+///
+/// Between two keyframes views change as [`View::interpolate`] has them,
+/// iteration limits geometrically, and formulas as [`Formula::interpolate`]
+/// has them.
+pub fn frame_at(keyframes: &[Keyframe], time: f64) -> Option<Keyframe> {
+    let first = keyframes.first()?;
+    let after = keyframes.partition_point(|k| k.time <= time);
+    let (a, b) = match after {
+        0 => return Some(Keyframe { time, ..*first }),
+        n if n == keyframes.len() => {
+            return Some(Keyframe {
+                time,
+                ..keyframes[n - 1]
+            });
+        }
+        n => (keyframes[n - 1], keyframes[n]),
+    };
+    let t = (time - a.time) / (b.time - a.time);
+    let (from, to) = (a.max_iter.max(1) as f64, b.max_iter.max(1) as f64);
+    let max_iter = from * (to / from).powf(t);
+    Some(Keyframe {
+        time,
+        view: a.view.interpolate(&b.view, t),
+        max_iter: max_iter.round() as usize,
+        formula: a.formula.interpolate(b.formula, t),
+    })
+}
+
+/// Seconds from the start to the last of `keyframes`, sorted by time.
+pub fn duration(keyframes: &[Keyframe]) -> f64 {
+    keyframes.last().map_or(0.0, |k| k.time.max(0.0))
+}
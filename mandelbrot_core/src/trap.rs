@@ -0,0 +1,125 @@
+//! Orbit traps: points, line segments, circles, and lattices of points in
+//! the plane. Pixels
+//! outside the set are tinted by how close their orbit passes to the traps,
+//! which brings out shapes the escape time alone doesn't show.
+
+use crate::{Formula, Image, IterationBuffer, View, palette::hsv_to_rgb};
+
+/// A shape orbits are measured against, in complex coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Trap {
+    /// A single point.
+    Point {
+        /// The point.
+        at: (f64, f64),
+    },
+    /// The line segment between two points.
+    Line {
+        /// One end.
+        from: (f64, f64),
+        /// The other end.
+        to: (f64, f64),
+    },
+    /// A circle, measured to its outline.
+    Circle {
+        /// Center of the circle.
+        center: (f64, f64),
+        /// Radius of the circle.
+        radius: f64,
+    },
+    /// The points `spacing · (m + ni)` for all integers m and n: with a
+    /// spacing of 1, the Gaussian integers.
+    Lattice {
+        /// Distance between neighbouring points, positive.
+        spacing: f64,
+    },
+}
+
+impl Trap {
+    /// Distance from z to the trap.
+    pub fn distance(&self, z: (f64, f64)) -> f64 {
+        match *self {
+            Trap::Point { at } => (z.0 - at.0).hypot(z.1 - at.1),
+            Trap::Line { from, to } => {
+                let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+                let length2 = dx * dx + dy * dy;
+                let t = if length2 > 0.0 {
+                    (((z.0 - from.0) * dx + (z.1 - from.1) * dy) / length2).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+                (z.0 - from.0 - t * dx).hypot(z.1 - from.1 - t * dy)
+            }
+            Trap::Circle { center, radius } => {
+                ((z.0 - center.0).hypot(z.1 - center.1) - radius).abs()
+            }
+            Trap::Lattice { spacing } => {
+                let offset = |x: f64| x - (x / spacing).round() * spacing;
+                offset(z.0).hypot(offset(z.1))
+            }
+        }
+    }
+}
+
+/// Color of the trap at `index` in a list. Hues step by the golden angle, so
+/// neighbouring traps get clearly different colors.
+pub fn trap_color(index: usize) -> [u8; 4] {
+    let hue = (60.0 + index as f32 * 137.507_77) % 360.0;
+    let (r, g, b) = hsv_to_rgb(hue, 0.8, 1.0);
+    [r, g, b, 255]
+}
+
+/// The index of the trap the orbit of c under `formula` passes closest to
+/// within its first `iterations` iterates, and how close. `None` without
+/// traps.
+pub fn trap_distance(
+    c: (f64, f64),
+    formula: Formula,
+    iterations: usize,
+    traps: &[Trap],
+) -> Option<(usize, f64)> {
+    let mut closest: Option<(usize, f64)> = None;
+    for z in formula.iterates(c).skip(1).take(iterations) {
+        for (i, trap) in traps.iter().enumerate() {
+            let distance = trap.distance(z);
+            if closest.is_none_or(|(_, d)| distance < d) {
+                closest = Some((i, distance));
+            }
+        }
+    }
+    closest
+}
+
+/// Tints the pixels of `image` that escape in `buffer`, rendered from
+/// `view` with `formula`, towards the color of the trap their orbit passes
+/// closest to: fully on it, fading out over about `width` away.
+pub fn color_traps(
+    image: &mut Image,
+    buffer: &IterationBuffer,
+    view: &View,
+    formula: Formula,
+    traps: &[Trap],
+    width: f64,
+) {
+    if traps.is_empty() {
+        return;
+    }
+    for (i, &iter) in buffer.data.iter().enumerate() {
+        if buffer.is_interior(iter as usize) {
+            continue;
+        }
+        let (x, y) = ((i % buffer.width) as f64, (i / buffer.width) as f64);
+        let c = view.pixel_to_complex(x, y, buffer.width, buffer.height);
+        let Some((trap, distance)) = trap_distance(c, formula, iter as usize, traps) else {
+            continue;
+        };
+        let glow = (-distance / width).exp();
+        let color = trap_color(trap);
+        let pixel = &mut image.pixels[i];
+        for channel in 0..3 {
+            let mixed = f64::from(pixel[channel]) * (1.0 - glow) + f64::from(color[channel]) * glow;
+            pixel[channel] = mixed.round() as u8;
+        }
+    }
+}
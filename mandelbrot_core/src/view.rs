@@ -0,0 +1,92 @@
+//! Mapping between image pixels and the complex plane.
+
+/// A window onto the complex plane with square pixels.
+///
+/// Pixel coordinates grow right and down from the top-left corner of the
+/// image; real parts grow to the right and imaginary parts grow downwards.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct View {
+    /// Complex coordinate at the center of the image, as `(re, im)`.
+    pub center: (f64, f64),
+    /// Width of the image in complex units.
+    pub scale: f64,
+}
+
+impl Default for View {
+    /// The whole set, centered.
+    fn default() -> Self {
+        Self {
+            center: (-0.5, 0.0),
+            scale: 3.0,
+        }
+    }
+}
+
+impl View {
+    /// Size of one pixel in complex units, for an image `width` pixels wide.
+    pub fn pixel_size(&self, width: usize) -> f64 {
+        self.scale / width as f64
+    }
+
+    /// Whether single precision can still tell the pixels apart in an image
+    /// `width` pixels wide, with some margin for rounding errors.
+    pub fn fits_f32(&self, width: usize) -> bool {
+        let magnitude = self.center.0.abs().max(self.center.1.abs()).max(1.0);
+        self.pixel_size(width) >= magnitude * f32::EPSILON as f64 * 4.0
+    }
+
+    /// Magnification relative to the default view.
+    pub fn zoom(&self) -> f64 {
+        View::default().scale / self.scale
+    }
+
+    /// Complex coordinate of pixel position `(x, y)` in a `width`×`height` image.
+    pub fn pixel_to_complex(&self, x: f64, y: f64, width: usize, height: usize) -> (f64, f64) {
+        let pixel = self.pixel_size(width);
+        let re = self.center.0 + (x - width as f64 / 2.0) * pixel;
+        let im = self.center.1 + (y - height as f64 / 2.0) * pixel;
+        (re, im)
+    }
+
+    /// Pixel position of complex coordinate `(re, im)` in a `width`×`height`
+    /// image. The inverse of [`View::pixel_to_complex`].
+    pub fn complex_to_pixel(&self, re: f64, im: f64, width: usize, height: usize) -> (f64, f64) {
+        let pixel = self.pixel_size(width);
+        let x = (re - self.center.0) / pixel + width as f64 / 2.0;
+        let y = (im - self.center.1) / pixel + height as f64 / 2.0;
+        (x, y)
+    }
+
+    /// Scales the view by `factor` (below 1 zooms in) while keeping the point
+    /// under pixel position `(x, y)` in place.
+    pub fn zoom_at(&mut self, x: f64, y: f64, width: usize, height: usize, factor: f64) {
+        let (re, im) = self.pixel_to_complex(x, y, width, height);
+        self.scale *= factor;
+        let (new_re, new_im) = self.pixel_to_complex(x, y, width, height);
+        self.center.0 += re - new_re;
+        self.center.1 += im - new_im;
+    }
+
+    /// The view a fraction `t`, from 0 to 1, of the way from this one to
+    /// `to`. The width changes geometrically, so zooming goes at an even
+    /// pace, and the center moves in step with the width, so a point zoomed
+    /// towards stays put on screen.
+    pub fn interpolate(&self, to: &View, t: f64) -> View {
+        let scale = self.scale * (to.scale / self.scale).powf(t);
+        // How far the width has come, which the center follows; without a
+        // change of width, just t
+        let w = if (self.scale - to.scale).abs() > 1e-9 * self.scale {
+            (self.scale - scale) / (self.scale - to.scale)
+        } else {
+            t
+        };
+        View {
+            center: (
+                self.center.0 + w * (to.center.0 - self.center.0),
+                self.center.1 + w * (to.center.1 - self.center.1),
+            ),
+            scale,
+        }
+    }
+}
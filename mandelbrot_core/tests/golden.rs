@@ -0,0 +1,85 @@
+use mandelbrot_core::{
+    Formula, Palette, ParallelRenderer, RenderSettings, Renderer, ScalarRenderer, View,
+    formula::CubicEscape, golden,
+};
+
+const SETTINGS: RenderSettings = RenderSettings {
+    width: 96,
+    height: 64,
+    max_iter: 200,
+    formula: Formula::Mandelbrot,
+};
+
+fn seahorse_valley() -> View {
+    View {
+        center: (-0.743643887, 0.131825904),
+        scale: 0.01,
+    }
+}
+
+#[test]
+fn reference_hashes() {
+    let cases = [
+        (View::default(), Palette::Rainbow, 11780773933644244866),
+        (seahorse_valley(), Palette::Fire, 276823684884470499),
+    ];
+    for (view, palette, expected) in cases {
+        let image = golden::render_reference(&view, &SETTINGS, palette);
+        assert_eq!(golden::image_hash(&image), expected, "{view:?} {palette:?}");
+    }
+}
+
+#[test]
+fn renderers_agree() {
+    let view = seahorse_valley();
+    let reference = golden::iteration_hash(&ScalarRenderer.render(&view, &SETTINGS));
+    for threads in [1, 3, 8] {
+        let renderer = ParallelRenderer {
+            threads: threads.try_into().unwrap(),
+        };
+        assert_eq!(
+            golden::iteration_hash(&renderer.render(&view, &SETTINGS)),
+            reference,
+            "{threads} threads"
+        );
+    }
+}
+
+#[test]
+fn compare_counts_differences() {
+    let a = golden::render_reference(&View::default(), &SETTINGS, Palette::Grayscale);
+    let mut b = a.clone();
+    b.pixels[0] = [10, 20, 30, 255];
+    b.pixels[5] = [
+        a.pixels[5][0].wrapping_add(7),
+        a.pixels[5][1],
+        a.pixels[5][2],
+        255,
+    ];
+    let diff = golden::compare(&a, &b).unwrap();
+    assert_eq!(diff.differing_pixels, 2);
+    assert!(!diff.is_identical());
+    let mut smaller = a.clone();
+    smaller.height -= 1;
+    assert_eq!(golden::compare(&a, &smaller), None);
+}
+
+#[test]
+fn cubic_smooth_escape_follows_the_escaping_orbit() {
+    // z → −z swaps the critical orbits of c and −c, so at c = 0.2 the second
+    // orbit escapes where the first does at −0.2, long before its own first
+    let (c, mirrored) = ((0.2, 0.0), (-0.2, 0.0));
+    for escape in [CubicEscape::Either, CubicEscape::Both] {
+        let formula = Formula::Cubic {
+            a: (-3.0, 0.0),
+            escape,
+        };
+        let smooth = formula.smooth_escape_time(c, 200).unwrap();
+        assert!(smooth.is_finite(), "{escape:?}");
+        assert_eq!(
+            Some(smooth),
+            formula.smooth_escape_time(mirrored, 200),
+            "{escape:?}"
+        );
+    }
+}
@@ -0,0 +1,146 @@
+//! A/B comparison: keeps one rendering setup as A and shows it side by side
+//! with the current one (B) in the view, split by a draggable line, so the
+//! effect of a palette, iteration limit, or renderer can be judged pixel for
+//! pixel.
+
+use eframe::egui;
+use mandelbrot_core::{Image, Palette};
+
+use crate::{i18n::tr, settings::Backend, to_color_image};
+
+/// What differs between the sides of the comparison.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Setup {
+    pub palette: Palette,
+    pub max_iter: usize,
+    pub backend: Backend,
+    /// Name of the renderer `backend` stands for.
+    pub renderer: &'static str,
+}
+
+impl Setup {
+    fn describe(&self) -> String {
+        tr!(
+            "ab-setup",
+            palette = self.palette.name(),
+            max_iter = self.max_iter,
+            renderer = self.renderer,
+        )
+    }
+}
+
+/// The View → A/B Comparison window and the split it draws over the view.
+pub struct AbWindow {
+    pub open: bool,
+    a: Option<Setup>,
+    /// Position of the split from the left edge, from 0 to 1.
+    split: f32,
+    texture: Option<egui::TextureHandle>, // A for the current frame; None until rendered
+}
+
+impl Default for AbWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            a: None,
+            split: 0.5,
+            texture: None,
+        }
+    }
+}
+
+impl AbWindow {
+    /// Forgets the rendering of A, for when a new frame is shown.
+    pub fn invalidate(&mut self, ctx: &egui::Context) {
+        self.texture = None;
+        if self.open && self.a.is_some() {
+            // Rendered again before the next frame is drawn
+            ctx.request_repaint();
+        }
+    }
+
+    /// The setup to render A with for the current frame, if it is needed and
+    /// not rendered yet.
+    pub fn pending(&self) -> Option<Setup> {
+        self.a.filter(|_| self.open && self.texture.is_none())
+    }
+
+    /// Stores `image`, the current frame rendered with A's setup.
+    pub fn set_image(&mut self, ctx: &egui::Context, image: &Image) {
+        self.texture = Some(ctx.load_texture(
+            "ab_compare",
+            to_color_image(image),
+            egui::TextureOptions::default(),
+        ));
+    }
+
+    /// Shows the window; `current` is the setup of B.
+    pub fn show(&mut self, ctx: &egui::Context, current: Setup) {
+        let mut open = self.open;
+        egui::Window::new(tr!("ab-title"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                if ui
+                    .button(tr!("ab-keep"))
+                    .on_hover_text(tr!("ab-keep-hover"))
+                    .clicked()
+                {
+                    self.a = Some(current);
+                    self.texture = None;
+                }
+                match &self.a {
+                    Some(a) => ui.label(tr!("ab-a", setup = a.describe())),
+                    None => ui.weak(tr!("ab-a-none")),
+                };
+                ui.label(tr!("ab-b", setup = current.describe()));
+                ui.add(egui::Slider::new(&mut self.split, 0.0..=1.0).text(tr!("split-position")));
+                ui.weak(tr!("ab-hint"));
+            });
+        self.open = open;
+    }
+
+    /// Draws A over the part of `image_rect` left of the split, with a
+    /// handle to drag the split.
+    pub fn draw(&mut self, ui: &egui::Ui, image_rect: egui::Rect) {
+        let Some(texture) = self.texture.as_ref().filter(|_| self.open) else {
+            return;
+        };
+        let split = image_rect.left() + image_rect.width() * self.split;
+        let rect = egui::Rect::from_x_y_ranges(image_rect.left()..=split, image_rect.y_range());
+        let uv = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(self.split, 1.0));
+        let painter = ui.painter();
+        painter.image(texture.id(), rect, uv, egui::Color32::WHITE);
+        let handle = egui::Rect::from_x_y_ranges(split - 6.0..=split + 6.0, image_rect.y_range());
+        let response = ui
+            .interact(handle, ui.id().with("ab_split"), egui::Sense::drag())
+            .on_hover_cursor(egui::CursorIcon::ResizeHorizontal);
+        if let Some(pos) = response.interact_pointer_pos() {
+            self.split = ((pos.x - image_rect.left()) / image_rect.width()).clamp(0.0, 1.0);
+        }
+        let width = if response.hovered() || response.dragged() {
+            3.0
+        } else {
+            1.5
+        };
+        painter.vline(
+            split,
+            image_rect.y_range(),
+            egui::Stroke::new(width, egui::Color32::WHITE),
+        );
+        let font = egui::FontId::proportional(16.0);
+        let top = image_rect.top() + 6.0;
+        for (x, align, text) in [
+            (split - 8.0, egui::Align2::RIGHT_TOP, "A"),
+            (split + 8.0, egui::Align2::LEFT_TOP, "B"),
+        ] {
+            painter.text(
+                egui::pos2(x, top),
+                align,
+                text,
+                font.clone(),
+                egui::Color32::WHITE,
+            );
+        }
+    }
+}
@@ -0,0 +1,864 @@
+//! Analysis tools: statistics about the selected point's orbit, its real
+//! dynamics near the real axis, the iteration histogram, comparison with
+//! distance estimation, area estimation, and box-counting dimension.
+
+use std::time::Duration;
+
+use eframe::egui;
+use mandelbrot_core::{
+    Formula, Image, IterationBuffer, RenderSettings, Renderer, View,
+    angles::{ExternalAngle, external_angle, rotation_number},
+    area::{AreaEstimate, AreaEstimator},
+    dimension::{BoxCounting, box_counting},
+    distance::render_distance,
+    formula::Bailout,
+    interior::{Interior, interior, period_mask},
+    iterate::{Preperiodic, preperiodic},
+    real::{BIFURCATION_RANGE, bifurcation_diagram, cobweb},
+    stats::{Histogram, histogram},
+};
+use web_time::Instant;
+
+use crate::i18n::tr;
+
+/// Time spent sampling per frame, so the window stays responsive.
+const SAMPLE_BUDGET: Duration = Duration::from_millis(20);
+/// Points sampled between checks of the time budget.
+const SAMPLE_CHUNK: u64 = 1000;
+
+/// Points within this many pixels of the real axis count as real.
+const REAL_AXIS_PIXELS: f64 = 3.0;
+/// Size of the cobweb and bifurcation plots.
+const REAL_PLOT_SIZE: egui::Vec2 = egui::vec2(240.0, 160.0);
+
+/// Number of bars in the iteration histogram.
+const HISTOGRAM_BINS: usize = 64;
+/// Color pixels of the selected histogram bin are marked with.
+const HIGHLIGHT_COLOR: [u8; 4] = [255, 0, 255, 200];
+
+/// Binary digits of the external angle shown before abbreviating.
+const BINARY_DIGITS: usize = 24;
+
+/// Iterates the sparklines of Re(zₙ) and Im(zₙ) show, from z₀.
+const SPARKLINE_ITERATES: usize = 100;
+/// Size of each sparkline.
+const SPARKLINE_SIZE: egui::Vec2 = egui::vec2(240.0, 36.0);
+
+/// Orbit statistics for one point, computed when it is selected.
+pub struct OrbitStats {
+    c: (f64, f64),
+    formula: Formula,
+    max_iter: usize,
+    iterations: usize,
+    converged: Option<usize>, // iterations to converge, for orbits that do
+    max_abs: f64,
+    last: (f64, f64),
+    interior: Option<Interior>,
+    rotation: Option<(usize, usize)>,
+    external: Option<ExternalAngle>,
+    preperiodic: Option<Preperiodic>,
+    path: Vec<(f64, f64)>, // the first iterates, for the sparklines
+}
+
+impl OrbitStats {
+    /// Statistics for c, given its orbit under `formula` as returned by
+    /// [`Formula::orbit`]. Cycles and angles are only found for z → z² + c.
+    pub fn new(c: (f64, f64), path: &[(f64, f64)], max_iter: usize, formula: Formula) -> Self {
+        let mandelbrot = formula == Formula::Mandelbrot;
+        let bailout = formula.bailout(c, max_iter);
+        let iterations = match bailout {
+            Some(Bailout::Escaped(n)) => n,
+            _ => max_iter,
+        };
+        let interior = (mandelbrot && iterations == max_iter)
+            .then(|| interior(c, max_iter))
+            .flatten();
+        Self {
+            c,
+            formula,
+            max_iter,
+            iterations,
+            converged: match bailout {
+                Some(Bailout::Converged(n)) => Some(n),
+                _ => None,
+            },
+            max_abs: path.iter().map(|&(x, y)| x.hypot(y)).fold(0.0, f64::max),
+            last: path.last().copied().unwrap_or_default(),
+            interior,
+            rotation: interior.and_then(|interior| rotation_number(c, &interior)),
+            external: mandelbrot.then(|| external_angle(c, max_iter)).flatten(),
+            preperiodic: preperiodic(path),
+            path: path.iter().copied().take(SPARKLINE_ITERATES).collect(),
+        }
+    }
+
+    /// Period of the attracting cycle, for points in the set where it's
+    /// found.
+    pub fn period(&self) -> Option<usize> {
+        self.interior.map(|interior| interior.period)
+    }
+
+    /// Where the orbit falls into a cycle, if it does numerically.
+    pub fn preperiodic(&self) -> Option<Preperiodic> {
+        self.preperiodic
+    }
+
+    pub fn show(&self, ui: &mut egui::Ui) {
+        egui::Grid::new("orbit_stats")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("c");
+                ui.label(format!("{:.9} {:+.9}i", self.c.0, self.c.1));
+                ui.end_row();
+                ui.label(tr!("orbit-escape-time"));
+                if self.iterations < self.max_iter {
+                    ui.label(self.iterations.to_string());
+                } else if let Some(n) = self.converged {
+                    ui.label(tr!("orbit-converged", n = n));
+                } else {
+                    ui.label(tr!("orbit-no-escape", max_iter = self.max_iter));
+                }
+                ui.end_row();
+                ui.label(tr!("orbit-largest"));
+                ui.label(format!("{:.6}", self.max_abs));
+                ui.end_row();
+                ui.label(tr!("orbit-last"));
+                ui.label(format!("{:.6} {:+.6}i", self.last.0, self.last.1));
+                ui.end_row();
+                if let Some(external) = &self.external {
+                    ui.label(tr!("orbit-external-angle"));
+                    ui.label(tr!("orbit-turns", turns = format!("{:.9}", external.turns)))
+                        .on_hover_text(tr!("orbit-external-angle-hover"));
+                    ui.end_row();
+                    ui.label(tr!("orbit-binary"));
+                    ui.label(format!("0.{}", abbreviate(&external.bits, BINARY_DIGITS)))
+                        .on_hover_text(format!("0.{}", external.bits));
+                    ui.end_row();
+                }
+                if self.formula == Formula::Mandelbrot && self.iterations == self.max_iter {
+                    match &self.interior {
+                        Some(interior) => {
+                            ui.label(tr!("orbit-period"));
+                            ui.label(interior.period.to_string());
+                            ui.end_row();
+                            ui.label(tr!("orbit-multiplier"));
+                            ui.label(format!("{:.6}", interior.multiplier));
+                            ui.end_row();
+                            ui.label(tr!("orbit-internal-angle"));
+                            ui.label(tr!(
+                                "orbit-turns",
+                                turns = format!("{:.6}", interior.internal_angle)
+                            ));
+                            ui.end_row();
+                            if let Some((q, p)) = self.rotation {
+                                ui.label(tr!("orbit-rotation-number"));
+                                ui.label(format!("{q}/{p}"))
+                                    .on_hover_text(tr!("orbit-rotation-number-hover"));
+                                ui.end_row();
+                            }
+                            ui.label(tr!("orbit-interior-distance"));
+                            ui.label(format!("{:.3e}", interior.distance))
+                                .on_hover_text(tr!("orbit-interior-distance-hover"));
+                            ui.end_row();
+                        }
+                        None => {
+                            ui.label(tr!("orbit-period"));
+                            ui.label(tr!("orbit-period-unsettled"));
+                            ui.end_row();
+                        }
+                    }
+                }
+                if let Some(found) = self.preperiodic {
+                    ui.label(tr!("orbit-preperiod"));
+                    ui.label(tr!(
+                        "orbit-preperiod-value",
+                        preperiod = found.preperiod,
+                        period = found.period
+                    ))
+                    .on_hover_text(tr!("orbit-preperiod-hover"));
+                    ui.end_row();
+                }
+            });
+        egui::CollapsingHeader::new(tr!("orbit-sparklines"))
+            .id_salt("orbit_sparklines")
+            .default_open(true)
+            .show(ui, |ui| {
+                egui::Grid::new("orbit_sparklines_grid")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label("Re");
+                        sparkline(ui, &self.path, |z| z.0, egui::Color32::LIGHT_BLUE);
+                        ui.end_row();
+                        ui.label("Im");
+                        sparkline(ui, &self.path, |z| z.1, egui::Color32::LIGHT_RED);
+                        ui.end_row();
+                    });
+            });
+    }
+}
+
+/// Plots `part` of each iterate in `path` against n, over [-2, 2], so
+/// oscillations stand out. Hovering shows the value of an iterate.
+fn sparkline(
+    ui: &mut egui::Ui,
+    path: &[(f64, f64)],
+    part: impl Fn((f64, f64)) -> f64,
+    color: egui::Color32,
+) {
+    let (response, painter) = ui.allocate_painter(SPARKLINE_SIZE, egui::Sense::hover());
+    let rect = response.rect;
+    painter.rect_filled(rect, 0.0, egui::Color32::BLACK);
+    let step = rect.width() / (SPARKLINE_ITERATES - 1) as f32;
+    let to_screen = |n: usize, value: f64| {
+        egui::pos2(
+            rect.left() + n as f32 * step,
+            rect.center().y - (value.clamp(-2.0, 2.0) / 4.0) as f32 * rect.height(),
+        )
+    };
+    painter.hline(
+        rect.x_range(),
+        rect.center().y,
+        egui::Stroke::new(1.0, egui::Color32::DARK_GRAY),
+    );
+    let points: Vec<_> = (path.iter().enumerate())
+        .map(|(n, &z)| to_screen(n, part(z)))
+        .collect();
+    painter.add(egui::Shape::line(points, egui::Stroke::new(1.0, color)));
+    if let Some(pointer) = response.hover_pos() {
+        let n = ((pointer.x - rect.left()) / step).round() as usize;
+        if let Some(&z) = path.get(n) {
+            let value = part(z);
+            painter.circle_filled(to_screen(n, value), 2.5, egui::Color32::WHITE);
+            response.on_hover_text_at_pointer(tr!(
+                "orbit-sparkline-value",
+                n = n,
+                value = format!("{value:.6}")
+            ));
+        }
+    }
+}
+
+/// Tints the pixels of the view in the set with the same period as the
+/// selected point, outlining the hyperbolic components of that period.
+#[derive(Default)]
+pub struct PeriodHighlight {
+    pub enabled: bool,
+    /// The mask for the shown frame and the period it marks.
+    texture: Option<(usize, egui::TextureHandle)>,
+}
+
+impl PeriodHighlight {
+    /// Forgets the mask, for when a new frame is shown.
+    pub fn invalidate(&mut self) {
+        self.texture = None;
+    }
+
+    /// The option to highlight `period`, the selected point's if it has one.
+    pub fn show(&mut self, ui: &mut egui::Ui, period: Option<usize>) {
+        if let Some(period) = period {
+            ui.checkbox(
+                &mut self.enabled,
+                tr!("orbit-highlight-period", period = period),
+            )
+            .on_hover_text(tr!("orbit-highlight-period-hover"));
+        }
+    }
+
+    /// Marks the pixels of `iterations`, rendered from `view`, with period
+    /// `period`, to be drawn over the frame. Computed the first time it's
+    /// asked for.
+    pub fn texture(
+        &mut self,
+        ctx: &egui::Context,
+        period: Option<usize>,
+        iterations: Option<&IterationBuffer>,
+        view: &View,
+    ) -> Option<&egui::TextureHandle> {
+        let (Some(period), Some(iterations), true) = (period, iterations, self.enabled) else {
+            return None;
+        };
+        if self
+            .texture
+            .as_ref()
+            .is_none_or(|(shown, _)| *shown != period)
+        {
+            let mut mask = Image::new(iterations.width, iterations.height, [0; 4]);
+            let marked = period_mask(iterations, view, period);
+            for (pixel, marked) in mask.pixels.iter_mut().zip(marked) {
+                if marked {
+                    *pixel = HIGHLIGHT_COLOR;
+                }
+            }
+            let texture = ctx.load_texture(
+                "period_highlight",
+                crate::to_color_image(&mask),
+                egui::TextureOptions::NEAREST,
+            );
+            self.texture = Some((period, texture));
+        }
+        self.texture.as_ref().map(|(_, texture)| texture)
+    }
+}
+
+/// `text` cut to `len` characters, with an ellipsis if anything was cut.
+fn abbreviate(text: &str, len: usize) -> String {
+    if text.chars().count() <= len {
+        text.to_owned()
+    } else {
+        text.chars().take(len).chain(['…']).collect()
+    }
+}
+
+/// Cobweb and bifurcation diagrams of x → x² + c, for points on or close to
+/// the real axis.
+#[derive(Default)]
+pub struct RealDynamics {
+    bifurcation: Option<egui::TextureHandle>, // rendered on first use
+}
+
+impl RealDynamics {
+    /// Shows the diagrams for `c` if it is within a few pixels of size
+    /// `pixel_size` of the real axis, treating it as real.
+    pub fn show(&mut self, ui: &mut egui::Ui, c: (f64, f64), pixel_size: f64, max_iter: usize) {
+        if c.1.abs() > REAL_AXIS_PIXELS * pixel_size {
+            return;
+        }
+        let c = c.0;
+        egui::CollapsingHeader::new(tr!("real-dynamics"))
+            .id_salt("real_dynamics")
+            .default_open(true)
+            .show(ui, |ui| {
+                ui.label(tr!("real-dynamics-map", c = format!("{c:.9}")));
+                ui.label(tr!("real-dynamics-cobweb"));
+                cobweb_plot(ui, c, max_iter.min(500));
+                ui.label(tr!("real-dynamics-bifurcation"));
+                self.bifurcation_plot(ui, c);
+            });
+    }
+
+    fn bifurcation_plot(&mut self, ui: &mut egui::Ui, c: f64) {
+        let texture = self.bifurcation.get_or_insert_with(|| {
+            let image = bifurcation_diagram(480, 320, 1000, 400);
+            ui.ctx().load_texture(
+                "bifurcation",
+                crate::to_color_image(&image),
+                egui::TextureOptions::LINEAR,
+            )
+        });
+        let (response, painter) = ui.allocate_painter(REAL_PLOT_SIZE, egui::Sense::hover());
+        let rect = response.rect;
+        painter.rect_filled(rect, 0.0, egui::Color32::BLACK);
+        let uv = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+        painter.image(texture.id(), rect, uv, egui::Color32::WHITE);
+        let (c_min, c_max) = BIFURCATION_RANGE;
+        let x = rect.left() + ((c - c_min) / (c_max - c_min)) as f32 * rect.width();
+        if (rect.left()..=rect.right()).contains(&x) {
+            painter.vline(
+                x,
+                rect.y_range(),
+                egui::Stroke::new(1.0, egui::Color32::RED),
+            );
+        }
+    }
+}
+
+/// Draws the graph of x² + c, the diagonal, and the cobweb over [-2, 2]².
+fn cobweb_plot(ui: &mut egui::Ui, c: f64, steps: usize) {
+    let (response, painter) = ui.allocate_painter(REAL_PLOT_SIZE, egui::Sense::hover());
+    let rect = response.rect;
+    painter.rect_filled(rect, 0.0, egui::Color32::BLACK);
+    let to_screen = |(x, y): (f64, f64)| {
+        egui::pos2(
+            rect.center().x + (x / 4.0) as f32 * rect.width(),
+            rect.center().y - (y / 4.0) as f32 * rect.height(),
+        )
+    };
+    let painter = painter.with_clip_rect(rect);
+    let gray = egui::Stroke::new(1.0, egui::Color32::GRAY);
+    painter.line_segment([to_screen((-2.0, -2.0)), to_screen((2.0, 2.0))], gray);
+    let graph: Vec<_> = (0..=100)
+        .map(|i| {
+            let x = -2.0 + 4.0 * i as f64 / 100.0;
+            to_screen((x, x * x + c))
+        })
+        .collect();
+    painter.add(egui::Shape::line(
+        graph,
+        egui::Stroke::new(1.5, egui::Color32::LIGHT_BLUE),
+    ));
+    let path: Vec<_> = cobweb(c, steps).into_iter().map(to_screen).collect();
+    painter.add(egui::Shape::line(
+        path,
+        egui::Stroke::new(1.0, egui::Color32::YELLOW),
+    ));
+}
+
+/// The histogram of the shown frame's escape times. Clicking a bar highlights
+/// its pixels in the view.
+#[derive(Default)]
+pub struct HistogramWindow {
+    pub open: bool,
+    log_scale: bool,
+    selected: Option<usize>,
+    histogram: Option<Histogram>, // None until computed for the current frame
+    highlight: Option<egui::TextureHandle>,
+}
+
+impl HistogramWindow {
+    /// Forgets the histogram, for when a new frame is shown.
+    pub fn invalidate(&mut self, ctx: &egui::Context) {
+        self.histogram = None;
+        self.highlight = None;
+        if self.open {
+            // Recomputed in the next frame's `show`
+            ctx.request_repaint();
+        }
+    }
+
+    /// Marks the pixels of the selected bin in the shown frame, to be drawn
+    /// over it.
+    pub fn highlight(&self) -> Option<&egui::TextureHandle> {
+        self.highlight.as_ref().filter(|_| self.open)
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, iterations: Option<&IterationBuffer>) {
+        if !self.open {
+            return;
+        }
+        let Some(iterations) = iterations else {
+            return;
+        };
+        let histogram = self
+            .histogram
+            .get_or_insert_with(|| histogram(iterations, HISTOGRAM_BINS));
+        // Low iteration limits have fewer bins
+        if self.selected.is_some_and(|bin| bin >= histogram.bins.len()) {
+            self.selected = None;
+        }
+        let mut open = self.open;
+        let mut clicked = None;
+        egui::Window::new(tr!("histogram-title"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.checkbox(&mut self.log_scale, tr!("histogram-log-scale"));
+                clicked = histogram_plot(ui, histogram, self.selected, self.log_scale);
+                let total = iterations.data.len().max(1);
+                ui.label(tr!(
+                    "histogram-interior",
+                    pixels = histogram.interior,
+                    percent = format!("{:.1}", 100.0 * histogram.interior as f64 / total as f64),
+                ));
+                match self.selected {
+                    Some(bin) => {
+                        let range = histogram.bin_range(bin);
+                        ui.label(tr!(
+                            "histogram-highlighted",
+                            from = range.start,
+                            to = range.end.saturating_sub(1),
+                            pixels = histogram.bins[bin],
+                        ));
+                    }
+                    None => {
+                        ui.label(tr!("histogram-hint"));
+                    }
+                }
+            });
+        self.open = open;
+        if let Some(bin) = clicked {
+            self.selected = (self.selected != Some(bin)).then_some(bin);
+            self.highlight = None;
+        }
+        if let Some(bin) = self.selected
+            && self.highlight.is_none()
+        {
+            let range = histogram.bin_range(bin);
+            let mut mask = Image::new(iterations.width, iterations.height, [0; 4]);
+            for (pixel, &iter) in mask.pixels.iter_mut().zip(&iterations.data) {
+                if range.contains(&(iter as usize)) {
+                    *pixel = HIGHLIGHT_COLOR;
+                }
+            }
+            self.highlight = Some(ctx.load_texture(
+                "histogram_highlight",
+                crate::to_color_image(&mask),
+                egui::TextureOptions::NEAREST,
+            ));
+        }
+    }
+}
+
+/// Draws the bars of `histogram`, and returns the bar clicked, if any.
+fn histogram_plot(
+    ui: &mut egui::Ui,
+    histogram: &Histogram,
+    selected: Option<usize>,
+    log_scale: bool,
+) -> Option<usize> {
+    let (response, painter) = ui.allocate_painter(egui::vec2(384.0, 160.0), egui::Sense::click());
+    let rect = response.rect;
+    painter.rect_filled(rect, 0.0, ui.visuals().extreme_bg_color);
+    let height = |count: usize| {
+        if log_scale {
+            (count as f32 + 1.0).ln()
+        } else {
+            count as f32
+        }
+    };
+    let tallest = histogram
+        .bins
+        .iter()
+        .map(|&c| height(c))
+        .fold(0.0, f32::max);
+    let bar_width = rect.width() / histogram.bins.len() as f32;
+    for (i, &count) in histogram.bins.iter().enumerate() {
+        let top = rect.bottom() - rect.height() * height(count) / tallest.max(1.0);
+        let left = rect.left() + i as f32 * bar_width;
+        let bar = egui::Rect::from_min_max(
+            egui::pos2(left, top),
+            egui::pos2(left + bar_width - 1.0, rect.bottom()),
+        );
+        let color = if selected == Some(i) {
+            egui::Color32::from_rgb(HIGHLIGHT_COLOR[0], HIGHLIGHT_COLOR[1], HIGHLIGHT_COLOR[2])
+        } else {
+            egui::Color32::LIGHT_BLUE
+        };
+        painter.rect_filled(bar, 0.0, color);
+    }
+    let bin_at = |pos: egui::Pos2| {
+        let bin = ((pos.x - rect.left()) / bar_width) as usize;
+        bin.min(histogram.bins.len() - 1)
+    };
+    if let Some(pos) = response.hover_pos() {
+        let bin = bin_at(pos);
+        let range = histogram.bin_range(bin);
+        response.clone().on_hover_text(tr!(
+            "histogram-bar",
+            from = range.start,
+            to = range.end.saturating_sub(1),
+            pixels = histogram.bins[bin],
+        ));
+    }
+    if response.clicked() {
+        response.interact_pointer_pos().map(bin_at)
+    } else {
+        None
+    }
+}
+
+/// Compares the shown escape-time coloring with a distance estimation (DEM)
+/// rendering of the same frame, split side by side or blended, to show what
+/// the iteration limit hides.
+pub struct CompareWindow {
+    pub open: bool,
+    blend: bool,
+    /// Split position, or DEM opacity when blending, from 0 to 1.
+    amount: f32,
+    texture: Option<egui::TextureHandle>, // None until rendered for the current frame
+}
+
+impl Default for CompareWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            blend: false,
+            amount: 0.5,
+            texture: None,
+        }
+    }
+}
+
+impl CompareWindow {
+    /// Forgets the DEM rendering, for when a new frame is shown.
+    pub fn invalidate(&mut self, ctx: &egui::Context) {
+        self.texture = None;
+        if self.open {
+            // Rendered again in the next frame's `show`
+            ctx.request_repaint();
+        }
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, iterations: Option<&IterationBuffer>, view: &View) {
+        if !self.open {
+            return;
+        }
+        if self.texture.is_none()
+            && let Some(iterations) = iterations
+        {
+            let image = render_distance(iterations, view);
+            self.texture = Some(ctx.load_texture(
+                "distance_estimate",
+                crate::to_color_image(&image),
+                egui::TextureOptions::default(),
+            ));
+        }
+        let mut open = self.open;
+        egui::Window::new(tr!("compare-title"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut self.blend, false, tr!("compare-split"));
+                    ui.radio_value(&mut self.blend, true, tr!("compare-blend"));
+                });
+                let label = if self.blend {
+                    tr!("compare-opacity")
+                } else {
+                    tr!("split-position")
+                };
+                ui.add(egui::Slider::new(&mut self.amount, 0.0..=1.0).text(label));
+                ui.label(tr!("compare-hint"));
+            });
+        self.open = open;
+    }
+
+    /// Draws the DEM rendering over the part of `image_rect` it's compared
+    /// on.
+    pub fn draw(&self, painter: &egui::Painter, image_rect: egui::Rect) {
+        let Some(texture) = self.texture.as_ref().filter(|_| self.open) else {
+            return;
+        };
+        if self.blend {
+            let uv = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+            let tint = egui::Color32::from_white_alpha((self.amount * 255.0) as u8);
+            painter.image(texture.id(), image_rect, uv, tint);
+        } else {
+            let split = image_rect.left() + image_rect.width() * self.amount;
+            let rect =
+                egui::Rect::from_x_y_ranges(split..=image_rect.right(), image_rect.y_range());
+            let uv = egui::Rect::from_min_max(egui::pos2(self.amount, 0.0), egui::pos2(1.0, 1.0));
+            painter.image(texture.id(), rect, uv, egui::Color32::WHITE);
+            painter.vline(
+                split,
+                image_rect.y_range(),
+                egui::Stroke::new(1.0, egui::Color32::RED),
+            );
+        }
+    }
+}
+
+/// Estimates the area of the set, within the view or overall, by sampling
+/// random points over several frames.
+pub struct AreaWindow {
+    pub open: bool,
+    whole_set: bool,
+    samples: u64,
+    seed: u64,
+    estimator: Option<AreaEstimator>,
+    estimate: Option<AreaEstimate>,
+}
+
+impl Default for AreaWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            whole_set: false,
+            samples: 1_000_000,
+            seed: 1,
+            estimator: None,
+            estimate: None,
+        }
+    }
+}
+
+impl AreaWindow {
+    pub fn show(&mut self, ctx: &egui::Context, view: &View, max_iter: usize) {
+        self.sample();
+        let mut open = self.open;
+        egui::Window::new(tr!("area-title"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let running = self.estimator.is_some();
+                ui.add_enabled_ui(!running, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.radio_value(&mut self.whole_set, false, tr!("area-current-view"));
+                        ui.radio_value(&mut self.whole_set, true, tr!("area-whole-set"));
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("area-samples"));
+                        ui.add(
+                            egui::DragValue::new(&mut self.samples)
+                                .range(1000..=1_000_000_000)
+                                .speed(1000),
+                        );
+                        ui.label(tr!("area-seed"));
+                        ui.add(egui::DragValue::new(&mut self.seed));
+                    });
+                });
+                ui.horizontal(|ui| {
+                    if running {
+                        if ui.button(tr!("area-stop")).clicked() {
+                            self.estimator = None;
+                        }
+                    } else if ui.button(tr!("area-estimate")).clicked() {
+                        self.estimator = Some(if self.whole_set {
+                            AreaEstimator::whole_set(max_iter, self.seed)
+                        } else {
+                            AreaEstimator::for_view(view, max_iter, self.seed)
+                        });
+                    }
+                    if running && let Some(estimate) = &self.estimate {
+                        let progress = estimate.samples as f32 / self.samples as f32;
+                        ui.add(egui::ProgressBar::new(progress).show_percentage());
+                    }
+                });
+                if let Some(estimate) = &self.estimate {
+                    let (low, high) = estimate.confidence_interval(1.96);
+                    egui::Grid::new("area_estimate")
+                        .num_columns(2)
+                        .show(ui, |ui| {
+                            ui.label(tr!("area-area"));
+                            ui.label(format!("{:.6e}", estimate.area));
+                            ui.end_row();
+                            ui.label(tr!("area-interval"));
+                            ui.label(format!("{low:.6e} – {high:.6e}"));
+                            ui.end_row();
+                            ui.label(tr!("area-in-set"));
+                            ui.label(tr!(
+                                "area-hits",
+                                hits = estimate.hits,
+                                samples = estimate.samples,
+                            ));
+                            ui.end_row();
+                        });
+                    ui.label(tr!("area-hint", max_iter = max_iter));
+                }
+            });
+        self.open = open;
+        if self.estimator.is_some() {
+            ctx.request_repaint();
+        }
+    }
+
+    /// Samples for up to [`SAMPLE_BUDGET`], finishing once enough samples
+    /// are taken.
+    fn sample(&mut self) {
+        let Some(estimator) = &mut self.estimator else {
+            return;
+        };
+        let started = Instant::now();
+        let mut remaining = self.samples.saturating_sub(estimator.estimate().samples);
+        while remaining > 0 && started.elapsed() < SAMPLE_BUDGET {
+            let count = remaining.min(SAMPLE_CHUNK);
+            estimator.sample(count);
+            remaining -= count;
+        }
+        self.estimate = Some(estimator.estimate());
+        if remaining == 0 {
+            self.estimator = None;
+        }
+    }
+}
+
+/// Estimates the fractal dimension of the boundary in the view by box
+/// counting, and plots the counts.
+pub struct DimensionWindow {
+    pub open: bool,
+    size: usize,
+    result: Option<BoxCounting>,
+}
+
+impl Default for DimensionWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            size: 1024,
+            result: None,
+        }
+    }
+}
+
+impl DimensionWindow {
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        renderer: &dyn Renderer,
+        view: &View,
+        max_iter: usize,
+        formula: Formula,
+    ) {
+        let mut open = self.open;
+        egui::Window::new(tr!("dimension-title"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr!("dimension-resolution"));
+                    ui.add(egui::DragValue::new(&mut self.size).range(64..=8192));
+                    if ui.button(tr!("dimension-analyze")).clicked() {
+                        let settings = RenderSettings {
+                            width: self.size,
+                            height: self.size,
+                            max_iter,
+                            formula,
+                        };
+                        self.result = Some(box_counting(&renderer.render(view, &settings)));
+                    }
+                });
+                let Some(result) = &self.result else {
+                    ui.label(tr!("dimension-hint"));
+                    return;
+                };
+                match result.fit {
+                    Some(fit) => ui.label(tr!(
+                        "dimension-result",
+                        dimension = format!("{:.4}", fit.slope),
+                        r_squared = format!("{:.4}", fit.r_squared),
+                    )),
+                    None => ui.label(tr!("dimension-no-boundary")),
+                };
+                plot(ui, result);
+            });
+        self.open = open;
+    }
+}
+
+/// Draws the log-log points of `result` and the fitted line.
+fn plot(ui: &mut egui::Ui, result: &BoxCounting) {
+    let points = result.log_points();
+    let (response, painter) = ui.allocate_painter(egui::vec2(320.0, 220.0), egui::Sense::hover());
+    let rect = response.rect;
+    painter.rect_filled(rect, 0.0, ui.visuals().extreme_bg_color);
+    if points.is_empty() {
+        return;
+    }
+    let bounds = |axis: fn(&(f64, f64)) -> f64| {
+        let min = points.iter().map(axis).fold(f64::INFINITY, f64::min);
+        let max = points.iter().map(axis).fold(f64::NEG_INFINITY, f64::max);
+        (min, (max - min).max(1e-9))
+    };
+    let ((x_min, x_range), (y_min, y_range)) = (bounds(|p| p.0), bounds(|p| p.1));
+    let plot_rect = rect.shrink(20.0);
+    let to_screen = |(x, y): (f64, f64)| {
+        egui::pos2(
+            plot_rect.left() + ((x - x_min) / x_range) as f32 * plot_rect.width(),
+            plot_rect.bottom() - ((y - y_min) / y_range) as f32 * plot_rect.height(),
+        )
+    };
+    let text_color = ui.visuals().text_color();
+    if let Some(fit) = result.fit {
+        let line = |x: f64| to_screen((x, fit.slope * x + fit.intercept));
+        painter.line_segment(
+            [line(x_min), line(x_min + x_range)],
+            egui::Stroke::new(1.5, egui::Color32::LIGHT_BLUE),
+        );
+    }
+    for &point in &points {
+        painter.circle_filled(to_screen(point), 3.0, egui::Color32::YELLOW);
+    }
+    painter.text(
+        rect.center_bottom(),
+        egui::Align2::CENTER_BOTTOM,
+        tr!("dimension-x-axis"),
+        egui::FontId::proportional(11.0),
+        text_color,
+    );
+    painter.text(
+        rect.left_top() + egui::vec2(4.0, 2.0),
+        egui::Align2::LEFT_TOP,
+        tr!("dimension-y-axis"),
+        egui::FontId::proportional(11.0),
+        text_color,
+    );
+}
@@ -0,0 +1,202 @@
+//! Animations keyed on wall-clock time rather than counted in frames, so they
+//! play at the same pace whether the window repaints at 30 or 144 Hz, or
+//! stalls for a moment.
+
+use mandelbrot_core::View;
+use web_time::Instant;
+
+/// How an animation speeds up and slows down between its start and end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Easing {
+    /// Starting and stopping gently, as for longer moves.
+    InOut,
+    /// Starting at full speed and stopping gently, as for responses to
+    /// input.
+    Out,
+}
+
+impl Easing {
+    /// How far along an animation eased this way is at `t`, from 0 to 1, of
+    /// its duration.
+    pub fn apply(self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::InOut => t * t * (3.0 - 2.0 * t),
+            Easing::Out => 1.0 - (1.0 - t).powi(3),
+        }
+    }
+}
+
+/// A change over a fixed time from when it was started.
+#[derive(Debug, Clone, Copy)]
+pub struct Tween {
+    start: Instant,
+    /// Seconds the change takes.
+    duration: f64,
+    easing: Easing,
+}
+
+impl Tween {
+    /// A tween of `duration` seconds starting now.
+    pub fn new(duration: f64, easing: Easing) -> Self {
+        Self {
+            start: Instant::now(),
+            duration,
+            easing,
+        }
+    }
+
+    /// How far along the change is, eased, from 0 to 1.
+    pub fn progress(&self) -> f64 {
+        let t = self.start.elapsed().as_secs_f64() / self.duration;
+        self.easing.apply(if self.duration > 0.0 { t } else { 1.0 })
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.start.elapsed().as_secs_f64() >= self.duration
+    }
+}
+
+/// A smooth move of the view to another one.
+#[derive(Debug, Clone, Copy)]
+pub struct ViewTransition {
+    from: View,
+    /// Where the view ends up.
+    pub to: View,
+    tween: Tween,
+    /// The view last shown by [`ViewTransition::advance`].
+    shown: View,
+}
+
+impl ViewTransition {
+    /// A move from `from` to `to` over `duration` seconds, starting now.
+    pub fn new(from: View, to: View, duration: f64, easing: Easing) -> Self {
+        Self {
+            from,
+            to,
+            tween: Tween::new(duration, easing),
+            shown: from,
+        }
+    }
+
+    /// The view to show now, as [`View::interpolate`] goes between them, or
+    /// `None` if `current` isn't the one last shown because something else
+    /// moved the view meanwhile.
+    pub fn advance(&mut self, current: &View) -> Option<View> {
+        if *current != self.shown {
+            return None;
+        }
+        self.shown = self.from.interpolate(&self.to, self.tween.progress());
+        Some(self.shown)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.tween.is_finished()
+    }
+}
+
+/// The time of something played back, advancing with the wall clock while
+/// playing.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Clock {
+    /// Seconds in when last started or moved.
+    offset: f64,
+    /// When last started, while playing.
+    started: Option<Instant>,
+}
+
+impl Clock {
+    /// A clock playing from the start now.
+    pub fn playing() -> Self {
+        let mut clock = Clock::default();
+        clock.play();
+        clock
+    }
+
+    /// Seconds in.
+    pub fn time(&self) -> f64 {
+        self.offset
+            + self
+                .started
+                .map_or(0.0, |started| started.elapsed().as_secs_f64())
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.started.is_some()
+    }
+
+    /// Plays on from the current time.
+    pub fn play(&mut self) {
+        if self.started.is_none() {
+            self.started = Some(Instant::now());
+        }
+    }
+
+    /// Stops at the current time.
+    pub fn pause(&mut self) {
+        self.offset = self.time();
+        self.started = None;
+    }
+
+    /// Moves to `time` seconds in, playing on from there if playing.
+    pub fn seek(&mut self, time: f64) {
+        self.offset = time;
+        if self.started.is_some() {
+            self.started = Some(Instant::now());
+        }
+    }
+
+    /// Once past `end` seconds, goes back to the start to play it again when
+    /// `looping`, or else stops at `end`.
+    pub fn keep_within(&mut self, end: f64, looping: bool) {
+        let time = self.time();
+        if time < end {
+            return;
+        }
+        if looping && end > 0.0 {
+            self.seek(time % end);
+        } else {
+            self.pause();
+            self.seek(end);
+        }
+    }
+
+    /// Plays, or pauses if playing. Having stopped at `end`, plays again from
+    /// the start.
+    pub fn toggle(&mut self, end: f64) {
+        if self.is_playing() {
+            self.pause();
+        } else {
+            if self.time() >= end {
+                self.seek(0.0);
+            }
+            self.play();
+        }
+    }
+}
+
+/// Measures the time between steps of an animation that moves on a little
+/// every frame, such as the autopilot's zoom.
+#[derive(Debug, Clone, Copy)]
+pub struct Ticker {
+    last: Instant,
+    /// Longest step taken, in seconds, so a stall doesn't cause a jump.
+    max_step: f64,
+}
+
+impl Ticker {
+    pub fn new(max_step: f64) -> Self {
+        Self {
+            last: Instant::now(),
+            max_step,
+        }
+    }
+
+    /// Seconds since the last tick, or since it was created, at most the
+    /// longest step.
+    pub fn tick(&mut self) -> f64 {
+        let dt = self.last.elapsed().as_secs_f64().min(self.max_step);
+        self.last = Instant::now();
+        dt
+    }
+}
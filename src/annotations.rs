@@ -0,0 +1,262 @@
+//! Annotations: labels, arrows, and markers placed at points of the complex
+//! plane, drawn over the view so they stay on their features while zooming
+//! and panning, for making figures. They are saved in session files.
+
+use eframe::egui;
+use mandelbrot_core::View;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::tr;
+
+/// Color of annotations, outlined in `SHADOW` to stand out on any palette.
+pub const COLOR: egui::Color32 = egui::Color32::WHITE;
+pub const SHADOW: egui::Color32 = egui::Color32::from_black_alpha(200);
+/// Radius of markers in points.
+pub const MARKER_RADIUS: f32 = 6.0;
+
+/// Something drawn over the view, positioned by complex coordinates.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Annotation {
+    /// Text centered on a point.
+    Label { at: (f64, f64), text: String },
+    /// An arrow from one point to another.
+    Arrow { from: (f64, f64), to: (f64, f64) },
+    /// A ring around a point.
+    Marker { at: (f64, f64) },
+}
+
+impl Annotation {
+    fn describe(&self) -> String {
+        match self {
+            Annotation::Label { at, text } => tr!(
+                "annotation-label",
+                text = text.as_str(),
+                at = format_point(*at)
+            ),
+            Annotation::Arrow { from, to } => tr!(
+                "annotation-arrow",
+                from = format_point(*from),
+                to = format_point(*to)
+            ),
+            Annotation::Marker { at } => tr!("annotation-marker", at = format_point(*at)),
+        }
+    }
+}
+
+fn format_point((re, im): (f64, f64)) -> String {
+    format!("{re:.6} {im:+.6}i")
+}
+
+/// What clicking or dragging on the view places.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tool {
+    Label,
+    Arrow,
+    Marker,
+}
+
+impl Tool {
+    const ALL: [Tool; 3] = [Tool::Label, Tool::Arrow, Tool::Marker];
+
+    fn name(self) -> String {
+        match self {
+            Tool::Label => tr!("annotations-tool-label"),
+            Tool::Arrow => tr!("annotations-tool-arrow"),
+            Tool::Marker => tr!("annotations-tool-marker"),
+        }
+    }
+}
+
+/// The View → Annotations window, which also places new annotations.
+#[derive(Default)]
+pub struct AnnotationsWindow {
+    pub open: bool,
+    pub annotations: Vec<Annotation>,
+    tool: Option<Tool>,
+    /// Text of new labels.
+    text: String,
+    /// Where the arrow being dragged starts.
+    arrow_start: Option<(f64, f64)>,
+}
+
+impl AnnotationsWindow {
+    /// Whether clicks and drags on the view place annotations rather than
+    /// select a path.
+    pub fn is_placing(&self) -> bool {
+        self.open && self.tool.is_some()
+    }
+
+    /// Shows the window; `center` is the center of the view, where new
+    /// annotations can be placed without a mouse.
+    pub fn show(&mut self, ctx: &egui::Context, center: (f64, f64)) {
+        let mut open = self.open;
+        egui::Window::new(tr!("annotations-title"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr!("annotations-place"));
+                    ui.selectable_value(&mut self.tool, None, tr!("annotations-tool-none"));
+                    for tool in Tool::ALL {
+                        ui.selectable_value(&mut self.tool, Some(tool), tool.name());
+                    }
+                });
+                match self.tool {
+                    Some(Tool::Label) => {
+                        ui.horizontal(|ui| {
+                            ui.label(tr!("annotations-text"));
+                            ui.text_edit_singleline(&mut self.text);
+                        });
+                        ui.weak(tr!("annotations-label-hint"));
+                    }
+                    Some(Tool::Arrow) => {
+                        ui.weak(tr!("annotations-arrow-hint"));
+                    }
+                    Some(Tool::Marker) => {
+                        ui.weak(tr!("annotations-marker-hint"));
+                    }
+                    None => {}
+                }
+                let at_center = match self.tool {
+                    Some(Tool::Label) if !self.text.trim().is_empty() => Some(Annotation::Label {
+                        at: center,
+                        text: self.text.trim().to_owned(),
+                    }),
+                    Some(Tool::Marker) => Some(Annotation::Marker { at: center }),
+                    _ => None,
+                };
+                if let Some(annotation) = at_center
+                    && ui.button(tr!("annotations-place-center")).clicked()
+                {
+                    self.annotations.push(annotation);
+                }
+                ui.separator();
+                let mut remove = None;
+                egui::ScrollArea::vertical()
+                    .max_height(200.0)
+                    .show(ui, |ui| {
+                        for (i, annotation) in self.annotations.iter().enumerate() {
+                            ui.horizontal(|ui| {
+                                let button = ui
+                                    .small_button("🗑")
+                                    .on_hover_text(tr!("annotations-remove"));
+                                button.widget_info(|| {
+                                    egui::WidgetInfo::labeled(
+                                        egui::WidgetType::Button,
+                                        true,
+                                        tr!(
+                                            "annotations-remove-one",
+                                            annotation = annotation.describe()
+                                        ),
+                                    )
+                                });
+                                if button.clicked() {
+                                    remove = Some(i);
+                                }
+                                ui.label(annotation.describe());
+                            });
+                        }
+                    });
+                if let Some(i) = remove {
+                    self.annotations.remove(i);
+                }
+                if self.annotations.is_empty() {
+                    ui.weak(tr!("annotations-none"));
+                } else if ui.button(tr!("annotations-remove-all")).clicked() {
+                    self.annotations.clear();
+                }
+            });
+        self.open = open;
+    }
+
+    /// Places annotations with the selected tool as `response`, the view's,
+    /// is clicked or dragged. `to_complex` maps screen positions to points.
+    pub fn place(
+        &mut self,
+        response: &egui::Response,
+        to_complex: impl Fn(egui::Pos2) -> (f64, f64),
+    ) {
+        let Some(tool) = self.tool else {
+            return;
+        };
+        // Drags end with the button released, when there's no interaction
+        let pos = (response.interact_pointer_pos())
+            .or_else(|| response.ctx.input(|i| i.pointer.latest_pos()));
+        let Some(pos) = pos else {
+            return;
+        };
+        match tool {
+            Tool::Label if response.clicked() && !self.text.trim().is_empty() => {
+                self.annotations.push(Annotation::Label {
+                    at: to_complex(pos),
+                    text: self.text.trim().to_owned(),
+                });
+            }
+            Tool::Marker if response.clicked() => {
+                self.annotations.push(Annotation::Marker {
+                    at: to_complex(pos),
+                });
+            }
+            Tool::Arrow => {
+                if response.drag_started() {
+                    let origin = response.ctx.input(|i| i.pointer.press_origin());
+                    self.arrow_start = Some(to_complex(origin.unwrap_or(pos)));
+                }
+                if response.drag_stopped()
+                    && let Some(from) = self.arrow_start.take()
+                {
+                    self.annotations.push(Annotation::Arrow {
+                        from,
+                        to: to_complex(pos),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Draws the annotations over `image_rect`, which shows `view`.
+    /// `pointer` is where the arrow being dragged ends.
+    pub fn draw(
+        &self,
+        painter: &egui::Painter,
+        image_rect: egui::Rect,
+        view: &View,
+        pointer: Option<egui::Pos2>,
+    ) {
+        let side = image_rect.width() as usize;
+        let to_screen = |(re, im): (f64, f64)| {
+            let (x, y) = view.complex_to_pixel(re, im, side, side);
+            image_rect.min + egui::vec2(x as f32, y as f32)
+        };
+        for annotation in &self.annotations {
+            match annotation {
+                Annotation::Label { at, text } => draw_label(painter, to_screen(*at), text),
+                Annotation::Arrow { from, to } => {
+                    draw_arrow(painter, to_screen(*from), to_screen(*to));
+                }
+                Annotation::Marker { at } => draw_marker(painter, to_screen(*at)),
+            }
+        }
+        if let (Some(from), Some(pointer)) = (self.arrow_start, pointer) {
+            draw_arrow(painter, to_screen(from), pointer);
+        }
+    }
+}
+
+fn draw_label(painter: &egui::Painter, center: egui::Pos2, text: &str) {
+    let galley = painter.layout_no_wrap(text.to_owned(), egui::FontId::proportional(14.0), COLOR);
+    let rect = egui::Rect::from_center_size(center, galley.size());
+    painter.rect_filled(rect.expand(3.0), 3.0, SHADOW);
+    painter.galley(rect.min, galley, COLOR);
+}
+
+fn draw_arrow(painter: &egui::Painter, from: egui::Pos2, to: egui::Pos2) {
+    painter.arrow(from, to - from, egui::Stroke::new(4.0, SHADOW));
+    painter.arrow(from, to - from, egui::Stroke::new(2.0, COLOR));
+}
+
+fn draw_marker(painter: &egui::Painter, center: egui::Pos2) {
+    painter.circle_stroke(center, MARKER_RADIUS, egui::Stroke::new(4.0, SHADOW));
+    painter.circle_stroke(center, MARKER_RADIUS, egui::Stroke::new(2.0, COLOR));
+}
@@ -0,0 +1,84 @@
+//! Endless guided zooming: the autopilot keeps choosing a spot next to the
+//! boundary and zooms towards it, raising the iteration limit with depth.
+
+use mandelbrot_core::{IterationBuffer, View, detail::find_detail};
+
+use crate::{DETAIL_ZOOM, MIN_DETAIL_ENTROPY, animation::Ticker, i18n::tr};
+
+/// Seconds for the view to shrink to half its width.
+const HALVING_SECONDS: f64 = 1.0;
+/// Longest frame time taken into account, so a stall doesn't cause a jump.
+const MAX_STEP_SECONDS: f64 = 0.1;
+/// Iteration limit at the default zoom, and added per doubling of the zoom.
+const BASE_ITER: f64 = 100.0;
+const ITER_PER_DOUBLING: f64 = 40.0;
+
+pub struct Autopilot {
+    target: Option<(f64, f64)>,
+    /// Width of the view at which a new target is picked.
+    retarget_scale: f64,
+    ticker: Ticker,
+}
+
+impl Autopilot {
+    pub fn new() -> Self {
+        Self {
+            target: None,
+            retarget_scale: f64::INFINITY,
+            ticker: Ticker::new(MAX_STEP_SECONDS),
+        }
+    }
+
+    /// Zooms `view` towards the target for the time since the last step,
+    /// picking a new target from `iterations`, the frame last shown, when
+    /// needed. Returns an error message when it can't go on.
+    pub fn step(
+        &mut self,
+        view: &mut View,
+        max_iter: &mut usize,
+        iterations: Option<&IterationBuffer>,
+    ) -> Result<(), String> {
+        let dt = self.ticker.tick();
+        let Some(iterations) = iterations else {
+            return Ok(());
+        };
+        if self.target.is_none() || view.scale <= self.retarget_scale {
+            self.target =
+                Some(pick_target(view, iterations).ok_or_else(|| tr!("autopilot-no-detail"))?);
+            self.retarget_scale = view.scale / DETAIL_ZOOM as f64;
+        }
+        let target = self.target.unwrap();
+        let factor = 0.5f64.powf(dt / HALVING_SECONDS);
+        // Zooming about the target keeps it in place on screen; shrinking its
+        // offset faster than that also brings it to the center
+        view.scale *= factor;
+        view.center.0 = target.0 + (view.center.0 - target.0) * factor * factor;
+        view.center.1 = target.1 + (view.center.1 - target.1) * factor * factor;
+        *max_iter = (*max_iter).max(max_iter_for(view));
+        let pixel = view.pixel_size(iterations.width);
+        let magnitude = view.center.0.abs().max(view.center.1.abs()).max(1.0);
+        if pixel < magnitude * f64::EPSILON * 4.0 {
+            return Err(tr!("autopilot-precision-limit"));
+        }
+        Ok(())
+    }
+}
+
+/// An iteration limit that shows `view` in enough detail, growing with
+/// its depth.
+pub fn max_iter_for(view: &View) -> usize {
+    (BASE_ITER + ITER_PER_DOUBLING * view.zoom().log2().max(0.0)) as usize
+}
+
+/// A point next to the boundary in the most detailed part of `iterations`:
+/// the slowest escaping pixel there.
+fn pick_target(view: &View, iterations: &IterationBuffer) -> Option<(f64, f64)> {
+    let detail = find_detail(iterations, iterations.width / DETAIL_ZOOM)
+        .filter(|detail| detail.entropy >= MIN_DETAIL_ENTROPY)?;
+    let tile = detail.tile;
+    let (x, y) = (tile.y..tile.y + tile.height)
+        .flat_map(|y| (tile.x..tile.x + tile.width).map(move |x| (x, y)))
+        .filter(|&(x, y)| !iterations.is_interior(iterations.get(x, y)))
+        .max_by_key(|&(x, y)| iterations.get(x, y))?;
+    Some(view.pixel_to_complex(x as f64, y as f64, iterations.width, iterations.height))
+}
@@ -0,0 +1,147 @@
+//! Crash recovery: the session is saved every so often to a recovery file in
+//! the app's data folder, which a clean exit removes. If the file is still
+//! there at startup, the last run didn't exit cleanly, and the session in it
+//! is offered back.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use eframe::egui;
+use tracing::{debug, info, warn};
+use web_time::Instant;
+
+use crate::{
+    APP_NAME,
+    i18n::tr,
+    session::{self, Session},
+};
+
+/// How often the session is saved, if it changed.
+const INTERVAL: Duration = Duration::from_secs(30);
+const FILE_NAME: &str = "recovery.ron";
+
+pub struct Autosave {
+    /// The recovery file, or `None` if there is nowhere to keep it.
+    path: Option<PathBuf>,
+    /// What was last written to it, so an unchanged session isn't written
+    /// again.
+    saved: Option<String>,
+    last_save: Instant,
+    /// The session left by a run that didn't exit cleanly, until restored or
+    /// discarded. Autosaving waits until then so as not to overwrite it.
+    recovered: Option<Session>,
+}
+
+impl Autosave {
+    /// Looks for a recovery file left by the last run.
+    pub fn new() -> Self {
+        let path = eframe::storage_dir(APP_NAME).map(|dir| dir.join(FILE_NAME));
+        let recovered = (path.as_deref())
+            .filter(|path| path.exists())
+            .and_then(|path| {
+                session::load(path)
+                    .inspect_err(|err| {
+                        warn!("could not read the recovery file {}: {err}", path.display())
+                    })
+                    .ok()
+            });
+        if recovered.is_some() {
+            info!("found the session of a run that didn't exit cleanly");
+        }
+        Self {
+            path,
+            saved: None,
+            last_save: Instant::now(),
+            recovered,
+        }
+    }
+
+    /// Asks whether to restore the session of the last run, if it didn't exit
+    /// cleanly. Returns the session once the user chooses to.
+    pub fn show_offer(&mut self, ctx: &egui::Context) -> Option<Session> {
+        let recovered = self.recovered.as_ref()?;
+        let mut restore = None;
+        egui::Modal::new(egui::Id::new("recovery")).show(ctx, |ui| {
+            ui.set_max_width(400.0);
+            ui.heading(tr!("recovery-title"));
+            ui.label(tr!("recovery-text"));
+            ui.weak(tr!(
+                "recovery-location",
+                re = format!("{:.6}", recovered.center.0),
+                im = format!("{:+.6}", recovered.center.1),
+                width = format!("{:.3e}", recovered.scale)
+            ));
+            ui.horizontal(|ui| {
+                if ui.button(tr!("recovery-restore")).clicked() {
+                    restore = Some(true);
+                }
+                if ui.button(tr!("recovery-discard")).clicked() {
+                    restore = Some(false);
+                }
+            });
+        });
+        let recovered = self.recovered.take();
+        match restore {
+            Some(true) => recovered,
+            Some(false) => None,
+            None => {
+                self.recovered = recovered;
+                None
+            }
+        }
+    }
+
+    /// Whether it's time to save the session.
+    pub fn is_due(&self) -> bool {
+        self.path.is_some() && self.recovered.is_none() && self.last_save.elapsed() >= INTERVAL
+    }
+
+    /// Writes `session` to the recovery file if it changed since last time.
+    pub fn save(&mut self, session: &Session) {
+        self.last_save = Instant::now();
+        let Some(path) = self.path.clone() else {
+            return;
+        };
+        if let Err(err) = self.write(&path, session) {
+            warn!("could not autosave to {}: {err}", path.display());
+        }
+    }
+
+    fn write(&mut self, path: &Path, session: &Session) -> io::Result<()> {
+        let text = session::to_string(path, session)?;
+        if self.saved.as_ref() == Some(&text) {
+            return Ok(());
+        }
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        // Written beside it and renamed over it, so that a crash while
+        // writing leaves the last save whole
+        let partial = path.with_extension("ron.partial");
+        fs::write(&partial, &text)?;
+        fs::rename(&partial, path)?;
+        debug!(path = %path.display(), "autosaved the session");
+        self.saved = Some(text);
+        Ok(())
+    }
+
+    /// Removes the recovery file, as the app is exiting cleanly. A session
+    /// still on offer is kept for the next run.
+    pub fn clean_exit(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        if self.recovered.is_none()
+            && let Err(err) = fs::remove_file(path)
+            && err.kind() != io::ErrorKind::NotFound
+        {
+            warn!(
+                "could not remove the recovery file {}: {err}",
+                path.display()
+            );
+        }
+    }
+}
@@ -0,0 +1,368 @@
+//! Unattended rendering of a list of locations to PNG files.
+//!
+//! A location list is a text file with one location per line, either a
+//! permalink or `re im scale [max_iter]`. Blank lines and lines starting with
+//! `#` are ignored.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    thread::{self, JoinHandle},
+    time::Instant,
+};
+
+use eframe::egui;
+
+use mandelbrot_core::{Formula, Palette, RenderSettings, Renderer, View, colorize};
+use tracing::{debug_span, info, info_span, warn};
+
+use crate::{
+    PROGRESS_REPAINT_INTERVAL, display_name, export,
+    i18n::tr,
+    invalid_data,
+    location::Location,
+    memory::{EXPORT_BYTES_PER_PIXEL, MemoryBudget},
+    permalink, progress_text,
+    scheduler::{Priority, Scheduled, Scheduler, lower_thread_priority},
+    settings::Settings,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Entry {
+    pub location: Location,
+    /// Palette from a permalink line; plain coordinate lines use the batch default.
+    pub palette: Option<Palette>,
+}
+
+pub fn load_list(path: &Path, default_max_iter: usize) -> io::Result<Vec<Entry>> {
+    parse_list(&fs::read_to_string(path)?, default_max_iter)
+}
+
+pub fn parse_list(text: &str, default_max_iter: usize) -> io::Result<Vec<Entry>> {
+    let mut entries = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let entry = parse_line(line, default_max_iter)
+            .map_err(|err| invalid_data(format!("line {}: {err}", number + 1)))?;
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+fn parse_line(line: &str, default_max_iter: usize) -> io::Result<Entry> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    if let [link] = fields[..] {
+        let (location, palette) = permalink::decode(link)?;
+        return Ok(Entry {
+            location,
+            palette: Some(palette),
+        });
+    }
+    let number = |s: &str| {
+        s.parse::<f64>()
+            .map_err(|_| invalid_data(format!("invalid number {s:?}")))
+    };
+    let (re, im, scale, max_iter) = match fields[..] {
+        [re, im, scale] => (re, im, scale, None),
+        [re, im, scale, max_iter] => (re, im, scale, Some(max_iter)),
+        _ => {
+            return Err(invalid_data(
+                "expected a permalink or `re im scale [max_iter]`",
+            ));
+        }
+    };
+    let max_iter = match max_iter {
+        Some(s) => s
+            .parse()
+            .map_err(|_| invalid_data(format!("invalid iteration count {s:?}")))?,
+        None => default_max_iter,
+    };
+    let scale = number(scale)?;
+    if !(scale.is_finite() && scale > 0.0) {
+        return Err(invalid_data("scale must be positive"));
+    }
+    let center = (number(re)?, number(im)?);
+    if !(center.0.is_finite() && center.1.is_finite()) {
+        return Err(invalid_data("center must be finite"));
+    }
+    Ok(Entry {
+        location: Location {
+            view: View { center, scale },
+            max_iter,
+        },
+        palette: None,
+    })
+}
+
+/// Renders every entry to a `size`×`size` PNG in `out_dir`, named like a
+/// screenshot. Stops early once `cancel` is set. `progress` is called with the
+/// number of finished entries after each one. Returns the failures.
+pub fn render_all(
+    renderer: &dyn Renderer,
+    entries: &[Entry],
+    size: usize,
+    out_dir: &Path,
+    default_palette: Palette,
+    cancel: &AtomicBool,
+    mut progress: impl FnMut(usize),
+) -> Vec<String> {
+    let _span = info_span!("batch", entries = entries.len(), size).entered();
+    let mut failures = Vec::new();
+    if let Err(err) = fs::create_dir_all(out_dir) {
+        failures.push(format!("{}: {err}", out_dir.display()));
+        return failures;
+    }
+    for (i, entry) in entries.iter().enumerate() {
+        if cancel.load(Ordering::Relaxed) {
+            info!(rendered = i, "batch cancelled");
+            break;
+        }
+        let _span = debug_span!("entry", index = i).entered();
+        let location = entry.location;
+        let palette = entry.palette.unwrap_or(default_palette);
+        let settings = RenderSettings {
+            width: size,
+            height: size,
+            max_iter: location.max_iter,
+            formula: Formula::Mandelbrot,
+        };
+        let image = colorize(&renderer.render(&location.view, &settings), palette);
+        let name = export::screenshot_name(&location.view);
+        let path = export::unique_path(out_dir, &name);
+        let link = permalink::encode(&location, palette);
+        if let Err(err) = export::write_png(&path, &image, Some(&link)) {
+            warn!("could not write {}: {err}", path.display());
+            failures.push(format!("{}: {err}", display_name(&path)));
+        }
+        progress(i + 1);
+    }
+    failures
+}
+
+/// A batch render running on a background thread.
+struct Job {
+    total: usize,
+    done: Arc<AtomicUsize>,
+    cancel: Arc<AtomicBool>,
+    handle: JoinHandle<Vec<String>>,
+    started: Instant,
+}
+
+/// The File → Batch Render window.
+pub struct BatchWindow {
+    pub open: bool,
+    list_path: Option<PathBuf>,
+    entries: Vec<Entry>,
+    out_dir: PathBuf,
+    size: usize,
+    job: Option<Job>,
+    message: Option<String>,
+}
+
+impl Default for BatchWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            list_path: None,
+            entries: Vec::new(),
+            out_dir: PathBuf::from("batch"),
+            size: 1024,
+            job: None,
+            message: None,
+        }
+    }
+}
+
+impl BatchWindow {
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        palette: Palette,
+        max_iter: usize,
+        settings: &Settings,
+        scheduler: &Arc<Scheduler>,
+        memory: &Arc<MemoryBudget>,
+    ) {
+        self.poll_job();
+        let mut open = self.open;
+        egui::Window::new(tr!("batch-title"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let running = self.job.is_some();
+                ui.add_enabled_ui(!running, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("batch-list"))
+                            .on_hover_text(tr!("batch-list-hover"));
+                        let name = self
+                            .list_path
+                            .as_deref()
+                            .map_or_else(|| tr!("batch-no-list"), display_name);
+                        ui.label(name);
+                        if ui.button(tr!("button-choose")).clicked()
+                            && let Some(path) = rfd::FileDialog::new().pick_file()
+                        {
+                            self.load_list(&path, max_iter);
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("batch-output-folder"));
+                        let mut dir = self.out_dir.display().to_string();
+                        if ui.text_edit_singleline(&mut dir).changed() {
+                            self.out_dir = dir.into();
+                        }
+                        if ui.button(tr!("button-browse")).clicked()
+                            && let Some(dir) = rfd::FileDialog::new().pick_folder()
+                        {
+                            self.out_dir = dir;
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("batch-size"));
+                        ui.add(
+                            egui::DragValue::new(&mut self.size)
+                                .range(16..=16384)
+                                .suffix(" px"),
+                        );
+                    });
+                });
+                if let Some(job) = &self.job {
+                    let done = job.done.load(Ordering::Relaxed);
+                    let fraction = done as f32 / job.total.max(1) as f32;
+                    ui.add(egui::ProgressBar::new(fraction).text(tr!(
+                        "batch-progress",
+                        done = done,
+                        total = job.total,
+                        progress = progress_text(fraction, job.started.elapsed()),
+                    )));
+                    ui.ctx().request_repaint_after(PROGRESS_REPAINT_INTERVAL);
+                    if scheduler.is_paused(Priority::Export) {
+                        ui.weak(tr!("batch-paused"));
+                    }
+                    if ui.button(tr!("button-cancel")).clicked() {
+                        job.cancel.store(true, Ordering::Relaxed);
+                    }
+                } else if ui
+                    .add_enabled(
+                        !self.entries.is_empty(),
+                        egui::Button::new(tr!("batch-render")),
+                    )
+                    .clicked()
+                {
+                    self.start(ctx, palette, settings, scheduler.clone(), memory);
+                }
+                if let Some(message) = &self.message {
+                    ui.label(message);
+                }
+            });
+        self.open = open;
+    }
+
+    fn load_list(&mut self, path: &Path, max_iter: usize) {
+        match load_list(path, max_iter) {
+            Ok(entries) => {
+                self.message = Some(tr!("batch-loaded", count = entries.len()));
+                self.entries = entries;
+                self.list_path = Some(path.to_owned());
+            }
+            Err(err) => {
+                self.message = Some(tr!(
+                    "batch-read-failed",
+                    name = display_name(path),
+                    error = err.to_string()
+                ));
+                self.entries.clear();
+                self.list_path = None;
+            }
+        }
+    }
+
+    fn start(
+        &mut self,
+        ctx: &egui::Context,
+        palette: Palette,
+        settings: &Settings,
+        scheduler: Arc<Scheduler>,
+        memory: &Arc<MemoryBudget>,
+    ) {
+        // Entries are rendered one at a time
+        let reservation = match memory.reserve(self.size * self.size * EXPORT_BYTES_PER_PIXEL) {
+            Ok(reservation) => reservation,
+            Err(message) => {
+                warn!("batch render refused: {message}");
+                self.message = Some(message);
+                return;
+            }
+        };
+        let done = Arc::new(AtomicUsize::new(0));
+        let cancel = Arc::new(AtomicBool::new(false));
+        let handle = {
+            let entries = self.entries.clone();
+            let out_dir = self.out_dir.clone();
+            let size = self.size;
+            let done = done.clone();
+            let cancel = cancel.clone();
+            let ctx = ctx.clone();
+            let parallel = settings.parallel_renderer();
+            let low_priority = settings.low_priority_exports;
+            thread::spawn(move || {
+                let _reservation = reservation;
+                // Before the renderer starts its threads, so they inherit it
+                if low_priority && let Err(err) = lower_thread_priority() {
+                    warn!("could not lower the batch render's priority: {err}");
+                }
+                let renderer = Scheduled {
+                    renderer: parallel,
+                    scheduler: &scheduler,
+                    priority: Priority::Export,
+                };
+                let failures =
+                    render_all(&renderer, &entries, size, &out_dir, palette, &cancel, |n| {
+                        done.store(n, Ordering::Relaxed);
+                        ctx.request_repaint();
+                    });
+                ctx.request_repaint();
+                failures
+            })
+        };
+        self.job = Some(Job {
+            total: self.entries.len(),
+            done,
+            cancel,
+            handle,
+            started: Instant::now(),
+        });
+        self.message = None;
+    }
+
+    fn poll_job(&mut self) {
+        if !self
+            .job
+            .as_ref()
+            .is_some_and(|job| job.handle.is_finished())
+        {
+            return;
+        }
+        let job = self.job.take().unwrap();
+        let done = job.done.load(Ordering::Relaxed);
+        let failures = job.handle.join().unwrap_or_default();
+        self.message = Some(if failures.is_empty() {
+            tr!("batch-done", done = done, total = job.total)
+        } else {
+            tr!(
+                "batch-done-failures",
+                done = done,
+                total = job.total,
+                failed = failures.len(),
+                failures = failures.join("\n"),
+            )
+        });
+    }
+}
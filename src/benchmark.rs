@@ -0,0 +1,52 @@
+//! The startup benchmark the Auto renderer setting goes by: a small render
+//! with each available renderer, timed, the fastest winning.
+
+use std::time::Duration;
+
+use mandelbrot_core::{Formula, RenderSettings, Renderer, View};
+use tracing::info;
+use web_time::Instant;
+
+use crate::settings::Backend;
+
+/// Side in pixels of the benchmark render.
+const SIZE: usize = 160;
+const MAX_ITER: usize = 500;
+/// Renders per renderer, of which the fastest counts, so that one-time costs
+/// such as compiling shaders on first use don't.
+const RUNS: usize = 2;
+
+/// The fastest of `renderers` at rendering the whole set, if there are any.
+pub fn fastest<'a>(
+    renderers: impl IntoIterator<Item = (Backend, &'a dyn Renderer)>,
+) -> Option<Backend> {
+    let settings = RenderSettings {
+        width: SIZE,
+        height: SIZE,
+        max_iter: MAX_ITER,
+        formula: Formula::Mandelbrot,
+    };
+    let time = |renderer: &dyn Renderer| {
+        (0..RUNS)
+            .map(|_| {
+                let started = Instant::now();
+                renderer.render(&View::default(), &settings);
+                started.elapsed()
+            })
+            .min()
+            .unwrap_or(Duration::MAX)
+    };
+    renderers
+        .into_iter()
+        .map(|(backend, renderer)| {
+            let elapsed = time(renderer);
+            info!(
+                renderer = renderer.name(),
+                ms = elapsed.as_secs_f64() * 1000.0,
+                "benchmarked renderer"
+            );
+            (elapsed, backend)
+        })
+        .min_by_key(|&(elapsed, _)| elapsed)
+        .map(|(_, backend)| backend)
+}
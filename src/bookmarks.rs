@@ -0,0 +1,192 @@
+//! Bookmarks: named views to come back to, saved in session files.
+//!
+//! Lists of bookmarks can be imported from plain text with one
+//! `name re im width` line each, the way many collections of locations are
+//! shared. Names may contain spaces; the last three fields are the
+//! coordinates. Blank lines and lines starting with `#` are ignored.
+
+// Only the desktop can import lists, as the web build has no file access.
+#![cfg_attr(target_arch = "wasm32", allow(dead_code))]
+
+use std::{fs, io, path::Path};
+
+use eframe::egui;
+use mandelbrot_core::View;
+use serde::{Deserialize, Serialize};
+
+use crate::{i18n::tr, invalid_data};
+
+/// A view with a name.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub name: String,
+    pub view: View,
+}
+
+impl Bookmark {
+    fn describe(&self) -> String {
+        let View { center, scale } = self.view;
+        tr!(
+            "bookmarks-location",
+            re = format!("{:.6}", center.0),
+            im = format!("{:+.6}", center.1),
+            width = format!("{scale:.3e}")
+        )
+    }
+}
+
+pub fn load_list(path: &Path) -> io::Result<Vec<Bookmark>> {
+    parse_list(&fs::read_to_string(path)?)
+}
+
+pub fn parse_list(text: &str) -> io::Result<Vec<Bookmark>> {
+    let mut bookmarks = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let bookmark =
+            parse_line(line).map_err(|err| invalid_data(format!("line {}: {err}", number + 1)))?;
+        bookmarks.push(bookmark);
+    }
+    Ok(bookmarks)
+}
+
+fn parse_line(line: &str) -> io::Result<Bookmark> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let [name @ .., re, im, width] = &fields[..] else {
+        return Err(invalid_data("expected `name re im width`"));
+    };
+    if name.is_empty() {
+        return Err(invalid_data("expected a name before the coordinates"));
+    }
+    let number = |s: &str| {
+        s.parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite())
+            .ok_or_else(|| invalid_data(format!("invalid number {s:?}")))
+    };
+    let scale = number(width)?;
+    if scale <= 0.0 {
+        return Err(invalid_data("width must be positive"));
+    }
+    Ok(Bookmark {
+        name: name.join(" "),
+        view: View {
+            center: (number(re)?, number(im)?),
+            scale,
+        },
+    })
+}
+
+/// The View → Bookmarks window.
+#[derive(Default)]
+pub struct BookmarksWindow {
+    pub open: bool,
+    pub bookmarks: Vec<Bookmark>,
+    /// Name of the next bookmark added.
+    name: String,
+    message: Option<String>,
+}
+
+impl BookmarksWindow {
+    /// Shows the window, where the current `view` can be bookmarked. Returns
+    /// the view of the bookmark chosen to go to, if any.
+    pub fn show(&mut self, ctx: &egui::Context, view: &View) -> Option<View> {
+        let mut open = self.open;
+        let mut chosen = None;
+        egui::Window::new(tr!("bookmarks-title"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let label = ui.label(tr!("bookmarks-name"));
+                    ui.text_edit_singleline(&mut self.name)
+                        .labelled_by(label.id);
+                    let name = self.name.trim();
+                    if ui
+                        .add_enabled(!name.is_empty(), egui::Button::new(tr!("bookmarks-add")))
+                        .clicked()
+                    {
+                        self.bookmarks.push(Bookmark {
+                            name: name.to_owned(),
+                            view: *view,
+                        });
+                        self.name.clear();
+                    }
+                });
+                #[cfg(not(target_arch = "wasm32"))]
+                if ui
+                    .button(tr!("bookmarks-import"))
+                    .on_hover_text(tr!("bookmarks-import-hover"))
+                    .clicked()
+                    && let Some(path) = rfd::FileDialog::new()
+                        .add_filter(tr!("filter-text"), &["txt"])
+                        .pick_file()
+                {
+                    self.import(&path);
+                }
+                if let Some(message) = &self.message {
+                    ui.label(message);
+                }
+                ui.separator();
+                let mut remove = None;
+                egui::ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        for (i, bookmark) in self.bookmarks.iter().enumerate() {
+                            ui.horizontal(|ui| {
+                                let button =
+                                    ui.small_button("🗑").on_hover_text(tr!("bookmarks-remove"));
+                                button.widget_info(|| {
+                                    egui::WidgetInfo::labeled(
+                                        egui::WidgetType::Button,
+                                        true,
+                                        tr!("bookmarks-remove-one", name = bookmark.name.as_str()),
+                                    )
+                                });
+                                if button.clicked() {
+                                    remove = Some(i);
+                                }
+                                if ui
+                                    .link(&bookmark.name)
+                                    .on_hover_text(bookmark.describe())
+                                    .clicked()
+                                {
+                                    chosen = Some(bookmark.view);
+                                }
+                            });
+                        }
+                    });
+                if let Some(i) = remove {
+                    self.bookmarks.remove(i);
+                }
+                if self.bookmarks.is_empty() {
+                    ui.weak(tr!("bookmarks-none"));
+                } else if ui.button(tr!("bookmarks-remove-all")).clicked() {
+                    self.bookmarks.clear();
+                }
+            });
+        self.open = open;
+        chosen
+    }
+
+    /// Adds the bookmarks listed in the file at `path` after those there are.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn import(&mut self, path: &Path) {
+        let name = crate::display_name(path);
+        self.message = Some(match load_list(path) {
+            Ok(bookmarks) => {
+                let count = bookmarks.len();
+                self.bookmarks.extend(bookmarks);
+                tr!("bookmarks-imported", count = count, name = name)
+            }
+            Err(err) => tr!(
+                "bookmarks-import-failed",
+                name = name,
+                error = err.to_string()
+            ),
+        });
+    }
+}
@@ -0,0 +1,132 @@
+//! A least-recently-used cache bounded by memory rather than entry count,
+//! used for everything the app keeps around to avoid re-rendering: frames
+//! in the window and tiles in the tile server.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::Hash,
+    sync::Arc,
+};
+
+use mandelbrot_core::IterationBuffer;
+
+/// Default memory budget for cached data.
+pub const DEFAULT_BUDGET_MB: usize = 512;
+
+/// Approximate memory an entry takes up, which counts against the budget.
+pub trait Weigh {
+    fn bytes(&self) -> usize;
+}
+
+impl Weigh for IterationBuffer {
+    fn bytes(&self) -> usize {
+        self.data.len() * size_of::<u32>()
+    }
+}
+
+impl Weigh for Vec<u8> {
+    fn bytes(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T: Weigh> Weigh for Arc<T> {
+    fn bytes(&self) -> usize {
+        T::bytes(self)
+    }
+}
+
+/// How well a cache is doing, for the diagnostics window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Entries dropped to make room for new ones.
+    pub evictions: u64,
+    pub entries: usize,
+    pub bytes: usize,
+    pub budget: usize,
+}
+
+pub struct LruCache<K, V> {
+    /// Values with the time they were last used.
+    entries: HashMap<K, (V, u64)>,
+    /// Keys by the time they were last used, oldest first.
+    order: BTreeMap<u64, K>,
+    clock: u64,
+    stats: CacheStats,
+}
+
+impl<K: Hash + Eq + Clone, V: Weigh> LruCache<K, V> {
+    /// An empty cache holding up to `budget` bytes.
+    pub fn new(budget: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+            clock: 0,
+            stats: CacheStats {
+                budget,
+                ..CacheStats::default()
+            },
+        }
+    }
+
+    /// The value for `key`, marking it as recently used.
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        self.clock += 1;
+        let Some((value, used)) = self.entries.get_mut(key) else {
+            self.stats.misses += 1;
+            return None;
+        };
+        self.stats.hits += 1;
+        let key = self.order.remove(used).unwrap();
+        self.order.insert(self.clock, key);
+        *used = self.clock;
+        Some(value)
+    }
+
+    /// Stores `value`, evicting the least recently used entries to make room.
+    /// Values bigger than the whole budget aren't stored.
+    pub fn insert(&mut self, key: K, value: V) {
+        self.remove(&key);
+        let bytes = value.bytes();
+        if bytes > self.stats.budget {
+            return;
+        }
+        self.clock += 1;
+        self.stats.bytes += bytes;
+        self.stats.entries += 1;
+        self.order.insert(self.clock, key.clone());
+        self.entries.insert(key, (value, self.clock));
+        self.evict();
+    }
+
+    /// Changes the memory budget, evicting entries if it shrank.
+    pub fn set_budget(&mut self, budget: usize) {
+        self.stats.budget = budget;
+        self.evict();
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        let (value, used) = self.entries.remove(key)?;
+        self.order.remove(&used);
+        self.stats.bytes -= value.bytes();
+        self.stats.entries -= 1;
+        Some(value)
+    }
+
+    fn evict(&mut self) {
+        while self.stats.bytes > self.stats.budget
+            && let Some((_, key)) = self.order.pop_first()
+        {
+            let (value, _) = self.entries.remove(&key).unwrap();
+            self.stats.bytes -= value.bytes();
+            self.stats.entries -= 1;
+            self.stats.evictions += 1;
+        }
+    }
+}
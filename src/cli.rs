@@ -0,0 +1,316 @@
+//! Command-line interface. Without a subcommand the GUI is started; the
+//! subcommands render without opening a window.
+
+use std::{io, num::NonZeroUsize, path::PathBuf, sync::atomic::AtomicBool};
+
+use clap::{Parser, Subcommand};
+
+use mandelbrot_core::{
+    DEFAULT_MAX_ITER, Dither, Formula, Palette, ParallelRenderer, RenderSettings, Renderer, View,
+    colorize,
+};
+
+use crate::{
+    batch, cache, distributed,
+    distributed::DistributedRenderer,
+    export::{self, ImageFormat},
+    image_export, invalid_data,
+    location::Location,
+    permalink, script,
+    settings::Settings,
+    tiles,
+};
+
+#[derive(Parser)]
+#[command(version, about = "Interactive Mandelbrot set explorer")]
+pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Render a single view to a PNG or TIFF file
+    Render {
+        /// Center of the view as `re,im`
+        #[arg(long, default_value_t = format_pair(View::default().center), allow_hyphen_values = true)]
+        center: String,
+        /// Width of the view in Mandelbrot units
+        #[arg(long, default_value_t = View::default().scale)]
+        scale: f64,
+        /// Width and height of the image in pixels
+        #[arg(long, default_value_t = 1024)]
+        size: usize,
+        #[arg(long, default_value_t = DEFAULT_MAX_ITER)]
+        max_iter: usize,
+        #[arg(long, default_value = "rainbow", value_parser = parse_palette)]
+        palette: Palette,
+        /// Take center, scale, iteration limit, and palette from a permalink
+        #[arg(long, conflicts_with_all = ["center", "scale", "max_iter", "palette"])]
+        permalink: Option<String>,
+        /// Output file; `.tif` or `.tiff` files are written at 16 bits per channel
+        #[arg(long)]
+        out: PathBuf,
+        /// Bits per channel: 16 colors by the smooth escape time, without banding
+        #[arg(long, default_value_t = 8, value_parser = parse_depth)]
+        depth: u8,
+        /// Write 16-bit images in linear light rather than sRGB, for compositing
+        #[arg(long)]
+        linear: bool,
+        /// Color 8-bit images smoothly and dither them (`none`, `ordered`, or
+        /// `blue-noise`) so gradients don't band
+        #[arg(long, default_value = "none", value_parser = parse_dither)]
+        dither: Dither,
+        /// Render on these workers (`host:port`, comma-separated); 8-bit only
+        #[arg(long, value_delimiter = ',')]
+        workers: Vec<String>,
+        /// Threads to render with here, one per core if not given
+        #[arg(long)]
+        threads: Option<NonZeroUsize>,
+    },
+    /// Render every location in a list file to PNG files
+    Batch {
+        /// One permalink or `re im scale [max_iter]` per line
+        list: PathBuf,
+        /// Width and height of each image in pixels
+        #[arg(long, default_value_t = 1024)]
+        size: usize,
+        /// Iteration limit for lines that don't give one
+        #[arg(long, default_value_t = DEFAULT_MAX_ITER)]
+        max_iter: usize,
+        /// Palette for lines that aren't permalinks
+        #[arg(long, default_value = "rainbow", value_parser = parse_palette)]
+        palette: Palette,
+        #[arg(long, default_value = ".")]
+        out_dir: PathBuf,
+        /// Render on these workers (`host:port`, comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        workers: Vec<String>,
+        /// Threads to render with here, one per core if not given
+        #[arg(long)]
+        threads: Option<NonZeroUsize>,
+    },
+    /// Render tiles for other instances' `--workers` until stopped
+    Worker {
+        /// Address to listen on
+        #[arg(long, default_value = "0.0.0.0:7878")]
+        listen: String,
+        /// Threads to render with, one per core if not given
+        #[arg(long)]
+        threads: Option<NonZeroUsize>,
+    },
+    /// Serve slippy-map tiles (`/{z}/{x}/{y}.png`) over HTTP until stopped
+    Tiles {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        listen: String,
+        /// Iteration limit at zoom level 0
+        #[arg(long, default_value_t = 256)]
+        max_iter: usize,
+        /// Iterations added per zoom level
+        #[arg(long, default_value_t = 64)]
+        iter_per_zoom: usize,
+        #[arg(long, default_value = "rainbow", value_parser = parse_palette)]
+        palette: Palette,
+        /// Memory for keeping rendered tiles, in megabytes
+        #[arg(long, default_value_t = cache::DEFAULT_BUDGET_MB)]
+        cache_mb: usize,
+    },
+    /// Run a Rhai script, starting from the default view
+    Script {
+        /// Script file; see the `script` module docs for the functions available
+        path: PathBuf,
+    },
+}
+
+pub fn run(command: Command) -> io::Result<()> {
+    match command {
+        Command::Render {
+            center,
+            scale,
+            size,
+            max_iter,
+            palette,
+            permalink: link,
+            out,
+            depth,
+            linear,
+            dither,
+            workers,
+            threads,
+        } => {
+            let (location, palette) = match link {
+                Some(link) => permalink::decode(&link)?,
+                None => {
+                    let location = Location {
+                        view: View {
+                            center: parse_pair(&center)?,
+                            scale,
+                        },
+                        max_iter,
+                    };
+                    (location, palette)
+                }
+            };
+            let tiff = (out.extension()).is_some_and(|ext| ext == "tif" || ext == "tiff");
+            let format = match (tiff, depth) {
+                (true, _) => ImageFormat::Tiff16,
+                (false, 16) => ImageFormat::Png16,
+                _ => ImageFormat::Png,
+            };
+            if format.is_deep() && dither != Dither::None {
+                return Err(invalid_data("--dither is for 8-bit PNG files"));
+            }
+            if format.is_deep() || dither != Dither::None {
+                if !workers.is_empty() {
+                    return Err(invalid_data("workers only render undithered 8-bit images"));
+                }
+                return image_export::export(
+                    &parallel_renderer(threads),
+                    &out,
+                    format,
+                    linear,
+                    dither,
+                    location,
+                    Formula::Mandelbrot,
+                    palette,
+                    [size, size],
+                );
+            }
+            if linear {
+                return Err(invalid_data("--linear needs --depth 16 or a TIFF file"));
+            }
+            let settings = RenderSettings {
+                width: size,
+                height: size,
+                max_iter: location.max_iter,
+                formula: Formula::Mandelbrot,
+            };
+            let image = colorize(
+                &renderer(workers, threads).render(&location.view, &settings),
+                palette,
+            );
+            let link = permalink::encode(&location, palette);
+            export::write_png(&out, &image, Some(&link))
+        }
+        Command::Batch {
+            list,
+            size,
+            max_iter,
+            palette,
+            out_dir,
+            workers,
+            threads,
+        } => {
+            let entries = batch::load_list(&list, max_iter)?;
+            let total = entries.len();
+            let failures = batch::render_all(
+                &*renderer(workers, threads),
+                &entries,
+                size,
+                &out_dir,
+                palette,
+                &AtomicBool::new(false),
+                |n| {
+                    eprintln!("rendered {n}/{total}");
+                },
+            );
+            for failure in &failures {
+                eprintln!("failed: {failure}");
+            }
+            if failures.is_empty() {
+                Ok(())
+            } else {
+                Err(io::Error::other(format!(
+                    "{} of {total} locations failed",
+                    failures.len()
+                )))
+            }
+        }
+        Command::Worker { listen, threads } => {
+            distributed::serve(listen, parallel_renderer(threads))
+        }
+        Command::Tiles {
+            listen,
+            max_iter,
+            iter_per_zoom,
+            palette,
+            cache_mb,
+        } => tiles::TileServer {
+            max_iter,
+            iter_per_zoom,
+            palette,
+            cache_mb,
+        }
+        .serve(listen),
+        Command::Script { path } => {
+            let state = script::State {
+                location: Location {
+                    view: View::default(),
+                    max_iter: DEFAULT_MAX_ITER,
+                },
+                palette: Palette::default(),
+            };
+            script::run_file(&path, state, |text| println!("{text}")).map(drop)
+        }
+    }
+}
+
+/// The parallel renderer with `threads` threads, as the settings would give
+/// it.
+fn parallel_renderer(threads: Option<NonZeroUsize>) -> ParallelRenderer {
+    Settings {
+        threads,
+        ..Settings::default()
+    }
+    .parallel_renderer()
+}
+
+/// The parallel renderer, or a distributed one if any workers are given.
+fn renderer(workers: Vec<String>, threads: Option<NonZeroUsize>) -> Box<dyn Renderer> {
+    let local = parallel_renderer(threads);
+    if workers.is_empty() {
+        Box::new(local)
+    } else {
+        Box::new(DistributedRenderer { workers, local })
+    }
+}
+
+fn format_pair((re, im): (f64, f64)) -> String {
+    format!("{re},{im}")
+}
+
+fn parse_pair(text: &str) -> io::Result<(f64, f64)> {
+    let invalid = || invalid_data(format!("expected `re,im`, got {text:?}"));
+    let (re, im) = text.split_once(',').ok_or_else(invalid)?;
+    let re = re.trim().parse().map_err(|_| invalid())?;
+    let im = im.trim().parse().map_err(|_| invalid())?;
+    Ok((re, im))
+}
+
+fn parse_depth(text: &str) -> Result<u8, String> {
+    match text {
+        "8" => Ok(8),
+        "16" => Ok(16),
+        _ => Err("expected 8 or 16".to_owned()),
+    }
+}
+
+fn parse_dither(text: &str) -> Result<Dither, String> {
+    match text {
+        "none" => Ok(Dither::None),
+        "ordered" => Ok(Dither::Ordered),
+        "blue-noise" => Ok(Dither::BlueNoise),
+        _ => Err("expected none, ordered, or blue-noise".to_owned()),
+    }
+}
+
+pub fn parse_palette(name: &str) -> Result<Palette, String> {
+    Palette::ALL
+        .into_iter()
+        .find(|palette| palette.name().eq_ignore_ascii_case(name))
+        .ok_or_else(|| {
+            let names: Vec<_> = Palette::ALL.iter().map(|p| p.name()).collect();
+            format!("expected one of {}", names.join(", "))
+        })
+}
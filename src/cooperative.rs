@@ -0,0 +1,96 @@
+//! Rendering the view on the UI thread a slice at a time, each frame taking
+//! no more than its budget, in Adam7 order like progressive renders. It's
+//! the alternative to background threads where there are none, as on the
+//! web, and keeps the window smooth however few cores there are.
+
+use std::time::Duration;
+
+use mandelbrot_core::{
+    IterationBuffer, RenderSettings, View,
+    interlace::{ADAM7, fill_blocks},
+};
+use web_time::Instant;
+
+/// Pixels computed between looks at the clock.
+const CHECK_EVERY: usize = 64;
+
+pub struct CooperativeRender {
+    view: View,
+    settings: RenderSettings,
+    /// Escape times of the pixels in the finished passes and some of the
+    /// current one.
+    data: Vec<u32>,
+    /// The pass computed next, and the row of the pass and column of the
+    /// row in it.
+    pass: usize,
+    row: usize,
+    column: usize,
+    pixels_done: usize,
+}
+
+impl CooperativeRender {
+    /// A render of `view` with nothing computed yet. Escape times are the
+    /// same as the reference renderers'.
+    pub fn new(view: View, settings: RenderSettings) -> Self {
+        Self {
+            view,
+            settings,
+            data: vec![0; settings.width * settings.height],
+            pass: 0,
+            row: 0,
+            column: 0,
+            pixels_done: 0,
+        }
+    }
+
+    /// Computes pixels for about `budget`. Returns the image with the passes
+    /// finished so far, each computed pixel filling its block, if this
+    /// finished any.
+    pub fn step(&mut self, budget: Duration) -> Option<IterationBuffer> {
+        let started = Instant::now();
+        let passes_before = self.pass;
+        let RenderSettings {
+            width,
+            height,
+            max_iter,
+            formula,
+        } = self.settings;
+        'slice: while let Some(pass) = ADAM7.get(self.pass) {
+            let Some(y) = pass.rows(height).nth(self.row) else {
+                (self.pass, self.row, self.column) = (self.pass + 1, 0, 0);
+                continue;
+            };
+            for x in pass.columns(width).skip(self.column) {
+                let c = self
+                    .view
+                    .pixel_to_complex(x as f64, y as f64, width, height);
+                self.data[y * width + x] = formula.buffer_value(c, max_iter);
+                self.column += 1;
+                self.pixels_done += 1;
+                if self.pixels_done.is_multiple_of(CHECK_EVERY) && started.elapsed() >= budget {
+                    break 'slice;
+                }
+            }
+            (self.row, self.column) = (self.row + 1, 0);
+        }
+        (self.pass > passes_before).then(|| {
+            let mut data = self.data.clone();
+            fill_blocks(&mut data, width, self.pass);
+            IterationBuffer {
+                width,
+                height,
+                max_iter,
+                data,
+            }
+        })
+    }
+
+    /// Fraction of the pixels computed so far.
+    pub fn progress(&self) -> f32 {
+        self.pixels_done as f32 / self.data.len().max(1) as f32
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.pass == ADAM7.len()
+    }
+}
@@ -0,0 +1,105 @@
+//! The readout of the point under the cursor: its c and estimated distance to
+//! the boundary of the set, shown in the status bar, and optionally a circle
+//! of that radius drawn around the cursor.
+
+use eframe::egui;
+use mandelbrot_core::{View, distance::exterior_distance, interior::interior};
+
+use crate::i18n::tr;
+
+const COLOR: egui::Color32 = egui::Color32::from_rgb(255, 160, 0);
+
+/// Estimated distance from a point to the boundary of the set.
+#[derive(Debug, Clone, Copy)]
+enum Distance {
+    Outside(f64),
+    Inside(f64),
+}
+
+impl Distance {
+    fn value(self) -> f64 {
+        match self {
+            Distance::Outside(distance) | Distance::Inside(distance) => distance,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct CursorReadout {
+    /// Whether to draw a circle of the estimated distance around the cursor.
+    pub circle: bool,
+    /// c under the cursor and the iteration limit its distance was estimated
+    /// with.
+    point: Option<((f64, f64), usize)>,
+    /// `None` where neither the orbit escapes nor a cycle is found in time.
+    distance: Option<Distance>,
+}
+
+impl CursorReadout {
+    /// Moves the readout to c, or hides it when the cursor isn't over the
+    /// view, estimating distances within `max_iter` iterations.
+    pub fn update(&mut self, c: Option<(f64, f64)>, max_iter: usize) {
+        let point = c.map(|c| (c, max_iter));
+        if point == self.point {
+            return;
+        }
+        self.point = point;
+        self.distance = c.and_then(|c| {
+            exterior_distance(c, max_iter)
+                .map(Distance::Outside)
+                .or_else(|| {
+                    interior(c, max_iter).map(|interior| Distance::Inside(interior.distance))
+                })
+        });
+    }
+
+    /// The status bar text for the point under the cursor, with distances
+    /// also in pixels of size `pixel_size`.
+    pub fn status(&self, pixel_size: f64) -> Option<String> {
+        let ((re, im), _) = self.point?;
+        let (re, im) = (format!("{re:.9}"), format!("{im:+.9}"));
+        let Some(distance) = self.distance else {
+            return Some(tr!("cursor-unknown", re = re, im = im));
+        };
+        let pixels = format!("{:.1}", distance.value() / pixel_size);
+        let value = format!("{:.3e}", distance.value());
+        Some(match distance {
+            Distance::Outside(_) => tr!(
+                "cursor-outside",
+                re = re,
+                im = im,
+                distance = value,
+                pixels = pixels
+            ),
+            Distance::Inside(_) => tr!(
+                "cursor-inside",
+                re = re,
+                im = im,
+                distance = value,
+                pixels = pixels
+            ),
+        })
+    }
+
+    /// Draws the circle of the estimated distance around the cursor over
+    /// `image_rect`, which shows `view`, and a fainter one of a quarter of it:
+    /// the true distance lies between the two.
+    pub fn draw(&self, painter: &egui::Painter, image_rect: egui::Rect, view: &View) {
+        let (Some(((re, im), _)), Some(distance), true) = (self.point, self.distance, self.circle)
+        else {
+            return;
+        };
+        let side = image_rect.width() as usize;
+        let (x, y) = view.complex_to_pixel(re, im, side, side);
+        let center = image_rect.min + egui::vec2(x as f32, y as f32);
+        let radius = (distance.value() / view.pixel_size(side)) as f32;
+        let shadow = egui::Stroke::new(3.0, egui::Color32::from_black_alpha(160));
+        painter.circle_stroke(center, radius, shadow);
+        painter.circle_stroke(center, radius, egui::Stroke::new(1.5, COLOR));
+        painter.circle_stroke(
+            center,
+            radius / 4.0,
+            egui::Stroke::new(1.0, COLOR.gamma_multiply(0.5)),
+        );
+    }
+}
@@ -0,0 +1,247 @@
+//! Logging through `tracing`, and a window listing recent events.
+//!
+//! Events go to stderr, filtered by `RUST_LOG` (warnings by default). The last
+//! [`CAPACITY`] events are kept for the diagnostics window regardless of
+//! `RUST_LOG`: debug level and above from this app and `mandelbrot_core`, info
+//! and above from dependencies (including their `log` records).
+
+use std::{
+    collections::VecDeque,
+    fmt::{self, Write},
+    sync::{Arc, Mutex},
+};
+
+use eframe::egui;
+use tracing::{
+    Event, Level, Subscriber,
+    field::{Field, Visit},
+};
+use tracing_subscriber::{
+    EnvFilter, Layer,
+    filter::{LevelFilter, Targets},
+    layer::{Context, SubscriberExt},
+    registry::LookupSpan,
+    util::SubscriberInitExt,
+};
+
+use crate::{
+    cache::CacheStats,
+    i18n::tr,
+    memory::{MemoryUsage, megabytes},
+};
+
+/// Number of events kept for the diagnostics window.
+pub const CAPACITY: usize = 1000;
+
+#[derive(Clone)]
+pub struct LogEntry {
+    pub level: Level,
+    pub target: String,
+    /// Names of the spans the event happened in, outermost first.
+    pub spans: Vec<&'static str>,
+    /// The message followed by the other fields as `name=value`.
+    pub message: String,
+}
+
+/// Recent events, shared between the subscriber and the window.
+#[derive(Clone, Default)]
+pub struct LogBuffer(Arc<Mutex<VecDeque<LogEntry>>>);
+
+impl<S> Layer<S> for LogBuffer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let spans = ctx
+            .event_scope(event)
+            .map(|scope| scope.from_root().map(|span| span.name()).collect())
+            .unwrap_or_default();
+        let mut entries = self.0.lock().unwrap();
+        if entries.len() == CAPACITY {
+            entries.pop_front();
+        }
+        entries.push_back(LogEntry {
+            level: *event.metadata().level(),
+            target: event.metadata().target().to_owned(),
+            spans,
+            message: visitor.message + visitor.fields.as_str(),
+        });
+    }
+}
+
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{value:?}");
+        } else {
+            let _ = write!(self.fields, " {}={value:?}", field.name());
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={value}", field.name());
+        }
+    }
+}
+
+/// Installs the global subscriber. Returns the buffer the diagnostics window
+/// reads from.
+pub fn init() -> LogBuffer {
+    let buffer = LogBuffer::default();
+    let stderr = tracing_subscriber::fmt::layer()
+        .with_writer(std::io::stderr)
+        .with_filter(
+            EnvFilter::builder()
+                .with_default_directive(LevelFilter::WARN.into())
+                .from_env_lossy(),
+        );
+    tracing_subscriber::registry()
+        .with(stderr)
+        .with(
+            buffer.clone().with_filter(
+                Targets::new()
+                    .with_target(env!("CARGO_CRATE_NAME"), Level::DEBUG)
+                    .with_target("mandelbrot_core", Level::DEBUG)
+                    .with_default(Level::INFO),
+            ),
+        )
+        .init();
+    buffer
+}
+
+pub struct DiagnosticsWindow {
+    pub open: bool,
+    log: LogBuffer,
+    min_level: Level,
+}
+
+impl DiagnosticsWindow {
+    pub fn new(log: LogBuffer) -> Self {
+        Self {
+            open: false,
+            log,
+            min_level: Level::INFO,
+        }
+    }
+
+    /// Shows the window, with the statistics of `caches` by name and the
+    /// `memory` in use.
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        caches: &[(String, CacheStats)],
+        memory: MemoryUsage,
+    ) {
+        let mut open = self.open;
+        egui::Window::new(tr!("diagnostics-title"))
+            .open(&mut open)
+            .default_size([600.0, 300.0])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr!("diagnostics-show"));
+                    egui::ComboBox::from_id_salt("min_level")
+                        .selected_text(self.min_level.as_str())
+                        .show_ui(ui, |ui| {
+                            for level in [Level::DEBUG, Level::INFO, Level::WARN, Level::ERROR] {
+                                ui.selectable_value(&mut self.min_level, level, level.as_str());
+                            }
+                        });
+                    if ui.button(tr!("diagnostics-clear")).clicked() {
+                        self.log.0.lock().unwrap().clear();
+                    }
+                });
+                egui::CollapsingHeader::new(tr!("diagnostics-memory-use")).show(ui, |ui| {
+                    egui::Grid::new("memory").striped(true).show(ui, |ui| {
+                        for (name, bytes) in [
+                            (tr!("diagnostics-memory-frame"), memory.frame),
+                            (tr!("diagnostics-memory-caches"), memory.caches),
+                            (tr!("diagnostics-memory-exports"), memory.exports),
+                        ] {
+                            ui.label(name);
+                            ui.label(format!("{} MB", megabytes(bytes)));
+                            ui.end_row();
+                        }
+                        ui.strong(tr!("diagnostics-memory-total"));
+                        let fraction = memory.total() as f32 / memory.cap.max(1) as f32;
+                        ui.add(egui::ProgressBar::new(fraction).text(tr!(
+                            "diagnostics-memory-of-cap",
+                            used = megabytes(memory.total()),
+                            cap = memory.cap >> 20
+                        )));
+                        ui.end_row();
+                    });
+                });
+                egui::CollapsingHeader::new(tr!("diagnostics-caches")).show(ui, |ui| {
+                    egui::Grid::new("caches").striped(true).show(ui, |ui| {
+                        ui.strong("");
+                        for heading in [
+                            tr!("diagnostics-entries"),
+                            tr!("diagnostics-memory"),
+                            tr!("diagnostics-hits"),
+                            tr!("diagnostics-misses"),
+                            tr!("diagnostics-evictions"),
+                        ] {
+                            ui.strong(heading);
+                        }
+                        ui.end_row();
+                        for (name, stats) in caches {
+                            ui.label(name);
+                            ui.label(stats.entries.to_string());
+                            ui.label(format!(
+                                "{:.1} / {} MB",
+                                stats.bytes as f64 / (1 << 20) as f64,
+                                stats.budget >> 20
+                            ));
+                            ui.label(stats.hits.to_string());
+                            ui.label(stats.misses.to_string());
+                            ui.label(stats.evictions.to_string());
+                            ui.end_row();
+                        }
+                    });
+                });
+                ui.separator();
+                // Copied out so events logged while drawing don't deadlock.
+                // More verbose levels compare as greater.
+                let entries: Vec<_> = (self.log.0.lock().unwrap().iter())
+                    .filter(|entry| entry.level <= self.min_level)
+                    .cloned()
+                    .collect();
+                egui::ScrollArea::vertical()
+                    .auto_shrink(false)
+                    .stick_to_bottom(true)
+                    .show(ui, |ui| {
+                        for entry in &entries {
+                            ui.horizontal_wrapped(|ui| {
+                                ui.colored_label(level_color(entry.level), entry.level.as_str());
+                                ui.weak(&entry.target);
+                                if !entry.spans.is_empty() {
+                                    ui.weak(entry.spans.join(":"));
+                                }
+                                ui.label(&entry.message);
+                            });
+                        }
+                    });
+            });
+        self.open = open;
+    }
+}
+
+fn level_color(level: Level) -> egui::Color32 {
+    match level {
+        Level::ERROR => egui::Color32::RED,
+        Level::WARN => egui::Color32::YELLOW,
+        Level::INFO => egui::Color32::LIGHT_GREEN,
+        _ => egui::Color32::GRAY,
+    }
+}
@@ -0,0 +1,272 @@
+//! Rendering tiles on other machines over TCP.
+//!
+//! A worker (`mandelbrot-explorer worker`) accepts connections and answers
+//! tile requests until the client disconnects. All numbers are little-endian:
+//!
+//! - request: version `u8`, center re/im and scale `f64`, image width, height,
+//!   and iteration limit `u32`, then the tile's x, y, width, and height `u32`
+//! - response: `u8` status, 0 for success followed by the tile's escape times
+//!   as `u32` row by row, otherwise a `u32` length and UTF-8 error message
+//!
+//! Workers compute tiles exactly like a local render, so distributed images
+//! are bit-identical to local ones. They refuse views that aren't finite and
+//! iteration limits beyond the app's; renders with higher limits stay local.
+//! There is no authentication: only run workers on trusted networks.
+
+use std::{
+    io::{self, BufReader, BufWriter, Read, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    sync::Mutex,
+    thread,
+};
+
+use mandelbrot_core::{
+    Formula, IterationBuffer, ParallelRenderer, RenderSettings, Renderer, Tile, View,
+    render::render_span,
+};
+use tracing::{info, info_span, warn};
+
+use crate::{MAX_ITER, invalid_data};
+
+const VERSION: u8 = 1;
+const REQUEST_LEN: usize = 1 + 3 * 8 + 7 * 4;
+/// Rows per tile handed to a worker.
+const TILE_ROWS: usize = 32;
+/// Largest tile a worker accepts, in pixels.
+const MAX_TILE_PIXELS: usize = 1 << 26;
+/// Longest error message the client accepts from a worker, in bytes.
+const MAX_MESSAGE_LEN: usize = 64 << 10;
+
+/// Serves tile requests on `addr` with `renderer` until the process is
+/// stopped.
+pub fn serve(addr: impl ToSocketAddrs, renderer: ParallelRenderer) -> io::Result<()> {
+    let listener = TcpListener::bind(addr)?;
+    eprintln!("listening on {}", listener.local_addr()?);
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(err) => {
+                warn!("could not accept connection: {err}");
+                continue;
+            }
+        };
+        thread::spawn(move || {
+            let peer = stream.peer_addr().map(|addr| addr.to_string());
+            let _span = info_span!("client", peer = peer.as_deref().unwrap_or("?")).entered();
+            info!("connected");
+            match handle_client(stream, &renderer) {
+                Ok(()) => info!("disconnected"),
+                Err(err) => warn!("connection failed: {err}"),
+            }
+        });
+    }
+    Ok(())
+}
+
+fn handle_client(stream: TcpStream, renderer: &ParallelRenderer) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = BufWriter::new(stream);
+    loop {
+        let mut request = [0; REQUEST_LEN];
+        // A clean disconnect is EOF before the first byte of a request
+        if reader.read(&mut request[..1])? == 0 {
+            return Ok(());
+        }
+        reader.read_exact(&mut request[1..])?;
+        match decode_request(&request) {
+            Ok((view, settings, tile)) => {
+                let data = renderer.render_tile(&view, &settings, &tile);
+                writer.write_all(&[0])?;
+                for iter in data {
+                    writer.write_all(&iter.to_le_bytes())?;
+                }
+            }
+            Err(err) => {
+                let message = err.to_string();
+                writer.write_all(&[1])?;
+                writer.write_all(&(message.len() as u32).to_le_bytes())?;
+                writer.write_all(message.as_bytes())?;
+            }
+        }
+        writer.flush()?;
+    }
+}
+
+fn encode_request(view: &View, settings: &RenderSettings, tile: &Tile) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(REQUEST_LEN);
+    bytes.push(VERSION);
+    for value in [view.center.0, view.center.1, view.scale] {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    for value in [
+        settings.width,
+        settings.height,
+        settings.max_iter,
+        tile.x,
+        tile.y,
+        tile.width,
+        tile.height,
+    ] {
+        bytes.extend_from_slice(&(value as u32).to_le_bytes());
+    }
+    bytes
+}
+
+fn decode_request(bytes: &[u8; REQUEST_LEN]) -> io::Result<(View, RenderSettings, Tile)> {
+    if bytes[0] != VERSION {
+        return Err(invalid_data(format!(
+            "unsupported protocol version {}",
+            bytes[0]
+        )));
+    }
+    let f64_at = |i: usize| f64::from_le_bytes(bytes[1 + i * 8..][..8].try_into().unwrap());
+    let u32_at = |i: usize| u32::from_le_bytes(bytes[25 + i * 4..][..4].try_into().unwrap());
+    let view = View {
+        center: (f64_at(0), f64_at(1)),
+        scale: f64_at(2),
+    };
+    let settings = RenderSettings {
+        width: u32_at(0) as usize,
+        height: u32_at(1) as usize,
+        max_iter: u32_at(2) as usize,
+        formula: Formula::Mandelbrot,
+    };
+    let tile = Tile {
+        x: u32_at(3) as usize,
+        y: u32_at(4) as usize,
+        width: u32_at(5) as usize,
+        height: u32_at(6) as usize,
+    };
+    if tile.x + tile.width > settings.width || tile.y + tile.height > settings.height {
+        return Err(invalid_data("tile outside the image"));
+    }
+    if tile.width * tile.height > MAX_TILE_PIXELS {
+        return Err(invalid_data("tile too large"));
+    }
+    if !(view.center.0.is_finite() && view.center.1.is_finite()) {
+        return Err(invalid_data("center must be finite"));
+    }
+    if !(view.scale.is_finite() && view.scale > 0.0) {
+        return Err(invalid_data("scale must be positive"));
+    }
+    if !(1..=MAX_ITER).contains(&settings.max_iter) {
+        return Err(invalid_data(format!(
+            "iteration limit must be from 1 to {MAX_ITER}"
+        )));
+    }
+    Ok((view, settings, tile))
+}
+
+/// Splits renders into bands of rows and sends them to workers, rendering
+/// locally whatever the workers can't.
+pub struct DistributedRenderer {
+    pub workers: Vec<String>,
+    /// Renders what the workers can't, and formulas they don't render.
+    pub local: ParallelRenderer,
+}
+
+impl DistributedRenderer {
+    /// Renders `tile` on the worker at the other end of `stream`.
+    fn request_tile(
+        stream: &mut TcpStream,
+        view: &View,
+        settings: &RenderSettings,
+        tile: &Tile,
+    ) -> io::Result<Vec<u32>> {
+        stream.write_all(&encode_request(view, settings, tile))?;
+        let mut reader = BufReader::new(&*stream);
+        let mut status = [0];
+        reader.read_exact(&mut status)?;
+        if status[0] != 0 {
+            let mut len = [0; 4];
+            reader.read_exact(&mut len)?;
+            let len = u32::from_le_bytes(len) as usize;
+            if len > MAX_MESSAGE_LEN {
+                return Err(invalid_data("worker error message too long"));
+            }
+            let mut message = vec![0; len];
+            reader.read_exact(&mut message)?;
+            return Err(io::Error::other(String::from_utf8_lossy(&message)));
+        }
+        let mut bytes = vec![0; tile.width * tile.height * 4];
+        reader.read_exact(&mut bytes)?;
+        Ok(bytes
+            .chunks_exact(4)
+            .map(|iter| u32::from_le_bytes(iter.try_into().unwrap()))
+            .collect())
+    }
+}
+
+impl Renderer for DistributedRenderer {
+    fn name(&self) -> &'static str {
+        "Distributed"
+    }
+
+    fn render(&self, view: &View, settings: &RenderSettings) -> IterationBuffer {
+        // Requests only describe renders of z → z² + c, and workers don't
+        // take limits beyond the app's
+        if settings.formula != Formula::Mandelbrot || settings.max_iter > MAX_ITER {
+            return self.local.render(view, settings);
+        }
+        let span = render_span(self, settings).entered();
+        let mut data = vec![0; settings.width * settings.height];
+        let bands = Mutex::new(
+            data.chunks_mut(settings.width.max(1) * TILE_ROWS)
+                .enumerate()
+                .collect::<Vec<_>>(),
+        );
+        // Bands whose worker failed, rendered locally at the end
+        let failed = Mutex::new(Vec::new());
+        let tile_of = |index: usize, band: &[u32]| Tile {
+            x: 0,
+            y: index * TILE_ROWS,
+            width: settings.width,
+            height: band.len() / settings.width.max(1),
+        };
+        thread::scope(|scope| {
+            for worker in &self.workers {
+                let (bands, failed, span) = (&bands, &failed, &span);
+                scope.spawn(move || {
+                    let _span = info_span!(parent: &**span, "worker", addr = worker).entered();
+                    let mut stream = match TcpStream::connect(worker) {
+                        Ok(stream) => stream,
+                        Err(err) => {
+                            warn!("could not connect to {worker}: {err}");
+                            return;
+                        }
+                    };
+                    while let Some((index, band)) = bands.lock().unwrap().pop() {
+                        let tile = tile_of(index, band);
+                        match Self::request_tile(&mut stream, view, settings, &tile) {
+                            Ok(iters) => band.copy_from_slice(&iters),
+                            Err(err) => {
+                                warn!("tile failed on {worker}, giving up on it: {err}");
+                                failed.lock().unwrap().push((index, band));
+                                return;
+                            }
+                        }
+                    }
+                });
+            }
+        });
+        let local = self.local;
+        let leftover = bands.into_inner().unwrap();
+        let failed = failed.into_inner().unwrap();
+        if !leftover.is_empty() || !failed.is_empty() {
+            warn!(
+                tiles = leftover.len() + failed.len(),
+                "rendering tiles locally"
+            );
+        }
+        for (index, band) in leftover.into_iter().chain(failed) {
+            let tile = tile_of(index, band);
+            band.copy_from_slice(&local.render_tile(view, settings, &tile));
+        }
+        IterationBuffer {
+            width: settings.width,
+            height: settings.height,
+            max_iter: settings.max_iter,
+            data,
+        }
+    }
+}
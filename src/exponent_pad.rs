@@ -0,0 +1,63 @@
+//! A pad for picking the complex exponent of the Multibrot formula by
+//! dragging over the plane of exponents, with the integer powers marked.
+
+use std::ops::RangeInclusive;
+
+use eframe::egui;
+
+/// Real parts of the exponents the pad covers.
+const REAL: RangeInclusive<f64> = -4.0..=8.0;
+/// Imaginary parts of the exponents the pad covers.
+const IMAG: RangeInclusive<f64> = -3.0..=3.0;
+const SIZE: egui::Vec2 = egui::vec2(240.0, 120.0);
+/// Exponents picked on the pad are rounded to multiples of this.
+const STEP: f64 = 0.01;
+const COLOR: egui::Color32 = egui::Color32::from_rgb(255, 200, 0);
+
+/// Shows the pad, moving `exponent` to wherever it's clicked or dragged.
+/// Exponents outside the pad's range can still be typed in elsewhere; the
+/// marker then sits at the nearest edge.
+pub fn exponent_pad(ui: &mut egui::Ui, exponent: &mut (f64, f64)) -> egui::Response {
+    let (rect, mut response) = ui.allocate_exact_size(SIZE, egui::Sense::click_and_drag());
+    let to_screen = |a: f64, b: f64| {
+        let x = (a - REAL.start()) / (REAL.end() - REAL.start());
+        let y = (IMAG.end() - b) / (IMAG.end() - IMAG.start());
+        rect.min + egui::vec2(x as f32, y as f32) * rect.size()
+    };
+    if let Some(pos) = response.interact_pointer_pos() {
+        let t = ((pos - rect.min) / rect.size()).clamp(egui::Vec2::ZERO, egui::Vec2::splat(1.0));
+        let a = REAL.start() + t.x as f64 * (REAL.end() - REAL.start());
+        let b = IMAG.end() - t.y as f64 * (IMAG.end() - IMAG.start());
+        let picked = ((a / STEP).round() * STEP, (b / STEP).round() * STEP);
+        if picked != *exponent {
+            *exponent = picked;
+            response.mark_changed();
+        }
+    }
+
+    let painter = ui.painter_at(rect);
+    let visuals = ui.visuals();
+    painter.rect_filled(rect, 2.0, visuals.extreme_bg_color);
+    let grid = egui::Stroke::new(1.0, visuals.weak_text_color().gamma_multiply(0.3));
+    let axis = egui::Stroke::new(1.0, visuals.weak_text_color());
+    for a in (*REAL.start() as i32)..=(*REAL.end() as i32) {
+        let x = to_screen(a as f64, 0.0).x;
+        let stroke = if a == 0 { axis } else { grid };
+        painter.vline(x, rect.y_range(), stroke);
+    }
+    for b in (*IMAG.start() as i32)..=(*IMAG.end() as i32) {
+        let y = to_screen(0.0, b as f64).y;
+        let stroke = if b == 0 { axis } else { grid };
+        painter.hline(rect.x_range(), y, stroke);
+    }
+    // The integer powers, whose maps have no branch cut
+    for a in 2..=(*REAL.end() as i32) {
+        painter.circle_filled(to_screen(a as f64, 0.0), 2.0, axis.color);
+    }
+    let a = exponent.0.clamp(*REAL.start(), *REAL.end());
+    let b = exponent.1.clamp(*IMAG.start(), *IMAG.end());
+    let marker = to_screen(a, b);
+    painter.circle_filled(marker, 4.0, COLOR);
+    painter.circle_stroke(marker, 4.0, egui::Stroke::new(1.0, egui::Color32::BLACK));
+    response
+}
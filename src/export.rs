@@ -0,0 +1,181 @@
+//! Writing rendered frames to image files.
+
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use mandelbrot_core::{Image, Image16, View};
+use tracing::{info, info_span};
+
+use crate::{i18n::tr, invalid_data};
+
+/// PNG text keyword under which exported frames record their permalink.
+const PERMALINK_KEY: &str = "Mandelbrot Permalink";
+
+/// File format and bit depth of exported images.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    /// 8 bits per channel, as shown on screen.
+    Png,
+    /// 16 bits per channel, smoothly colored.
+    Png16,
+    Tiff16,
+}
+
+impl ImageFormat {
+    pub const ALL: [ImageFormat; 3] = [ImageFormat::Png, ImageFormat::Png16, ImageFormat::Tiff16];
+
+    pub fn name(self) -> String {
+        match self {
+            ImageFormat::Png => tr!("image-format-png"),
+            ImageFormat::Png16 => tr!("image-format-png16"),
+            ImageFormat::Tiff16 => tr!("image-format-tiff16"),
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Png | ImageFormat::Png16 => "png",
+            ImageFormat::Tiff16 => "tiff",
+        }
+    }
+
+    /// Whether images are written at 16 bits per channel.
+    pub fn is_deep(self) -> bool {
+        self != ImageFormat::Png
+    }
+}
+
+/// Writes `image` as a PNG, embedding `permalink` so the file can be reopened.
+pub fn write_png(path: &Path, image: &Image, permalink: Option<&str>) -> io::Result<()> {
+    let _span = info_span!("export", path = %path.display()).entered();
+    encode_png(BufWriter::new(File::create(path)?), image, permalink)?;
+    info!(width = image.width, height = image.height, "wrote PNG");
+    Ok(())
+}
+
+/// Encodes `image` as a PNG into `writer`, embedding `permalink` like
+/// [`write_png`].
+pub fn encode_png(writer: impl Write, image: &Image, permalink: Option<&str>) -> io::Result<()> {
+    let mut encoder = png::Encoder::new(writer, image.width as u32, image.height as u32);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    if let Some(permalink) = permalink {
+        encoder
+            .add_text_chunk(PERMALINK_KEY.to_owned(), permalink.to_owned())
+            .map_err(io::Error::other)?;
+    }
+    let mut writer = encoder.write_header().map_err(io::Error::other)?;
+    writer
+        .write_image_data(image.as_bytes())
+        .map_err(io::Error::other)?;
+    writer.finish().map_err(io::Error::other)
+}
+
+/// Writes `image` as a 16-bit PNG, embedding `permalink` like [`write_png`].
+/// If `linear`, the image is in linear light, which the file says so viewers
+/// don't take it for sRGB.
+pub fn write_png16(
+    path: &Path,
+    image: &Image16,
+    linear: bool,
+    permalink: Option<&str>,
+) -> io::Result<()> {
+    let _span = info_span!("export", path = %path.display()).entered();
+    let mut encoder = png::Encoder::new(
+        BufWriter::new(File::create(path)?),
+        image.width as u32,
+        image.height as u32,
+    );
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Sixteen);
+    if linear {
+        encoder.set_source_gamma(png::ScaledFloat::new(1.0));
+    }
+    if let Some(permalink) = permalink {
+        encoder
+            .add_text_chunk(PERMALINK_KEY.to_owned(), permalink.to_owned())
+            .map_err(io::Error::other)?;
+    }
+    // PNG stores samples big-endian
+    let bytes: Vec<u8> = (image.as_samples().iter())
+        .flat_map(|sample| sample.to_be_bytes())
+        .collect();
+    let mut writer = encoder.write_header().map_err(io::Error::other)?;
+    writer.write_image_data(&bytes).map_err(io::Error::other)?;
+    writer.finish().map_err(io::Error::other)?;
+    info!(
+        width = image.width,
+        height = image.height,
+        "wrote 16-bit PNG"
+    );
+    Ok(())
+}
+
+/// Writes `image` as a 16-bit TIFF.
+pub fn write_tiff16(path: &Path, image: &Image16) -> io::Result<()> {
+    let _span = info_span!("export", path = %path.display()).entered();
+    let mut encoder = tiff::encoder::TiffEncoder::new(BufWriter::new(File::create(path)?))
+        .map_err(io::Error::other)?;
+    encoder
+        .write_image::<tiff::encoder::colortype::RGBA16>(
+            image.width as u32,
+            image.height as u32,
+            image.as_samples(),
+        )
+        .map_err(io::Error::other)?;
+    info!(
+        width = image.width,
+        height = image.height,
+        "wrote 16-bit TIFF"
+    );
+    Ok(())
+}
+
+/// Reads the permalink embedded by [`write_png`].
+pub fn read_png_permalink(path: &Path) -> io::Result<String> {
+    let decoder = png::Decoder::new(BufReader::new(File::open(path)?));
+    let reader = decoder.read_info().map_err(io::Error::other)?;
+    reader
+        .info()
+        .uncompressed_latin1_text
+        .iter()
+        .find(|chunk| chunk.keyword == PERMALINK_KEY)
+        .map(|chunk| chunk.text.clone())
+        .ok_or_else(|| invalid_data("no location metadata in image"))
+}
+
+/// A file name that locates the frame, e.g. `m_-0.743643_0.131825_z1e7.png`.
+///
+/// Coordinates get enough decimals to resolve a pixel at the current zoom.
+pub fn screenshot_name(view: &View) -> String {
+    let decimals = ((-view.scale.log10()).ceil() as i32 + 3).clamp(6, 17) as usize;
+    let zoom = format!("{:.2e}", view.zoom());
+    // Trim "1.00e7" to "1e7" and "1.50e7" to "1.5e7"
+    let zoom = match zoom.split_once('e') {
+        Some((mantissa, exponent)) => {
+            let mantissa = mantissa.trim_end_matches('0').trim_end_matches('.');
+            format!("{mantissa}e{exponent}")
+        }
+        None => zoom,
+    };
+    format!(
+        "m_{:.decimals$}_{:.decimals$}_z{zoom}.png",
+        view.center.0, view.center.1
+    )
+}
+
+/// Returns `dir/name`, adding a numeric suffix if that file already exists.
+pub fn unique_path(dir: &Path, name: &str) -> PathBuf {
+    let path = dir.join(name);
+    if !path.exists() {
+        return path;
+    }
+    let (stem, ext) = name.rsplit_once('.').unwrap_or((name, ""));
+    (2..)
+        .map(|n| dir.join(format!("{stem}_{n}.{ext}")))
+        .find(|path| !path.exists())
+        .unwrap()
+}
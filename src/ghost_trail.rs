@@ -0,0 +1,93 @@
+//! The animated path: a point moving along the orbit of the selected point,
+//! one iterate after the other, leaving a trail that fades out behind it.
+//! Orbits that stay bounded are played in a loop; escaping ones play once
+//! and stop at the point that escaped.
+
+use eframe::egui;
+use mandelbrot_core::iterate::ESCAPE_RADIUS;
+
+use crate::animation::Clock;
+
+/// How long the trail behind the moving point takes to fade out.
+const TRAIL_SECONDS: f32 = 1.5;
+/// Trail segments drawn per iteration, for a smooth fade.
+const SEGMENTS_PER_ITERATION: usize = 4;
+const COLOR: egui::Color32 = egui::Color32::YELLOW;
+
+pub struct GhostTrail {
+    /// Whether the path is animated rather than drawn as a line.
+    pub enabled: bool,
+    /// Iterations the point moves through per second.
+    pub speed: f32,
+    /// Time into the animation of the current path.
+    clock: Clock,
+}
+
+impl Default for GhostTrail {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            speed: 4.0,
+            clock: Clock::playing(),
+        }
+    }
+}
+
+impl GhostTrail {
+    /// Plays the animation from the start, such as for a newly selected path.
+    pub fn restart(&mut self) {
+        self.clock = Clock::playing();
+    }
+
+    /// Draws the moving point and its trail along `path`, the iterates of an
+    /// orbit, with `to_screen` mapping them to screen positions. Asks for
+    /// repaints until the animation is over.
+    pub fn draw(
+        &self,
+        painter: &egui::Painter,
+        path: &[(f64, f64)],
+        to_screen: impl Fn((f64, f64)) -> egui::Pos2,
+    ) {
+        if path.len() < 2 {
+            return;
+        }
+        let last = path.len() - 1;
+        let looping = path[last].0.hypot(path[last].1) < ESCAPE_RADIUS;
+        // Time in iterations, so the trail is as long as the point moves
+        // while it fades
+        let now = self.clock.time() as f32 * self.speed;
+        let trail = TRAIL_SECONDS * self.speed;
+        // A position along the path, between the iterates it falls between
+        let position = |s: f32| {
+            let s = if looping {
+                s % last as f32
+            } else {
+                s.min(last as f32)
+            };
+            let i = (s as usize).min(last - 1);
+            let t = s - i as f32;
+            let (from, to) = (to_screen(path[i]), to_screen(path[i + 1]));
+            from + (to - from) * t
+        };
+        let segments = (trail * SEGMENTS_PER_ITERATION as f32).ceil() as usize;
+        let step = trail / segments as f32;
+        for k in 0..segments {
+            let (older, newer) = (now - (k + 1) as f32 * step, now - k as f32 * step);
+            if older < 0.0 {
+                break;
+            }
+            // Looping jumps from the last iterate back to the first
+            if looping && (older / last as f32).floor() != (newer / last as f32).floor() {
+                continue;
+            }
+            let fade = 1.0 - k as f32 / segments as f32;
+            let color = COLOR.gamma_multiply(fade);
+            let (from, to) = (position(older), position(newer));
+            painter.line_segment([from, to], egui::Stroke::new(2.0 * fade + 0.5, color));
+        }
+        painter.circle_filled(position(now), 4.0, COLOR);
+        if looping || now - trail < last as f32 {
+            painter.ctx().request_repaint();
+        }
+    }
+}
@@ -0,0 +1,494 @@
+//! Escape-time rendering on the GPU through eframe's OpenGL context.
+//!
+//! [`GpuRenderer`] computes iteration buffers like the CPU backends, and
+//! [`ScreenRenderer`] draws a colored view straight into the window. The
+//! fragment shaders work in single precision, so images turn blocky beyond a
+//! zoom of about 10⁵; the CPU backends keep full `f64` precision.
+
+use std::{cell::Cell, sync::Arc};
+
+use eframe::{
+    egui::PaintCallbackInfo,
+    egui_glow::ShaderVersion,
+    glow::{self, HasContext},
+};
+use mandelbrot_core::{
+    Formula, IterationBuffer, Palette, ParallelRenderer, RenderSettings, Renderer, ScalarRenderer,
+    View, render::render_span,
+};
+
+const VERTEX_SHADER: &str = r#"
+void main() {
+    // One triangle covering the whole viewport
+    vec2 pos = vec2(float((gl_VertexID & 1) * 4 - 1), float((gl_VertexID & 2) * 2 - 1));
+    gl_Position = vec4(pos, 0.0, 1.0);
+}
+"#;
+
+const FRAGMENT_SHADER: &str = r#"
+uniform vec2 u_center;
+uniform float u_pixel;
+uniform vec2 u_size;
+uniform int u_max_iter;
+out vec4 out_color;
+
+void main() {
+    // Pixel coordinates from the top-left corner, as on the CPU
+    vec2 pixel = vec2(gl_FragCoord.x - 0.5, u_size.y - gl_FragCoord.y - 0.5);
+    vec2 c = u_center + (pixel - u_size * 0.5) * u_pixel;
+    vec2 z = vec2(0.0);
+    int iter = 0;
+    while (dot(z, z) < 4.0 && iter < u_max_iter) {
+        z = vec2(z.x * z.x - z.y * z.y, 2.0 * z.x * z.y) + c;
+        iter++;
+    }
+    // Escape time as little-endian bytes
+    out_color = vec4(
+        float(iter & 255),
+        float((iter >> 8) & 255),
+        float((iter >> 16) & 255),
+        float((iter >> 24) & 255)
+    ) / 255.0;
+}
+"#;
+
+/// Most colors in [`ScreenRenderer`]'s palette texture. Up to this iteration
+/// limit every escape time has its own color, as on the CPU; above it
+/// neighboring escape times share colors.
+const MAX_PALETTE_SIZE: usize = 4096;
+
+/// Shared by [`ScreenRenderer`]'s fragment shaders: where the fragment is in
+/// the callback's rectangle, and the color for an escape time.
+const SCREEN_COMMON: &str = r#"
+// Left, bottom, width, and height of the callback's rectangle in framebuffer
+// pixels
+uniform vec4 u_viewport;
+uniform int u_max_iter;
+uniform sampler2D u_palette;
+out vec4 out_color;
+
+// Pixel coordinates from the top-left corner, as on the CPU
+vec2 pixel_position() {
+    return vec2(
+        gl_FragCoord.x - u_viewport.x - 0.5,
+        u_viewport.y + u_viewport.w - gl_FragCoord.y - 0.5
+    );
+}
+
+// Color of escape time `iter`, as mandelbrot_core::colorize picks it
+vec4 iteration_color(int iter) {
+    if (iter >= u_max_iter) {
+        return vec4(0.0, 0.0, 0.0, 1.0);
+    }
+    int size = textureSize(u_palette, 0).x;
+    return texelFetch(u_palette, ivec2(iter * (size - 1) / u_max_iter, 0), 0);
+}
+"#;
+
+/// Computes and colors the escape time of each pixel.
+const LIVE_FRAGMENT_SHADER: &str = r#"
+uniform vec2 u_center;
+uniform float u_pixel;
+
+void main() {
+    vec2 c = u_center + (pixel_position() - u_viewport.zw * 0.5) * u_pixel;
+    vec2 z = vec2(0.0);
+    int iter = 0;
+    while (dot(z, z) < 4.0 && iter < u_max_iter) {
+        z = vec2(z.x * z.x - z.y * z.y, 2.0 * z.x * z.y) + c;
+        iter++;
+    }
+    out_color = iteration_color(iter);
+}
+"#;
+
+/// Colors escape times already computed, stretched over the rectangle.
+const RECOLOR_FRAGMENT_SHADER: &str = r#"
+uniform highp usampler2D u_iterations;
+
+void main() {
+    ivec2 size = textureSize(u_iterations, 0);
+    // Sampled at the pixel's center, away from texel edges
+    ivec2 texel = ivec2((pixel_position() + 0.5) / u_viewport.zw * vec2(size));
+    texel = clamp(texel, ivec2(0), size - 1);
+    out_color = iteration_color(int(texelFetch(u_iterations, texel, 0).r));
+}
+"#;
+
+/// Compiles and links a program from the full-viewport vertex shader and
+/// `fragment_shader`. Fails on contexts older than GLSL 1.30 / ES 3.0, which
+/// lack integer operations in shaders.
+fn compile_program(gl: &glow::Context, fragment_shader: &str) -> Result<glow::Program, String> {
+    let version = ShaderVersion::get(gl);
+    if !version.is_new_shader_interface() {
+        return Err(format!("{version:?} is too old for GPU rendering"));
+    }
+    let mut header = version.version_declaration().to_owned();
+    header.push('\n');
+    if version.is_embedded() {
+        header.push_str("precision highp float;\nprecision highp int;\n");
+    }
+    unsafe {
+        let program = gl.create_program()?;
+        let mut shaders = Vec::new();
+        for (kind, source) in [
+            (glow::VERTEX_SHADER, VERTEX_SHADER),
+            (glow::FRAGMENT_SHADER, fragment_shader),
+        ] {
+            let shader = gl.create_shader(kind)?;
+            gl.shader_source(shader, &format!("{header}{source}"));
+            gl.compile_shader(shader);
+            if !gl.get_shader_compile_status(shader) {
+                let log = gl.get_shader_info_log(shader);
+                gl.delete_shader(shader);
+                gl.delete_program(program);
+                return Err(log);
+            }
+            gl.attach_shader(program, shader);
+            shaders.push(shader);
+        }
+        gl.link_program(program);
+        for shader in shaders {
+            gl.detach_shader(program, shader);
+            gl.delete_shader(shader);
+        }
+        if !gl.get_program_link_status(program) {
+            let log = gl.get_program_info_log(program);
+            gl.delete_program(program);
+            return Err(log);
+        }
+        Ok(program)
+    }
+}
+
+pub struct GpuRenderer {
+    gl: Arc<glow::Context>,
+    program: glow::Program,
+    vertex_array: glow::VertexArray,
+    /// Why the last render that fell back to the CPU did, until taken.
+    failure: Cell<Option<String>>,
+}
+
+impl GpuRenderer {
+    /// Compiles the shaders.
+    pub fn new(gl: Arc<glow::Context>) -> Result<Self, String> {
+        let program = compile_program(&gl, FRAGMENT_SHADER)?;
+        let vertex_array = unsafe { gl.create_vertex_array()? };
+        Ok(Self {
+            gl,
+            program,
+            vertex_array,
+            failure: Cell::new(None),
+        })
+    }
+
+    /// Why rendering on the GPU failed, if it did since the last call.
+    pub fn take_failure(&self) -> Option<String> {
+        self.failure.take()
+    }
+
+    fn try_render(
+        &self,
+        view: &View,
+        settings: &RenderSettings,
+    ) -> Result<IterationBuffer, String> {
+        let gl = &self.gl;
+        let (width, height) = (settings.width as i32, settings.height as i32);
+        let mut bytes = vec![0u8; settings.width * settings.height * 4];
+        unsafe {
+            let texture = gl.create_texture()?;
+            gl.bind_texture(glow::TEXTURE_2D, Some(texture));
+            gl.tex_image_2d(
+                glow::TEXTURE_2D,
+                0,
+                glow::RGBA8 as i32,
+                width,
+                height,
+                0,
+                glow::RGBA,
+                glow::UNSIGNED_BYTE,
+                glow::PixelUnpackData::Slice(None),
+            );
+            gl.bind_texture(glow::TEXTURE_2D, None);
+            let framebuffer = gl.create_framebuffer()?;
+            gl.bind_framebuffer(glow::FRAMEBUFFER, Some(framebuffer));
+            gl.framebuffer_texture_2d(
+                glow::FRAMEBUFFER,
+                glow::COLOR_ATTACHMENT0,
+                glow::TEXTURE_2D,
+                Some(texture),
+                0,
+            );
+            let status = gl.check_framebuffer_status(glow::FRAMEBUFFER);
+            if status == glow::FRAMEBUFFER_COMPLETE {
+                gl.viewport(0, 0, width, height);
+                gl.disable(glow::SCISSOR_TEST);
+                gl.disable(glow::BLEND);
+                gl.use_program(Some(self.program));
+                let uniform = |name| gl.get_uniform_location(self.program, name);
+                gl.uniform_2_f32(
+                    uniform("u_center").as_ref(),
+                    view.center.0 as f32,
+                    view.center.1 as f32,
+                );
+                gl.uniform_1_f32(
+                    uniform("u_pixel").as_ref(),
+                    view.pixel_size(settings.width) as f32,
+                );
+                gl.uniform_2_f32(uniform("u_size").as_ref(), width as f32, height as f32);
+                gl.uniform_1_i32(uniform("u_max_iter").as_ref(), settings.max_iter as i32);
+                gl.bind_vertex_array(Some(self.vertex_array));
+                gl.draw_arrays(glow::TRIANGLES, 0, 3);
+                gl.bind_vertex_array(None);
+                gl.use_program(None);
+                gl.pixel_store_i32(glow::PACK_ALIGNMENT, 1);
+                gl.read_pixels(
+                    0,
+                    0,
+                    width,
+                    height,
+                    glow::RGBA,
+                    glow::UNSIGNED_BYTE,
+                    glow::PixelPackData::Slice(Some(&mut bytes)),
+                );
+            }
+            gl.bind_framebuffer(glow::FRAMEBUFFER, None);
+            gl.delete_framebuffer(framebuffer);
+            gl.delete_texture(texture);
+            if status != glow::FRAMEBUFFER_COMPLETE {
+                return Err(format!("incomplete framebuffer ({status:#x})"));
+            }
+        }
+        // GL rows start at the bottom
+        let data = bytes
+            .chunks_exact(settings.width.max(1) * 4)
+            .rev()
+            .flat_map(|row| row.chunks_exact(4))
+            .map(|pixel| u32::from_le_bytes(pixel.try_into().unwrap()))
+            .collect();
+        Ok(IterationBuffer {
+            width: settings.width,
+            height: settings.height,
+            max_iter: settings.max_iter,
+            data,
+        })
+    }
+}
+
+impl Renderer for GpuRenderer {
+    fn name(&self) -> &'static str {
+        "GPU"
+    }
+
+    /// Falls back to the scalar CPU renderer if the GPU can't render,
+    /// keeping the error for [`GpuRenderer::take_failure`], and renders other
+    /// formulas than z → z² + c on all cores.
+    fn render(&self, view: &View, settings: &RenderSettings) -> IterationBuffer {
+        if settings.formula != Formula::Mandelbrot {
+            return ParallelRenderer::default().render(view, settings);
+        }
+        let _span = render_span(self, settings).entered();
+        self.try_render(view, settings).unwrap_or_else(|err| {
+            tracing::error!("GPU rendering failed, using the CPU: {err}");
+            self.failure.set(Some(err));
+            ScalarRenderer.render(view, settings)
+        })
+    }
+}
+
+impl Drop for GpuRenderer {
+    fn drop(&mut self) {
+        unsafe {
+            self.gl.delete_program(self.program);
+            self.gl.delete_vertex_array(self.vertex_array);
+        }
+    }
+}
+
+/// Draws views straight into egui's frame from paint callbacks, skipping the
+/// coloring and texture upload on the CPU: either computing the escape times
+/// in the shader too, or coloring an uploaded iteration buffer. It gets the
+/// context from the callback rather than holding it, as callbacks must be
+/// `Send` and the web's context isn't, so it must be [destroyed] explicitly.
+///
+/// [destroyed]: ScreenRenderer::destroy
+pub struct ScreenRenderer {
+    live_program: glow::Program,
+    recolor_program: glow::Program,
+    vertex_array: glow::VertexArray,
+    palette_texture: glow::Texture,
+    /// The palette in `palette_texture` and its number of colors, once
+    /// filled.
+    palette: Option<(Palette, usize)>,
+    iteration_texture: glow::Texture,
+    /// The buffer in `iteration_texture`, once uploaded.
+    iterations: Option<Arc<IterationBuffer>>,
+}
+
+impl ScreenRenderer {
+    /// Compiles the shaders.
+    pub fn new(gl: &glow::Context) -> Result<Self, String> {
+        let live_program = compile_program(gl, &format!("{SCREEN_COMMON}{LIVE_FRAGMENT_SHADER}"))?;
+        let recolor_program =
+            compile_program(gl, &format!("{SCREEN_COMMON}{RECOLOR_FRAGMENT_SHADER}"))
+                .inspect_err(|_| unsafe { gl.delete_program(live_program) })?;
+        unsafe {
+            Ok(Self {
+                live_program,
+                recolor_program,
+                vertex_array: gl.create_vertex_array()?,
+                palette_texture: gl.create_texture()?,
+                palette: None,
+                iteration_texture: gl.create_texture()?,
+                iterations: None,
+            })
+        }
+    }
+
+    /// Computes and draws `view` with escape times up to `max_iter` over the
+    /// callback's rectangle.
+    pub fn paint_view(
+        &mut self,
+        gl: &glow::Context,
+        info: &PaintCallbackInfo,
+        view: &View,
+        max_iter: usize,
+        palette: Palette,
+    ) {
+        let width = info.viewport_in_pixels().width_px.max(1) as usize;
+        let program = self.live_program;
+        self.draw(gl, program, info, max_iter, palette, |uniform| unsafe {
+            gl.uniform_2_f32(
+                uniform("u_center").as_ref(),
+                view.center.0 as f32,
+                view.center.1 as f32,
+            );
+            gl.uniform_1_f32(uniform("u_pixel").as_ref(), view.pixel_size(width) as f32);
+        });
+    }
+
+    /// Draws `iterations` colored with `palette` over the callback's
+    /// rectangle, uploading them if they aren't the buffer drawn last.
+    pub fn paint_iterations(
+        &mut self,
+        gl: &glow::Context,
+        info: &PaintCallbackInfo,
+        iterations: &Arc<IterationBuffer>,
+        palette: Palette,
+    ) {
+        unsafe {
+            gl.active_texture(glow::TEXTURE1);
+            gl.bind_texture(glow::TEXTURE_2D, Some(self.iteration_texture));
+            if !(self.iterations.as_ref()).is_some_and(|uploaded| Arc::ptr_eq(uploaded, iterations))
+            {
+                let bytes: Vec<u8> = (iterations.data.iter())
+                    .flat_map(|iter| iter.to_ne_bytes())
+                    .collect();
+                gl.pixel_store_i32(glow::UNPACK_ALIGNMENT, 4);
+                gl.tex_image_2d(
+                    glow::TEXTURE_2D,
+                    0,
+                    glow::R32UI as i32,
+                    iterations.width as i32,
+                    iterations.height as i32,
+                    0,
+                    glow::RED_INTEGER,
+                    glow::UNSIGNED_INT,
+                    glow::PixelUnpackData::Slice(Some(&bytes)),
+                );
+                set_nearest_filtering(gl);
+                self.iterations = Some(iterations.clone());
+            }
+        }
+        let program = self.recolor_program;
+        self.draw(
+            gl,
+            program,
+            info,
+            iterations.max_iter,
+            palette,
+            |uniform| unsafe {
+                gl.uniform_1_i32(uniform("u_iterations").as_ref(), 1);
+            },
+        );
+        unsafe {
+            gl.active_texture(glow::TEXTURE1);
+            gl.bind_texture(glow::TEXTURE_2D, None);
+            gl.active_texture(glow::TEXTURE0);
+        }
+    }
+
+    /// Draws over the callback's rectangle with `program`, after setting the
+    /// uniforms in [`SCREEN_COMMON`] and calling `set_uniforms` with a lookup
+    /// for the rest.
+    fn draw(
+        &mut self,
+        gl: &glow::Context,
+        program: glow::Program,
+        info: &PaintCallbackInfo,
+        max_iter: usize,
+        palette: Palette,
+        set_uniforms: impl FnOnce(&dyn Fn(&str) -> Option<glow::UniformLocation>),
+    ) {
+        let viewport = info.viewport_in_pixels();
+        let size = max_iter.clamp(1, MAX_PALETTE_SIZE - 1) + 1;
+        unsafe {
+            gl.active_texture(glow::TEXTURE0);
+            gl.bind_texture(glow::TEXTURE_2D, Some(self.palette_texture));
+            if self.palette != Some((palette, size)) {
+                let colors: Vec<u8> = (0..size).flat_map(|i| palette.color(i, size - 1)).collect();
+                gl.pixel_store_i32(glow::UNPACK_ALIGNMENT, 1);
+                gl.tex_image_2d(
+                    glow::TEXTURE_2D,
+                    0,
+                    glow::RGBA8 as i32,
+                    size as i32,
+                    1,
+                    0,
+                    glow::RGBA,
+                    glow::UNSIGNED_BYTE,
+                    glow::PixelUnpackData::Slice(Some(&colors)),
+                );
+                set_nearest_filtering(gl);
+                self.palette = Some((palette, size));
+            }
+            gl.disable(glow::BLEND);
+            gl.use_program(Some(program));
+            let uniform = |name: &str| gl.get_uniform_location(program, name);
+            gl.uniform_4_f32(
+                uniform("u_viewport").as_ref(),
+                viewport.left_px as f32,
+                viewport.from_bottom_px as f32,
+                viewport.width_px as f32,
+                viewport.height_px as f32,
+            );
+            gl.uniform_1_i32(uniform("u_max_iter").as_ref(), max_iter as i32);
+            gl.uniform_1_i32(uniform("u_palette").as_ref(), 0);
+            set_uniforms(&uniform);
+            gl.bind_vertex_array(Some(self.vertex_array));
+            gl.draw_arrays(glow::TRIANGLES, 0, 3);
+            gl.bind_vertex_array(None);
+            gl.use_program(None);
+            gl.bind_texture(glow::TEXTURE_2D, None);
+        }
+    }
+
+    /// Frees the GPU resources.
+    pub fn destroy(&self, gl: &glow::Context) {
+        unsafe {
+            gl.delete_program(self.live_program);
+            gl.delete_program(self.recolor_program);
+            gl.delete_vertex_array(self.vertex_array);
+            gl.delete_texture(self.palette_texture);
+            gl.delete_texture(self.iteration_texture);
+        }
+    }
+}
+
+/// Makes the bound texture sample without interpolation, which integer
+/// textures require and lookup tables want.
+unsafe fn set_nearest_filtering(gl: &glow::Context) {
+    for parameter in [glow::TEXTURE_MIN_FILTER, glow::TEXTURE_MAG_FILTER] {
+        unsafe { gl.tex_parameter_i32(glow::TEXTURE_2D, parameter, glow::NEAREST as i32) };
+    }
+}
@@ -0,0 +1,493 @@
+//! The height map window: the view as a landscape, with the smooth escape
+//! time as height and the set as a plateau on top, drawn as a shaded surface
+//! that can be orbited by dragging and zoomed by scrolling. On desktop, a
+//! turntable or flyover of it can be exported as a video.
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::f64::consts::TAU;
+use std::{f64::consts::FRAC_PI_2, sync::Arc};
+
+use eframe::egui;
+#[cfg(not(target_arch = "wasm32"))]
+use mandelbrot_core::Image;
+use mandelbrot_core::{Formula, Palette, View, render::INTERIOR_COLOR};
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{
+    animation::Easing,
+    video::{ExportOptions, VideoJob},
+};
+use crate::{i18n::tr, scheduler::Scheduler, settings::Settings};
+
+/// Samples along each side of the surface.
+const SAMPLES: usize = 128;
+const CANVAS_SIZE: egui::Vec2 = egui::vec2(480.0, 360.0);
+/// Radians turned per point dragged.
+const DRAG_SPEED: f64 = 0.01;
+/// Direction light falls from, normalized.
+const LIGHT: [f64; 3] = [-0.408, -0.408, 0.816];
+/// Brightness of faces turned away from the light.
+const AMBIENT: f64 = 0.3;
+const BACKGROUND: [u8; 4] = [24, 24, 24, 255];
+/// Closest and farthest the camera gets to the center of the surface.
+const MIN_DISTANCE: f64 = 1.5;
+const MAX_DISTANCE: f64 = 10.0;
+/// Lowest angle the camera looks down at the surface from, in radians.
+const MIN_PITCH: f64 = 0.1;
+/// Height of the plateau of the set when the window opens.
+const DEFAULT_HEIGHT: f64 = 0.5;
+/// Where a flyover starts, looking almost straight down from afar, and where
+/// it ends, as `(pitch, distance)`.
+#[cfg(not(target_arch = "wasm32"))]
+const FLYOVER_START: (f64, f64) = (1.4, MAX_DISTANCE);
+#[cfg(not(target_arch = "wasm32"))]
+const FLYOVER_END: (f64, f64) = (0.4, 2.0);
+/// Exported frames are rendered this many times larger in each direction
+/// and scaled down, to smooth their edges.
+#[cfg(not(target_arch = "wasm32"))]
+const SUPERSAMPLING: usize = 2;
+
+/// Where the surface is seen from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Camera {
+    /// Rotation about the vertical axis, in radians.
+    yaw: f64,
+    /// Angle looked down at the surface from, in radians.
+    pitch: f64,
+    /// Distance from the center of the surface, which spans −1 to 1.
+    distance: f64,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self {
+            yaw: 0.5,
+            pitch: 0.8,
+            distance: 3.5,
+        }
+    }
+}
+
+impl Camera {
+    /// Where world point `p` appears in `rect`, and its distance from the
+    /// camera.
+    fn project(self, rect: egui::Rect, p: [f64; 3]) -> (egui::Pos2, f64) {
+        let [x, y, z] = p;
+        let (sin_yaw, cos_yaw) = self.yaw.sin_cos();
+        let (x, y) = (x * cos_yaw - y * sin_yaw, x * sin_yaw + y * cos_yaw);
+        // The camera looks along +y, tilted down by the pitch
+        let (sin_pitch, cos_pitch) = self.pitch.sin_cos();
+        let (depth, up) = (y * cos_pitch - z * sin_pitch, y * sin_pitch + z * cos_pitch);
+        let depth = depth + self.distance;
+        let focal = f64::from(rect.height()) * 1.2;
+        let pos =
+            rect.center() + egui::vec2((x * focal / depth) as f32, (-up * focal / depth) as f32);
+        (pos, depth)
+    }
+}
+
+/// How the camera moves in an exported video.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CameraPath {
+    /// Once around the surface, at the current angle and distance.
+    Turntable,
+    /// From far above, swooping down towards the surface while turning a
+    /// quarter of the way around.
+    Flyover,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl CameraPath {
+    const ALL: [CameraPath; 2] = [CameraPath::Turntable, CameraPath::Flyover];
+
+    fn name(self) -> String {
+        match self {
+            CameraPath::Turntable => tr!("height-map-turntable"),
+            CameraPath::Flyover => tr!("height-map-flyover"),
+        }
+    }
+
+    /// The camera a fraction `t`, from 0 to 1, of the way along the path,
+    /// starting from `start`.
+    fn camera(self, start: Camera, t: f64) -> Camera {
+        match self {
+            CameraPath::Turntable => Camera {
+                yaw: start.yaw + t * TAU,
+                ..start
+            },
+            CameraPath::Flyover => {
+                let s = Easing::InOut.apply(t);
+                let (from, to) = (FLYOVER_START, FLYOVER_END);
+                Camera {
+                    yaw: start.yaw + s * TAU / 4.0,
+                    pitch: from.0 + s * (to.0 - from.0),
+                    distance: from.1 + s * (to.1 - from.1),
+                }
+            }
+        }
+    }
+}
+
+/// The sampled surface.
+#[derive(Debug, Clone)]
+struct Surface {
+    /// Heights of the samples, 0 to 1, row by row from the top-left, and
+    /// their colors.
+    samples: Vec<(f64, [u8; 4])>,
+    /// Height of the plateau of the set.
+    height: f64,
+}
+
+impl Surface {
+    /// The sample at `(x, y)` in world coordinates: x right and y up across
+    /// the view from −1 to 1, and z up out of it.
+    fn world(&self, x: usize, y: usize) -> [f64; 3] {
+        let scale = 2.0 / (SAMPLES - 1) as f64;
+        [
+            x as f64 * scale - 1.0,
+            1.0 - y as f64 * scale,
+            self.samples[y * SAMPLES + x].0 * self.height,
+        ]
+    }
+
+    /// Brightness of the surface at sample `(x, y)` from its slope.
+    fn shade(&self, x: usize, y: usize) -> f64 {
+        let height = |x: usize, y: usize| self.world(x, y)[2];
+        let (left, right) = (x.saturating_sub(1), (x + 1).min(SAMPLES - 1));
+        let (top, bottom) = (y.saturating_sub(1), (y + 1).min(SAMPLES - 1));
+        let step = 2.0 / (SAMPLES - 1) as f64;
+        let dx = (height(right, y) - height(left, y)) / ((right - left) as f64 * step);
+        let dy = (height(x, top) - height(x, bottom)) / ((bottom - top) as f64 * step);
+        let normal = [-dx, -dy, 1.0];
+        let length = normal.iter().map(|v| v * v).sum::<f64>().sqrt();
+        let diffuse = (normal.iter().zip(LIGHT).map(|(n, l)| n * l).sum::<f64>() / length).max(0.0);
+        AMBIENT + (1.0 - AMBIENT) * diffuse
+    }
+
+    /// Where each sample appears in `rect` seen by `camera`, row by row, its
+    /// distance from the camera, and its shaded color; `None` if the surface
+    /// hasn't been sampled.
+    fn vertices(
+        &self,
+        camera: Camera,
+        rect: egui::Rect,
+    ) -> Option<Vec<(egui::Pos2, f64, [u8; 4])>> {
+        if self.samples.len() != SAMPLES * SAMPLES {
+            return None;
+        }
+        let vertices = (0..SAMPLES * SAMPLES)
+            .map(|i| {
+                let (x, y) = (i % SAMPLES, i / SAMPLES);
+                let (pos, depth) = camera.project(rect, self.world(x, y));
+                let [r, g, b, a] = self.samples[i].1;
+                let light = self.shade(x, y);
+                let lit = |channel: u8| (f64::from(channel) * light) as u8;
+                (pos, depth, [lit(r), lit(g), lit(b), a])
+            })
+            .collect();
+        Some(vertices)
+    }
+
+    /// The corners of the quads between samples, in order around each, that
+    /// are wholly in front of the camera given `depths`.
+    fn quads(depths: impl Fn(usize) -> f64) -> impl Iterator<Item = [usize; 4]> {
+        let index = |x: usize, y: usize| y * SAMPLES + x;
+        (0..SAMPLES - 1)
+            .flat_map(move |y| {
+                (0..SAMPLES - 1).map(move |x| {
+                    [
+                        index(x, y),
+                        index(x + 1, y),
+                        index(x + 1, y + 1),
+                        index(x, y + 1),
+                    ]
+                })
+            })
+            .filter(move |quad| quad.iter().all(|&i| depths(i) > 0.0))
+    }
+
+    fn draw(&self, painter: &egui::Painter, rect: egui::Rect, camera: Camera) {
+        let Some(vertices) = self.vertices(camera, rect) else {
+            return;
+        };
+        let [r, g, b, _] = BACKGROUND;
+        painter.rect_filled(rect, 0.0, egui::Color32::from_rgb(r, g, b));
+        let mut mesh = egui::Mesh::default();
+        for &(pos, _, [r, g, b, a]) in &vertices {
+            mesh.vertices.push(egui::epaint::Vertex {
+                pos,
+                uv: egui::epaint::WHITE_UV,
+                color: egui::Color32::from_rgba_unmultiplied(r, g, b, a),
+            });
+        }
+        // Painter's algorithm: quads from the farthest to the nearest
+        let mut quads: Vec<_> = Self::quads(|i| vertices[i].1).collect();
+        let depth_of = |quad: &[usize; 4]| quad.iter().map(|&i| vertices[i].1).sum::<f64>();
+        quads.sort_by(|a, b| depth_of(b).total_cmp(&depth_of(a)));
+        for [a, b, c, d] in quads {
+            let [a, b, c, d] = [a, b, c, d].map(|i| i as u32);
+            mesh.add_triangle(a, b, c);
+            mesh.add_triangle(a, c, d);
+        }
+        painter.add(mesh);
+    }
+
+    /// The surface seen by `camera` as a `width`×`height` image, drawn with
+    /// a depth buffer.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn render(&self, camera: Camera, width: usize, height: usize) -> Image {
+        let (big_width, big_height) = (width * SUPERSAMPLING, height * SUPERSAMPLING);
+        let mut image = Image::new(big_width, big_height, BACKGROUND);
+        let rect = egui::Rect::from_min_size(
+            egui::Pos2::ZERO,
+            egui::vec2(big_width as f32, big_height as f32),
+        );
+        let Some(vertices) = self.vertices(camera, rect) else {
+            return image.downsample(SUPERSAMPLING);
+        };
+        let mut depths = vec![f64::INFINITY; big_width * big_height];
+        for [a, b, c, d] in Self::quads(|i| vertices[i].1) {
+            for triangle in [[a, b, c], [a, c, d]] {
+                let corners = triangle.map(|i| vertices[i]);
+                fill_triangle(&mut image, &mut depths, corners);
+            }
+        }
+        image.downsample(SUPERSAMPLING)
+    }
+}
+
+/// Draws the triangle with `corners`, each a position, distance from the
+/// camera, and color, into `image`, where it's nearer than what `depths` says
+/// is there already, blending the corners' colors and distances across it.
+#[cfg(not(target_arch = "wasm32"))]
+fn fill_triangle(image: &mut Image, depths: &mut [f64], corners: [(egui::Pos2, f64, [u8; 4]); 3]) {
+    let [p0, p1, p2] = corners.map(|(pos, _, _)| (f64::from(pos.x), f64::from(pos.y)));
+    let edge = |a: (f64, f64), b: (f64, f64), p: (f64, f64)| {
+        (b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0)
+    };
+    let area = edge(p0, p1, p2);
+    if area.abs() < 1e-12 {
+        return;
+    }
+    let xs = [p0.0, p1.0, p2.0];
+    let ys = [p0.1, p1.1, p2.1];
+    let min = |v: [f64; 3]| v.into_iter().fold(f64::INFINITY, f64::min);
+    let max = |v: [f64; 3]| v.into_iter().fold(f64::NEG_INFINITY, f64::max);
+    let x_range =
+        (min(xs).floor().max(0.0) as usize)..(max(xs).ceil().min(image.width as f64) as usize);
+    let y_range =
+        (min(ys).floor().max(0.0) as usize)..(max(ys).ceil().min(image.height as f64) as usize);
+    for y in y_range {
+        for x in x_range.clone() {
+            let p = (x as f64 + 0.5, y as f64 + 0.5);
+            let weights = [
+                edge(p1, p2, p) / area,
+                edge(p2, p0, p) / area,
+                edge(p0, p1, p) / area,
+            ];
+            if weights.iter().any(|&w| w < 0.0) {
+                continue;
+            }
+            let depth: f64 = (0..3).map(|i| weights[i] * corners[i].1).sum();
+            let i = y * image.width + x;
+            if depth >= depths[i] {
+                continue;
+            }
+            depths[i] = depth;
+            image.pixels[i] = std::array::from_fn(|channel| {
+                let value: f64 = (0..3)
+                    .map(|k| weights[k] * f64::from(corners[k].2[channel]))
+                    .sum();
+                value.round() as u8
+            });
+        }
+    }
+}
+
+pub struct HeightMapWindow {
+    pub open: bool,
+    camera: Camera,
+    surface: Surface,
+    /// What `surface` was sampled from.
+    shown: Option<(View, Formula, usize, Palette)>,
+    #[cfg(not(target_arch = "wasm32"))]
+    path: CameraPath,
+    /// Seconds an exported video lasts.
+    #[cfg(not(target_arch = "wasm32"))]
+    duration: f64,
+    #[cfg(not(target_arch = "wasm32"))]
+    export: ExportOptions,
+    #[cfg(not(target_arch = "wasm32"))]
+    job: Option<VideoJob>,
+    #[cfg(not(target_arch = "wasm32"))]
+    message: Option<String>,
+}
+
+impl Default for HeightMapWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            camera: Camera::default(),
+            surface: Surface {
+                samples: Vec::new(),
+                height: DEFAULT_HEIGHT,
+            },
+            shown: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            path: CameraPath::Turntable,
+            #[cfg(not(target_arch = "wasm32"))]
+            duration: 10.0,
+            #[cfg(not(target_arch = "wasm32"))]
+            export: ExportOptions::new("height_map"),
+            #[cfg(not(target_arch = "wasm32"))]
+            job: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            message: None,
+        }
+    }
+}
+
+impl HeightMapWindow {
+    /// Shows the window, sampling `view` again when it, the formula,
+    /// iteration limit, or palette changed.
+    ///
+    /// `settings` and `scheduler` are for exports, which the web version
+    /// doesn't have.
+    #[allow(clippy::too_many_arguments)]
+    #[cfg_attr(target_arch = "wasm32", allow(unused_variables))]
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        view: &View,
+        formula: Formula,
+        max_iter: usize,
+        palette: Palette,
+        settings: &Settings,
+        scheduler: &Arc<Scheduler>,
+    ) {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(message) = VideoJob::poll(&mut self.job) {
+            self.message = Some(message);
+        }
+        if !self.open {
+            return;
+        }
+        let key = (*view, formula, max_iter, palette);
+        if self.shown != Some(key) {
+            self.surface.samples = sample(view, formula, max_iter, palette);
+            self.shown = Some(key);
+        }
+        let mut open = self.open;
+        egui::Window::new(tr!("height-map-title"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let (rect, response) = ui.allocate_exact_size(CANVAS_SIZE, egui::Sense::drag());
+                let delta = response.drag_delta();
+                let camera = &mut self.camera;
+                camera.yaw += f64::from(delta.x) * DRAG_SPEED;
+                camera.pitch =
+                    (camera.pitch + f64::from(delta.y) * DRAG_SPEED).clamp(MIN_PITCH, FRAC_PI_2);
+                if response.hovered() {
+                    let scroll = ui.input(|i| i.smooth_scroll_delta.y);
+                    camera.distance = (camera.distance * (-f64::from(scroll) / 500.0).exp())
+                        .clamp(MIN_DISTANCE, MAX_DISTANCE);
+                }
+                self.surface.draw(&ui.painter_at(rect), rect, self.camera);
+                ui.horizontal(|ui| {
+                    ui.label(tr!("height-map-height"));
+                    ui.add(egui::Slider::new(&mut self.surface.height, 0.0..=2.0));
+                    if ui.button(tr!("height-map-reset")).clicked() {
+                        self.camera = Camera::default();
+                        self.surface.height = DEFAULT_HEIGHT;
+                    }
+                });
+                ui.label(tr!("height-map-hint"));
+                #[cfg(not(target_arch = "wasm32"))]
+                self.show_export(ui, settings, scheduler);
+            });
+        self.open = open;
+    }
+
+    /// The export controls: the camera's path, the video's length, where to,
+    /// at what frame rate and size, and the progress of a running export.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn show_export(&mut self, ui: &mut egui::Ui, settings: &Settings, scheduler: &Arc<Scheduler>) {
+        ui.separator();
+        let running = self.job.is_some();
+        ui.add_enabled_ui(!running, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(tr!("height-map-camera-path"));
+                for path in CameraPath::ALL {
+                    ui.selectable_value(&mut self.path, path, path.name());
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label(tr!("julia-morph-duration"));
+                ui.add(
+                    egui::DragValue::new(&mut self.duration)
+                        .range(1.0..=600.0)
+                        .suffix(" s"),
+                );
+            });
+            self.export.show(ui);
+        });
+        if let Some(job) = &self.job {
+            job.show_progress(ui, scheduler);
+        } else if ui
+            .add_enabled(
+                !self.surface.samples.is_empty(),
+                egui::Button::new(tr!("height-map-export")),
+            )
+            .on_hover_text(tr!("height-map-export-hover"))
+            .clicked()
+        {
+            let total = ((self.duration * f64::from(self.export.fps)).round() as usize).max(2);
+            let (surface, path, start) = (self.surface.clone(), self.path, self.camera);
+            let width = self.export.size;
+            let height = width * CANVAS_SIZE.y as usize / CANVAS_SIZE.x as usize;
+            // A turntable comes back to where it started, so its last frame
+            // would repeat the first
+            let steps = match path {
+                CameraPath::Turntable => total,
+                CameraPath::Flyover => total - 1,
+            };
+            self.job = Some(VideoJob::start(
+                ui.ctx(),
+                scheduler.clone(),
+                settings.low_priority_exports,
+                &self.export,
+                total,
+                move |i| {
+                    let camera = path.camera(start, i as f64 / steps as f64);
+                    surface.render(camera, width, height)
+                },
+            ));
+            self.message = None;
+        }
+        if let Some(message) = &self.message {
+            ui.label(message);
+        }
+    }
+}
+
+/// Heights and colors of [`SAMPLES`]² points across `view`: the smooth escape
+/// time on a logarithmic scale, reaching 1 on the set.
+fn sample(view: &View, formula: Formula, max_iter: usize, palette: Palette) -> Vec<(f64, [u8; 4])> {
+    let top = (1.0 + max_iter as f64).ln();
+    (0..SAMPLES * SAMPLES)
+        .map(|i| {
+            let (x, y) = (i % SAMPLES, i / SAMPLES);
+            let c = view.pixel_to_complex(x as f64, y as f64, SAMPLES, SAMPLES);
+            match formula.smooth_escape_time(c, max_iter) {
+                Some(smooth) => {
+                    let smooth = smooth.clamp(0.0, max_iter as f64);
+                    let color = palette.color(smooth as usize, max_iter);
+                    ((1.0 + smooth).ln() / top, color)
+                }
+                None => (1.0, INTERIOR_COLOR),
+            }
+        })
+        .collect()
+}
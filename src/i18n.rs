@@ -0,0 +1,109 @@
+//! Translations of the user interface. Each language's text is a Fluent
+//! resource in `locales/`, built into the program and looked up by message
+//! id with [`tr!`]. Messages missing from a translation are shown in English.
+//!
+//! Palette, interior coloring, and renderer names come from
+//! `mandelbrot_core` and stay as they are, since permalinks and the command
+//! line use them too.
+
+use std::sync::{
+    LazyLock,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use fluent::{FluentArgs, FluentResource, concurrent::FluentBundle};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use unic_langid::{LanguageIdentifier, langid};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::German];
+
+    /// The language's name in that language, for choosing it.
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+        }
+    }
+
+    fn identifier(self) -> LanguageIdentifier {
+        match self {
+            Language::English => langid!("en-US"),
+            Language::German => langid!("de-DE"),
+        }
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Language::English => include_str!("../locales/en.ftl"),
+            Language::German => include_str!("../locales/de.ftl"),
+        }
+    }
+}
+
+/// A bundle per language, in the order of [`Language::ALL`].
+static BUNDLES: LazyLock<[FluentBundle<FluentResource>; 2]> =
+    LazyLock::new(|| Language::ALL.map(bundle));
+/// Index of the current language in [`Language::ALL`].
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+
+fn bundle(language: Language) -> FluentBundle<FluentResource> {
+    let resource = FluentResource::try_new(language.source().to_owned())
+        .unwrap_or_else(|(_, errors)| panic!("invalid {language:?} translation: {errors:?}"));
+    let mut bundle = FluentBundle::new_concurrent(vec![language.identifier()]);
+    // egui's fonts draw the Unicode isolation marks around arguments as boxes
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(resource)
+        .unwrap_or_else(|errors| panic!("invalid {language:?} translation: {errors:?}"));
+    bundle
+}
+
+/// Shows the user interface in `language` from now on.
+pub fn set_language(language: Language) {
+    let index = Language::ALL.iter().position(|&l| l == language).unwrap();
+    CURRENT.store(index, Ordering::Relaxed);
+}
+
+/// The text of message `id` in the current language, with `args` filled in.
+/// Use [`tr!`] rather than calling this directly.
+pub fn message(id: &str, args: Option<&FluentArgs>) -> String {
+    let current = &BUNDLES[CURRENT.load(Ordering::Relaxed)];
+    let found = [current, &BUNDLES[0]].into_iter().find_map(|bundle| {
+        let pattern = bundle.get_message(id)?.value()?;
+        Some((bundle, pattern))
+    });
+    let Some((bundle, pattern)) = found else {
+        warn!("no translation for {id}");
+        return id.to_owned();
+    };
+    let mut errors = Vec::new();
+    let text = bundle.format_pattern(pattern, args, &mut errors);
+    if !errors.is_empty() {
+        warn!("could not translate {id}: {errors:?}");
+    }
+    text.into_owned()
+}
+
+/// The text of a message in the current language: `tr!("id")`, or
+/// `tr!("id", name = value, …)` for one with arguments.
+macro_rules! tr {
+    ($id:literal) => {
+        $crate::i18n::message($id, None)
+    };
+    ($id:literal, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = fluent::FluentArgs::new();
+        $(args.set(stringify!($name), $value);)+
+        $crate::i18n::message($id, Some(&args))
+    }};
+}
+
+pub(crate) use tr;
@@ -0,0 +1,245 @@
+//! The File → Export Image window: the view rendered afresh at any size,
+//! as an 8-bit PNG like screenshots or, smoothly colored, as a 16-bit PNG or
+//! TIFF whose gradients survive editing without banding, optionally in
+//! linear light for compositing. 8-bit PNGs can also be smoothly colored and
+//! dithered down, so long gradients don't band either.
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+    thread::{self, JoinHandle},
+};
+
+use eframe::egui;
+use mandelbrot_core::{
+    Dither, Formula, Palette, ParallelRenderer, RenderSettings, Renderer, colorize, colorize_smooth,
+};
+use tracing::warn;
+
+use crate::{
+    display_name,
+    export::{self, ImageFormat},
+    i18n::tr,
+    location::Location,
+    memory::{DEEP_EXPORT_BYTES_PER_PIXEL, EXPORT_BYTES_PER_PIXEL, MemoryBudget},
+    permalink,
+    scheduler::{Priority, Scheduler, lower_thread_priority},
+    settings::Settings,
+};
+
+/// Renders `location` with `formula` and `palette` at `size` and writes it to
+/// `path` in `format`, decoded to linear light if `linear` and the format is
+/// 16-bit. 8-bit images are smoothly colored and rounded with `dither` unless
+/// that is [`Dither::None`]. PNGs record the location's permalink.
+#[allow(clippy::too_many_arguments)]
+pub fn export(
+    renderer: &ParallelRenderer,
+    path: &Path,
+    format: ImageFormat,
+    linear: bool,
+    dither: Dither,
+    location: Location,
+    formula: Formula,
+    palette: Palette,
+    [width, height]: [usize; 2],
+) -> io::Result<()> {
+    let settings = RenderSettings {
+        width,
+        height,
+        max_iter: location.max_iter,
+        formula,
+    };
+    let link = permalink::encode(&location, palette);
+    if !format.is_deep() && dither == Dither::None {
+        let image = colorize(&renderer.render(&location.view, &settings), palette);
+        return export::write_png(path, &image, Some(&link));
+    }
+    let image = colorize_smooth(renderer, &location.view, &settings, palette);
+    if !format.is_deep() {
+        return export::write_png(path, &image.to_8bit(dither), Some(&link));
+    }
+    let image = if linear { image.to_linear() } else { image };
+    match format {
+        ImageFormat::Tiff16 => export::write_tiff16(path, &image),
+        _ => export::write_png16(path, &image, linear, Some(&link)),
+    }
+}
+
+pub struct ImageExportWindow {
+    pub open: bool,
+    size: [usize; 2],
+    format: ImageFormat,
+    linear: bool,
+    dither: Dither,
+    /// The export running on a background thread, writing to the path.
+    job: Option<(PathBuf, JoinHandle<io::Result<()>>)>,
+    message: Option<String>,
+}
+
+impl Default for ImageExportWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            size: [2048, 2048],
+            format: ImageFormat::Png16,
+            linear: false,
+            dither: Dither::None,
+            job: None,
+            message: None,
+        }
+    }
+}
+
+impl ImageExportWindow {
+    #[allow(clippy::too_many_arguments)]
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        location: Location,
+        formula: Formula,
+        palette: Palette,
+        settings: &Settings,
+        scheduler: &Arc<Scheduler>,
+        memory: &Arc<MemoryBudget>,
+    ) {
+        self.poll_job();
+        if !self.open {
+            return;
+        }
+        let mut open = self.open;
+        egui::Window::new(tr!("image-export-title"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let running = self.job.is_some();
+                ui.add_enabled_ui(!running, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("batch-size"));
+                        for side in &mut self.size {
+                            ui.add(egui::DragValue::new(side).range(16..=16384).suffix(" px"));
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("image-export-format"));
+                        for format in ImageFormat::ALL {
+                            ui.selectable_value(&mut self.format, format, format.name());
+                        }
+                    });
+                    ui.add_enabled(
+                        self.format.is_deep(),
+                        egui::Checkbox::new(&mut self.linear, tr!("image-export-linear")),
+                    )
+                    .on_hover_text(tr!("image-export-linear-hover"));
+                    ui.add_enabled_ui(!self.format.is_deep(), |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label(tr!("image-export-dither"))
+                                .on_hover_text(tr!("image-export-dither-hover"));
+                            for dither in Dither::ALL {
+                                ui.selectable_value(&mut self.dither, dither, dither_name(dither));
+                            }
+                        });
+                    });
+                });
+                if running {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(tr!("image-export-rendering"));
+                    });
+                    if scheduler.is_paused(Priority::Export) {
+                        ui.weak(tr!("video-paused"));
+                    }
+                } else if ui.button(tr!("image-export-save")).clicked() {
+                    let name = export::screenshot_name(&location.view)
+                        .replace(".png", &format!(".{}", self.format.extension()));
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter(self.format.name(), &[self.format.extension()])
+                        .set_file_name(name)
+                        .save_file()
+                    {
+                        self.start(
+                            ctx, path, location, formula, palette, settings, scheduler, memory,
+                        );
+                    }
+                }
+                if let Some(message) = &self.message {
+                    ui.label(message);
+                }
+            });
+        self.open = open;
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn start(
+        &mut self,
+        ctx: &egui::Context,
+        path: PathBuf,
+        location: Location,
+        formula: Formula,
+        palette: Palette,
+        settings: &Settings,
+        scheduler: &Arc<Scheduler>,
+        memory: &Arc<MemoryBudget>,
+    ) {
+        let (format, linear, dither, size) = (self.format, self.linear, self.dither, self.size);
+        let bytes_per_pixel = if format.is_deep() || dither != Dither::None {
+            DEEP_EXPORT_BYTES_PER_PIXEL
+        } else {
+            EXPORT_BYTES_PER_PIXEL
+        };
+        let reservation = match memory.reserve(size[0] * size[1] * bytes_per_pixel) {
+            Ok(reservation) => reservation,
+            Err(message) => {
+                warn!("image export refused: {message}");
+                self.message = Some(message);
+                return;
+            }
+        };
+        let renderer = settings.parallel_renderer();
+        let low_priority = settings.low_priority_exports;
+        let (scheduler, ctx) = (scheduler.clone(), ctx.clone());
+        let target = path.clone();
+        let handle = thread::spawn(move || {
+            let _reservation = reservation;
+            // Before the renderer starts its threads, so they inherit it
+            if low_priority && let Err(err) = lower_thread_priority() {
+                warn!("could not lower the image export's priority: {err}");
+            }
+            let result = scheduler.run(Priority::Export, || {
+                export(
+                    &renderer, &target, format, linear, dither, location, formula, palette, size,
+                )
+            });
+            ctx.request_repaint();
+            result
+        });
+        self.job = Some((path, handle));
+        self.message = None;
+    }
+
+    fn poll_job(&mut self) {
+        if !(self.job.as_ref()).is_some_and(|(_, handle)| handle.is_finished()) {
+            return;
+        }
+        let (path, handle) = self.job.take().unwrap();
+        let result = handle
+            .join()
+            .unwrap_or_else(|_| Err(io::Error::other("the export thread panicked")));
+        self.message = Some(match result {
+            Ok(()) => tr!("status-exported", name = display_name(&path)),
+            Err(err) => tr!(
+                "status-export-failed",
+                name = display_name(&path),
+                error = err.to_string()
+            ),
+        });
+    }
+}
+
+fn dither_name(dither: Dither) -> String {
+    match dither {
+        Dither::None => tr!("dither-none"),
+        Dither::Ordered => tr!("dither-ordered"),
+        Dither::BlueNoise => tr!("dither-blue-noise"),
+    }
+}
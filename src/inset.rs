@@ -0,0 +1,100 @@
+//! The inset: a small second view pinned in a corner of the main one, showing
+//! a deeper zoom around a chosen point, such as a minibrot next to the whole
+//! set. It is rendered separately and stays put while the main view moves.
+
+use eframe::egui;
+use mandelbrot_core::{Image, View};
+
+use crate::to_color_image;
+
+/// Width and height of the inset in points.
+pub const INSET_SIZE: f32 = 200.0;
+/// Gap between the inset and the edges of the main view, in points.
+const MARGIN: f32 = 8.0;
+
+pub struct Inset {
+    pub center: (f64, f64),
+    /// How many times deeper the inset is than the main view was when it
+    /// was pinned.
+    pub zoom: f64,
+    /// Scale of the main view when the inset was pinned.
+    pinned_scale: f64,
+    texture: Option<egui::TextureHandle>,
+    /// The view and iteration limit rendered into `texture`.
+    shown: Option<(View, usize)>,
+}
+
+impl Inset {
+    /// An inset `zoom` times deeper than `view`, centered on `c`.
+    pub fn new(view: &View, c: (f64, f64), zoom: f64) -> Self {
+        Self {
+            center: c,
+            zoom,
+            pinned_scale: view.scale,
+            texture: None,
+            shown: None,
+        }
+    }
+
+    /// The view the inset shows.
+    pub fn view(&self) -> View {
+        View {
+            center: self.center,
+            scale: self.pinned_scale / self.zoom,
+        }
+    }
+
+    /// Whether the inset needs rendering again with `max_iter`.
+    pub fn needs_render(&self, max_iter: usize) -> bool {
+        self.shown != Some((self.view(), max_iter))
+    }
+
+    /// Stores `image`, the rendering of the view with `max_iter`.
+    pub fn set_image(&mut self, ctx: &egui::Context, max_iter: usize, image: &Image) {
+        let image = to_color_image(image);
+        match &mut self.texture {
+            Some(texture) => texture.set(image, egui::TextureOptions::LINEAR),
+            None => {
+                self.texture = Some(ctx.load_texture("inset", image, egui::TextureOptions::LINEAR));
+            }
+        }
+        self.shown = Some((self.view(), max_iter));
+    }
+
+    /// Forgets what the inset shows, so it's rendered again, such as after
+    /// the coloring changed.
+    pub fn invalidate(&mut self) {
+        self.shown = None;
+    }
+
+    /// Draws the inset in the bottom-right corner of `image_rect`, which
+    /// shows `view`, and outlines the area it shows there.
+    pub fn draw(&self, painter: &egui::Painter, image_rect: egui::Rect, view: &View) {
+        let stroke = egui::Stroke::new(1.5, egui::Color32::WHITE);
+        let side = image_rect.width() as usize;
+        let (x, y) = view.complex_to_pixel(self.center.0, self.center.1, side, side);
+        let center = image_rect.min + egui::vec2(x as f32, y as f32);
+        let half = (self.view().scale / view.pixel_size(side) / 2.0) as f32;
+        if half >= 3.0 {
+            painter.rect_stroke(
+                egui::Rect::from_center_size(center, egui::Vec2::splat(2.0 * half)),
+                0.0,
+                stroke,
+                egui::StrokeKind::Middle,
+            );
+        } else {
+            // Too small to outline
+            painter.circle_stroke(center, 6.0, stroke);
+        }
+        let Some(texture) = &self.texture else {
+            return;
+        };
+        let rect = egui::Rect::from_min_size(
+            image_rect.max - egui::Vec2::splat(INSET_SIZE + MARGIN),
+            egui::Vec2::splat(INSET_SIZE),
+        );
+        let uv = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+        painter.image(texture.id(), rect, uv, egui::Color32::WHITE);
+        painter.rect_stroke(rect, 0.0, stroke, egui::StrokeKind::Outside);
+    }
+}
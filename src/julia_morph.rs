@@ -0,0 +1,368 @@
+//! The Julia morph window: a path through the parameter plane, drawn on the
+//! view or picked from points and paths there, and the Julia set of c played
+//! back as c travels along it, optionally written to a video.
+
+use std::sync::Arc;
+
+use eframe::egui;
+use mandelbrot_core::{
+    Formula, Palette, ParallelRenderer, RenderSettings, View, colorize,
+    julia::{is_connected, point_along, render_julia},
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::video::{ExportOptions, VideoJob};
+use crate::{animation::Clock, i18n::tr, scheduler::Scheduler, settings::Settings, to_color_image};
+
+/// Side of the preview in pixels.
+const PREVIEW_SIZE: usize = 256;
+/// The part of the dynamical plane shown, which holds every connected
+/// Julia set.
+const JULIA_VIEW: View = View {
+    center: (0.0, 0.0),
+    scale: 3.2,
+};
+/// Closest together, in points, that points of a drawn path are kept.
+const DRAW_SPACING: f32 = 4.0;
+const PATH_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 120, 200);
+/// Colors of the badge for connected Julia sets and for Cantor dust.
+const CONNECTED_COLOR: egui::Color32 = egui::Color32::from_rgb(90, 200, 90);
+const DUST_COLOR: egui::Color32 = egui::Color32::from_rgb(230, 140, 60);
+
+pub struct JuliaMorphWindow {
+    pub open: bool,
+    /// Points of the parameter plane the path runs through.
+    path: Vec<(f64, f64)>,
+    /// Whether dragging on the view draws the path.
+    drawing: bool,
+    /// Seconds into going along the path.
+    clock: Clock,
+    /// Seconds to go along the whole path.
+    duration: f64,
+    looping: bool,
+    texture: Option<egui::TextureHandle>,
+    /// What `texture` was rendered with.
+    shown: Option<((f64, f64), usize, Palette)>,
+    /// Whether the Julia set in `texture` is connected.
+    connected: bool,
+    #[cfg(not(target_arch = "wasm32"))]
+    export: ExportOptions,
+    #[cfg(not(target_arch = "wasm32"))]
+    job: Option<VideoJob>,
+    #[cfg(not(target_arch = "wasm32"))]
+    message: Option<String>,
+}
+
+impl Default for JuliaMorphWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            path: Vec::new(),
+            drawing: false,
+            clock: Clock::default(),
+            duration: 10.0,
+            looping: true,
+            texture: None,
+            shown: None,
+            connected: false,
+            #[cfg(not(target_arch = "wasm32"))]
+            export: ExportOptions::new("julia_morph"),
+            #[cfg(not(target_arch = "wasm32"))]
+            job: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            message: None,
+        }
+    }
+}
+
+impl JuliaMorphWindow {
+    /// Shows the window, advancing playback and rendering the Julia set of
+    /// the current c again when it, the iteration limit, or the palette
+    /// changed. `selected` is the point whose path is shown and
+    /// `selected_path` that path, either of which can be added to the
+    /// morph's path. Returns the current c when its critical orbit, which
+    /// decides whether the Julia set is connected, is to be shown as the path;
+    /// it can only be while `formula` is z² + c.
+    ///
+    /// `settings` and `scheduler` are for exports, which the web version
+    /// doesn't have.
+    #[allow(clippy::too_many_arguments)]
+    #[cfg_attr(target_arch = "wasm32", allow(unused_variables))]
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        renderer: &ParallelRenderer,
+        max_iter: usize,
+        palette: Palette,
+        formula: Formula,
+        selected: Option<(f64, f64)>,
+        selected_path: &[(f64, f64)],
+        settings: &Settings,
+        scheduler: &Arc<Scheduler>,
+    ) -> Option<(f64, f64)> {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(message) = VideoJob::poll(&mut self.job) {
+            self.message = Some(message);
+        }
+        if !self.open {
+            self.drawing = false;
+            return None;
+        }
+        self.clock.keep_within(self.duration, self.looping);
+        if self.clock.is_playing() {
+            ctx.request_repaint();
+        }
+        if let Some(c) = self.c() {
+            let key = (c, max_iter, palette);
+            if self.shown != Some(key) {
+                let image =
+                    to_color_image(&julia_image(renderer, c, PREVIEW_SIZE, max_iter, palette));
+                match &mut self.texture {
+                    Some(texture) => texture.set(image, egui::TextureOptions::LINEAR),
+                    None => {
+                        self.texture = Some(ctx.load_texture(
+                            "julia-morph",
+                            image,
+                            egui::TextureOptions::LINEAR,
+                        ));
+                    }
+                }
+                self.shown = Some(key);
+                self.connected = is_connected(c, max_iter);
+            }
+        }
+        let mut show_orbit = None;
+        let mut open = self.open;
+        egui::Window::new(tr!("julia-morph-title"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.toggle_value(&mut self.drawing, tr!("julia-morph-draw"))
+                        .on_hover_text(tr!("julia-morph-draw-hover"));
+                    if ui
+                        .add_enabled(
+                            selected.is_some(),
+                            egui::Button::new(tr!("julia-morph-add-point")),
+                        )
+                        .on_hover_text(tr!("julia-morph-add-point-hover"))
+                        .clicked()
+                        && let Some(c) = selected
+                    {
+                        self.path.push(c);
+                    }
+                    if ui
+                        .add_enabled(
+                            selected_path.len() > 1,
+                            egui::Button::new(tr!("julia-morph-use-path")),
+                        )
+                        .on_hover_text(tr!("julia-morph-use-path-hover"))
+                        .clicked()
+                    {
+                        self.path = selected_path.to_vec();
+                        self.clock.seek(0.0);
+                    }
+                    if ui.button(tr!("julia-morph-clear")).clicked() {
+                        self.path.clear();
+                        self.clock = Clock::default();
+                    }
+                });
+                match self.c() {
+                    Some(c) => {
+                        if let Some(texture) = &self.texture {
+                            ui.image((texture.id(), egui::Vec2::splat(PREVIEW_SIZE as f32)));
+                        }
+                        ui.label(tr!(
+                            "julia-morph-c",
+                            re = format!("{:+.6}", c.0),
+                            im = format!("{:+.6}", c.1),
+                        ));
+                        ui.horizontal(|ui| {
+                            let (color, badge) = if self.connected {
+                                (CONNECTED_COLOR, tr!("julia-morph-connected"))
+                            } else {
+                                (DUST_COLOR, tr!("julia-morph-dust"))
+                            };
+                            ui.colored_label(color, format!("● {badge}"))
+                                .on_hover_text(tr!("julia-morph-connectivity-hover"));
+                            if ui
+                                .add_enabled(
+                                    formula == Formula::Mandelbrot,
+                                    egui::Button::new(tr!("julia-morph-show-orbit")),
+                                )
+                                .on_hover_text(tr!("julia-morph-show-orbit-hover"))
+                                .on_disabled_hover_text(tr!("julia-morph-show-orbit-disabled"))
+                                .clicked()
+                            {
+                                show_orbit = Some(c);
+                            }
+                        });
+                    }
+                    None => {
+                        ui.weak(tr!("julia-morph-no-path"));
+                    }
+                }
+                ui.add_enabled_ui(self.path.len() > 1, |ui| {
+                    ui.horizontal(|ui| {
+                        let (icon, hover) = if self.clock.is_playing() {
+                            ("⏸", tr!("playback-pause"))
+                        } else {
+                            ("▶", tr!("playback-play"))
+                        };
+                        if ui.button(icon).on_hover_text(hover).clicked() {
+                            self.clock.toggle(self.duration);
+                        }
+                        let mut position = self.position();
+                        if ui
+                            .add(egui::Slider::new(&mut position, 0.0..=1.0).show_value(false))
+                            .changed()
+                        {
+                            self.clock.seek(position * self.duration);
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("julia-morph-duration"));
+                        let position = self.position();
+                        let duration = ui.add(
+                            egui::DragValue::new(&mut self.duration)
+                                .range(1.0..=600.0)
+                                .suffix(" s"),
+                        );
+                        if duration.changed() {
+                            // Staying at the same point of the path
+                            self.clock.seek(position * self.duration);
+                        }
+                        ui.checkbox(&mut self.looping, tr!("playback-loop"));
+                    });
+                });
+                #[cfg(not(target_arch = "wasm32"))]
+                self.show_export(ui, max_iter, palette, settings, scheduler);
+            });
+        self.open = open;
+        if !self.open {
+            self.drawing = false;
+        }
+        show_orbit
+    }
+
+    /// The export controls: where to, at what frame rate and size, and the
+    /// progress of a running export.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn show_export(
+        &mut self,
+        ui: &mut egui::Ui,
+        max_iter: usize,
+        palette: Palette,
+        settings: &Settings,
+        scheduler: &Arc<Scheduler>,
+    ) {
+        ui.separator();
+        let running = self.job.is_some();
+        ui.add_enabled_ui(!running, |ui| self.export.show(ui));
+        if let Some(job) = &self.job {
+            job.show_progress(ui, scheduler);
+        } else if ui
+            .add_enabled(
+                self.path.len() > 1,
+                egui::Button::new(tr!("julia-morph-export")),
+            )
+            .on_hover_text(tr!("video-export-hover"))
+            .clicked()
+        {
+            let total = ((self.duration * f64::from(self.export.fps)).round() as usize).max(2);
+            let (path, size) = (self.path.clone(), self.export.size);
+            let renderer = settings.parallel_renderer();
+            self.job = Some(VideoJob::start(
+                ui.ctx(),
+                scheduler.clone(),
+                settings.low_priority_exports,
+                &self.export,
+                total,
+                move |i| {
+                    let t = i as f64 / (total - 1) as f64;
+                    let c = point_along(&path, t).unwrap_or_default();
+                    julia_image(&renderer, c, size, max_iter, palette)
+                },
+            ));
+            self.message = None;
+        }
+        if let Some(message) = &self.message {
+            ui.label(message);
+        }
+    }
+
+    /// How far along the path c is, from 0 to 1.
+    fn position(&self) -> f64 {
+        (self.clock.time() / self.duration).min(1.0)
+    }
+
+    /// The parameter at the current position along the path.
+    fn c(&self) -> Option<(f64, f64)> {
+        point_along(&self.path, self.position())
+    }
+
+    /// Draws the path as `response`, the view's, is dragged while drawing.
+    /// `to_complex` maps screen positions to points. Returns whether the
+    /// pointer was used, so it doesn't also select a path.
+    pub fn interact(
+        &mut self,
+        response: &egui::Response,
+        to_complex: impl Fn(egui::Pos2) -> (f64, f64),
+        to_screen: impl Fn((f64, f64)) -> egui::Pos2,
+    ) -> bool {
+        if !self.open || !self.drawing {
+            return false;
+        }
+        if response.drag_started() {
+            self.path.clear();
+            self.clock = Clock::default();
+        }
+        if response.dragged()
+            && let Some(pos) = response.interact_pointer_pos()
+            && (self.path.last()).is_none_or(|&last| to_screen(last).distance(pos) >= DRAW_SPACING)
+        {
+            self.path.push(to_complex(pos));
+        }
+        true
+    }
+
+    /// Draws the path and the current c over the view in `image_rect`.
+    pub fn draw(&self, painter: &egui::Painter, image_rect: egui::Rect, view: &View) {
+        if !self.open || self.path.is_empty() {
+            return;
+        }
+        let side = image_rect.width() as usize;
+        let to_screen = |(re, im): (f64, f64)| {
+            let (x, y) = view.complex_to_pixel(re, im, side, side);
+            image_rect.min + egui::vec2(x as f32, y as f32)
+        };
+        let points: Vec<_> = self.path.iter().map(|&c| to_screen(c)).collect();
+        let shadow = egui::Stroke::new(3.0, egui::Color32::from_black_alpha(160));
+        painter.add(egui::Shape::line(points.clone(), shadow));
+        painter.add(egui::Shape::line(
+            points,
+            egui::Stroke::new(1.5, PATH_COLOR),
+        ));
+        if let Some(c) = self.c() {
+            painter.circle_filled(to_screen(c), 4.0, PATH_COLOR);
+            painter.circle_stroke(to_screen(c), 4.0, shadow);
+        }
+    }
+}
+
+/// The Julia set of `c`, `size` pixels square and colored with `palette`.
+fn julia_image(
+    renderer: &ParallelRenderer,
+    c: (f64, f64),
+    size: usize,
+    max_iter: usize,
+    palette: Palette,
+) -> mandelbrot_core::Image {
+    let settings = RenderSettings {
+        width: size,
+        height: size,
+        max_iter,
+        formula: Formula::Mandelbrot,
+    };
+    colorize(&render_julia(renderer, &JULIA_VIEW, &settings, c), palette)
+}
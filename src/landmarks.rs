@@ -0,0 +1,110 @@
+//! Landmarks of the real axis of z → z² + c, where the dynamics of the
+//! real map x → x² + c change: the cusp, the period doublings and the
+//! Feigenbaum point they pile up at, the period 3 window, and the tip.
+
+use mandelbrot_core::View;
+
+use crate::{annotations::Annotation, i18n::tr};
+
+/// How far above a landmark its label goes, as a fraction of the width of
+/// the view.
+const LABEL_OFFSET: f64 = 0.08;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Landmark {
+    /// c = 1/4, where the fixed point stops being attracting and orbits
+    /// escape.
+    Cusp,
+    /// Where the attracting cycle of period `2ⁿ` doubles, for n = 0 to 3.
+    Doubling(u8),
+    Feigenbaum,
+    /// c = −7/4, where the period 3 window opens.
+    PeriodThree,
+    /// c = −2, the end of the set.
+    Tip,
+}
+
+impl Landmark {
+    pub const ALL: [Landmark; 8] = [
+        Landmark::Cusp,
+        Landmark::Doubling(0),
+        Landmark::Doubling(1),
+        Landmark::Doubling(2),
+        Landmark::Doubling(3),
+        Landmark::Feigenbaum,
+        Landmark::PeriodThree,
+        Landmark::Tip,
+    ];
+
+    /// The real c of the landmark.
+    pub fn c(self) -> f64 {
+        match self {
+            Landmark::Cusp => 0.25,
+            Landmark::Doubling(0) => -0.75,
+            Landmark::Doubling(1) => -1.25,
+            Landmark::Doubling(2) => -1.368_098_939_4,
+            Landmark::Doubling(_) => -1.394_046_156_6,
+            Landmark::Feigenbaum => -1.401_155_189_092_050_6,
+            Landmark::PeriodThree => -1.75,
+            Landmark::Tip => -2.0,
+        }
+    }
+
+    /// A view centered on the landmark, narrow enough to tell it from its
+    /// neighbours.
+    pub fn view(self) -> View {
+        let scale = match self {
+            Landmark::Cusp => 0.1,
+            Landmark::Doubling(0) => 0.3,
+            Landmark::Doubling(1) => 0.15,
+            Landmark::Doubling(2) => 0.03,
+            Landmark::Doubling(_) => 0.006,
+            Landmark::Feigenbaum => 0.002,
+            Landmark::PeriodThree => 0.03,
+            Landmark::Tip => 0.05,
+        };
+        View {
+            center: (self.c(), 0.0),
+            scale,
+        }
+    }
+
+    pub fn name(self) -> String {
+        match self {
+            Landmark::Cusp => tr!("landmark-cusp"),
+            Landmark::Doubling(n) => tr!("landmark-doubling", from = 1u32 << n, to = 2u32 << n),
+            Landmark::Feigenbaum => tr!("landmark-feigenbaum"),
+            Landmark::PeriodThree => tr!("landmark-period-three"),
+            Landmark::Tip => tr!("landmark-tip"),
+        }
+    }
+
+    /// What happens at the landmark.
+    pub fn explanation(self) -> String {
+        match self {
+            Landmark::Cusp => tr!("landmark-cusp-explanation"),
+            Landmark::Doubling(n) => tr!(
+                "landmark-doubling-explanation",
+                from = 1u32 << n,
+                to = 2u32 << n
+            ),
+            Landmark::Feigenbaum => tr!("landmark-feigenbaum-explanation"),
+            Landmark::PeriodThree => tr!("landmark-period-three-explanation"),
+            Landmark::Tip => tr!("landmark-tip-explanation"),
+        }
+    }
+
+    /// A marker on the landmark and its name above it, for the view
+    /// [`Landmark::view`] gives.
+    pub fn annotations(self) -> [Annotation; 2] {
+        let at = (self.c(), 0.0);
+        let above = (self.c(), -self.view().scale * LABEL_OFFSET);
+        [
+            Annotation::Marker { at },
+            Annotation::Label {
+                at: above,
+                text: self.name(),
+            },
+        ]
+    }
+}
@@ -0,0 +1,151 @@
+//! The View → Coloring Layers window: a stack of colorings (see
+//! [`mandelbrot_core::layers`]) blended from the bottom up, used instead of
+//! the palette and the tint of the orbit traps while any layer is visible.
+//! Layers are saved in session files.
+
+use eframe::egui;
+use mandelbrot_core::{
+    Palette,
+    layers::{BlendMode, DEFAULT_STRIPE_DENSITY, Layer, LayerSource},
+};
+
+use crate::i18n::tr;
+
+/// One layer of each kind, colored through `palette` where they have one.
+fn sources(palette: Palette) -> [LayerSource; 4] {
+    [
+        LayerSource::EscapeTime(palette),
+        LayerSource::Smooth(palette),
+        LayerSource::Stripes {
+            palette,
+            density: DEFAULT_STRIPE_DENSITY,
+        },
+        LayerSource::Traps,
+    ]
+}
+
+#[derive(Default)]
+pub struct LayersWindow {
+    pub open: bool,
+    /// The layers from the bottom up.
+    pub layers: Vec<Layer>,
+}
+
+impl LayersWindow {
+    /// Whether the layers color the view, rather than the palette.
+    pub fn in_use(&self) -> bool {
+        self.layers.iter().any(|layer| layer.visible)
+    }
+
+    /// Shows the window. New layers are colored through `palette`.
+    pub fn show(&mut self, ctx: &egui::Context, palette: Palette) {
+        let mut open = self.open;
+        egui::Window::new(tr!("layers-title"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.menu_button(tr!("layers-add"), |ui| {
+                        for source in sources(palette) {
+                            if ui.button(source.name()).clicked() {
+                                ui.close_menu();
+                                self.layers.push(Layer::new(source));
+                            }
+                        }
+                    });
+                    if !self.layers.is_empty() && ui.button(tr!("layers-remove-all")).clicked() {
+                        self.layers.clear();
+                    }
+                });
+                ui.separator();
+                if self.layers.is_empty() {
+                    ui.weak(tr!("layers-none"));
+                    return;
+                }
+                ui.weak(tr!("layers-order-hint"));
+                let (mut remove, mut raise) = (None, None);
+                let count = self.layers.len();
+                egui::Grid::new("layers").striped(true).show(ui, |ui| {
+                    // Listed top down, as they appear
+                    for (i, layer) in self.layers.iter_mut().enumerate().rev() {
+                        ui.push_id(i, |ui| {
+                            ui.checkbox(&mut layer.visible, "")
+                                .on_hover_text(tr!("layers-visible-hover"));
+                        });
+                        layer_controls(ui, i, layer);
+                        ui.horizontal(|ui| {
+                            if ui
+                                .add_enabled(i + 1 < count, egui::Button::new("⏶").small())
+                                .on_hover_text(tr!("layers-raise"))
+                                .clicked()
+                            {
+                                raise = Some(i);
+                            }
+                            if ui
+                                .add_enabled(i > 0, egui::Button::new("⏷").small())
+                                .on_hover_text(tr!("layers-lower"))
+                                .clicked()
+                            {
+                                raise = Some(i - 1);
+                            }
+                            if ui
+                                .small_button("🗑")
+                                .on_hover_text(tr!("layers-remove"))
+                                .clicked()
+                            {
+                                remove = Some(i);
+                            }
+                        });
+                        ui.end_row();
+                    }
+                });
+                if let Some(i) = raise {
+                    self.layers.swap(i, i + 1);
+                }
+                if let Some(i) = remove {
+                    self.layers.remove(i);
+                }
+            });
+        self.open = open;
+    }
+}
+
+/// The source, palette, blend mode and opacity of `layer`, the `i`th from
+/// the bottom, each in a cell of the grid.
+fn layer_controls(ui: &mut egui::Ui, i: usize, layer: &mut Layer) {
+    let palette = (layer.source.palette_mut()).map_or(Palette::default(), |palette| *palette);
+    egui::ComboBox::from_id_salt(("layer_source", i))
+        .selected_text(layer.source.name())
+        .show_ui(ui, |ui| {
+            for source in sources(palette) {
+                let checked = source.name() == layer.source.name();
+                if ui.selectable_label(checked, source.name()).clicked() && !checked {
+                    layer.source = source;
+                }
+            }
+        });
+    ui.horizontal(|ui| {
+        if let Some(palette) = layer.source.palette_mut() {
+            egui::ComboBox::from_id_salt(("layer_palette", i))
+                .selected_text(palette.name())
+                .show_ui(ui, |ui| {
+                    for choice in Palette::ALL {
+                        ui.selectable_value(palette, choice, choice.name());
+                    }
+                });
+        }
+        if let LayerSource::Stripes { density, .. } = &mut layer.source {
+            ui.add(egui::DragValue::new(density).range(1.0..=32.0).speed(0.1))
+                .on_hover_text(tr!("layers-density-hover"));
+        }
+    });
+    egui::ComboBox::from_id_salt(("layer_mode", i))
+        .selected_text(layer.mode.name())
+        .show_ui(ui, |ui| {
+            for mode in BlendMode::ALL {
+                ui.selectable_value(&mut layer.mode, mode, mode.name());
+            }
+        });
+    ui.add(egui::Slider::new(&mut layer.opacity, 0.0..=1.0))
+        .on_hover_text(tr!("layers-opacity-hover"));
+}
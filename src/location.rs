@@ -0,0 +1,223 @@
+//! Location files from other fractal programs.
+//!
+//! Two formats are understood:
+//! - Kalles Fraktaler `.kfr`: `Key: value` lines, where `Zoom` is relative to
+//!   a view 4 units high.
+//! - Fractint `.par`: `name { key=value ... }` entries, where the magnification
+//!   in `center-mag` is relative to a view 2 units high. Only the first entry of
+//!   a file is read.
+
+// Only permalinks use `Location` in the web build, which has no file access.
+#![cfg_attr(target_arch = "wasm32", allow(dead_code))]
+
+use std::{fs, io, path::Path};
+
+use mandelbrot_core::{DEFAULT_MAX_ITER, View};
+
+use crate::invalid_data;
+
+/// View height of a Kalles Fraktaler location at zoom 1.
+const KFR_UNIT_HEIGHT: f64 = 4.0;
+/// View height of a Fractint location at magnification 1.
+const PAR_UNIT_HEIGHT: f64 = 2.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Location {
+    pub view: View,
+    pub max_iter: usize,
+}
+
+impl Default for Location {
+    fn default() -> Self {
+        Self {
+            view: View::default(),
+            max_iter: DEFAULT_MAX_ITER,
+        }
+    }
+}
+
+pub fn load(path: &Path) -> io::Result<Location> {
+    let text = fs::read_to_string(path)?;
+    match extension(path).as_deref() {
+        Some("kfr") => parse_kfr(&text),
+        Some("par") => parse_par(&text),
+        _ => Err(invalid_data("expected a .kfr or .par file")),
+    }
+}
+
+pub fn save(path: &Path, location: &Location) -> io::Result<()> {
+    let text = match extension(path).as_deref() {
+        Some("kfr") => to_kfr(location),
+        Some("par") => {
+            let name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "location".to_owned());
+            to_par(&name, location)
+        }
+        _ => return Err(invalid_data("expected a .kfr or .par file")),
+    };
+    fs::write(path, text)
+}
+
+pub fn parse_kfr(text: &str) -> io::Result<Location> {
+    let mut re = None;
+    let mut im = None;
+    let mut zoom = None;
+    let mut max_iter = DEFAULT_MAX_ITER;
+    for line in text.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim() {
+            "Re" => re = Some(parse_real(key, value)?),
+            "Im" => im = Some(parse_real(key, value)?),
+            "Zoom" => zoom = Some(parse_real(key, value)?),
+            "Iterations" => max_iter = parse_number::<usize>(key, value)?,
+            _ => {}
+        }
+    }
+    let (Some(re), Some(im)) = (re, im) else {
+        return Err(invalid_data("missing Re or Im"));
+    };
+    let zoom: f64 = zoom.unwrap_or(1.0);
+    // Subnormal zooms would give an infinite scale
+    if !(zoom > 0.0 && (KFR_UNIT_HEIGHT / zoom).is_finite()) {
+        return Err(invalid_data("Zoom must be positive"));
+    }
+    if max_iter == 0 {
+        return Err(invalid_data("Iterations must be at least 1"));
+    }
+    Ok(Location {
+        view: View {
+            center: (re, im),
+            scale: KFR_UNIT_HEIGHT / zoom,
+        },
+        max_iter,
+    })
+}
+
+pub fn to_kfr(location: &Location) -> String {
+    format!(
+        "Re: {}\r\nIm: {}\r\nZoom: {:e}\r\nIterations: {}\r\n",
+        location.view.center.0,
+        location.view.center.1,
+        KFR_UNIT_HEIGHT / location.view.scale,
+        location.max_iter
+    )
+}
+
+pub fn parse_par(text: &str) -> io::Result<Location> {
+    // Strip comments, then take the body of the first `name { ... }` entry
+    let text: String = text
+        .lines()
+        .map(|line| line.split(';').next().unwrap_or(""))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let body = text
+        .split_once('{')
+        .and_then(|(_, rest)| rest.split_once('}'))
+        .map(|(body, _)| body)
+        .ok_or_else(|| invalid_data("no parameter entry found"))?;
+
+    let mut location = Location::default();
+    let mut found_view = false;
+    for token in body.split_whitespace() {
+        let Some((key, value)) = token.split_once('=') else {
+            continue;
+        };
+        match key {
+            "type" if value != "mandel" => {
+                return Err(invalid_data(format!("unsupported fractal type {value:?}")));
+            }
+            "center-mag" => {
+                let fields = parse_fields(key, value)?;
+                let [x, y, mag, ..] = fields[..] else {
+                    return Err(invalid_data("center-mag needs x/y/mag"));
+                };
+                if !(mag > 0.0 && (PAR_UNIT_HEIGHT / mag).is_finite()) {
+                    return Err(invalid_data("center-mag magnification must be positive"));
+                }
+                location.view = View {
+                    center: (x, y),
+                    scale: PAR_UNIT_HEIGHT / mag,
+                };
+                found_view = true;
+            }
+            "corners" => {
+                let fields = parse_fields(key, value)?;
+                let [xmin, xmax, ymin, ymax, ..] = fields[..] else {
+                    return Err(invalid_data("corners needs xmin/xmax/ymin/ymax"));
+                };
+                // Our view is square, so fit the larger of the two spans
+                let scale = (xmax - xmin).abs().max((ymax - ymin).abs());
+                if !(scale.is_finite() && scale > 0.0) {
+                    return Err(invalid_data("corners must span an area"));
+                }
+                location.view = View {
+                    center: ((xmin + xmax) / 2.0, (ymin + ymax) / 2.0),
+                    scale,
+                };
+                found_view = true;
+            }
+            "maxiter" => location.max_iter = parse_number(key, value)?,
+            _ => {}
+        }
+    }
+    if !found_view {
+        return Err(invalid_data("missing center-mag or corners"));
+    }
+    if location.max_iter == 0 {
+        return Err(invalid_data("maxiter must be at least 1"));
+    }
+    Ok(location)
+}
+
+pub fn to_par(name: &str, location: &Location) -> String {
+    // Entry names can't contain whitespace or braces
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_whitespace() || c == '{' || c == '}' {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+    format!(
+        "{name} {{\n  reset type=mandel center-mag={}/{}/{:e}\n  maxiter={}\n}}\n",
+        location.view.center.0,
+        location.view.center.1,
+        PAR_UNIT_HEIGHT / location.view.scale,
+        location.max_iter
+    )
+}
+
+fn parse_number<T: std::str::FromStr>(key: &str, value: &str) -> io::Result<T> {
+    value
+        .parse()
+        .map_err(|_| invalid_data(format!("invalid {key} value {value:?}")))
+}
+
+/// A finite number, unlike the infinities and NaN `f64` also parses.
+fn parse_real(key: &str, value: &str) -> io::Result<f64> {
+    let number: f64 = parse_number(key, value)?;
+    if !number.is_finite() {
+        return Err(invalid_data(format!("invalid {key} value {value:?}")));
+    }
+    Ok(number)
+}
+
+fn parse_fields(key: &str, value: &str) -> io::Result<Vec<f64>> {
+    value
+        .split('/')
+        .map(|field| parse_real(key, field))
+        .collect()
+}
+
+fn extension(path: &Path) -> Option<String> {
+    path.extension()
+        .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+}
@@ -0,0 +1,95 @@
+//! The magnifier: a round lens following the cursor over the view that shows
+//! the area underneath zoomed in, for inspecting detail without zooming the
+//! view itself. What it shows is rendered whenever the cursor moves.
+
+use eframe::egui;
+use mandelbrot_core::{Image, View};
+
+use crate::to_color_image;
+
+/// Diameter of the lens in points.
+pub const LENS_SIZE: f32 = 160.0;
+/// Segments of the lens outline.
+const SEGMENTS: usize = 64;
+
+pub struct Magnifier {
+    pub enabled: bool,
+    /// How many times closer the lens shows the area than the view.
+    pub zoom: f64,
+    texture: Option<egui::TextureHandle>,
+    /// The view rendered into `texture`.
+    shown: Option<View>,
+}
+
+impl Default for Magnifier {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            zoom: 4.0,
+            texture: None,
+            shown: None,
+        }
+    }
+}
+
+impl Magnifier {
+    /// The view the lens shows around `c` over `view` drawn `side` points
+    /// wide.
+    pub fn lens_view(&self, view: &View, c: (f64, f64), side: usize) -> View {
+        View {
+            center: c,
+            scale: view.scale * f64::from(LENS_SIZE) / side as f64 / self.zoom,
+        }
+    }
+
+    /// Whether the lens needs `lens_view` rendered.
+    pub fn needs_render(&self, lens_view: &View) -> bool {
+        self.shown.as_ref() != Some(lens_view)
+    }
+
+    /// Stores `image`, the rendering of `lens_view`.
+    pub fn set_image(&mut self, ctx: &egui::Context, lens_view: View, image: &Image) {
+        let image = to_color_image(image);
+        match &mut self.texture {
+            Some(texture) => texture.set(image, egui::TextureOptions::LINEAR),
+            None => {
+                self.texture =
+                    Some(ctx.load_texture("magnifier", image, egui::TextureOptions::LINEAR));
+            }
+        }
+        self.shown = Some(lens_view);
+    }
+
+    /// Forgets what the lens shows, so it's rendered again, such as after the
+    /// coloring changed.
+    pub fn invalidate(&mut self) {
+        self.shown = None;
+    }
+
+    /// Draws the lens centered on `center`.
+    pub fn draw(&self, painter: &egui::Painter, center: egui::Pos2) {
+        let Some(texture) = &self.texture else {
+            return;
+        };
+        let radius = LENS_SIZE / 2.0;
+        let mut mesh = egui::Mesh::with_texture(texture.id());
+        mesh.vertices.push(egui::epaint::Vertex {
+            pos: center,
+            uv: egui::pos2(0.5, 0.5),
+            color: egui::Color32::WHITE,
+        });
+        for i in 0..=SEGMENTS {
+            let direction = egui::Vec2::angled(i as f32 / SEGMENTS as f32 * std::f32::consts::TAU);
+            mesh.vertices.push(egui::epaint::Vertex {
+                pos: center + direction * radius,
+                uv: egui::pos2(0.5, 0.5) + direction * 0.5,
+                color: egui::Color32::WHITE,
+            });
+            if i > 0 {
+                mesh.add_triangle(0, i as u32, i as u32 + 1);
+            }
+        }
+        painter.add(mesh);
+        painter.circle_stroke(center, radius, egui::Stroke::new(2.0, egui::Color32::WHITE));
+    }
+}
@@ -1,94 +1,3030 @@
-use eframe::{App, CreationContext, egui};
+mod ab_compare;
+mod analysis;
+mod animation;
+mod annotations;
+mod autopilot;
+#[cfg(not(target_arch = "wasm32"))]
+mod autosave;
+#[cfg(not(target_arch = "wasm32"))]
+mod batch;
+mod benchmark;
+mod bookmarks;
+mod cache;
+#[cfg(not(target_arch = "wasm32"))]
+mod cli;
+mod cooperative;
+mod cursor;
+mod diagnostics;
+#[cfg(not(target_arch = "wasm32"))]
+mod distributed;
+mod exponent_pad;
+#[cfg(not(target_arch = "wasm32"))]
+mod export;
+mod ghost_trail;
+mod gpu;
+mod height_map;
+mod i18n;
+#[cfg(not(target_arch = "wasm32"))]
+mod image_export;
+mod inset;
+mod julia_morph;
+mod landmarks;
+mod layers;
+mod location;
+mod magnifier;
+mod measure;
+mod memory;
+#[cfg(not(target_arch = "wasm32"))]
+mod npy_export;
+#[cfg(not(target_arch = "wasm32"))]
+mod palette_export;
+mod permalink;
+#[cfg(not(target_arch = "wasm32"))]
+mod plugin;
+#[cfg(not(target_arch = "wasm32"))]
+mod progressive;
+mod recorder;
+#[cfg(not(target_arch = "wasm32"))]
+mod remote;
+mod scheduler;
+#[cfg(not(target_arch = "wasm32"))]
+mod script;
+#[cfg(not(target_arch = "wasm32"))]
+mod session;
+mod settings;
+mod sphere;
+mod surprise;
+#[cfg(not(target_arch = "wasm32"))]
+mod svg_export;
+mod telemetry;
+#[cfg(not(target_arch = "wasm32"))]
+mod tiles;
+mod timeline;
+mod tour;
+mod traps;
+#[cfg(not(target_arch = "wasm32"))]
+mod video;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::{cell::RefCell, fs, path::Path, rc::Rc};
+use std::{
+    io,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+use clap::Parser;
+use eframe::{App, CreationContext, egui, egui_glow};
+use mandelbrot_core::{
+    DEFAULT_MAX_ITER, FastRenderer, Formula, Image, IterationBuffer, Palette, ParallelRenderer,
+    RenderSettings, Renderer, ScalarRenderer, View, colorize, colorize_stretched,
+    detail::find_detail,
+    formula::{CubicEscape, c_to_lambda, lambda_scale, lambda_to_c},
+    hybrid::Pattern,
+    interior::{InteriorColoring, Minibrot, color_interior, component, nearest_nucleus},
+    layers::composite,
+    overlay::{Overlay, draw_overlay},
+    random::SplitMix64,
+    render,
+    shading::{Shading, occlude, shade},
+    stats::{FrameStats, escape_range, frame_stats},
+    timeline::Keyframe,
+    trap::color_traps,
+};
+use tracing::{debug, info, warn};
+use web_time::Instant;
+
+use ab_compare::{AbWindow, Setup};
+use analysis::{
+    AreaWindow, CompareWindow, DimensionWindow, HistogramWindow, OrbitStats, PeriodHighlight,
+    RealDynamics,
+};
+use animation::{Easing, ViewTransition};
+use annotations::AnnotationsWindow;
+use autopilot::{Autopilot, max_iter_for};
+#[cfg(not(target_arch = "wasm32"))]
+use autosave::Autosave;
+#[cfg(not(target_arch = "wasm32"))]
+use batch::BatchWindow;
+use bookmarks::BookmarksWindow;
+use cache::{LruCache, Weigh};
+use cooperative::CooperativeRender;
+use cursor::CursorReadout;
+use diagnostics::{DiagnosticsWindow, LogBuffer};
+use exponent_pad::exponent_pad;
+use ghost_trail::GhostTrail;
+use gpu::{GpuRenderer, ScreenRenderer};
+use height_map::HeightMapWindow;
+use i18n::{Language, tr};
+#[cfg(not(target_arch = "wasm32"))]
+use image_export::ImageExportWindow;
+use inset::{INSET_SIZE, Inset};
+use julia_morph::JuliaMorphWindow;
+use landmarks::Landmark;
+use layers::LayersWindow;
+use location::Location;
+use magnifier::{LENS_SIZE, Magnifier};
+use measure::Measure;
+use memory::MemoryBudget;
+#[cfg(not(target_arch = "wasm32"))]
+use npy_export::NpyExportWindow;
+#[cfg(not(target_arch = "wasm32"))]
+use plugin::ColoringPlugin;
+#[cfg(not(target_arch = "wasm32"))]
+use progressive::ProgressiveRender;
+use recorder::RecorderWindow;
+#[cfg(not(target_arch = "wasm32"))]
+use remote::RemoteServer;
+use scheduler::{IDLE_DELAY, Priority, Scheduler};
+#[cfg(not(target_arch = "wasm32"))]
+use session::Session;
+use settings::{Backend, Quality, QualityMode, Settings};
+use sphere::SphereWindow;
+use surprise::surprise;
+#[cfg(not(target_arch = "wasm32"))]
+use svg_export::SvgExportWindow;
+use telemetry::TelemetryWindow;
+use timeline::TimelineWindow;
+use tour::TourWindow;
+use traps::TrapsWindow;
+
+/// Find Detail zooms into a square this many times smaller than the frame.
+const DETAIL_ZOOM: usize = 4;
+/// Below this entropy in bits, a square is too flat to be worth zooming into.
+const MIN_DETAIL_ENTROPY: f64 = 1.0;
+/// Renders after one that took at least this long are shown progressively.
+const PROGRESSIVE_THRESHOLD: Duration = Duration::from_millis(200);
+/// How often progress bars of running renders are redrawn.
+const PROGRESS_REPAINT_INTERVAL: Duration = Duration::from_millis(250);
+/// While the zoom is held, a render under way goes on this long before it is
+/// started over for the view that moved on, so slow renders show passes.
+const HOLD_ZOOM_REFRESH: Duration = Duration::from_millis(250);
+/// Highest iteration limit the controls offer, and workers render with.
+const MAX_ITER: usize = 100_000;
+/// Iteration limit the path gets when it is given one of its own.
+const ORBIT_MAX_ITER: usize = 10_000;
+/// How many points at the start of the path View → Orbit values labels.
+const ORBIT_VALUE_LABELS: usize = 8;
+/// How close to a point of the path, in points, the cursor shows its value.
+const ORBIT_HOVER_RADIUS: f32 = 6.0;
+/// Seconds the view takes to move after a key press, and to go back to the
+/// whole set or to the inset's view.
+const KEY_TRANSITION_SECONDS: f64 = 0.15;
+/// Scrolling, in points, that counts as one notch of the mouse wheel, for
+/// touchpads and wheels that scroll by points.
+const WHEEL_POINTS_PER_NOTCH: f32 = 50.0;
+const JUMP_TRANSITION_SECONDS: f64 = 0.5;
+/// Largest image, in pixels on a side, the remote `render` command renders.
+#[cfg(not(target_arch = "wasm32"))]
+const REMOTE_RENDER_MAX_SIZE: usize = 2048;
+/// Color of the path of the second critical point, for formulas with one.
+const OTHER_PATH_COLOR: egui::Color32 = egui::Color32::from_rgb(0, 200, 255);
+/// Color of the part of the path on its cycle, for paths that fall into one.
+const CYCLE_PATH_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 110, 60);
+
+/// Identifies a rendered frame: the view's center and scale as bits, what was
+/// rendered, and the renderer, whose results can differ slightly.
+type FrameKey = ([u64; 3], RenderSettings, &'static str);
 
 struct MandelbrotApp {
     mandelbrot_texture: egui::TextureHandle,
+    mandelbrot_image: Option<Image>, // last frame as shown, None until needed if colored on the GPU
+    iterations: Option<Arc<IterationBuffer>>, // escape times of that frame, once rendered in `update`
+    frames: LruCache<FrameKey, Arc<IterationBuffer>>,
+    autopilot: Option<Autopilot>,
+    transition: Option<ViewTransition>, // smooth move of the view under way
+    zoom_factor: String,                // typed into the zoom field, such as "1000x"
+    rng: SplitMix64,                    // for Surprise Me
+    annotate_landmarks: bool,           // jumping to a landmark labels it
+    frame_stats: Option<FrameStats>,    // Some while the statistics overlay is shown
     last_size: [usize; 2],
-    last_click: Option<(usize, usize)>,
+    frame_quality: Quality, // preset the frame was rendered with
+    needs_render: bool,
+    needs_recolor: bool, // coloring changed, but the stored iterations are still current
+    orbit: Option<(f64, f64)>, // c of the point whose path is shown
     last_path: Vec<(f64, f64)>,
-    center: (f64, f64), // center of view in Mandelbrot space
-    scale: f64,         // Mandelbrot units per image width
+    other_path: Vec<(f64, f64)>, // of the second critical point, for formulas with one
+    orbit_values: bool,          // label the first points of the path with their value
+    orbit_stats: Option<OrbitStats>,
+    period_highlight: PeriodHighlight,
+    real_dynamics: RealDynamics,
+    view: View,
+    max_iter: usize,
+    formula: Formula,
+    orbit_max_iter: Option<usize>, // the path's own iteration limit, if not max_iter
+    palette: Palette,
+    auto_contrast: bool, // whether the palette is stretched over the frame's escape times
+    contrast_clip: f64,  // percent of escape times left out at either end when it is
+    interior_coloring: InteriorColoring,
+    overlay: Overlay,
+    shading: Shading,
+    occlusion: f64, // intensity of the ambient occlusion, 0 for none
+    #[cfg(not(target_arch = "wasm32"))]
+    plugins: Vec<ColoringPlugin>,
+    #[cfg(not(target_arch = "wasm32"))]
+    coloring: Option<usize>, // index of the plugin used instead of the palette
+    #[cfg(not(target_arch = "wasm32"))]
+    clipboard: Option<arboard::Clipboard>, // kept alive so copied images outlive the call
+    settings: Settings,
+    #[cfg(not(target_arch = "wasm32"))]
+    settings_open: bool,
+    parallel: ParallelRenderer,
+    fast: FastRenderer,
+    gpu: Option<GpuRenderer>, // None without a usable OpenGL context, or once it failed
+    auto_backend: Backend,    // what Backend::Auto stands for, the fastest at startup
+    screen: Option<Arc<Mutex<ScreenRenderer>>>, // likewise; shared with paint callbacks
+    last_interaction: Option<Instant>, // last pointer press or scroll, for deferring renders
+    #[cfg(not(target_arch = "wasm32"))]
+    progressive: Option<(ProgressiveRender, FrameKey, Instant)>, // render in the background, with its start
+    cooperative: Option<(CooperativeRender, FrameKey, Instant)>, // render in slices on this thread, with its start
+    holding_zoom: bool, // whether the zoom was held last frame, for letting renders go on
+    last_render_time: Duration, // per pixel, of the last view rendered in full, for choosing how to render the next
+    #[cfg(not(target_arch = "wasm32"))]
+    batch: BatchWindow,
+    #[cfg(not(target_arch = "wasm32"))]
+    svg_export: SvgExportWindow,
+    #[cfg(not(target_arch = "wasm32"))]
+    image_export: ImageExportWindow,
+    #[cfg(not(target_arch = "wasm32"))]
+    npy_export: NpyExportWindow,
+    #[cfg(not(target_arch = "wasm32"))]
+    remote: Option<RemoteServer>,
+    area: AreaWindow,
+    histogram: HistogramWindow,
+    compare: CompareWindow,
+    dimension: DimensionWindow,
+    telemetry: TelemetryWindow,
+    magnifier: Magnifier,
+    measure: Measure,
+    cursor: CursorReadout,
+    ghost_trail: GhostTrail,
+    ab: AbWindow,
+    annotations: AnnotationsWindow,
+    bookmarks: BookmarksWindow,
+    traps: TrapsWindow,
+    layers: LayersWindow,
+    sphere: SphereWindow,
+    height_map: HeightMapWindow,
+    julia_morph: JuliaMorphWindow,
+    timeline: TimelineWindow,
+    tour: TourWindow,
+    recorder: RecorderWindow,
+    inset: Option<Inset>,
+    inset_zoom: f64,                   // for new insets
+    context_point: Option<(f64, f64)>, // c of the last right click on the view
+    minibrot: Option<Minibrot>,        // around the nucleus found last, to frame
+    scheduler: Arc<Scheduler>,
+    memory: Arc<MemoryBudget>, // shared with exports, which reserve memory from it
+    #[cfg(not(target_arch = "wasm32"))]
+    autosave: Autosave,
+    diagnostics: DiagnosticsWindow,
+    status: Option<String>,
 }
 
 impl MandelbrotApp {
-    fn new(cc: &CreationContext<'_>) -> Self {
+    fn new(cc: &CreationContext<'_>, log: LogBuffer) -> Self {
         let size = [800, 600];
-        let image = render_mandelbrot(size[0], size[1], (-0.5, 0.0), 3.0);
-        let mandelbrot_texture =
-            cc.egui_ctx
-                .load_texture("mandelbrot", image, egui::TextureOptions::default());
-        Self {
+        let image = render(
+            &View::default(),
+            size[0],
+            size[1],
+            DEFAULT_MAX_ITER,
+            Palette::default(),
+        );
+        let mandelbrot_texture = cc.egui_ctx.load_texture(
+            "mandelbrot",
+            to_color_image(&image),
+            egui::TextureOptions::default(),
+        );
+        let settings: Settings = cc
+            .storage
+            .and_then(|storage| eframe::get_value(storage, settings::STORAGE_KEY))
+            .unwrap_or_default();
+        i18n::set_language(settings.language);
+        let gpu = cc.gl.clone().and_then(|gl| {
+            GpuRenderer::new(gl)
+                .inspect_err(|err| warn!("GPU renderer unavailable: {err}"))
+                .ok()
+        });
+        let screen = cc.gl.as_ref().and_then(|gl| {
+            ScreenRenderer::new(gl)
+                .inspect_err(|err| warn!("live GPU preview unavailable: {err}"))
+                .ok()
+                .map(|screen| Arc::new(Mutex::new(screen)))
+        });
+        let (parallel, fast) = (settings.parallel_renderer(), settings.fast_renderer());
+        let memory = Arc::new(MemoryBudget::new(settings.memory_cap_mb << 20));
+        let mut app = Self {
             mandelbrot_texture,
+            mandelbrot_image: Some(image),
+            iterations: None,
+            frames: LruCache::new(settings.cache_mb << 20),
+            autopilot: None,
+            transition: None,
+            zoom_factor: String::new(),
+            rng: SplitMix64::new(time_seed()),
+            annotate_landmarks: true,
+            frame_stats: None,
             last_size: size,
-            last_click: None,
+            frame_quality: Quality::Normal,
+            needs_render: false,
+            needs_recolor: false,
+            orbit: None,
             last_path: Vec::new(),
-            center: (-0.5, 0.0), // default Mandelbrot center
-            scale: 3.0,          // default Mandelbrot width
+            other_path: Vec::new(),
+            orbit_values: false,
+            orbit_stats: None,
+            period_highlight: PeriodHighlight::default(),
+            real_dynamics: RealDynamics::default(),
+            view: View::default(),
+            max_iter: DEFAULT_MAX_ITER,
+            formula: Formula::Mandelbrot,
+            orbit_max_iter: None,
+            palette: Palette::default(),
+            auto_contrast: false,
+            contrast_clip: 1.0,
+            interior_coloring: InteriorColoring::default(),
+            overlay: Overlay::default(),
+            shading: Shading::default(),
+            occlusion: 0.0,
+            #[cfg(not(target_arch = "wasm32"))]
+            plugins: Vec::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            coloring: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            clipboard: None,
+            settings,
+            #[cfg(not(target_arch = "wasm32"))]
+            settings_open: false,
+            parallel,
+            fast,
+            gpu,
+            auto_backend: Backend::Parallel,
+            screen,
+            last_interaction: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            progressive: None,
+            cooperative: None,
+            holding_zoom: false,
+            last_render_time: Duration::ZERO,
+            #[cfg(not(target_arch = "wasm32"))]
+            batch: BatchWindow::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            svg_export: SvgExportWindow::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            image_export: ImageExportWindow::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            npy_export: NpyExportWindow::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            remote: None,
+            area: AreaWindow::default(),
+            histogram: HistogramWindow::default(),
+            compare: CompareWindow::default(),
+            dimension: DimensionWindow::default(),
+            telemetry: TelemetryWindow::default(),
+            magnifier: Magnifier::default(),
+            measure: Measure::default(),
+            cursor: CursorReadout::default(),
+            ghost_trail: GhostTrail::default(),
+            ab: AbWindow::default(),
+            annotations: AnnotationsWindow::default(),
+            bookmarks: BookmarksWindow::default(),
+            traps: TrapsWindow::default(),
+            layers: LayersWindow::default(),
+            sphere: SphereWindow::default(),
+            height_map: HeightMapWindow::default(),
+            julia_morph: JuliaMorphWindow::default(),
+            timeline: TimelineWindow::default(),
+            tour: TourWindow::default(),
+            recorder: RecorderWindow::default(),
+            inset: None,
+            inset_zoom: 100.0,
+            context_point: None,
+            minibrot: None,
+            scheduler: Arc::default(),
+            memory,
+            #[cfg(not(target_arch = "wasm32"))]
+            autosave: Autosave::new(),
+            diagnostics: DiagnosticsWindow::new(log),
+            status: None,
+        };
+        #[cfg(not(target_arch = "wasm32"))]
+        app.load_plugins();
+        #[cfg(target_arch = "wasm32")]
+        app.open_page_permalink();
+        app.choose_auto_backend();
+        app
+    }
+
+    /// Benchmarks the renderers that can stand for [`Backend::Auto`] and
+    /// picks the fastest.
+    fn choose_auto_backend(&mut self) {
+        let candidates = [Backend::Parallel, Backend::Fast, Backend::Gpu];
+        let available =
+            (candidates.into_iter()).filter_map(|backend| Some((backend, self.backend(backend)?)));
+        self.auto_backend = benchmark::fastest(available).unwrap_or(Backend::Parallel);
+        info!(backend = ?self.auto_backend, "chose the renderer for Auto");
+        // The GPU may have failed while being benchmarked
+        self.check_gpu();
+    }
+
+    /// Stops using the GPU to render if it failed, with a notice in the
+    /// status bar. Renders it failed were done on the CPU instead.
+    fn check_gpu(&mut self) {
+        let Some(error) = self.gpu.as_ref().and_then(GpuRenderer::take_failure) else {
+            return;
+        };
+        self.gpu = None;
+        if self.auto_backend == Backend::Gpu {
+            self.choose_auto_backend();
+        }
+        self.status = Some(tr!(
+            "status-gpu-failed",
+            renderer = self.renderer().name(),
+            error = error
+        ));
+    }
+
+    /// The backend rendering the view: the one selected in the settings, or
+    /// what Auto stands for, or the parallel one if that isn't available.
+    fn backend_in_use(&self) -> Backend {
+        match self.settings.backend {
+            Backend::Auto => self.auto_backend,
+            backend if self.backend(backend).is_some() => backend,
+            _ => Backend::Parallel,
+        }
+    }
+
+    /// The renderer for `backend`, if it's available.
+    fn backend(&self, backend: Backend) -> Option<&dyn Renderer> {
+        match backend {
+            Backend::Auto => self.backend(self.auto_backend),
+            Backend::Scalar => Some(&ScalarRenderer),
+            Backend::Parallel => Some(&self.parallel),
+            Backend::Fast => Some(&self.fast),
+            Backend::Gpu => self.gpu.as_ref().map(|gpu| gpu as &dyn Renderer),
+        }
+    }
+
+    /// The name of `backend` in the renderer menu, if it's available.
+    fn backend_name(&self, backend: Backend) -> Option<String> {
+        let name = self.backend(backend)?.name();
+        Some(match backend {
+            Backend::Auto => tr!("renderer-auto", name = name),
+            _ => name.to_owned(),
+        })
+    }
+
+    /// The renderer of [`MandelbrotApp::backend_in_use`].
+    fn renderer(&self) -> &dyn Renderer {
+        self.backend(self.backend_in_use())
+            .unwrap_or(&self.parallel)
+    }
+
+    fn location(&self) -> Location {
+        Location {
+            view: self.view,
+            max_iter: self.max_iter,
+        }
+    }
+
+    fn apply_location(&mut self, location: Location) {
+        self.view = location.view;
+        self.max_iter = location.max_iter;
+        self.set_orbit(self.orbit);
+        self.needs_render = true;
+    }
+
+    /// Shows a frame of the timeline or a step of a recording.
+    fn apply_keyframe(&mut self, frame: Keyframe) {
+        if (frame.view, frame.max_iter, frame.formula) == (self.view, self.max_iter, self.formula) {
+            return;
+        }
+        let formula_changed = frame.formula != self.formula;
+        self.view = frame.view;
+        self.max_iter = frame.max_iter;
+        self.formula = frame.formula;
+        if formula_changed {
+            self.set_orbit(self.orbit);
+        }
+        self.needs_render = true;
+    }
+
+    fn set_orbit(&mut self, orbit: Option<(f64, f64)>) {
+        if orbit != self.orbit {
+            self.ghost_trail.restart();
+        }
+        self.orbit = orbit;
+        self.last_path = match orbit {
+            Some(c) => self.formula.orbit(c, self.orbit_limit()),
+            None => Vec::new(),
+        };
+        self.other_path = (orbit.and_then(|c| self.formula.other_orbit(c, self.orbit_limit())))
+            .unwrap_or_default();
+        self.orbit_stats =
+            orbit.map(|c| OrbitStats::new(c, &self.last_path, self.orbit_limit(), self.formula));
+    }
+
+    /// Iteration limit of the path and its statistics.
+    fn orbit_limit(&self) -> usize {
+        self.orbit_max_iter.unwrap_or(self.max_iter)
+    }
+
+    /// Shows the path of the nucleus closest to c, whose critical orbit is an
+    /// exact superattracting cycle.
+    fn snap_orbit(&mut self, c: (f64, f64)) {
+        match nearest_nucleus(c, self.orbit_limit()) {
+            Some((period, nucleus)) => {
+                self.set_orbit(Some(nucleus));
+                self.minibrot = Some(Minibrot::new(nucleus, period));
+                self.status = Some(tr!(
+                    "status-snapped",
+                    period = period,
+                    re = format!("{:.12}", nucleus.0),
+                    im = format!("{:+.12}", nucleus.1),
+                ));
+            }
+            None => self.status = Some(tr!("status-no-nucleus")),
+        }
+    }
+
+    /// Says which hyperbolic component c lies in, with its center and the
+    /// internal angle and radius of c, or which one it is nearest to.
+    fn identify_component(&mut self, c: (f64, f64)) {
+        let Some(found) = component(c, self.orbit_limit()) else {
+            self.status = Some(tr!("status-no-component"));
+            return;
+        };
+        self.minibrot = Some(Minibrot::new(found.nucleus, found.period));
+        let (re, im) = (
+            format!("{:.12}", found.nucleus.0),
+            format!("{:+.12}", found.nucleus.1),
+        );
+        let radius = format!("{:.4}", found.internal_radius);
+        self.status = Some(if found.contains() {
+            tr!(
+                "status-component",
+                period = found.period,
+                re = re,
+                im = im,
+                angle = format!("{:.6}", found.internal_angle),
+                radius = radius
+            )
+        } else {
+            tr!(
+                "status-component-outside",
+                period = found.period,
+                re = re,
+                im = im,
+                radius = radius
+            )
+        });
+    }
+
+    /// Brings the memory budget up to date with the settings and the frame
+    /// shown, and shrinks the frame cache to what the cap leaves for it.
+    fn update_memory(&mut self) {
+        self.memory.set_cap(self.settings.memory_cap_mb << 20);
+        let frame = self.iterations.as_ref().map_or(0, Weigh::bytes)
+            + (self.mandelbrot_image.as_ref())
+                .map_or(0, |image| image.pixels.len() * size_of::<[u8; 4]>());
+        self.memory.set_frame(frame);
+        let budget = (self.settings.cache_mb << 20).min(self.memory.room_for_caches());
+        self.frames.set_budget(budget);
+    }
+
+    /// Zooms into the part of the frame with the most varied escape times.
+    fn find_detail(&mut self) {
+        let Some(iterations) = &self.iterations else {
+            return;
+        };
+        let cell = iterations.width / DETAIL_ZOOM;
+        match find_detail(iterations, cell) {
+            Some(detail) if detail.entropy >= MIN_DETAIL_ENTROPY => {
+                let tile = detail.tile;
+                self.view.center = self.view.pixel_to_complex(
+                    (tile.x + tile.width / 2) as f64,
+                    (tile.y + tile.height / 2) as f64,
+                    iterations.width,
+                    iterations.height,
+                );
+                self.view.scale *= tile.width as f64 / iterations.width as f64;
+                self.needs_render = true;
+                self.status = Some(tr!(
+                    "status-detail-found",
+                    entropy = format!("{:.1}", detail.entropy)
+                ));
+            }
+            _ => self.status = Some(tr!("status-no-detail")),
+        }
+    }
+
+    /// Glides to a random detailed place on the boundary at a random depth.
+    fn surprise(&mut self) {
+        match surprise(&mut self.rng, &self.parallel) {
+            Some(found) => {
+                self.max_iter = found.max_iter;
+                self.set_orbit(self.orbit);
+                self.move_view(found.view, JUMP_TRANSITION_SECONDS, Easing::InOut);
+                self.needs_render = true;
+                self.status = Some(tr!(
+                    "status-surprise",
+                    zoom = format!("{:.1e}", found.view.zoom()),
+                    entropy = format!("{:.1}", found.entropy)
+                ));
+            }
+            None => self.status = Some(tr!("status-no-surprise")),
+        }
+    }
+
+    fn toggle_autopilot(&mut self) {
+        if self.autopilot.take().is_some() {
+            self.status = Some(tr!("status-autopilot-stopped"));
+        } else {
+            self.autopilot = Some(Autopilot::new());
+            self.status = Some(tr!("status-autopilot-on"));
+        }
+    }
+
+    /// Moves the autopilot on a frame, unless the user has taken over.
+    fn update_autopilot(&mut self, ctx: &egui::Context) {
+        let Some(autopilot) = &mut self.autopilot else {
+            return;
+        };
+        let interrupted = ctx.input(|i| {
+            i.events.iter().any(|event| {
+                matches!(
+                    event,
+                    egui::Event::Key { pressed: true, .. }
+                        | egui::Event::PointerButton { pressed: true, .. }
+                        | egui::Event::MouseWheel { .. }
+                )
+            })
+        });
+        if interrupted {
+            self.toggle_autopilot();
+            return;
+        }
+        let max_iter = self.max_iter;
+        match autopilot.step(
+            &mut self.view,
+            &mut self.max_iter,
+            self.iterations.as_deref(),
+        ) {
+            Ok(()) => {
+                if self.max_iter != max_iter {
+                    self.set_orbit(self.orbit);
+                }
+                self.needs_render = true;
+                ctx.request_repaint();
+            }
+            Err(reason) => {
+                self.autopilot = None;
+                self.status = Some(tr!("status-autopilot-failed", reason = reason));
+            }
+        }
+    }
+
+    /// Moves the view smoothly to `to` over `duration` seconds, on from
+    /// where a move under way has got to.
+    fn move_view(&mut self, to: View, duration: f64, easing: Easing) {
+        self.transition = Some(ViewTransition::new(self.view, to, duration, easing));
+    }
+
+    /// Moves the view on a frame of a smooth move, dropping the move once it
+    /// is done or the user or anything else has moved the view meanwhile.
+    fn update_transition(&mut self, ctx: &egui::Context) {
+        let Some(transition) = &mut self.transition else {
+            return;
+        };
+        let smooth_zoom = self.settings.smooth_zoom;
+        let interrupted = ctx.input(|i| {
+            i.events.iter().any(|event| match event {
+                egui::Event::PointerButton { pressed: true, .. } => true,
+                // Smooth zooming adds to the move under way instead
+                egui::Event::MouseWheel { .. } => !smooth_zoom,
+                _ => false,
+            })
+        });
+        let Some(view) = transition.advance(&self.view).filter(|_| !interrupted) else {
+            self.transition = None;
+            return;
+        };
+        self.view = view;
+        if transition.is_finished() {
+            self.transition = None;
+        }
+        self.needs_render = true;
+        self.scheduler.interacting();
+        ctx.request_repaint();
+    }
+
+    fn copy_permalink(&mut self, ctx: &egui::Context) {
+        ctx.copy_text(permalink::encode(&self.location(), self.palette));
+        self.status = Some(tr!("status-permalink-copied"));
+    }
+
+    fn menu_bar(&mut self, ui: &mut egui::Ui) {
+        egui::menu::bar(ui, |ui| {
+            ui.menu_button(tr!("menu-file"), |ui| {
+                #[cfg(not(target_arch = "wasm32"))]
+                self.desktop_file_items(ui);
+                if ui.button(tr!("menu-copy-permalink")).clicked() {
+                    ui.close_menu();
+                    self.copy_permalink(ui.ctx());
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    if ui.button(tr!("menu-paste-permalink")).clicked() {
+                        ui.close_menu();
+                        self.paste_permalink();
+                    }
+                    ui.separator();
+                    if ui.button(tr!("menu-settings")).clicked() {
+                        ui.close_menu();
+                        self.settings_open = true;
+                    }
+                }
+                if ui.button(tr!("menu-diagnostics")).clicked() {
+                    ui.close_menu();
+                    self.diagnostics.open = true;
+                }
+                // The desktop has it in the settings
+                #[cfg(target_arch = "wasm32")]
+                ui.menu_button(tr!("menu-language"), |ui| self.language_items(ui));
+            });
+            ui.menu_button(tr!("menu-view"), |ui| {
+                if ui.button(tr!("menu-bookmarks")).clicked() {
+                    ui.close_menu();
+                    self.bookmarks.open = true;
+                }
+                if ui.button(tr!("menu-tour")).clicked() {
+                    ui.close_menu();
+                    self.tour.open = true;
+                }
+                ui.menu_button(tr!("menu-landmarks"), |ui| self.landmark_items(ui));
+                if ui.button(tr!("menu-annotations")).clicked() {
+                    ui.close_menu();
+                    self.annotations.open = true;
+                }
+                if ui.button(tr!("menu-orbit-traps")).clicked() {
+                    ui.close_menu();
+                    self.traps.open = true;
+                }
+                if ui.button(tr!("menu-layers")).clicked() {
+                    ui.close_menu();
+                    self.layers.open = true;
+                }
+                if ui.button(tr!("menu-riemann-sphere")).clicked() {
+                    ui.close_menu();
+                    self.sphere.open = true;
+                }
+                if ui.button(tr!("menu-height-map")).clicked() {
+                    ui.close_menu();
+                    self.height_map.open = true;
+                }
+                if ui.button(tr!("menu-julia-morph")).clicked() {
+                    ui.close_menu();
+                    self.julia_morph.open = true;
+                }
+                if ui.button(tr!("menu-timeline")).clicked() {
+                    ui.close_menu();
+                    self.timeline.open = true;
+                }
+                if ui.button(tr!("menu-recorder")).clicked() {
+                    ui.close_menu();
+                    self.recorder.open = true;
+                }
+                if ui.button(tr!("menu-ab-comparison")).clicked() {
+                    ui.close_menu();
+                    self.ab.open = true;
+                }
+                if ui
+                    .checkbox(&mut self.magnifier.enabled, tr!("menu-magnifier"))
+                    .on_hover_text(tr!("menu-magnifier-hover"))
+                    .changed()
+                {
+                    ui.close_menu();
+                }
+                ui.add_enabled(
+                    self.magnifier.enabled,
+                    egui::Slider::new(&mut self.magnifier.zoom, 4.0..=8.0)
+                        .step_by(1.0)
+                        .suffix("×")
+                        .text(tr!("menu-magnification")),
+                );
+                if ui
+                    .checkbox(&mut self.measure.enabled, tr!("menu-measure"))
+                    .on_hover_text(tr!("menu-measure-hover"))
+                    .changed()
+                {
+                    ui.close_menu();
+                }
+                if ui
+                    .checkbox(&mut self.cursor.circle, tr!("menu-distance-circle"))
+                    .on_hover_text(tr!("menu-distance-circle-hover"))
+                    .changed()
+                {
+                    ui.close_menu();
+                }
+                if ui
+                    .checkbox(&mut self.ghost_trail.enabled, tr!("menu-animate-path"))
+                    .on_hover_text(tr!("menu-animate-path-hover"))
+                    .changed()
+                {
+                    ui.close_menu();
+                    self.ghost_trail.restart();
+                }
+                ui.add_enabled(
+                    self.ghost_trail.enabled,
+                    egui::Slider::new(&mut self.ghost_trail.speed, 1.0..=500.0)
+                        .logarithmic(true)
+                        .suffix(tr!("menu-animation-speed-unit"))
+                        .text(tr!("menu-animation-speed")),
+                );
+                if ui
+                    .checkbox(&mut self.orbit_values, tr!("menu-orbit-values"))
+                    .on_hover_text(tr!("menu-orbit-values-hover"))
+                    .changed()
+                {
+                    ui.close_menu();
+                }
+                if ui
+                    .checkbox(&mut self.settings.hold_zoom, tr!("menu-hold-zoom"))
+                    .on_hover_text(tr!("menu-hold-zoom-hover"))
+                    .changed()
+                {
+                    ui.close_menu();
+                }
+                ui.add_enabled(
+                    self.settings.hold_zoom,
+                    egui::Slider::new(&mut self.settings.hold_zoom_rate, 1.1..=16.0)
+                        .logarithmic(true)
+                        .suffix(tr!("menu-hold-zoom-rate-unit"))
+                        .text(tr!("menu-hold-zoom-rate")),
+                );
+                ui.separator();
+                let zoom = match &mut self.inset {
+                    Some(inset) => &mut inset.zoom,
+                    None => &mut self.inset_zoom,
+                };
+                ui.add(
+                    egui::Slider::new(zoom, 2.0..=1e6)
+                        .logarithmic(true)
+                        .suffix("×")
+                        .text(tr!("menu-inset-zoom")),
+                )
+                .on_hover_text(tr!("menu-inset-zoom-hover"));
+                if ui
+                    .add_enabled(
+                        self.inset.is_some(),
+                        egui::Button::new(tr!("menu-go-to-inset")),
+                    )
+                    .clicked()
+                {
+                    ui.close_menu();
+                    self.go_to_inset();
+                }
+                if ui
+                    .add_enabled(
+                        self.inset.is_some(),
+                        egui::Button::new(tr!("menu-remove-inset")),
+                    )
+                    .clicked()
+                {
+                    ui.close_menu();
+                    self.inset = None;
+                }
+                ui.separator();
+                let lambda = self.formula == Formula::Lambda;
+                if ui
+                    .add_enabled(
+                        lambda || self.formula == Formula::Mandelbrot,
+                        egui::Button::new(if lambda {
+                            tr!("menu-map-to-c-plane")
+                        } else {
+                            tr!("menu-map-to-lambda-plane")
+                        }),
+                    )
+                    .on_hover_text(tr!("menu-map-plane-hover"))
+                    .clicked()
+                {
+                    ui.close_menu();
+                    self.map_plane();
+                }
+            });
+            ui.menu_button(tr!("menu-analysis"), |ui| {
+                if ui.button(tr!("menu-estimate-area")).clicked() {
+                    ui.close_menu();
+                    self.area.open = true;
+                }
+                if ui.button(tr!("menu-histogram")).clicked() {
+                    ui.close_menu();
+                    self.histogram.open = true;
+                }
+                if ui.button(tr!("menu-compare-dem")).clicked() {
+                    ui.close_menu();
+                    self.compare.open = true;
+                }
+                let mut show_stats = self.frame_stats.is_some();
+                if ui
+                    .checkbox(&mut show_stats, tr!("menu-frame-statistics"))
+                    .changed()
+                {
+                    ui.close_menu();
+                    self.frame_stats = show_stats
+                        .then_some(self.iterations.as_deref())
+                        .flatten()
+                        .map(|iterations| frame_stats(iterations, &self.view));
+                }
+                if ui.button(tr!("menu-find-detail")).clicked() {
+                    ui.close_menu();
+                    self.find_detail();
+                }
+                let autopilot = if self.autopilot.is_some() {
+                    tr!("menu-stop-autopilot")
+                } else {
+                    tr!("menu-start-autopilot")
+                };
+                if ui.button(autopilot).clicked() {
+                    ui.close_menu();
+                    self.toggle_autopilot();
+                }
+                if ui.button(tr!("menu-dimension")).clicked() {
+                    ui.close_menu();
+                    self.dimension.open = true;
+                }
+                if ui.button(tr!("menu-tile-telemetry")).clicked() {
+                    ui.close_menu();
+                    self.telemetry.open = true;
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                if ui.button(tr!("menu-export-arrays")).clicked() {
+                    ui.close_menu();
+                    self.npy_export.open = true;
+                }
+            });
+        });
+    }
+
+    /// Jumps to the landmarks of the real axis, and whether to label them.
+    fn landmark_items(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.annotate_landmarks, tr!("menu-annotate-landmarks"));
+        ui.separator();
+        let mandelbrot = self.formula == Formula::Mandelbrot;
+        for landmark in Landmark::ALL {
+            if ui
+                .add_enabled(mandelbrot, egui::Button::new(landmark.name()))
+                .on_hover_text(landmark.explanation())
+                .on_disabled_hover_text(tr!("menu-landmarks-disabled"))
+                .clicked()
+            {
+                ui.close_menu();
+                self.go_to_landmark(landmark);
+            }
+        }
+    }
+
+    /// Glides to `landmark`, labelling it if asked to, and explains it in the
+    /// status bar.
+    fn go_to_landmark(&mut self, landmark: Landmark) {
+        let view = landmark.view();
+        self.max_iter = self.max_iter.max(max_iter_for(&view));
+        self.set_orbit(self.orbit);
+        self.move_view(view, JUMP_TRANSITION_SECONDS, Easing::InOut);
+        self.needs_render = true;
+        if self.annotate_landmarks {
+            for annotation in landmark.annotations() {
+                if !self.annotations.annotations.contains(&annotation) {
+                    self.annotations.annotations.push(annotation);
+                }
+            }
+        }
+        self.status = Some(landmark.explanation());
+    }
+
+    /// Choices of the language the interface is shown in.
+    fn language_items(&mut self, ui: &mut egui::Ui) {
+        for language in Language::ALL {
+            if ui
+                .selectable_value(&mut self.settings.language, language, language.name())
+                .changed()
+            {
+                i18n::set_language(language);
+            }
+        }
+    }
+
+    /// Colors the image with `palette` instead of a plugin or the palette
+    /// before.
+    fn set_palette(&mut self, palette: Palette) {
+        self.palette = palette;
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.coloring = None;
+        }
+        self.needs_recolor = true;
+    }
+
+    /// Name of the palette, plugin, or layers the image is colored with.
+    fn coloring_name(&self) -> String {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(index) = self.coloring {
+            return self.plugins[index].name.clone();
+        }
+        if self.layers.in_use() {
+            return tr!("controls-palette-layers");
+        }
+        self.palette.name().to_owned()
+    }
+
+    /// Colors `iterations`, rendered from `view`.
+    fn colorize(&self, iterations: &IterationBuffer, view: &View) -> Image {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(index) = self.coloring {
+            let image = self.plugins[index].colorize(iterations);
+            return self.finish_coloring(image, iterations, view);
+        }
+        if self.layers.in_use() {
+            let image = composite(
+                &self.layers.layers,
+                iterations,
+                view,
+                self.formula,
+                &self.traps.traps,
+                self.traps.width,
+            );
+            return self.finish_coloring(image, iterations, view);
+        }
+        self.colorize_with(iterations, view, self.palette)
+    }
+
+    /// Colors `iterations` with `palette` instead of the chosen coloring,
+    /// stretched over the escape times present with auto contrast.
+    fn colorize_with(&self, iterations: &IterationBuffer, view: &View, palette: Palette) -> Image {
+        let range = (self.auto_contrast)
+            .then(|| escape_range(iterations, self.contrast_clip / 100.0))
+            .flatten();
+        let image = match range {
+            Some(range) => colorize_stretched(iterations, palette, range),
+            None => colorize(iterations, palette),
+        };
+        self.finish_coloring(image, iterations, view)
+    }
+
+    /// Adds the orbit traps, ambient occlusion, slope shading, interior
+    /// coloring, and overlays to `image`, colored from `iterations`, rendered
+    /// from `view`. The last three are only for z → z² + c, and the traps
+    /// are left to the layers while they are in use.
+    fn finish_coloring(
+        &self,
+        mut image: Image,
+        iterations: &IterationBuffer,
+        view: &View,
+    ) -> Image {
+        if !self.layers.in_use() {
+            color_traps(
+                &mut image,
+                iterations,
+                view,
+                self.formula,
+                &self.traps.traps,
+                self.traps.width,
+            );
+        }
+        occlude(&mut image, iterations, self.occlusion);
+        if self.formula == Formula::Mandelbrot {
+            shade(&mut image, iterations, view, self.shading);
+            color_interior(&mut image, iterations, view, self.interior_coloring);
+            draw_overlay(&mut image, iterations, view, self.overlay);
+        }
+        image
+    }
+
+    /// Sliders for the light slope shading is lit by.
+    fn lighting_controls(&mut self, ui: &mut egui::Ui) {
+        let Shading {
+            azimuth,
+            elevation,
+            strength,
+            ..
+        } = &mut self.shading;
+        let sliders = [
+            ui.add(
+                egui::Slider::new(azimuth, 0.0..=360.0)
+                    .suffix("°")
+                    .text(tr!("controls-light-azimuth")),
+            ),
+            ui.add(
+                egui::Slider::new(elevation, 5.0..=90.0)
+                    .suffix("°")
+                    .text(tr!("controls-light-elevation")),
+            ),
+            ui.add(egui::Slider::new(strength, 0.0..=1.0).text(tr!("controls-light-strength"))),
+        ];
+        if sliders.iter().any(egui::Response::changed) {
+            self.needs_recolor = true;
+        }
+    }
+
+    fn view_controls(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let label = ui.label(tr!("controls-formula"));
+            let formula = self.formula;
+            egui::ComboBox::from_id_salt("formula")
+                .selected_text(self.formula.name())
+                .show_ui(ui, |ui| {
+                    for choice in Formula::ALL {
+                        let checked = self.formula.same_kind(choice);
+                        if ui.selectable_label(checked, choice.name()).clicked() && !checked {
+                            self.formula = choice;
+                        }
+                    }
+                })
+                .response
+                .labelled_by(label.id);
+            if let Formula::Multibrot { exponent } = &mut self.formula {
+                ui.label(tr!("controls-exponent"))
+                    .on_hover_text(tr!("controls-exponent-hover"));
+                ui.add(
+                    egui::DragValue::new(&mut exponent.0)
+                        .speed(0.01)
+                        .max_decimals(6),
+                );
+                ui.add(
+                    egui::DragValue::new(&mut exponent.1)
+                        .speed(0.01)
+                        .max_decimals(6)
+                        .suffix("i"),
+                );
+                ui.menu_button("⊞", |ui| exponent_pad(ui, exponent))
+                    .response
+                    .on_hover_text(tr!("controls-exponent-pad-hover"));
+            }
+            if let Formula::Cubic { a, escape } = &mut self.formula {
+                ui.label("a").on_hover_text(tr!("controls-cubic-a-hover"));
+                ui.add(egui::DragValue::new(&mut a.0).speed(0.01).max_decimals(6));
+                ui.add(
+                    egui::DragValue::new(&mut a.1)
+                        .speed(0.01)
+                        .max_decimals(6)
+                        .suffix("i"),
+                );
+                ui.selectable_value(escape, CubicEscape::Either, tr!("controls-cubic-either"))
+                    .on_hover_text(tr!("controls-cubic-either-hover"));
+                ui.selectable_value(escape, CubicEscape::Both, tr!("controls-cubic-both"))
+                    .on_hover_text(tr!("controls-cubic-both-hover"));
+            }
+            if let Formula::Quaternion { offset, julia } = &mut self.formula {
+                ui.label("j")
+                    .on_hover_text(tr!("controls-quaternion-offset-hover"));
+                ui.add(egui::Slider::new(&mut offset.0, -2.0..=2.0).max_decimals(6));
+                ui.label("k")
+                    .on_hover_text(tr!("controls-quaternion-offset-hover"));
+                ui.add(egui::Slider::new(&mut offset.1, -2.0..=2.0).max_decimals(6));
+                let mut dynamical = julia.is_some();
+                if ui
+                    .checkbox(&mut dynamical, tr!("controls-nova-julia"))
+                    .on_hover_text(tr!("controls-quaternion-julia-hover"))
+                    .changed()
+                {
+                    *julia = dynamical.then(|| {
+                        let (re, im) = self.orbit.unwrap_or_default();
+                        [re, im, offset.0, offset.1]
+                    });
+                }
+                if let Some(c) = julia {
+                    for (part, suffix) in c.iter_mut().zip(["", "i", "j", "k"]) {
+                        ui.add(
+                            egui::DragValue::new(part)
+                                .speed(0.01)
+                                .max_decimals(9)
+                                .suffix(suffix),
+                        );
+                    }
+                }
+            }
+            if let Formula::Hybrid { pattern } = &mut self.formula {
+                let label = ui.label(tr!("controls-hybrid-pattern"));
+                let mut letters = pattern.to_string();
+                let edit = ui
+                    .add(egui::TextEdit::singleline(&mut letters).desired_width(80.0))
+                    .labelled_by(label.id)
+                    .on_hover_text(tr!("controls-hybrid-pattern-hover"));
+                // Letters standing for no step are dropped as they're typed
+                if edit.changed()
+                    && let Some(parsed) = Pattern::parse(&letters)
+                {
+                    *pattern = parsed;
+                }
+            }
+            if let Formula::Phoenix { p } = &mut self.formula {
+                ui.label("p").on_hover_text(tr!("controls-phoenix-p-hover"));
+                ui.add(egui::DragValue::new(&mut p.0).speed(0.01).max_decimals(6));
+                ui.add(
+                    egui::DragValue::new(&mut p.1)
+                        .speed(0.01)
+                        .max_decimals(6)
+                        .suffix("i"),
+                );
+            }
+            if let Formula::Nova {
+                relaxation,
+                power,
+                julia,
+            } = &mut self.formula
+            {
+                ui.label("R")
+                    .on_hover_text(tr!("controls-nova-relaxation-hover"));
+                ui.add(
+                    egui::DragValue::new(&mut relaxation.0)
+                        .speed(0.01)
+                        .max_decimals(6),
+                );
+                ui.add(
+                    egui::DragValue::new(&mut relaxation.1)
+                        .speed(0.01)
+                        .max_decimals(6)
+                        .suffix("i"),
+                );
+                ui.label("p")
+                    .on_hover_text(tr!("controls-nova-power-hover"));
+                ui.add(egui::DragValue::new(power).range(2..=8));
+                let mut dynamical = julia.is_some();
+                if ui
+                    .checkbox(&mut dynamical, tr!("controls-nova-julia"))
+                    .on_hover_text(tr!("controls-nova-julia-hover"))
+                    .changed()
+                {
+                    *julia = dynamical.then(|| self.orbit.unwrap_or_default());
+                }
+                if let Some(c) = julia {
+                    ui.add(egui::DragValue::new(&mut c.0).speed(0.01).max_decimals(9));
+                    ui.add(
+                        egui::DragValue::new(&mut c.1)
+                            .speed(0.01)
+                            .max_decimals(9)
+                            .suffix("i"),
+                    );
+                }
+            }
+            if self.formula != formula {
+                self.set_orbit(self.orbit);
+                self.needs_render = true;
+            }
+            let label = ui.label(tr!("controls-palette"));
+            egui::ComboBox::from_id_salt("palette")
+                .selected_text(self.coloring_name())
+                .show_ui(ui, |ui| {
+                    for palette in Palette::ALL {
+                        let checked = self.coloring_name() == palette.name();
+                        if ui.selectable_label(checked, palette.name()).clicked() {
+                            self.set_palette(palette);
+                        }
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    self.plugin_items(ui);
+                })
+                .response
+                .labelled_by(label.id);
+            if ui
+                .button("🎲")
+                .on_hover_text(tr!("controls-randomize-palette-hover"))
+                .clicked()
+            {
+                let seed = self.rng.next_u64() as u32;
+                self.set_palette(Palette::Random { seed });
+            }
+            if let Palette::Random { mut seed } = self.palette
+                && ui
+                    .add(egui::DragValue::new(&mut seed).prefix("#"))
+                    .on_hover_text(tr!("controls-palette-seed-hover"))
+                    .changed()
+            {
+                self.set_palette(Palette::Random { seed });
+            }
+            let contrast = ui
+                .checkbox(&mut self.auto_contrast, tr!("controls-auto-contrast"))
+                .on_hover_text(tr!("controls-auto-contrast-hover"));
+            let clip = ui
+                .add_enabled(
+                    self.auto_contrast,
+                    egui::DragValue::new(&mut self.contrast_clip)
+                        .range(0.0..=25.0)
+                        .speed(0.1)
+                        .max_decimals(1)
+                        .suffix("%"),
+                )
+                .labelled_by(contrast.id)
+                .on_hover_text(tr!("controls-contrast-clip-hover"));
+            if contrast.changed() || clip.changed() {
+                self.needs_recolor = true;
+            }
+            let label = ui.label(tr!("controls-max-iterations"));
+            let response = ui
+                .add(egui::DragValue::new(&mut self.max_iter).range(10..=MAX_ITER))
+                .labelled_by(label.id);
+            if response.changed() {
+                self.set_orbit(self.orbit);
+                self.needs_render = true;
+            }
+            let mut separate = self.orbit_max_iter.is_some();
+            if ui
+                .checkbox(&mut separate, tr!("controls-path-iterations"))
+                .on_hover_text(tr!("controls-path-iterations-hover"))
+                .changed()
+            {
+                self.orbit_max_iter = separate.then_some(self.max_iter.max(ORBIT_MAX_ITER));
+                self.set_orbit(self.orbit);
+            }
+            if let Some(orbit_max_iter) = &mut self.orbit_max_iter
+                && ui
+                    .add(egui::DragValue::new(orbit_max_iter).range(10..=1_000_000))
+                    .on_hover_text(tr!("controls-path-iterations-hover"))
+                    .changed()
+            {
+                self.set_orbit(self.orbit);
+            }
+            let label = ui.label(tr!("controls-interior"));
+            egui::ComboBox::from_id_salt("interior")
+                .selected_text(self.interior_coloring.name())
+                .show_ui(ui, |ui| {
+                    for coloring in InteriorColoring::ALL {
+                        if ui
+                            .selectable_value(
+                                &mut self.interior_coloring,
+                                coloring,
+                                coloring.name(),
+                            )
+                            .changed()
+                        {
+                            self.needs_recolor = true;
+                        }
+                    }
+                })
+                .response
+                .labelled_by(label.id)
+                .on_hover_text(tr!("controls-interior-hover"));
+            let label = ui.label(tr!("controls-renderer"));
+            let selected = (self.backend_name(self.settings.backend))
+                .unwrap_or_else(|| self.renderer().name().to_owned());
+            egui::ComboBox::from_id_salt("renderer")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    for backend in Backend::ALL {
+                        let Some(name) = self.backend_name(backend) else {
+                            continue;
+                        };
+                        if ui
+                            .selectable_value(&mut self.settings.backend, backend, name)
+                            .changed()
+                        {
+                            self.needs_render = true;
+                        }
+                    }
+                })
+                .response
+                .labelled_by(label.id);
+        });
+        ui.horizontal(|ui| {
+            let label = ui.label(tr!("controls-zoom"));
+            let edit = ui
+                .add(
+                    egui::TextEdit::singleline(&mut self.zoom_factor)
+                        .hint_text("1000x")
+                        .desired_width(80.0),
+                )
+                .labelled_by(label.id)
+                .on_hover_text(tr!("controls-zoom-hover"));
+            if edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                // Not to also show the path of the center, as Enter does
+                ui.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Enter));
+                self.zoom_by_typed_factor();
+            }
+            if ui
+                .add_enabled(
+                    self.formula == Formula::Mandelbrot,
+                    egui::Button::new(tr!("controls-surprise")),
+                )
+                .on_hover_text(tr!("controls-surprise-hover"))
+                .on_disabled_hover_text(tr!("controls-surprise-disabled"))
+                .clicked()
+            {
+                self.surprise();
+            }
+            let label = ui.label(tr!("controls-field-lines"));
+            let lines = ui
+                .add(egui::DragValue::new(&mut self.overlay.field_lines).range(0..=256))
+                .labelled_by(label.id)
+                .on_hover_text(tr!("controls-field-lines-hover"));
+            let equipotentials = ui
+                .checkbox(
+                    &mut self.overlay.equipotentials,
+                    tr!("controls-equipotentials"),
+                )
+                .on_hover_text(tr!("controls-equipotentials-hover"));
+            if lines.changed() || equipotentials.changed() {
+                self.needs_recolor = true;
+            }
+            if ui
+                .checkbox(&mut self.shading.enabled, tr!("controls-shading"))
+                .on_hover_text(tr!("controls-shading-hover"))
+                .changed()
+            {
+                self.needs_recolor = true;
+            }
+            ui.add_enabled_ui(self.shading.enabled, |ui| {
+                ui.menu_button("☀", |ui| self.lighting_controls(ui))
+                    .response
+                    .on_hover_text(tr!("controls-lighting-hover"));
+            });
+            let label = ui.label(tr!("controls-occlusion"));
+            if ui
+                .add(
+                    egui::DragValue::new(&mut self.occlusion)
+                        .range(0.0..=1.0)
+                        .speed(0.01)
+                        .max_decimals(2),
+                )
+                .labelled_by(label.id)
+                .on_hover_text(tr!("controls-occlusion-hover"))
+                .changed()
+            {
+                self.needs_recolor = true;
+            }
+            ui.label(tr!("controls-quality"));
+            for quality in Quality::ALL {
+                ui.selectable_value(
+                    &mut self.settings.quality,
+                    QualityMode::Fixed(quality),
+                    quality.name(),
+                )
+                .on_hover_text(tr!(
+                    "quality-hover",
+                    resolution = quality.resolution_scale() * 100.0,
+                    samples = quality.supersampling().pow(2),
+                    limit = quality.max_iter(100),
+                ));
+            }
+            ui.selectable_value(
+                &mut self.settings.quality,
+                QualityMode::Auto,
+                tr!("quality-auto"),
+            )
+            .on_hover_text(tr!("quality-auto-hover"));
+            if self.screen.is_some() {
+                ui.checkbox(&mut self.settings.live_shader, tr!("controls-live-preview"))
+                    .on_hover_text(tr!("controls-live-preview-hover"));
+            }
+            let sliced = ui
+                .checkbox(&mut self.settings.cooperative, tr!("controls-sliced"))
+                .on_hover_text(tr!("controls-sliced-hover"));
+            if sliced.changed() {
+                self.needs_render = true;
+            }
+            ui.add_enabled(
+                self.settings.cooperative,
+                egui::DragValue::new(&mut self.settings.frame_budget_ms)
+                    .range(1..=50)
+                    .suffix(" ms"),
+            )
+            .labelled_by(sliced.id)
+            .on_hover_text(tr!("controls-frame-budget-hover"));
+        });
+    }
+
+    /// The renderer to draw the view with directly on the GPU, if live
+    /// preview is on and the shader can match the normal image: a built-in
+    /// palette with nothing drawn on top, at a zoom `f32` can handle across
+    /// `width` pixels.
+    fn live_shader(&self, width: usize) -> Option<Arc<Mutex<ScreenRenderer>>> {
+        let mandelbrot = self.formula == Formula::Mandelbrot;
+        (self.settings.live_shader && mandelbrot && self.view.fits_f32(width))
+            .then(|| self.gpu_palette())
+            .flatten()
+    }
+
+    /// The renderer to color frames with on the GPU: the one for palettes,
+    /// unless the frame is supersampled, as averaging the samples happens on
+    /// the CPU.
+    fn gpu_coloring(&self) -> Option<Arc<Mutex<ScreenRenderer>>> {
+        (self.frame_quality.supersampling() == 1)
+            .then(|| self.gpu_palette())
+            .flatten()
+    }
+
+    /// The renderer to apply the palette with on the GPU, if there is one and
+    /// the coloring is just a built-in palette. The shader paints points
+    /// whose orbit converged like the inside of the set.
+    fn gpu_palette(&self) -> Option<Arc<Mutex<ScreenRenderer>>> {
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.coloring.is_some() {
+            return None;
+        }
+        (!self.formula.converges()
+            && self.interior_coloring == InteriorColoring::Black
+            && self.overlay.is_empty()
+            && !self.shading.enabled
+            && self.occlusion == 0.0
+            && self.traps.traps.is_empty()
+            && !self.layers.in_use()
+            && !self.auto_contrast)
+            .then(|| self.screen.clone())
+            .flatten()
+    }
+
+    /// The preset to render the view with now.
+    fn quality(&self) -> Quality {
+        match self.settings.quality {
+            QualityMode::Fixed(quality) => quality,
+            QualityMode::Auto => {
+                let interacting = self.autopilot.is_some()
+                    || (self.last_interaction).is_some_and(|time| time.elapsed() < IDLE_DELAY);
+                if interacting {
+                    Quality::Draft
+                } else {
+                    Quality::High
+                }
+            }
+        }
+    }
+
+    /// Whether to render the view in the background, showing it as it
+    /// sharpens: on the desktop, on the CPU, when a render of `pixels`
+    /// pixels would be slow at the pace of the last one, and not for the
+    /// autopilot, which needs each frame finished.
+    fn progressive_wanted(&self, pixels: usize) -> bool {
+        cfg!(not(target_arch = "wasm32"))
+            && self.backend_in_use() != Backend::Gpu
+            && self.autopilot.is_none()
+            && self.last_render_time.mul_f64(pixels as f64) >= PROGRESSIVE_THRESHOLD
+    }
+
+    /// Whether to render the view a slice per frame on this thread: when
+    /// that is turned on, on the CPU, and not for the autopilot.
+    fn cooperative_wanted(&self) -> bool {
+        self.settings.cooperative
+            && self.backend_in_use() != Backend::Gpu
+            && self.autopilot.is_none()
+    }
+
+    /// Renders the next slice of the cooperative render, shows the passes it
+    /// finished, and keeps the render once it's done.
+    fn step_cooperative(&mut self, ctx: &egui::Context) {
+        let budget = Duration::from_millis(self.settings.frame_budget_ms.into());
+        let Some((render, ..)) = &mut self.cooperative else {
+            return;
+        };
+        let image = render.step(budget);
+        if !render.is_done() {
+            ctx.request_repaint();
+        }
+        let Some(iterations) = image.map(Arc::new) else {
+            return;
+        };
+        if render.is_done()
+            && let Some((_, key, started)) = self.cooperative.take()
+        {
+            let elapsed = started.elapsed();
+            self.last_render_time = elapsed.div_f64((key.1.width * key.1.height) as f64);
+            info!(
+                renderer = key.2,
+                side = key.1.width,
+                max_iter = key.1.max_iter,
+                ms = elapsed.as_secs_f64() * 1000.0,
+                "rendered view cooperatively"
+            );
+            self.frames.insert(key, iterations.clone());
+            self.show_frame(ctx, iterations);
+        } else {
+            self.color_frame(&iterations);
+            self.iterations = Some(iterations);
+        }
+        self.needs_recolor = false;
+    }
+
+    /// Shows a newly rendered frame and updates what is derived from it.
+    fn show_frame(&mut self, ctx: &egui::Context, iterations: Arc<IterationBuffer>) {
+        self.color_frame(&iterations);
+        if self.frame_stats.is_some() {
+            self.frame_stats = Some(frame_stats(&iterations, &self.view));
+        }
+        self.iterations = Some(iterations);
+        self.histogram.invalidate(ctx);
+        self.period_highlight.invalidate();
+        self.compare.invalidate(ctx);
+        self.ab.invalidate(ctx);
+    }
+
+    /// Pans with the arrow keys (finely with Shift), zooms with plus and
+    /// minus, goes back to the whole set with Home, and shows or hides the
+    /// path of the center with Enter and Escape.
+    fn navigate_by_keys(&mut self, ctx: &egui::Context) {
+        let zoom_step = self.settings.zoom_step;
+        let (pan, zoom, home, enter, escape) = ctx.input(|i| {
+            let step = if i.modifiers.shift { 0.01 } else { 0.1 };
+            let axis = |negative, positive| {
+                (i.key_pressed(positive) as i32 - i.key_pressed(negative) as i32) as f64 * step
+            };
+            let pan = (
+                axis(egui::Key::ArrowLeft, egui::Key::ArrowRight),
+                axis(egui::Key::ArrowUp, egui::Key::ArrowDown),
+            );
+            let zoom = if i.key_pressed(egui::Key::Plus) || i.key_pressed(egui::Key::Equals) {
+                1.0 / zoom_step
+            } else if i.key_pressed(egui::Key::Minus) {
+                zoom_step
+            } else {
+                1.0
+            };
+            let home = i.key_pressed(egui::Key::Home);
+            (
+                pan,
+                zoom,
+                home,
+                i.key_pressed(egui::Key::Enter),
+                i.key_pressed(egui::Key::Escape),
+            )
+        });
+        if pan != (0.0, 0.0) || zoom != 1.0 || home {
+            // Presses in quick succession add up
+            let mut to = self.transition.map_or(self.view, |t| t.to);
+            to.center.0 += pan.0 * to.scale;
+            to.center.1 += pan.1 * to.scale;
+            to.scale *= zoom;
+            if home {
+                self.move_view(View::default(), JUMP_TRANSITION_SECONDS, Easing::InOut);
+            } else {
+                self.move_view(to, KEY_TRANSITION_SECONDS, Easing::Out);
+            }
+            self.last_interaction = Some(Instant::now());
+            self.scheduler.interacting();
+        }
+        if enter {
+            self.set_orbit(Some(self.view.center));
+        } else if escape {
+            self.set_orbit(None);
+        }
+    }
+
+    /// Zooms for a turn of the mouse wheel by `notches`, positive away from
+    /// the user, with the cursor at pixel `(px, py)` of the view of `side`
+    /// pixels, as the settings say.
+    fn zoom_by_wheel(&mut self, (px, py): (f64, f64), side: usize, notches: f32) {
+        let settings = &self.settings;
+        let notches = if settings.invert_wheel {
+            -notches
+        } else {
+            notches
+        };
+        let (px, py) = if settings.zoom_about_cursor {
+            (px, py)
+        } else {
+            (side as f64 / 2.0, side as f64 / 2.0)
+        };
+        if settings.smooth_zoom {
+            let factor = settings.zoom_step.powf(-notches as f64);
+            // Turns in quick succession add up
+            let mut to = self.transition.map_or(self.view, |t| t.to);
+            to.zoom_at(px, py, side, side, factor);
+            self.move_view(to, KEY_TRANSITION_SECONDS, Easing::Out);
+        } else {
+            let factor = if notches > 0.0 {
+                1.0 / settings.zoom_step
+            } else {
+                settings.zoom_step
+            };
+            self.view.zoom_at(px, py, side, side, factor);
+            self.needs_render = true;
+        }
+    }
+
+    /// Zooms in, for `direction` 1, or out, for -1, by the hold-to-zoom rate
+    /// over the time since the last frame, towards `pointer`, a pixel of the
+    /// view of `side` pixels, or the center if the view isn't pointed at.
+    /// Slow renders show their first passes while the view keeps moving, see
+    /// `render_goes_on_while_held`.
+    fn zoom_while_held(
+        &mut self,
+        ctx: &egui::Context,
+        pointer: Option<(f64, f64)>,
+        side: usize,
+        direction: f64,
+    ) {
+        let dt = ctx.input(|i| i.stable_dt) as f64;
+        let (px, py) = pointer.unwrap_or((side as f64 / 2.0, side as f64 / 2.0));
+        let factor = self.settings.hold_zoom_rate.powf(-direction * dt);
+        self.transition = None;
+        self.view.zoom_at(px, py, side, side, factor);
+        self.needs_render = true;
+        self.last_interaction = Some(Instant::now());
+        self.scheduler.interacting();
+        ctx.request_repaint();
+    }
+
+    /// Whether the zoom is held and the render under way started too recently
+    /// to be started over for the view that moved on since.
+    fn render_goes_on_while_held(&self) -> bool {
+        let started = self.cooperative.as_ref().map(|(_, _, started)| *started);
+        #[cfg(not(target_arch = "wasm32"))]
+        let started = started.or(self.progressive.as_ref().map(|(_, _, started)| *started));
+        self.holding_zoom && started.is_some_and(|started| started.elapsed() < HOLD_ZOOM_REFRESH)
+    }
+
+    /// Zooms in about the center by the factor typed into the zoom field,
+    /// or out for factors below 1, on from where a move under way is going.
+    fn zoom_by_typed_factor(&mut self) {
+        let Some(factor) = parse_zoom_factor(&self.zoom_factor) else {
+            self.status = Some(tr!(
+                "status-zoom-factor-invalid",
+                text = self.zoom_factor.clone()
+            ));
+            return;
+        };
+        let mut to = self.transition.map_or(self.view, |t| t.to);
+        to.scale /= factor;
+        self.move_view(to, JUMP_TRANSITION_SECONDS, Easing::InOut);
+        self.last_interaction = Some(Instant::now());
+    }
+
+    /// Moves the view to the one the inset shows, removing the inset.
+    fn go_to_inset(&mut self) {
+        if let Some(inset) = self.inset.take() {
+            self.move_view(inset.view(), JUMP_TRANSITION_SECONDS, Easing::InOut);
+            self.status = Some(tr!("status-moved-to-inset"));
+        }
+    }
+
+    /// Switches between the c-plane of z → z² + c and the λ-plane of the
+    /// logistic map, moving the view and the selected point to where the
+    /// conjugacy takes them.
+    fn map_plane(&mut self) {
+        let center = self.view.center;
+        let (formula, center, scale, orbit) = match self.formula {
+            Formula::Mandelbrot => {
+                let lambda = c_to_lambda(center);
+                // Near the cusp λ = 1 the map squashes lengths to nothing
+                let scale = self.view.scale / lambda_scale(lambda).max(1e-12);
+                (Formula::Lambda, lambda, scale, self.orbit.map(c_to_lambda))
+            }
+            Formula::Lambda => {
+                let scale = self.view.scale * lambda_scale(center);
+                let orbit = self.orbit.map(lambda_to_c);
+                (Formula::Mandelbrot, lambda_to_c(center), scale, orbit)
+            }
+            _ => return,
+        };
+        self.formula = formula;
+        self.view = View { center, scale };
+        self.set_orbit(orbit);
+        self.needs_render = true;
+        self.status = Some(if formula == Formula::Lambda {
+            tr!("status-mapped-to-lambda-plane")
+        } else {
+            tr!("status-mapped-to-c-plane")
+        });
+    }
+
+    /// Draws the inset over `image_rect`, rendering it first if it changed.
+    fn show_inset(&mut self, ui: &egui::Ui, image_rect: egui::Rect) {
+        let max_iter = self.frame_quality.max_iter(self.max_iter);
+        if let Some(view) = (self.inset.as_ref())
+            .filter(|inset| inset.needs_render(max_iter))
+            .map(Inset::view)
+        {
+            let side = (INSET_SIZE * ui.ctx().pixels_per_point()).round() as usize;
+            let settings = RenderSettings {
+                width: side,
+                height: side,
+                max_iter,
+                formula: self.formula,
+            };
+            let iterations = self
+                .scheduler
+                .run(Priority::View, || self.renderer().render(&view, &settings));
+            let image = self.colorize(&iterations, &view);
+            if let Some(inset) = &mut self.inset {
+                inset.set_image(ui.ctx(), max_iter, &image);
+            }
+        }
+        if let Some(inset) = &self.inset {
+            inset.draw(
+                &ui.painter().with_clip_rect(image_rect),
+                image_rect,
+                &self.view,
+            );
+        }
+    }
+
+    /// Draws the magnifier at `pos`, over the point `offset` from the
+    /// top-left corner of the view, rendering what it shows if that moved.
+    fn show_magnifier(&mut self, ui: &egui::Ui, offset: egui::Vec2, pos: egui::Pos2, side: usize) {
+        let c = (self.view).pixel_to_complex(offset.x as f64, offset.y as f64, side, side);
+        let lens_view = self.magnifier.lens_view(&self.view, c, side);
+        if self.magnifier.needs_render(&lens_view) {
+            let lens_side = (LENS_SIZE * ui.ctx().pixels_per_point()).round() as usize;
+            let settings = RenderSettings {
+                width: lens_side,
+                height: lens_side,
+                max_iter: self.frame_quality.max_iter(self.max_iter),
+                formula: self.formula,
+            };
+            let iterations = self.scheduler.run(Priority::View, || {
+                self.renderer().render(&lens_view, &settings)
+            });
+            let image = self.colorize(&iterations, &lens_view);
+            self.magnifier.set_image(ui.ctx(), lens_view, &image);
+        }
+        self.magnifier.draw(ui.painter(), pos);
+    }
+
+    /// Colors the escape times of the frame, averaging its samples if it was
+    /// supersampled.
+    fn colorize_frame(&self, iterations: &IterationBuffer) -> Image {
+        self.average_samples(self.colorize(iterations, &self.view))
+    }
+
+    /// `image`, colored from the frame's escape times, at the size shown:
+    /// with its samples averaged if the frame was supersampled.
+    fn average_samples(&self, image: Image) -> Image {
+        match self.frame_quality.supersampling() {
+            1 => image,
+            factor => image.downsample(factor),
+        }
+    }
+
+    /// The setup the view is rendered with, for A/B comparisons.
+    fn ab_setup(&self) -> Setup {
+        Setup {
+            palette: self.palette,
+            max_iter: self.max_iter,
+            backend: self.backend_in_use(),
+            renderer: self.renderer().name(),
+        }
+    }
+
+    /// Renders the frame again with `setup`, for comparing it with the view.
+    fn render_ab(&self, setup: &Setup) -> Option<Image> {
+        let frame = self.iterations.as_ref()?;
+        let settings = RenderSettings {
+            width: frame.width,
+            height: frame.height,
+            max_iter: self.frame_quality.max_iter(setup.max_iter),
+            formula: self.formula,
+        };
+        let renderer = self.backend(setup.backend).unwrap_or(&self.parallel);
+        let iterations =
+            (self.scheduler).run(Priority::View, || renderer.render(&self.view, &settings));
+        Some(self.average_samples(self.colorize_with(&iterations, &self.view, setup.palette)))
+    }
+
+    /// Colors `iterations` for display: on the CPU into the texture, or not
+    /// at all if the GPU colors them while drawing.
+    fn color_frame(&mut self, iterations: &IterationBuffer) {
+        self.magnifier.invalidate();
+        if let Some(inset) = &mut self.inset {
+            inset.invalidate();
+        }
+        if self.gpu_coloring().is_some() {
+            self.mandelbrot_image = None;
+            return;
+        }
+        let image = self.colorize_frame(iterations);
+        self.mandelbrot_texture
+            .set(to_color_image(&image), egui::TextureOptions::default());
+        self.mandelbrot_image = Some(image);
+    }
+
+    fn status_bar(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(tr!(
+                "status-bar-view",
+                re = format!("{:.6}", self.view.center.0),
+                im = format!("{:+.6}", self.view.center.1),
+                width = format!("{:.3e}", self.view.scale),
+            ));
+            let running = (self.cooperative.as_ref())
+                .map(|(render, _, started)| (render.progress(), *started));
+            #[cfg(not(target_arch = "wasm32"))]
+            let running = running.or_else(|| {
+                (self.progressive.as_ref())
+                    .map(|(render, _, started)| (render.progress(), *started))
+            });
+            if let Some((done, started)) = running {
+                ui.separator();
+                ui.add(
+                    egui::ProgressBar::new(done)
+                        .desired_width(240.0)
+                        .text(progress_text(done, started.elapsed())),
+                );
+                if ui.button(tr!("button-cancel")).clicked() {
+                    // The passes finished so far stay on screen
+                    #[cfg(not(target_arch = "wasm32"))]
+                    {
+                        self.progressive = None;
+                    }
+                    self.cooperative = None;
+                    self.status = Some(tr!("status-render-cancelled"));
+                } else {
+                    ui.ctx().request_repaint_after(PROGRESS_REPAINT_INTERVAL);
+                }
+            }
+            if let Some(cursor) = self.cursor.status(self.view.pixel_size(self.last_size[0])) {
+                ui.separator();
+                ui.label(cursor);
+            }
+            if let Some(status) = &self.status {
+                ui.separator();
+                ui.label(status);
+            }
+            if let Some(minibrot) = self.minibrot {
+                let angle = format!("{:.1}", minibrot.orientation * 360.0);
+                if ui
+                    .button(tr!("button-frame-minibrot"))
+                    .on_hover_text(tr!(
+                        "button-frame-minibrot-hover",
+                        period = minibrot.period,
+                        size = format!("{:.3e}", minibrot.size),
+                        angle = angle.clone(),
+                    ))
+                    .clicked()
+                {
+                    self.move_view(minibrot.view(), JUMP_TRANSITION_SECONDS, Easing::InOut);
+                    self.minibrot = None;
+                    self.status = Some(tr!(
+                        "status-framed-minibrot",
+                        period = minibrot.period,
+                        angle = angle
+                    ));
+                }
+            }
+        });
+    }
+}
+
+/// File, clipboard, plugin, and script actions, which need the desktop.
+#[cfg(not(target_arch = "wasm32"))]
+impl MandelbrotApp {
+    /// Shows the passes of the background render finished since the last
+    /// frame, and keeps the render once it's done.
+    fn poll_progressive(&mut self, ctx: &egui::Context) {
+        let Some((render, ..)) = &mut self.progressive else {
+            return;
+        };
+        let Some(iterations) = render.poll().map(Arc::new) else {
+            return;
+        };
+        if render.is_done()
+            && let Some((_, key, started)) = self.progressive.take()
+        {
+            let elapsed = started.elapsed();
+            self.last_render_time = elapsed.div_f64((key.1.width * key.1.height) as f64);
+            info!(
+                renderer = key.2,
+                side = key.1.width,
+                max_iter = key.1.max_iter,
+                ms = elapsed.as_secs_f64() * 1000.0,
+                "rendered view progressively"
+            );
+            self.frames.insert(key, iterations.clone());
+            self.show_frame(ctx, iterations);
+        } else {
+            self.color_frame(&iterations);
+            self.iterations = Some(iterations);
+        }
+        self.needs_recolor = false;
+    }
+
+    /// The frame as shown, colored on the CPU now if the GPU colored it.
+    fn frame_image(&mut self) -> &Image {
+        if self.mandelbrot_image.is_none()
+            && let Some(iterations) = self.iterations.clone()
+        {
+            self.mandelbrot_image = Some(self.colorize_frame(&iterations));
+        }
+        self.mandelbrot_image
+            .as_ref()
+            .expect("frames colored on the GPU have iterations")
+    }
+
+    fn load_plugins(&mut self) {
+        let (plugins, errors) = plugin::discover(&self.settings.plugin_dir);
+        for error in &errors {
+            warn!("could not load plugin {error}");
+        }
+        if !errors.is_empty() {
+            self.status = Some(tr!("status-plugins-failed", count = errors.len()));
+        }
+        self.plugins = plugins;
+    }
+
+    /// Copy and screenshot shortcuts, dropped files, remote commands, and
+    /// crash recovery.
+    fn handle_desktop_input(&mut self, ctx: &egui::Context) {
+        if let Some(session) = self.autosave.show_offer(ctx) {
+            self.apply_session(session);
+            self.status = Some(tr!("status-recovered"));
+        }
+        if self.autosave.is_due() {
+            let session = self.session();
+            self.autosave.save(&session);
+        }
+        self.update_remote(ctx);
+        while let Some(request) = self.remote.as_ref().and_then(RemoteServer::try_recv) {
+            let result = self.remote_command(&request.command);
+            request.reply(result);
+        }
+        // Ctrl+C copies the image unless a text field wants it
+        let copy_requested = ctx.memory(|m| m.focused().is_none())
+            && ctx.input(|i| i.events.iter().any(|e| matches!(e, egui::Event::Copy)));
+        if copy_requested {
+            self.copy_image();
+        }
+        if ctx.input(|i| i.key_pressed(egui::Key::F12)) {
+            self.save_screenshot();
+        }
+        let command = |key| egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, key);
+        if ctx.input_mut(|i| i.consume_shortcut(&command(egui::Key::O))) {
+            self.open_session();
+        }
+        if ctx.input_mut(|i| i.consume_shortcut(&command(egui::Key::S))) {
+            self.save_session();
+        }
+        let dropped: Vec<_> = ctx.input(|i| {
+            i.raw
+                .dropped_files
+                .iter()
+                .filter_map(|file| file.path.clone())
+                .collect()
+        });
+        for path in dropped {
+            self.open_file(&path);
+        }
+        if ctx.input(|i| !i.raw.hovered_files.is_empty()) {
+            let painter = ctx.layer_painter(egui::LayerId::new(
+                egui::Order::Foreground,
+                "drop_hint".into(),
+            ));
+            let rect = ctx.screen_rect();
+            painter.rect_filled(rect, 0.0, egui::Color32::from_black_alpha(160));
+            painter.text(
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                tr!("drop-to-open"),
+                egui::FontId::proportional(24.0),
+                egui::Color32::WHITE,
+            );
+        }
+    }
+
+    /// Starts or stops the remote control server to match the settings.
+    fn update_remote(&mut self, ctx: &egui::Context) {
+        let port = self.settings.remote_port;
+        if !self.settings.remote_enabled {
+            self.remote = None;
+        } else if self
+            .remote
+            .as_ref()
+            .is_none_or(|remote| remote.port != port)
+        {
+            self.remote = None; // release the old port first
+            match RemoteServer::start(port, ctx.clone()) {
+                Ok(server) => self.remote = Some(server),
+                Err(err) => {
+                    self.settings.remote_enabled = false;
+                    self.status = Some(tr!("status-remote-failed", error = err.to_string()));
+                }
+            }
+        }
+    }
+
+    fn remote_command(&mut self, command: &remote::Command) -> Result<serde_json::Value, String> {
+        use base64::Engine;
+        use remote::Command;
+        use serde_json::json;
+
+        let side = self.last_size[0];
+        match command {
+            Command::GetView => Ok(json!({
+                "center": self.view.center,
+                "scale": self.view.scale,
+                "max_iter": self.max_iter,
+                "palette": self.palette.name(),
+            })),
+            Command::SetView {
+                center,
+                scale,
+                max_iter,
+                palette,
+            } => {
+                if let Some(scale) = scale
+                    && !(scale.is_finite() && *scale > 0.0)
+                {
+                    return Err(format!("invalid scale {scale}"));
+                }
+                if let Some((re, im)) = center
+                    && !(re.is_finite() && im.is_finite())
+                {
+                    return Err(format!("invalid center {re} {im}"));
+                }
+                let palette = palette.as_deref().map(cli::parse_palette).transpose()?;
+                let location = Location {
+                    view: View {
+                        center: center.unwrap_or(self.view.center),
+                        scale: scale.unwrap_or(self.view.scale),
+                    },
+                    max_iter: max_iter.unwrap_or(self.max_iter).max(1),
+                };
+                self.palette = palette.unwrap_or(self.palette);
+                self.apply_location(location);
+                Ok(json!({}))
+            }
+            Command::QueryPixel { x, y } => {
+                if !(0.0..side as f64).contains(x) || !(0.0..side as f64).contains(y) {
+                    return Err(format!("pixel outside the {side}x{side} image"));
+                }
+                let (re, im) = self.view.pixel_to_complex(*x, *y, side, side);
+                let iterations = self.formula.escape_time((re, im), self.max_iter);
+                Ok(json!({ "re": re, "im": im, "iterations": iterations,
+                    "inside": iterations >= self.max_iter }))
+            }
+            Command::QueryPoint { re, im } => {
+                let iterations = self.formula.escape_time((*re, *im), self.max_iter);
+                Ok(json!({ "iterations": iterations, "inside": iterations >= self.max_iter }))
+            }
+            Command::Render { size } => {
+                // Rendered on this thread, so no bigger than a large window
+                if !(1..=REMOTE_RENDER_MAX_SIZE).contains(size) {
+                    return Err(format!(
+                        "size must be between 1 and {REMOTE_RENDER_MAX_SIZE}"
+                    ));
+                }
+                let _reservation =
+                    (self.memory).reserve(size * size * memory::EXPORT_BYTES_PER_PIXEL)?;
+                let settings = RenderSettings {
+                    width: *size,
+                    height: *size,
+                    max_iter: self.max_iter,
+                    formula: self.formula,
+                };
+                let iterations = (self.scheduler).run(Priority::View, || {
+                    self.renderer().render(&self.view, &settings)
+                });
+                let image = self.colorize(&iterations, &self.view);
+                let link = permalink::encode(&self.location(), self.palette);
+                let mut png = Vec::new();
+                export::encode_png(&mut png, &image, Some(&link)).map_err(|e| e.to_string())?;
+                Ok(json!({ "png": base64::engine::general_purpose::STANDARD.encode(png) }))
+            }
+            Command::Export { path } => {
+                // Only a file name, so clients can't write outside the folder
+                let mut components = path.components();
+                let (Some(std::path::Component::Normal(name)), None) =
+                    (components.next(), components.next())
+                else {
+                    return Err(format!(
+                        "{} is not a file name; frames are saved in the screenshot folder",
+                        path.display()
+                    ));
+                };
+                let dir = &self.settings.screenshot_dir;
+                let path = dir.join(name);
+                if path.exists() {
+                    return Err(format!("{} already exists", path.display()));
+                }
+                let link = permalink::encode(&self.location(), self.palette);
+                fs::create_dir_all(dir)
+                    .and_then(|()| export::write_png(&path, self.frame_image(), Some(&link)))
+                    .map_err(|err| err.to_string())?;
+                Ok(json!({ "path": path.display().to_string() }))
+            }
+            Command::Permalink => Ok(json!({
+                "permalink": permalink::encode(&self.location(), self.palette),
+            })),
+        }
+    }
+
+    /// File menu entries above Copy Permalink.
+    fn desktop_file_items(&mut self, ui: &mut egui::Ui) {
+        if ui
+            .add(egui::Button::new(tr!("menu-open-session")).shortcut_text("Ctrl+O"))
+            .clicked()
+        {
+            ui.close_menu();
+            self.open_session();
+        }
+        if ui
+            .add(egui::Button::new(tr!("menu-save-session")).shortcut_text("Ctrl+S"))
+            .clicked()
+        {
+            ui.close_menu();
+            self.save_session();
+        }
+        ui.separator();
+        if ui.button(tr!("menu-import-location")).clicked() {
+            ui.close_menu();
+            self.import_location();
+        }
+        if ui.button(tr!("menu-export-location")).clicked() {
+            ui.close_menu();
+            self.export_location();
+        }
+        ui.separator();
+        if ui
+            .add(egui::Button::new(tr!("menu-save-screenshot")).shortcut_text("F12"))
+            .clicked()
+        {
+            ui.close_menu();
+            self.save_screenshot();
+        }
+        if ui.button(tr!("menu-export-image")).clicked() {
+            ui.close_menu();
+            self.image_export.open = true;
+        }
+        if ui
+            .add_enabled(
+                self.coloring.is_none(),
+                egui::Button::new(tr!("menu-export-palette")),
+            )
+            .on_hover_text(tr!("menu-export-palette-hover"))
+            .clicked()
+        {
+            ui.close_menu();
+            self.export_palette();
+        }
+        if ui.button(tr!("menu-export-svg")).clicked() {
+            ui.close_menu();
+            // Lined up with screenshots, unless another size is chosen
+            self.svg_export.size = self.last_size;
+            self.svg_export.open = true;
+        }
+        if ui.button(tr!("menu-batch-render")).clicked() {
+            ui.close_menu();
+            self.batch.open = true;
+        }
+        if ui.button(tr!("menu-run-script")).clicked() {
+            ui.close_menu();
+            self.run_script();
+        }
+        if ui
+            .add(egui::Button::new(tr!("menu-copy-image")).shortcut_text("Ctrl+C"))
+            .clicked()
+        {
+            ui.close_menu();
+            self.copy_image();
+        }
+    }
+
+    /// Entries for the loaded plugins in the palette menu.
+    fn plugin_items(&mut self, ui: &mut egui::Ui) {
+        if !self.plugins.is_empty() {
+            ui.separator();
+        }
+        for (index, plugin) in self.plugins.iter().enumerate() {
+            if ui
+                .selectable_label(self.coloring == Some(index), &plugin.name)
+                .clicked()
+            {
+                self.coloring = Some(index);
+                self.needs_recolor = true;
+            }
+        }
+    }
+
+    fn session(&self) -> Session {
+        Session {
+            center: self.view.center,
+            scale: self.view.scale,
+            max_iter: self.max_iter,
+            palette: self.palette,
+            orbit: self.orbit,
+            orbit_max_iter: self.orbit_max_iter,
+            formula: self.formula,
+            bookmarks: self.bookmarks.bookmarks.clone(),
+            annotations: self.annotations.annotations.clone(),
+            traps: self.traps.traps.clone(),
+            trap_width: self.traps.width,
+            layers: self.layers.layers.clone(),
+            keyframes: self.timeline.keyframes.clone(),
+        }
+    }
+
+    fn apply_session(&mut self, session: Session) {
+        self.view = View {
+            center: session.center,
+            scale: session.scale,
+        };
+        self.max_iter = session.max_iter;
+        self.palette = session.palette;
+        self.orbit_max_iter = session.orbit_max_iter;
+        self.formula = session.formula;
+        self.set_orbit(session.orbit);
+        self.bookmarks.bookmarks = session.bookmarks;
+        self.annotations.annotations = session.annotations;
+        self.traps.traps = session.traps;
+        self.traps.width = session.trap_width;
+        self.layers.layers = session.layers;
+        self.timeline.keyframes = session.keyframes;
+        self.needs_render = true;
+    }
+
+    fn open_session(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter(tr!("filter-session"), &["ron", "json"])
+            .pick_file()
+        else {
+            return;
+        };
+        self.open_file(&path);
+    }
+
+    /// Opens a session, location, or exported PNG, going by the file extension.
+    fn open_file(&mut self, path: &Path) {
+        let extension = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_ascii_lowercase());
+        let result = match extension.as_deref() {
+            Some("ron" | "json") => session::load(path).map(|session| self.apply_session(session)),
+            Some("kfr" | "par") => {
+                location::load(path).map(|location| self.apply_location(location))
+            }
+            Some("png") => export::read_png_permalink(path)
+                .and_then(|text| permalink::decode(&text))
+                .map(|(location, palette)| {
+                    self.palette = palette;
+                    self.apply_location(location);
+                }),
+            _ => Err(invalid_data(tr!("error-unsupported-file"))),
+        };
+        self.status = Some(match result {
+            Ok(()) => tr!("status-opened", name = display_name(path)),
+            Err(err) => tr!(
+                "status-open-failed",
+                name = display_name(path),
+                error = err.to_string()
+            ),
+        });
+    }
+
+    fn save_session(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter(tr!("filter-session"), &["ron", "json"])
+            .set_file_name("session.ron")
+            .save_file()
+        else {
+            return;
+        };
+        self.status = Some(match session::save(&path, &self.session()) {
+            Ok(()) => tr!("status-saved", name = display_name(&path)),
+            Err(err) => tr!(
+                "status-save-failed",
+                name = display_name(&path),
+                error = err.to_string()
+            ),
+        });
+    }
+
+    fn import_location(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter(tr!("filter-location"), &["kfr", "par"])
+            .add_filter("Kalles Fraktaler", &["kfr"])
+            .add_filter("Fractint", &["par"])
+            .pick_file()
+        else {
+            return;
+        };
+        self.open_file(&path);
+    }
+
+    fn export_location(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Kalles Fraktaler", &["kfr"])
+            .add_filter("Fractint", &["par"])
+            .set_file_name("location.kfr")
+            .save_file()
+        else {
+            return;
+        };
+        self.status = Some(match location::save(&path, &self.location()) {
+            Ok(()) => tr!("status-exported", name = display_name(&path)),
+            Err(err) => tr!(
+                "status-export-failed",
+                name = display_name(&path),
+                error = err.to_string()
+            ),
+        });
+    }
+
+    fn export_palette(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("GIMP", &["gpl"])
+            .add_filter("Adobe Swatch Exchange", &["ase"])
+            .set_file_name(format!("{}.gpl", self.palette.name()))
+            .save_file()
+        else {
+            return;
+        };
+        self.status = Some(match palette_export::write(&path, self.palette) {
+            Ok(()) => tr!("status-exported", name = display_name(&path)),
+            Err(err) => tr!(
+                "status-export-failed",
+                name = display_name(&path),
+                error = err.to_string()
+            ),
+        });
+    }
+
+    fn save_screenshot(&mut self) {
+        let dir = &self.settings.screenshot_dir;
+        let name = export::screenshot_name(&self.view);
+        let path = export::unique_path(dir, &name);
+        let link = permalink::encode(&self.location(), self.palette);
+        let result = fs::create_dir_all(dir)
+            .and_then(|()| export::write_png(&path, self.frame_image(), Some(&link)));
+        self.status = Some(match result {
+            Ok(()) => tr!("status-screenshot-saved", path = path.display().to_string()),
+            Err(err) => tr!(
+                "status-screenshot-failed",
+                path = path.display().to_string(),
+                error = err.to_string()
+            ),
+        });
+    }
+
+    fn run_script(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter(tr!("filter-script"), &["rhai"])
+            .pick_file()
+        else {
+            return;
+        };
+        let state = script::State {
+            location: self.location(),
+            palette: self.palette,
+        };
+        let output = Rc::new(RefCell::new(None));
+        let result = script::run_file(&path, state, {
+            let output = output.clone();
+            move |text| *output.borrow_mut() = Some(text.to_owned())
+        });
+        self.status = Some(match result {
+            Ok(state) => {
+                self.palette = state.palette;
+                self.apply_location(state.location);
+                // The last line printed is the most useful summary
+                match output.take() {
+                    Some(text) => tr!(
+                        "status-script-output",
+                        name = display_name(&path),
+                        output = text
+                    ),
+                    None => tr!("status-script-ran", name = display_name(&path)),
+                }
+            }
+            Err(err) => tr!(
+                "status-script-failed",
+                name = display_name(&path),
+                error = err.to_string()
+            ),
+        });
+    }
+
+    fn clipboard(&mut self) -> Result<&mut arboard::Clipboard, arboard::Error> {
+        if self.clipboard.is_none() {
+            self.clipboard = Some(arboard::Clipboard::new()?);
+        }
+        Ok(self.clipboard.as_mut().unwrap())
+    }
+
+    fn copy_image(&mut self) {
+        let image = self.frame_image();
+        let data = arboard::ImageData {
+            width: image.width,
+            height: image.height,
+            bytes: image.as_bytes().to_vec().into(),
+        };
+        let result = self.clipboard().and_then(|c| c.set_image(data));
+        self.status = Some(match result {
+            Ok(()) => tr!("status-image-copied"),
+            Err(err) => tr!("status-copy-failed", error = err.to_string()),
+        });
+    }
+
+    fn paste_permalink(&mut self) {
+        let text = match self.clipboard().and_then(|c| c.get_text()) {
+            Ok(text) => text,
+            Err(err) => {
+                self.status = Some(tr!("status-clipboard-failed", error = err.to_string()));
+                return;
+            }
+        };
+        match permalink::decode(&text) {
+            Ok((location, palette)) => {
+                self.palette = palette;
+                self.apply_location(location);
+                self.status = Some(tr!("status-permalink-opened"));
+            }
+            Err(err) => {
+                self.status = Some(tr!("status-permalink-failed", error = err.to_string()));
+            }
+        }
+    }
+
+    fn settings_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.settings_open;
+        egui::Window::new(tr!("settings-title"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let label = ui.label(tr!("settings-language"));
+                    egui::ComboBox::from_id_salt("language")
+                        .selected_text(self.settings.language.name())
+                        .show_ui(ui, |ui| self.language_items(ui))
+                        .response
+                        .labelled_by(label.id);
+                });
+                ui.horizontal(|ui| {
+                    let label = ui.label(tr!("settings-screenshot-folder"));
+                    let mut dir = self.settings.screenshot_dir.display().to_string();
+                    if ui
+                        .text_edit_singleline(&mut dir)
+                        .labelled_by(label.id)
+                        .changed()
+                    {
+                        self.settings.screenshot_dir = dir.into();
+                    }
+                    if ui.button(tr!("button-browse")).clicked()
+                        && let Some(dir) = rfd::FileDialog::new()
+                            .set_directory(&self.settings.screenshot_dir)
+                            .pick_folder()
+                    {
+                        self.settings.screenshot_dir = dir;
+                    }
+                });
+                ui.horizontal(|ui| {
+                    let label = ui.label(tr!("settings-plugin-folder"));
+                    let mut dir = self.settings.plugin_dir.display().to_string();
+                    if ui
+                        .text_edit_singleline(&mut dir)
+                        .labelled_by(label.id)
+                        .changed()
+                    {
+                        self.settings.plugin_dir = dir.into();
+                    }
+                    if ui.button(tr!("button-browse")).clicked()
+                        && let Some(dir) = rfd::FileDialog::new()
+                            .set_directory(&self.settings.plugin_dir)
+                            .pick_folder()
+                    {
+                        self.settings.plugin_dir = dir;
+                    }
+                });
+                ui.label(tr!("settings-plugins-note"));
+                ui.separator();
+                ui.horizontal(|ui| {
+                    let label =
+                        ui.checkbox(&mut self.settings.remote_enabled, tr!("settings-remote"));
+                    ui.add(
+                        egui::DragValue::new(&mut self.settings.remote_port).range(1024..=65535),
+                    )
+                    .labelled_by(label.id);
+                })
+                .response
+                .on_hover_text(tr!("settings-remote-hover"));
+                ui.horizontal(|ui| {
+                    let label = ui.label(tr!("settings-cache"));
+                    ui.add(
+                        egui::DragValue::new(&mut self.settings.cache_mb)
+                            .range(0..=65536)
+                            .suffix(" MB"),
+                    )
+                    .labelled_by(label.id);
+                })
+                .response
+                .on_hover_text(tr!("settings-cache-hover"));
+                ui.horizontal(|ui| {
+                    let label = ui.label(tr!("settings-memory-cap"));
+                    ui.add(
+                        egui::DragValue::new(&mut self.settings.memory_cap_mb)
+                            .range(64..=262144)
+                            .suffix(" MB"),
+                    )
+                    .labelled_by(label.id);
+                })
+                .response
+                .on_hover_text(tr!("settings-memory-cap-hover"));
+                ui.horizontal(|ui| {
+                    let mut automatic = self.settings.threads.is_none();
+                    let cores = ParallelRenderer::default().threads;
+                    let label =
+                        ui.checkbox(&mut automatic, tr!("settings-threads", cores = cores.get()));
+                    let mut changed = label.changed();
+                    let mut threads = self.settings.threads.unwrap_or(cores);
+                    if !automatic {
+                        changed |= ui
+                            .add(egui::DragValue::new(&mut threads).range(1..=1024))
+                            .labelled_by(label.id)
+                            .changed();
+                    }
+                    if changed {
+                        self.settings.threads = (!automatic).then_some(threads);
+                        self.parallel = self.settings.parallel_renderer();
+                        self.fast = self.settings.fast_renderer();
+                    }
+                });
+                ui.checkbox(
+                    &mut self.settings.low_priority_exports,
+                    tr!("settings-low-priority"),
+                )
+                .on_hover_text(tr!("settings-low-priority-hover"));
+                ui.separator();
+                ui.horizontal(|ui| {
+                    let label = ui.label(tr!("settings-zoom-step"));
+                    ui.add(
+                        egui::DragValue::new(&mut self.settings.zoom_step)
+                            .range(1.01..=4.0)
+                            .speed(0.01)
+                            .prefix("×"),
+                    )
+                    .labelled_by(label.id);
+                })
+                .response
+                .on_hover_text(tr!("settings-zoom-step-hover"));
+                ui.checkbox(
+                    &mut self.settings.invert_wheel,
+                    tr!("settings-invert-wheel"),
+                );
+                ui.checkbox(
+                    &mut self.settings.zoom_about_cursor,
+                    tr!("settings-zoom-about-cursor"),
+                )
+                .on_hover_text(tr!("settings-zoom-about-cursor-hover"));
+                ui.checkbox(&mut self.settings.smooth_zoom, tr!("settings-smooth-zoom"))
+                    .on_hover_text(tr!("settings-smooth-zoom-hover"));
+            });
+        self.settings_open = open;
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl MandelbrotApp {
+    /// Opens the permalink in the page URL's fragment (`…#<permalink>`), so
+    /// an embedding page can link to a location.
+    fn open_page_permalink(&mut self) {
+        let Some(hash) = web_sys::window().and_then(|window| window.location().hash().ok()) else {
+            return;
+        };
+        let link = hash.trim_start_matches('#');
+        if link.is_empty() {
+            return;
+        }
+        match permalink::decode(link) {
+            Ok((location, palette)) => {
+                self.palette = palette;
+                self.apply_location(location);
+            }
+            Err(err) => {
+                self.status = Some(tr!("status-permalink-failed", error = err.to_string()));
+            }
         }
     }
 }
 
 impl App for MandelbrotApp {
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, settings::STORAGE_KEY, &self.settings);
+    }
+
+    fn on_exit(&mut self, gl: Option<&eframe::glow::Context>) {
+        #[cfg(not(target_arch = "wasm32"))]
+        self.autosave.clean_exit();
+        if let (Some(screen), Some(gl)) = (&self.screen, gl) {
+            screen.lock().unwrap().destroy(gl);
+        }
+    }
+
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        #[cfg(not(target_arch = "wasm32"))]
+        self.handle_desktop_input(ctx);
+        self.check_gpu();
+        self.update_autopilot(ctx);
+        self.update_transition(ctx);
+        let interacting = ctx.input(|i| {
+            i.pointer.any_down()
+                || (i.events.iter()).any(|event| matches!(event, egui::Event::MouseWheel { .. }))
+        });
+        if interacting {
+            self.last_interaction = Some(Instant::now());
+        }
+        if interacting || self.autopilot.is_some() {
+            self.scheduler.interacting();
+        }
+        let traps = (self.traps.traps.clone(), self.traps.width);
+        let layers = self.layers.layers.clone();
+        egui::TopBottomPanel::top("menu_bar").show(ctx, |ui| self.menu_bar(ui));
+        egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| self.status_bar(ui));
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.settings_window(ctx);
+            self.batch.show(
+                ctx,
+                self.palette,
+                self.max_iter,
+                &self.settings,
+                &self.scheduler,
+                &self.memory,
+            );
+            self.npy_export.show(
+                ctx,
+                self.iterations.as_ref(),
+                &self.view,
+                self.formula,
+                &self.settings,
+                &self.scheduler,
+                &self.memory,
+            );
+            self.image_export.show(
+                ctx,
+                self.location(),
+                self.formula,
+                self.palette,
+                &self.settings,
+                &self.scheduler,
+                &self.memory,
+            );
+            self.svg_export.show(
+                ctx,
+                &self.view,
+                self.formula,
+                svg_export::Layers {
+                    path: &self.last_path,
+                    other_path: &self.other_path,
+                    annotations: &self.annotations.annotations,
+                },
+            );
+        }
+        self.area.show(ctx, &self.view, self.max_iter);
+        self.histogram.show(ctx, self.iterations.as_deref());
+        self.compare
+            .show(ctx, self.iterations.as_deref(), &self.view);
+        self.dimension
+            .show(ctx, &self.parallel, &self.view, self.max_iter, self.formula);
+        let frame_settings = self.iterations.as_deref().map(|frame| RenderSettings {
+            width: frame.width,
+            height: frame.height,
+            max_iter: frame.max_iter,
+            formula: self.formula,
+        });
+        self.telemetry
+            .show(ctx, &self.parallel, &self.view, frame_settings);
+        self.ab.show(ctx, self.ab_setup());
+        if let Some(view) = self.bookmarks.show(ctx, &self.view) {
+            self.move_view(view, JUMP_TRANSITION_SECONDS, Easing::InOut);
+        }
+        self.annotations.show(ctx, self.view.center);
+        self.traps.show(ctx);
+        self.layers.show(ctx, self.palette);
+        self.sphere.show(
+            ctx,
+            &self.parallel,
+            self.formula,
+            self.max_iter,
+            self.palette,
+            self.orbit,
+        );
+        self.height_map.show(
+            ctx,
+            &self.view,
+            self.formula,
+            self.max_iter,
+            self.palette,
+            &self.settings,
+            &self.scheduler,
+        );
+        if let Some(c) = self.julia_morph.show(
+            ctx,
+            &self.parallel,
+            self.max_iter,
+            self.palette,
+            self.formula,
+            self.orbit,
+            &self.last_path,
+            &self.settings,
+            &self.scheduler,
+        ) {
+            self.set_orbit(Some(c));
+        }
+        let current = Keyframe {
+            time: 0.0,
+            view: self.view,
+            max_iter: self.max_iter,
+            formula: self.formula,
+        };
+        self.recorder.record(current);
+        if let Some(frame) =
+            (self.timeline).show(ctx, current, self.palette, &self.settings, &self.scheduler)
+        {
+            self.apply_keyframe(frame);
+        }
+        if let Some(frame) = self.tour.show(ctx) {
+            self.apply_keyframe(frame);
+        }
+        if let Some(step) = (self.recorder).show(ctx, self.palette, &self.settings, &self.scheduler)
+        {
+            self.apply_keyframe(step);
+        }
+        self.update_memory();
+        let caches = [(tr!("diagnostics-frames"), self.frames.stats())];
+        let memory = self.memory.usage(caches[0].1.bytes);
+        self.diagnostics.show(ctx, &caches, memory);
+        if let (Some(stats), Some(c)) = (&self.orbit_stats, self.orbit) {
+            let pixel_size = self.view.pixel_size(self.last_size[0]);
+            let (max_iter, real_dynamics) = (self.orbit_limit(), &mut self.real_dynamics);
+            let period_highlight = &mut self.period_highlight;
+            let formula = self.formula;
+            let own_window = self.settings.orbit_window;
+            let orbit_window = &mut self.settings.orbit_window;
+            let mut contents = |ui: &mut egui::Ui| {
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.heading(tr!("orbit-title"));
+                        let (label, hover) = if *orbit_window {
+                            (tr!("orbit-dock"), tr!("orbit-dock-hover"))
+                        } else {
+                            (tr!("orbit-pop-out"), tr!("orbit-pop-out-hover"))
+                        };
+                        if ui.small_button(label).on_hover_text(hover).clicked() {
+                            *orbit_window = !*orbit_window;
+                        }
+                    });
+                    stats.show(ui);
+                    period_highlight.show(ui, stats.period());
+                    if formula == Formula::Mandelbrot {
+                        real_dynamics.show(ui, c, pixel_size, max_iter);
+                    }
+                });
+            };
+            if own_window {
+                let viewport = egui::ViewportBuilder::default()
+                    .with_title(tr!("orbit-title"))
+                    .with_inner_size([360.0, 640.0]);
+                let closed = ctx.show_viewport_immediate(
+                    egui::ViewportId::from_hash_of("orbit"),
+                    viewport,
+                    |ctx, class| {
+                        if class == egui::ViewportClass::Embedded {
+                            // Without support for more windows, as on the web
+                            egui::Window::new(tr!("orbit-title")).show(ctx, |ui| contents(ui));
+                            false
+                        } else {
+                            egui::CentralPanel::default().show(ctx, |ui| contents(ui));
+                            ctx.input(|i| i.viewport().close_requested())
+                        }
+                    },
+                );
+                if closed {
+                    // Closing the window docks the panel again
+                    self.settings.orbit_window = false;
+                }
+            } else {
+                egui::SidePanel::right("analysis").show(ctx, contents);
+            }
+        }
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.heading("Mandelbrot Explorer");
             ui.group(|ui| {
-                ui.label("Click to show a path");
-                ui.label("Scroll wheel to zoom");
-            }).response.on_hover_text("The Mandelbrot set is computed by iterating the equation z = z^2 + c, where c is the complex coordinate for each pixel. Points that do not escape to infinity after many iterations are part of the set and are colored black; others are colored based on how quickly they escape.\n\nWhen you click, the yellow path shows the sequence of complex values z as it is iterated for the selected point. If the path escapes the circle of radius 2, the point is not in the Mandelbrot set.");
+                ui.label(tr!("help-click"));
+                ui.label(tr!("help-snap"));
+                ui.label(tr!("help-scroll"));
+            })
+            .response
+            .on_hover_text(tr!("help-hover"));
+            self.view_controls(ui);
             let available = ui.available_size();
             let side = available.x.min(available.y).max(100.0).round() as usize;
             let size = [side, side];
-            let mut zoomed = false;
-            let image_size = egui::Vec2::new(side as f32, side as f32);
-            let offset_x = (available.x - image_size.x) / 2.0;
-            let offset_y = (available.y - image_size.y) / 2.0;
-            let image_rect = egui::Rect::from_min_size(
-                egui::pos2(offset_x.max(0.0), offset_y.max(0.0)),
-                image_size,
-            );
-            let hover_pos = ctx.input(|i| i.pointer.hover_pos());
-            // Handle zoom (mouse wheel) only if hovered
-            if let Some(hover_pos) = hover_pos {
-                if image_rect.contains(hover_pos) {
-                    let zoom_event = ui.input(|i| {
-                        i.events.iter().find_map(|e| match e {
-                            egui::Event::MouseWheel { delta, .. } => Some(delta.y),
-                            _ => None,
-                        })
-                    });
-                    if let Some(scroll) = zoom_event {
-                        if scroll.abs() > 0.0 {
-                            // Mandelbrot coordinate under mouse before zoom
-                            let px = (hover_pos.x - offset_x.max(0.0)).clamp(0.0, side as f32 - 1.0)
-                                as usize;
-                            let py = (hover_pos.y - offset_y.max(0.0)).clamp(0.0, side as f32 - 1.0)
-                                as usize;
-                            let (cx, cy) =
-                                pixel_to_mandelbrot(px, py, side, side, self.center, self.scale);
-                            // Zoom factor
-                            let zoom_factor = if scroll > 0.0 { 0.8 } else { 1.25 };
-                            let new_scale = self.scale * zoom_factor;
-                            // After zoom, what center keeps (cx, cy) under the mouse?
-                            let (new_center_x, new_center_y) = {
-                                let fx = px as f64 / side as f64;
-                                let fy = py as f64 / side as f64;
-                                let new_center_x = cx - (fx - 0.5) * new_scale;
-                                let new_center_y = cy - (fy - 0.5) * new_scale;
-                                (new_center_x, new_center_y)
-                            };
-                            self.center = (new_center_x, new_center_y);
-                            self.scale = new_scale;
-                            zoomed = true;
-                        }
+            // While the user is zooming, the live shader shows the view and the
+            // render waits until they stop
+            let physical_side = (side as f32 * ui.ctx().pixels_per_point()) as usize;
+            let live_shader = self.live_shader(physical_side);
+            let deferred = live_shader.is_some()
+                && size == self.last_size
+                && (self.last_interaction).is_some_and(|time| time.elapsed() < IDLE_DELAY);
+            if deferred && self.needs_render {
+                ui.ctx().request_repaint_after(IDLE_DELAY);
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            self.poll_progressive(ui.ctx());
+            self.step_cooperative(ui.ctx());
+            let quality = self.quality();
+            if self.settings.quality == QualityMode::Auto && quality == Quality::Draft {
+                // Rendered again in High once the user stops
+                ui.ctx().request_repaint_after(IDLE_DELAY);
+            }
+            // Re-render before laying out the image so it gets the new size
+            if (size != self.last_size || self.needs_render || quality != self.frame_quality)
+                && !deferred
+                && !self.render_goes_on_while_held()
+            {
+                let scaled_side = (side as f64 * quality.resolution_scale()).round() as usize;
+                let render_side = scaled_side.max(1) * quality.supersampling();
+                let settings = RenderSettings {
+                    width: render_side,
+                    height: render_side,
+                    max_iter: quality.max_iter(self.max_iter),
+                    formula: self.formula,
+                };
+                self.frame_quality = quality;
+                let started = Instant::now();
+                let cooperative = self.cooperative_wanted();
+                let progressive =
+                    !cooperative && self.progressive_wanted(render_side * render_side);
+                // Progressive and cooperative renders compute what the
+                // parallel renderer does
+                let renderer = if progressive || cooperative {
+                    self.parallel.name()
+                } else {
+                    self.renderer().name()
+                };
+                let key = (
+                    [
+                        self.view.center.0.to_bits(),
+                        self.view.center.1.to_bits(),
+                        self.view.scale.to_bits(),
+                    ],
+                    settings,
+                    renderer,
+                );
+                // A render of an outdated view is cancelled
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    self.progressive = None;
+                }
+                self.cooperative = None;
+                match self.frames.get(&key).cloned() {
+                    None if cooperative => {
+                        let render = CooperativeRender::new(self.view, settings);
+                        self.cooperative = Some((render, key, started));
+                        // Started now, so a render that fits in the budget
+                        // shows in this frame
+                        self.step_cooperative(ui.ctx());
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    None if progressive => {
+                        let render = ProgressiveRender::start(
+                            ui.ctx(),
+                            self.view,
+                            settings,
+                            self.parallel,
+                            self.scheduler.clone(),
+                        );
+                        self.progressive = Some((render, key, started));
+                    }
+                    cached => {
+                        let iterations = match cached {
+                            Some(iterations) => iterations,
+                            None => {
+                                let iterations = self.scheduler.run(Priority::View, || {
+                                    Arc::new(self.renderer().render(&self.view, &settings))
+                                });
+                                self.last_render_time = started
+                                    .elapsed()
+                                    .div_f64((render_side * render_side) as f64);
+                                self.frames.insert(key, iterations.clone());
+                                iterations
+                            }
+                        };
+                        info!(
+                            renderer,
+                            side,
+                            max_iter = self.max_iter,
+                            ms = started.elapsed().as_secs_f64() * 1000.0,
+                            "rendered view"
+                        );
+                        self.show_frame(ui.ctx(), iterations);
                     }
                 }
-            }
-            // Re-render if size changed or zoomed
-            if size != self.last_size || zoomed {
-                let image = render_mandelbrot(side, side, self.center, self.scale);
-                self.mandelbrot_texture
-                    .set(image, egui::TextureOptions::default());
                 self.last_size = size;
+                self.needs_render = false;
+            } else if self.needs_recolor
+                && let Some(iterations) = self.iterations.clone()
+            {
+                let started = Instant::now();
+                self.color_frame(&iterations);
+                // A shares the interior coloring and overlays
+                self.ab.invalidate(ui.ctx());
+                debug!(
+                    ms = started.elapsed().as_secs_f64() * 1000.0,
+                    "recolored view"
+                );
+            }
+            self.needs_recolor = false;
+            if let Some(setup) = self.ab.pending()
+                && let Some(image) = self.render_ab(&setup)
+            {
+                self.ab.set_image(ui.ctx(), &image);
             }
             let image_size = egui::Vec2::new(side as f32, side as f32);
             let offset_x = (available.x - image_size.x) / 2.0;
@@ -96,163 +3032,502 @@ impl App for MandelbrotApp {
             ui.add_space(offset_y.max(0.0));
             ui.horizontal_centered(|ui| {
                 ui.add_space(offset_x.max(0.0));
-                let image_response = ui
-                    .image(&self.mandelbrot_texture)
-                    .interact(egui::Sense::click_and_drag());
+                let frame_size = egui::vec2(self.last_size[0] as f32, self.last_size[1] as f32);
+                let (image_rect, image_response) =
+                    ui.allocate_exact_size(frame_size, egui::Sense::click_and_drag());
+                let palette = self.palette;
+                let callback = match (live_shader, self.gpu_coloring(), &self.iterations) {
+                    (Some(screen), _, _) if deferred => {
+                        let (view, max_iter) = (self.view, quality.max_iter(self.max_iter));
+                        Some(egui_glow::CallbackFn::new(move |info, painter| {
+                            let mut screen = screen.lock().unwrap();
+                            screen.paint_view(painter.gl(), &info, &view, max_iter, palette);
+                        }))
+                    }
+                    (_, Some(screen), Some(iterations)) => {
+                        let iterations = iterations.clone();
+                        Some(egui_glow::CallbackFn::new(move |info, painter| {
+                            let mut screen = screen.lock().unwrap();
+                            screen.paint_iterations(painter.gl(), &info, &iterations, palette);
+                        }))
+                    }
+                    _ => None,
+                };
+                match callback {
+                    Some(callback) => {
+                        ui.painter().add(egui::PaintCallback {
+                            rect: image_rect,
+                            callback: Arc::new(callback),
+                        });
+                    }
+                    None => {
+                        let uv =
+                            egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+                        ui.painter().image(
+                            self.mandelbrot_texture.id(),
+                            image_rect,
+                            uv,
+                            egui::Color32::WHITE,
+                        );
+                    }
+                }
+                self.compare.draw(ui.painter(), image_rect);
+                self.telemetry.draw(ui.painter(), image_rect, &self.view);
+                self.ab.draw(ui, image_rect);
+                if let Some(stats) = &self.frame_stats {
+                    let max_iter = self.frame_quality.max_iter(self.max_iter);
+                    draw_frame_stats(ui, image_rect, stats, max_iter);
+                }
+                let period = self.orbit_stats.as_ref().and_then(OrbitStats::period);
+                let highlights = [
+                    self.histogram.highlight().map(egui::TextureHandle::id),
+                    (self.period_highlight)
+                        .texture(ui.ctx(), period, self.iterations.as_deref(), &self.view)
+                        .map(egui::TextureHandle::id),
+                ];
+                for highlight in highlights.into_iter().flatten() {
+                    let uv = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+                    ui.painter()
+                        .image(highlight, image_rect, uv, egui::Color32::WHITE);
+                }
+                let to_pixel = |pos: egui::Pos2| -> (f64, f64) {
+                    let px = (pos.x - image_rect.left()).clamp(0.0, side as f32 - 1.0) as usize;
+                    let py = (pos.y - image_rect.top()).clamp(0.0, side as f32 - 1.0) as usize;
+                    (px as f64, py as f64)
+                };
+                image_response.widget_info(|| {
+                    egui::WidgetInfo::labeled(
+                        egui::WidgetType::Image,
+                        true,
+                        tr!(
+                            "view-description",
+                            re = format!("{:.6}", self.view.center.0),
+                            im = format!("{:+.6}", self.view.center.1),
+                            width = format!("{:.3e}", self.view.scale),
+                        ),
+                    )
+                });
+                // The keyboard moves the view when it has focus, or when
+                // nothing does
+                if image_response.has_focus() {
+                    ui.memory_mut(|memory| {
+                        let filter = egui::EventFilter {
+                            horizontal_arrows: true,
+                            vertical_arrows: true,
+                            ..Default::default()
+                        };
+                        memory.set_focus_lock_filter(image_response.id, filter);
+                    });
+                }
+                let keyboard =
+                    image_response.has_focus() || ui.memory(|memory| memory.focused().is_none());
+                if keyboard {
+                    self.navigate_by_keys(ui.ctx());
+                }
+                // Handle zoom (mouse wheel) only if hovered
+                if let Some(hover_pos) = image_response.hover_pos() {
+                    // Touchpads send several events a frame, which add up
+                    let notches: f32 = ui.input(|i| {
+                        (i.events.iter())
+                            .map(|e| match e {
+                                egui::Event::MouseWheel {
+                                    unit: egui::MouseWheelUnit::Point,
+                                    delta,
+                                    ..
+                                } => delta.y / WHEEL_POINTS_PER_NOTCH,
+                                egui::Event::MouseWheel { delta, .. } => delta.y,
+                                _ => 0.0,
+                            })
+                            .sum()
+                    });
+                    if notches != 0.0 {
+                        self.zoom_by_wheel(to_pixel(hover_pos), side, notches);
+                        ui.ctx().request_repaint();
+                    }
+                }
                 // Handle click or drag
                 let pointer_pos = if image_response.dragged() || image_response.clicked() {
                     image_response.interact_pointer_pos()
                 } else {
                     None
                 };
-                if let Some(pos) = pointer_pos {
-                    let px = (pos.x - offset_x.max(0.0)).clamp(0.0, side as f32 - 1.0) as usize;
-                    let py = (pos.y - offset_y.max(0.0)).clamp(0.0, side as f32 - 1.0) as usize;
-                    let path = mandelbrot_path(px, py, side, side, self.center, self.scale);
-                    self.last_click = Some((px, py));
-                    self.last_path = path;
+                let to_complex = |pos: egui::Pos2| {
+                    let offset = pos - image_rect.min;
+                    (self.view).pixel_to_complex(offset.x as f64, offset.y as f64, side, side)
+                };
+                let to_screen = |(re, im): (f64, f64)| {
+                    let (x, y) = self.view.complex_to_pixel(re, im, side, side);
+                    image_rect.min + egui::vec2(x as f32, y as f32)
+                };
+                let mut hold_zoom_pointer = false;
+                if self.annotations.is_placing() {
+                    self.annotations.place(&image_response, to_complex);
+                } else if self.traps.interact(&image_response, to_complex, to_screen) {
+                    // Placing a trap or moving one
+                } else if (self.julia_morph).interact(&image_response, to_complex, to_screen) {
+                    // Drawing the path of a Julia morph
+                } else if self.measure.enabled {
+                    self.measure.drag(&image_response, |pos| {
+                        let offset = pos - image_rect.min;
+                        (self.view).pixel_to_complex(offset.x as f64, offset.y as f64, side, side)
+                    });
+                } else if self.settings.hold_zoom {
+                    // Holding the button zooms instead of selecting points
+                    hold_zoom_pointer = true;
+                } else if let Some(pos) = pointer_pos {
+                    let (px, py) = to_pixel(pos);
+                    let c = self.view.pixel_to_complex(px, py, side, side);
+                    let snap = ui.input(|i| i.modifiers.command);
+                    if image_response.clicked() && snap && self.formula == Formula::Mandelbrot {
+                        self.snap_orbit(c);
+                    } else {
+                        self.set_orbit(Some(c));
+                    }
+                }
+                self.holding_zoom = false;
+                if self.settings.hold_zoom {
+                    let pointer = image_response.hover_pos().map(to_pixel);
+                    let held = ui.input(|i| {
+                        let button = hold_zoom_pointer
+                            && image_response.is_pointer_button_down_on()
+                            && i.pointer.primary_down();
+                        let keys = keyboard && i.modifiers.is_none();
+                        if (button && !i.modifiers.shift) || (keys && i.key_down(egui::Key::Z)) {
+                            1.0
+                        } else if (button && i.modifiers.shift)
+                            || (keys && i.key_down(egui::Key::X))
+                        {
+                            -1.0
+                        } else {
+                            0.0
+                        }
+                    });
+                    if held != 0.0 {
+                        self.holding_zoom = true;
+                        self.zoom_while_held(ui.ctx(), pointer, side, held);
+                    }
                 }
                 // Draw the path if available
                 if !self.last_path.is_empty() {
                     let painter = ui.painter();
                     let to_screen = |zx: f64, zy: f64| -> egui::Pos2 {
-                        let (fx, fy) =
-                            mandelbrot_to_pixel(zx, zy, side, side, self.center, self.scale);
-                        egui::pos2(
-                            image_response.rect.left() + fx,
-                            image_response.rect.top() + fy,
-                        )
+                        let (fx, fy) = self.view.complex_to_pixel(zx, zy, side, side);
+                        image_rect.min + egui::vec2(fx as f32, fy as f32)
                     };
-                    for w in self.last_path.windows(2) {
-                        let p0 = to_screen(w[0].0, w[0].1);
-                        let p1 = to_screen(w[1].0, w[1].1);
-                        painter
-                            .line_segment([p0, p1], egui::Stroke::new(2.0, egui::Color32::YELLOW));
+                    if !self.other_path.is_empty() {
+                        let points = (self.other_path.iter())
+                            .map(|&(zx, zy)| to_screen(zx, zy))
+                            .collect();
+                        painter.add(egui::Shape::line(
+                            points,
+                            egui::Stroke::new(2.0, OTHER_PATH_COLOR),
+                        ));
                     }
+                    if self.ghost_trail.enabled {
+                        (self.ghost_trail)
+                            .draw(painter, &self.last_path, |(zx, zy)| to_screen(zx, zy));
+                    } else {
+                        // One shape per part, as paths can have many thousands
+                        // of points: the transient, then the cycle
+                        let points: Vec<_> = (self.last_path.iter())
+                            .map(|&(zx, zy)| to_screen(zx, zy))
+                            .collect();
+                        let cycle = (self.orbit_stats.as_ref())
+                            .and_then(OrbitStats::preperiodic)
+                            .map_or(points.len(), |found| found.preperiod);
+                        painter.add(egui::Shape::line(
+                            points[..(cycle + 1).min(points.len())].to_vec(),
+                            egui::Stroke::new(2.0, egui::Color32::YELLOW),
+                        ));
+                        if cycle < points.len() {
+                            painter.add(egui::Shape::line(
+                                points[cycle..].to_vec(),
+                                egui::Stroke::new(2.0, CYCLE_PATH_COLOR),
+                            ));
+                        }
+                    }
+                    // Values to about the precision of a pixel, of the first
+                    // few points and the one under the cursor
+                    let digits = (-self.view.pixel_size(side).log10())
+                        .ceil()
+                        .clamp(1.0, 16.0);
+                    let painter = painter.with_clip_rect(image_rect);
+                    let labelled = if self.orbit_values {
+                        ORBIT_VALUE_LABELS
+                    } else {
+                        0
+                    };
+                    let hovered = image_response.hover_pos().and_then(|pointer| {
+                        (self.last_path.iter().enumerate())
+                            .map(|(n, &(zx, zy))| (n, to_screen(zx, zy).distance(pointer)))
+                            .filter(|&(_, distance)| distance <= ORBIT_HOVER_RADIUS)
+                            .min_by(|a, b| a.1.total_cmp(&b.1))
+                            .map(|(n, _)| n)
+                    });
+                    // The hovered point last, so its label is on top
+                    let shown = (0..labelled.min(self.last_path.len()))
+                        .filter(|&n| Some(n) != hovered)
+                        .chain(hovered);
+                    for n in shown {
+                        let z = self.last_path[n];
+                        let pos = to_screen(z.0, z.1);
+                        let highlight = Some(n) == hovered;
+                        draw_orbit_value(&painter, pos, n, z, digits as usize, highlight);
+                    }
+                }
+                self.annotations.draw(
+                    &ui.painter().with_clip_rect(image_rect),
+                    image_rect,
+                    &self.view,
+                    image_response.interact_pointer_pos(),
+                );
+                self.traps.draw(
+                    &ui.painter().with_clip_rect(image_rect),
+                    image_rect,
+                    &self.view,
+                    image_response.interact_pointer_pos(),
+                );
+                self.julia_morph.draw(
+                    &ui.painter().with_clip_rect(image_rect),
+                    image_rect,
+                    &self.view,
+                );
+                (self.measure).draw(
+                    &ui.painter().with_clip_rect(image_rect),
+                    image_rect,
+                    &self.view,
+                );
+                let hovered = image_response.hover_pos().map(|pos| {
+                    let (px, py) = to_pixel(pos);
+                    self.view.pixel_to_complex(px, py, side, side)
+                });
+                // Distances are only estimated for z → z² + c
+                let hovered = hovered.filter(|_| self.formula == Formula::Mandelbrot);
+                self.cursor.update(hovered, self.max_iter);
+                (self.cursor).draw(
+                    &ui.painter().with_clip_rect(image_rect),
+                    image_rect,
+                    &self.view,
+                );
+                self.show_inset(ui, image_rect);
+                if image_response.secondary_clicked()
+                    && let Some(pos) = image_response.interact_pointer_pos()
+                {
+                    let (px, py) = to_pixel(pos);
+                    self.context_point = Some(self.view.pixel_to_complex(px, py, side, side));
+                }
+                image_response.context_menu(|ui| {
+                    if ui.button(tr!("context-pin-inset")).clicked() {
+                        ui.close_menu();
+                        if let Some(c) = self.context_point {
+                            let zoom = self
+                                .inset
+                                .as_ref()
+                                .map_or(self.inset_zoom, |inset| inset.zoom);
+                            self.inset = Some(Inset::new(&self.view, c, zoom));
+                        }
+                    }
+                    if ui
+                        .add_enabled(
+                            self.formula == Formula::Mandelbrot,
+                            egui::Button::new(tr!("context-component")),
+                        )
+                        .on_hover_text(tr!("context-component-hover"))
+                        .clicked()
+                    {
+                        ui.close_menu();
+                        if let Some(c) = self.context_point {
+                            self.identify_component(c);
+                        }
+                    }
+                    if self.inset.is_some() && ui.button(tr!("menu-remove-inset")).clicked() {
+                        ui.close_menu();
+                        self.inset = None;
+                    }
+                });
+                if self.magnifier.enabled
+                    && let Some(pos) = image_response.hover_pos()
+                {
+                    self.show_magnifier(ui, pos - image_rect.min, pos, side);
                 }
             });
         });
+        if (&self.traps.traps, self.traps.width) != (&traps.0, traps.1)
+            || self.layers.layers != layers
+        {
+            self.needs_recolor = true;
+            ctx.request_repaint();
+        }
     }
 }
 
-fn render_mandelbrot(
-    width: usize,
-    height: usize,
-    center: (f64, f64),
-    scale: f64,
-) -> egui::ColorImage {
-    let mut pixels = Vec::with_capacity(width * height);
-    let max_iter = 100;
-    for y in 0..height {
-        for x in 0..width {
-            let (cx, cy) = pixel_to_mandelbrot(x, y, width, height, center, scale);
-            let mut zx = 0.0;
-            let mut zy = 0.0;
-            let mut iter = 0;
-            while zx * zx + zy * zy < 4.0 && iter < max_iter {
-                let tmp = zx * zx - zy * zy + cx;
-                zy = 2.0 * zx * zy + cy;
-                zx = tmp;
-                iter += 1;
-            }
-            let color = if iter == max_iter {
-                egui::Color32::BLACK
-            } else {
-                // Map t to hue (0..360) for a rainbow spectrum
-                let t = 1.0 - (iter as f32 / max_iter as f32);
-                let hue = t * 360.0;
-                let (r, g, b) = hsv_to_rgb(hue, 1.0, 1.0);
-                egui::Color32::from_rgb(r, g, b)
-            };
-            pixels.push(color);
-        }
+/// Draws `stats` in a box in the top-left corner of the image.
+fn draw_frame_stats(ui: &egui::Ui, image_rect: egui::Rect, stats: &FrameStats, max_iter: usize) {
+    let percent = |n: usize| 100.0 * n as f64 / stats.pixels.max(1) as f64;
+    let mut text = tr!(
+        "frame-stats",
+        interior = format!("{:.1}", percent(stats.interior)),
+        at_limit = format!("{:.1}", percent(stats.at_limit)),
+    );
+    if let Some((min, median, max)) = stats.escaped {
+        text += "\n";
+        text += &tr!(
+            "frame-stats-escape-time",
+            min = min,
+            median = median,
+            max = max
+        );
     }
-    egui::ColorImage {
-        size: [width, height],
-        pixels,
+    // Pixels at the limit without a cycle may escape with more iterations
+    let unresolved = percent(stats.at_limit - stats.interior);
+    if unresolved >= 1.0 {
+        text += "\n";
+        text += &tr!(
+            "frame-stats-unresolved",
+            percent = format!("{unresolved:.1}"),
+            max_iter = max_iter,
+        );
     }
+    let painter = ui.painter();
+    let galley = painter.layout_no_wrap(text, egui::FontId::monospace(12.0), egui::Color32::WHITE);
+    let pos = image_rect.min + egui::vec2(8.0, 8.0);
+    let background = egui::Rect::from_min_size(pos, galley.size()).expand(4.0);
+    painter.rect_filled(background, 4.0, egui::Color32::from_black_alpha(160));
+    painter.galley(pos, galley, egui::Color32::WHITE);
+}
+
+/// Marks point `n` of the path, at `pos`, and labels it with its value `z`
+/// to `digits` decimal places, brighter if it's `highlight`ed.
+fn draw_orbit_value(
+    painter: &egui::Painter,
+    pos: egui::Pos2,
+    n: usize,
+    (re, im): (f64, f64),
+    digits: usize,
+    highlight: bool,
+) {
+    let text = tr!(
+        "orbit-value",
+        n = n,
+        re = format!("{re:.digits$}"),
+        im = format!("{im:+.digits$}"),
+    );
+    let color = if highlight {
+        egui::Color32::WHITE
+    } else {
+        egui::Color32::YELLOW
+    };
+    painter.circle_filled(pos, if highlight { 4.0 } else { 3.0 }, color);
+    let galley = painter.layout_no_wrap(text, egui::FontId::monospace(11.0), color);
+    let min = pos + egui::vec2(6.0, -6.0 - galley.size().y);
+    let background = egui::Rect::from_min_size(min, galley.size()).expand(2.0);
+    painter.rect_filled(background, 3.0, egui::Color32::from_black_alpha(180));
+    painter.galley(min, galley, color);
 }
 
-fn pixel_to_mandelbrot(
-    x: usize,
-    y: usize,
-    width: usize,
-    height: usize,
-    center: (f64, f64),
-    scale: f64,
-) -> (f64, f64) {
-    let fx = x as f64 / width as f64;
-    let fy = y as f64 / height as f64;
-    let cx = center.0 + (fx - 0.5) * scale;
-    let cy = center.1 + (fy - 0.5) * scale;
-    (cx, cy)
+/// The zoom factor in `text`, such as "1000x", "1e6", or "0.5×": a positive
+/// number, optionally followed by an x.
+fn parse_zoom_factor(text: &str) -> Option<f64> {
+    let number = text.trim().trim_end_matches(['x', 'X', '×']).trim_end();
+    number
+        .parse::<f64>()
+        .ok()
+        .filter(|factor| factor.is_finite() && *factor > 0.0)
 }
 
-fn mandelbrot_to_pixel(
-    zx: f64,
-    zy: f64,
-    width: usize,
-    height: usize,
-    center: (f64, f64),
-    scale: f64,
-) -> (f32, f32) {
-    let fx = ((zx - center.0) / scale + 0.5) * width as f64;
-    let fy = ((zy - center.1) / scale + 0.5) * height as f64;
-    (fx as f32, fy as f32)
+/// A seed that differs from run to run, from the clock.
+fn time_seed() -> u64 {
+    web_time::SystemTime::now()
+        .duration_since(web_time::UNIX_EPOCH)
+        .map_or(0, |time| time.as_nanos() as u64)
 }
 
-fn mandelbrot_path(
-    px: usize,
-    py: usize,
-    width: usize,
-    height: usize,
-    center: (f64, f64),
-    scale: f64,
-) -> Vec<(f64, f64)> {
-    let mut path = Vec::new();
-    let (cx, cy) = pixel_to_mandelbrot(px, py, width, height, center, scale);
-    let mut zx = 0.0;
-    let mut zy = 0.0;
-    let max_iter = 100;
-    for _ in 0..max_iter {
-        path.push((zx, zy));
-        if zx * zx + zy * zy >= 4.0 {
-            break;
-        }
-        let tmp = zx * zx - zy * zy + cx;
-        zy = 2.0 * zx * zy + cy;
-        zx = tmp;
-    }
-    path
+fn invalid_data(err: impl std::fmt::Display) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err.to_string())
 }
 
-fn hsv_to_rgb(h: f32, s: f32, v: f32) -> (u8, u8, u8) {
-    let c = v * s;
-    let x = c * (1.0 - ((h / 60.0) % 2.0 - 1.0).abs());
-    let m = v - c;
-    let (r1, g1, b1) = match h as u32 {
-        0..=59 => (c, x, 0.0),
-        60..=119 => (x, c, 0.0),
-        120..=179 => (0.0, c, x),
-        180..=239 => (0.0, x, c),
-        240..=299 => (x, 0.0, c),
-        300..=359 => (c, 0.0, x),
-        _ => (0.0, 0.0, 0.0),
+/// "40% · 12 s elapsed, 18 s left" for a job `done` (0 to 1) of the way
+/// through after `elapsed`, the time left extrapolated from the pace so far.
+fn progress_text(done: f32, elapsed: Duration) -> String {
+    let seconds = |duration: f64| match duration.round() as u64 {
+        seconds @ 0..60 => tr!("duration-seconds", seconds = seconds),
+        seconds => tr!(
+            "duration-minutes",
+            minutes = seconds / 60,
+            seconds = seconds % 60
+        ),
     };
-    let r = ((r1 + m) * 255.0).round() as u8;
-    let g = ((g1 + m) * 255.0).round() as u8;
-    let b = ((b1 + m) * 255.0).round() as u8;
-    (r, g, b)
+    let percent = format!("{:.0}", done * 100.0);
+    let elapsed = elapsed.as_secs_f64();
+    if done > 0.0 && done < 1.0 {
+        let left = elapsed * f64::from((1.0 - done) / done);
+        tr!(
+            "progress-time-left",
+            percent = percent,
+            elapsed = seconds(elapsed),
+            left = seconds(left),
+        )
+    } else {
+        tr!("progress", percent = percent, elapsed = seconds(elapsed))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn display_name(path: &Path) -> String {
+    path.file_name()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy()
+        .into_owned()
 }
 
+fn to_color_image(image: &Image) -> egui::ColorImage {
+    egui::ColorImage::from_rgba_unmultiplied([image.width, image.height], image.as_bytes())
+}
+
+/// Name of the window, and of the folder the app keeps its data in.
+#[cfg(not(target_arch = "wasm32"))]
+const APP_NAME: &str = "Mandelbrot Explorer";
+
+#[cfg(not(target_arch = "wasm32"))]
 fn main() -> eframe::Result<()> {
+    let log = diagnostics::init();
+    let args = cli::Args::parse();
+    if let Some(command) = args.command {
+        if let Err(err) = cli::run(command) {
+            eprintln!("error: {err}");
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default().with_inner_size([800.0, 600.0]),
         ..Default::default()
     };
     eframe::run_native(
-        "Mandelbrot Explorer",
+        APP_NAME,
         options,
-        Box::new(|cc| Ok(Box::new(MandelbrotApp::new(cc)))),
+        Box::new(|cc| Ok(Box::new(MandelbrotApp::new(cc, log)))),
     )
 }
+
+#[cfg(target_arch = "wasm32")]
+fn main() {
+    use eframe::wasm_bindgen::JsCast;
+
+    let log = diagnostics::init();
+    let canvas = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id("mandelbrot_canvas"))
+        .and_then(|element| element.dyn_into::<web_sys::HtmlCanvasElement>().ok())
+        .expect("page has no canvas with id `mandelbrot_canvas`");
+    wasm_bindgen_futures::spawn_local(async move {
+        eframe::WebRunner::new()
+            .start(
+                canvas,
+                eframe::WebOptions::default(),
+                Box::new(|cc| Ok(Box::new(MandelbrotApp::new(cc, log)))),
+            )
+            .await
+            .expect("failed to start the app");
+    });
+}
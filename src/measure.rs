@@ -0,0 +1,74 @@
+//! The measuring tool: drag between two points of the view to get their
+//! distance in the complex plane and in pixels, and their midpoint, such as
+//! for estimating the size of a minibrot or the spacing of features.
+
+use eframe::egui;
+use mandelbrot_core::View;
+
+use crate::i18n::tr;
+
+const COLOR: egui::Color32 = egui::Color32::from_rgb(0, 255, 255);
+
+#[derive(Default)]
+pub struct Measure {
+    /// Whether drags on the view measure rather than select a path.
+    pub enabled: bool,
+    /// The points measured between, the second one following the pointer
+    /// while dragging.
+    points: Option<((f64, f64), (f64, f64))>,
+}
+
+impl Measure {
+    /// Measures as `response`, the view's, is dragged. `to_complex` maps
+    /// screen positions to points.
+    pub fn drag(
+        &mut self,
+        response: &egui::Response,
+        to_complex: impl Fn(egui::Pos2) -> (f64, f64),
+    ) {
+        if response.drag_started()
+            && let Some(origin) = response.ctx.input(|i| i.pointer.press_origin())
+        {
+            let start = to_complex(origin);
+            self.points = Some((start, start));
+        }
+        if response.dragged()
+            && let (Some((start, _)), Some(pos)) = (self.points, response.interact_pointer_pos())
+        {
+            self.points = Some((start, to_complex(pos)));
+        }
+    }
+
+    /// Draws the measurement over `image_rect`, which shows `view`.
+    pub fn draw(&self, painter: &egui::Painter, image_rect: egui::Rect, view: &View) {
+        let Some(((x0, y0), (x1, y1))) = self.points.filter(|_| self.enabled) else {
+            return;
+        };
+        let side = image_rect.width() as usize;
+        let to_screen = |re: f64, im: f64| {
+            let (x, y) = view.complex_to_pixel(re, im, side, side);
+            image_rect.min + egui::vec2(x as f32, y as f32)
+        };
+        let (from, to) = (to_screen(x0, y0), to_screen(x1, y1));
+        let shadow = egui::Stroke::new(4.0, egui::Color32::from_black_alpha(160));
+        painter.line_segment([from, to], shadow);
+        painter.line_segment([from, to], egui::Stroke::new(2.0, COLOR));
+        for end in [from, to] {
+            painter.circle_filled(end, 4.0, COLOR);
+        }
+        let distance = (x1 - x0).hypot(y1 - y0);
+        let text = tr!(
+            "measure-result",
+            distance = format!("{distance:.6e}"),
+            pixels = format!("{:.1}", distance / view.pixel_size(side)),
+            re = format!("{:.12}", (x0 + x1) / 2.0),
+            im = format!("{:+.12}", (y0 + y1) / 2.0),
+        );
+        let galley =
+            painter.layout_no_wrap(text, egui::FontId::monospace(12.0), egui::Color32::WHITE);
+        let pos = to + egui::vec2(12.0, 12.0);
+        let background = egui::Rect::from_min_size(pos, galley.size()).expand(4.0);
+        painter.rect_filled(background, 4.0, egui::Color32::from_black_alpha(160));
+        painter.galley(pos, galley, egui::Color32::WHITE);
+    }
+}
@@ -0,0 +1,128 @@
+//! Accounting of the memory the app's big buffers take up: the frame shown,
+//! the frame cache, and exports running in the background, held together
+//! under the cap from the settings. The cache gives way first, shrinking to
+//! what the rest leaves; an export that would go over the cap even with the
+//! cache emptied is refused before it starts rather than left to run out of
+//! memory.
+
+// Only the desktop has exports, as the web build has no file access.
+#![cfg_attr(target_arch = "wasm32", allow(dead_code))]
+
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use crate::i18n::tr;
+
+/// Default cap on the memory of frames, caches and exports.
+pub const DEFAULT_CAP_MB: usize = 4096;
+
+/// Bytes per pixel an export holds at most while it runs: escape times and
+/// the colored image, or the data of an array and its encoding.
+pub const EXPORT_BYTES_PER_PIXEL: usize = 8;
+/// Likewise for 16-bit images: smooth escape times, the image, and its copy
+/// in linear light.
+pub const DEEP_EXPORT_BYTES_PER_PIXEL: usize = 20;
+
+/// Memory in use, in bytes, for the diagnostics window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// The frame shown, its escape times and colored image.
+    pub frame: usize,
+    pub caches: usize,
+    pub exports: usize,
+    pub cap: usize,
+}
+
+impl MemoryUsage {
+    pub fn total(&self) -> usize {
+        self.frame + self.caches + self.exports
+    }
+}
+
+/// The cap and what counts against it besides the caches, shared with the
+/// threads exports run on.
+#[derive(Debug, Default)]
+pub struct MemoryBudget {
+    cap: AtomicUsize,
+    frame: AtomicUsize,
+    exports: AtomicUsize,
+}
+
+impl MemoryBudget {
+    pub fn new(cap: usize) -> Self {
+        Self {
+            cap: AtomicUsize::new(cap),
+            ..Self::default()
+        }
+    }
+
+    pub fn set_cap(&self, cap: usize) {
+        self.cap.store(cap, Ordering::Relaxed);
+    }
+
+    /// Records the memory the frame shown takes up.
+    pub fn set_frame(&self, bytes: usize) {
+        self.frame.store(bytes, Ordering::Relaxed);
+    }
+
+    /// What the cap leaves for caches after the frame and exports.
+    pub fn room_for_caches(&self) -> usize {
+        let usage = self.usage(0);
+        usage.cap.saturating_sub(usage.frame + usage.exports)
+    }
+
+    /// Memory in use, with `caches` bytes cached.
+    pub fn usage(&self, caches: usize) -> MemoryUsage {
+        MemoryUsage {
+            frame: self.frame.load(Ordering::Relaxed),
+            caches,
+            exports: self.exports.load(Ordering::Relaxed),
+            cap: self.cap.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Sets aside `bytes` for an export until the reservation is dropped, if
+    /// that fits under the cap with the frame and the exports already
+    /// running. Otherwise returns a message saying why the export can't run.
+    pub fn reserve(self: &Arc<Self>, bytes: usize) -> Result<Reservation, String> {
+        let cap = self.cap.load(Ordering::Relaxed);
+        let frame = self.frame.load(Ordering::Relaxed);
+        self.exports
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |exports| {
+                let total = exports.checked_add(bytes)?.checked_add(frame)?;
+                (total <= cap).then_some(exports + bytes)
+            })
+            .map_err(|exports| {
+                tr!(
+                    "memory-export-refused",
+                    needed = megabytes(bytes),
+                    available = megabytes(cap.saturating_sub(frame + exports)),
+                    cap = cap >> 20
+                )
+            })?;
+        Ok(Reservation {
+            budget: self.clone(),
+            bytes,
+        })
+    }
+}
+
+/// Memory set aside for an export, given back when dropped.
+#[derive(Debug)]
+pub struct Reservation {
+    budget: Arc<MemoryBudget>,
+    bytes: usize,
+}
+
+impl Drop for Reservation {
+    fn drop(&mut self) {
+        self.budget.exports.fetch_sub(self.bytes, Ordering::Relaxed);
+    }
+}
+
+/// `bytes` in megabytes, to one decimal.
+pub fn megabytes(bytes: usize) -> String {
+    format!("{:.1}", bytes as f64 / (1 << 20) as f64)
+}
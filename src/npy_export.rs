@@ -0,0 +1,329 @@
+//! The Analysis → Export Arrays window: per-pixel buffers of the frame shown
+//! — escape times, smooth escape times, distance estimates, and statistics
+//! of each pixel's orbit — written as NumPy `.npy` files, which
+//! `numpy.load` reads without any parsing code.
+
+use std::{
+    collections::BTreeSet,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+    thread::{self, JoinHandle},
+};
+
+use eframe::egui;
+use mandelbrot_core::{
+    Formula, IterationBuffer, ParallelRenderer, RenderSettings, View, distance::exterior_distance,
+};
+use tracing::{info, info_span, warn};
+
+use crate::{
+    i18n::tr,
+    memory::{EXPORT_BYTES_PER_PIXEL, MemoryBudget},
+    scheduler::{Priority, Scheduler, lower_thread_priority},
+    settings::Settings,
+};
+
+/// A buffer that can be exported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Buffer {
+    /// Escape times as rendered, with points in the set at the iteration
+    /// limit and converged ones flagged.
+    Iterations,
+    /// Escape times interpolated between iterations; NaN in the set.
+    Smooth,
+    /// Estimated distance to the set; NaN in it. Only for z → z² + c.
+    Distance,
+    /// Smallest |z| along the orbit.
+    OrbitMin,
+    /// Average |z| along the orbit.
+    OrbitMean,
+}
+
+impl Buffer {
+    const ALL: [Buffer; 5] = [
+        Buffer::Iterations,
+        Buffer::Smooth,
+        Buffer::Distance,
+        Buffer::OrbitMin,
+        Buffer::OrbitMean,
+    ];
+
+    fn name(self) -> String {
+        match self {
+            Buffer::Iterations => tr!("arrays-iterations"),
+            Buffer::Smooth => tr!("arrays-smooth"),
+            Buffer::Distance => tr!("arrays-distance"),
+            Buffer::OrbitMin => tr!("arrays-orbit-min"),
+            Buffer::OrbitMean => tr!("arrays-orbit-mean"),
+        }
+    }
+
+    fn file_name(self) -> &'static str {
+        match self {
+            Buffer::Iterations => "iterations.npy",
+            Buffer::Smooth => "smooth_iterations.npy",
+            Buffer::Distance => "distance.npy",
+            Buffer::OrbitMin => "orbit_min_abs.npy",
+            Buffer::OrbitMean => "orbit_mean_abs.npy",
+        }
+    }
+
+    /// Whether the buffer can be computed for `formula`.
+    fn supports(self, formula: Formula) -> bool {
+        self != Buffer::Distance || formula == Formula::Mandelbrot
+    }
+}
+
+pub struct NpyExportWindow {
+    pub open: bool,
+    out_dir: PathBuf,
+    buffers: BTreeSet<Buffer>,
+    /// The export running on a background thread, writing to the folder.
+    job: Option<(PathBuf, JoinHandle<io::Result<usize>>)>,
+    message: Option<String>,
+}
+
+impl Default for NpyExportWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            out_dir: PathBuf::from("arrays"),
+            buffers: BTreeSet::from([Buffer::Iterations, Buffer::Smooth]),
+            job: None,
+            message: None,
+        }
+    }
+}
+
+impl NpyExportWindow {
+    /// Shows the window. `iterations` is the frame shown, of `view`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        iterations: Option<&Arc<IterationBuffer>>,
+        view: &View,
+        formula: Formula,
+        settings: &Settings,
+        scheduler: &Arc<Scheduler>,
+        memory: &Arc<MemoryBudget>,
+    ) {
+        self.poll_job();
+        if !self.open {
+            return;
+        }
+        let mut open = self.open;
+        egui::Window::new(tr!("arrays-title"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let running = self.job.is_some();
+                ui.add_enabled_ui(!running, |ui| {
+                    for buffer in Buffer::ALL {
+                        let mut checked = self.buffers.contains(&buffer);
+                        let checkbox = egui::Checkbox::new(&mut checked, buffer.name());
+                        if ui.add_enabled(buffer.supports(formula), checkbox).changed() {
+                            if checked {
+                                self.buffers.insert(buffer);
+                            } else {
+                                self.buffers.remove(&buffer);
+                            }
+                        }
+                    }
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("batch-output-folder"));
+                        let mut dir = self.out_dir.display().to_string();
+                        if ui.text_edit_singleline(&mut dir).changed() {
+                            self.out_dir = dir.into();
+                        }
+                        if ui.button(tr!("button-browse")).clicked()
+                            && let Some(dir) = rfd::FileDialog::new().pick_folder()
+                        {
+                            self.out_dir = dir;
+                        }
+                    });
+                });
+                if let Some(buffer) = iterations {
+                    ui.weak(tr!(
+                        "arrays-shape",
+                        height = buffer.height,
+                        width = buffer.width
+                    ));
+                }
+                if running {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(tr!("arrays-exporting"));
+                    });
+                    if scheduler.is_paused(Priority::Export) {
+                        ui.weak(tr!("video-paused"));
+                    }
+                } else if ui
+                    .add_enabled(
+                        iterations.is_some() && !self.buffers.is_empty(),
+                        egui::Button::new(tr!("arrays-export")),
+                    )
+                    .clicked()
+                    && let Some(buffer) = iterations
+                {
+                    self.start(
+                        ctx,
+                        buffer.clone(),
+                        view,
+                        formula,
+                        settings,
+                        scheduler,
+                        memory,
+                    );
+                }
+                if let Some(message) = &self.message {
+                    ui.label(message);
+                }
+            });
+        self.open = open;
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn start(
+        &mut self,
+        ctx: &egui::Context,
+        iterations: Arc<IterationBuffer>,
+        view: &View,
+        formula: Formula,
+        settings: &Settings,
+        scheduler: &Arc<Scheduler>,
+        memory: &Arc<MemoryBudget>,
+    ) {
+        // Arrays are written one at a time
+        let pixels = iterations.width * iterations.height;
+        let reservation = match memory.reserve(pixels * EXPORT_BYTES_PER_PIXEL) {
+            Ok(reservation) => reservation,
+            Err(message) => {
+                warn!("array export refused: {message}");
+                self.message = Some(message);
+                return;
+            }
+        };
+        let buffers: Vec<Buffer> = (self.buffers.iter())
+            .copied()
+            .filter(|buffer| buffer.supports(formula))
+            .collect();
+        let (out_dir, view) = (self.out_dir.clone(), *view);
+        let renderer = settings.parallel_renderer();
+        let low_priority = settings.low_priority_exports;
+        let (scheduler, ctx) = (scheduler.clone(), ctx.clone());
+        let handle = thread::spawn(move || {
+            let _reservation = reservation;
+            // Before the renderer starts its threads, so they inherit it
+            if low_priority && let Err(err) = lower_thread_priority() {
+                warn!("could not lower the array export's priority: {err}");
+            }
+            let _span = info_span!("arrays", dir = %out_dir.display()).entered();
+            fs::create_dir_all(&out_dir)?;
+            for &buffer in &buffers {
+                let path = out_dir.join(buffer.file_name());
+                scheduler.run(Priority::Export, || {
+                    write(&path, buffer, &renderer, &iterations, &view, formula)
+                })?;
+                info!(path = %path.display(), "wrote array");
+            }
+            ctx.request_repaint();
+            Ok(buffers.len())
+        });
+        self.job = Some((self.out_dir.clone(), handle));
+        self.message = None;
+    }
+
+    fn poll_job(&mut self) {
+        if !(self.job.as_ref()).is_some_and(|(_, handle)| handle.is_finished()) {
+            return;
+        }
+        let (dir, handle) = self.job.take().unwrap();
+        let result = handle
+            .join()
+            .unwrap_or_else(|_| Err(io::Error::other("the export thread panicked")));
+        self.message = Some(match result {
+            Ok(count) => tr!(
+                "arrays-done",
+                count = count,
+                folder = dir.display().to_string()
+            ),
+            Err(err) => tr!("arrays-failed", error = err.to_string()),
+        });
+    }
+}
+
+/// Computes `buffer` for `view`, shown in `iterations`, at the same size, and
+/// writes it to `path`.
+fn write(
+    path: &Path,
+    buffer: Buffer,
+    renderer: &ParallelRenderer,
+    iterations: &IterationBuffer,
+    view: &View,
+    formula: Formula,
+) -> io::Result<()> {
+    let shape = [iterations.height, iterations.width];
+    let settings = RenderSettings {
+        width: iterations.width,
+        height: iterations.height,
+        max_iter: iterations.max_iter,
+        formula,
+    };
+    let max_iter = iterations.max_iter;
+    let values = match buffer {
+        Buffer::Iterations => {
+            let data = iterations.data.iter().flat_map(|v| v.to_le_bytes());
+            return write_npy(path, "<u4", shape, data);
+        }
+        Buffer::Smooth => renderer.render_values(view, &settings, |c| {
+            formula.smooth_escape_time(c, max_iter).map(|v| v as f32)
+        }),
+        Buffer::Distance => renderer.render_values(view, &settings, |c| {
+            exterior_distance(c, max_iter).map(|v| v as f32)
+        }),
+        Buffer::OrbitMin | Buffer::OrbitMean => renderer.render_values(view, &settings, |c| {
+            let orbit = formula.orbit(c, max_iter);
+            let abs = orbit.iter().map(|z| z.0.hypot(z.1));
+            let value = if buffer == Buffer::OrbitMin {
+                abs.fold(f64::INFINITY, f64::min)
+            } else {
+                abs.sum::<f64>() / orbit.len().max(1) as f64
+            };
+            Some(value as f32)
+        }),
+    };
+    write_npy(
+        path,
+        "<f4",
+        shape,
+        values.iter().flat_map(|v| v.to_le_bytes()),
+    )
+}
+
+/// Writes an array of type `descr`, such as `<f4` for little-endian 32-bit
+/// floats, with `shape` and row-major `data` in the NumPy format, version
+/// 1.0: a magic string, the header's length, and a header that is a Python
+/// dict literal, padded so that the data starts 64-byte aligned.
+fn write_npy(
+    path: &Path,
+    descr: &str,
+    [rows, columns]: [usize; 2],
+    data: impl Iterator<Item = u8>,
+) -> io::Result<()> {
+    const MAGIC: &[u8] = b"\x93NUMPY\x01\x00";
+    let mut header =
+        format!("{{'descr': '{descr}', 'fortran_order': False, 'shape': ({rows}, {columns}), }}");
+    // The magic string and the header's length take 10 bytes
+    let padding = (64 - (MAGIC.len() + 2 + header.len() + 1) % 64) % 64;
+    header.extend(std::iter::repeat_n(' ', padding));
+    header.push('\n');
+    let mut file = io::BufWriter::new(fs::File::create(path)?);
+    file.write_all(MAGIC)?;
+    file.write_all(&(header.len() as u16).to_le_bytes())?;
+    file.write_all(header.as_bytes())?;
+    file.write_all(&data.collect::<Vec<u8>>())?;
+    file.flush()
+}
@@ -0,0 +1,93 @@
+//! Writing palettes as swatch files for image editors and other fractal
+//! software: GIMP palettes (`.gpl`), which Inkscape and Krita read too, and
+//! Adobe Swatch Exchange files (`.ase`).
+
+use std::{fs, io, path::Path};
+
+use mandelbrot_core::Palette;
+use tracing::info;
+
+/// Colors a palette is sampled at, evenly spaced from its start to its end.
+const SWATCHES: usize = 256;
+/// Swatches per row GIMP shows.
+const GPL_COLUMNS: usize = 16;
+/// Types of Adobe Swatch Exchange blocks.
+const ASE_GROUP_START: u16 = 0xc001;
+const ASE_GROUP_END: u16 = 0xc002;
+const ASE_COLOR: u16 = 0x0001;
+/// Color type of swatches that are neither global nor spot colors.
+const ASE_NORMAL: u16 = 2;
+
+/// `palette` sampled at [`SWATCHES`] colors, as `[r, g, b]`.
+fn swatches(palette: Palette) -> Vec<[u8; 3]> {
+    (0..SWATCHES)
+        .map(|i| {
+            let [r, g, b, _] = palette.color(i, SWATCHES - 1);
+            [r, g, b]
+        })
+        .collect()
+}
+
+/// Writes `palette` to `path`, as an Adobe Swatch Exchange file if it ends in
+/// `.ase` and as a GIMP palette otherwise.
+pub fn write(path: &Path, palette: Palette) -> io::Result<()> {
+    let colors = swatches(palette);
+    let ase = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("ase"));
+    if ase {
+        fs::write(path, encode_ase(palette.name(), &colors))?;
+    } else {
+        fs::write(path, encode_gpl(palette.name(), &colors))?;
+    }
+    info!(path = %path.display(), palette = palette.name(), "wrote palette");
+    Ok(())
+}
+
+/// A GIMP palette named `name`, with a numbered swatch for each color.
+fn encode_gpl(name: &str, colors: &[[u8; 3]]) -> String {
+    let mut text = format!("GIMP Palette\nName: {name}\nColumns: {GPL_COLUMNS}\n#\n");
+    for (i, [r, g, b]) in colors.iter().enumerate() {
+        text.push_str(&format!("{r:3} {g:3} {b:3}\t{name} {i}\n"));
+    }
+    text
+}
+
+/// An Adobe Swatch Exchange file with the colors in a group named `name`.
+///
+/// The format is big-endian: a signature and version, the number of blocks,
+/// then blocks of a type, their length, and their contents, with names in
+/// UTF-16 ending in a zero.
+fn encode_ase(name: &str, colors: &[[u8; 3]]) -> Vec<u8> {
+    let mut data = b"ASEF".to_vec();
+    data.extend([1u16, 0].iter().flat_map(|v| v.to_be_bytes()));
+    data.extend((colors.len() as u32 + 2).to_be_bytes());
+    ase_block(&mut data, ASE_GROUP_START, &ase_name(name));
+    for (i, color) in colors.iter().enumerate() {
+        let mut contents = ase_name(&format!("{name} {i}"));
+        contents.extend(b"RGB ");
+        for channel in color {
+            contents.extend((f32::from(*channel) / 255.0).to_be_bytes());
+        }
+        contents.extend(ASE_NORMAL.to_be_bytes());
+        ase_block(&mut data, ASE_COLOR, &contents);
+    }
+    ase_block(&mut data, ASE_GROUP_END, &[]);
+    data
+}
+
+/// Appends a block of type `kind` holding `contents` to `data`.
+fn ase_block(data: &mut Vec<u8>, kind: u16, contents: &[u8]) {
+    data.extend(kind.to_be_bytes());
+    data.extend((contents.len() as u32).to_be_bytes());
+    data.extend(contents);
+}
+
+/// `name` as Adobe Swatch Exchange files store it: its length in UTF-16
+/// code units, counting the zero at the end, then the code units.
+fn ase_name(name: &str) -> Vec<u8> {
+    let units: Vec<u16> = name.encode_utf16().chain([0]).collect();
+    let mut data = (units.len() as u16).to_be_bytes().to_vec();
+    data.extend(units.iter().flat_map(|unit| unit.to_be_bytes()));
+    data
+}
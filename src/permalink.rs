@@ -0,0 +1,69 @@
+//! Compact, single-line location strings for sharing.
+//!
+//! A permalink is URL-safe base64 (without padding) of a little-endian record:
+//! a version byte, center re/im and scale as f64, max_iter as u32, and the
+//! palette index as u8, followed for random palettes by their seed as u32.
+
+use std::io;
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+
+use mandelbrot_core::{Palette, View};
+
+use crate::{invalid_data, location::Location};
+
+const VERSION: u8 = 1;
+const LEN: usize = 1 + 8 + 8 + 8 + 4 + 1;
+const SEED_LEN: usize = 4;
+
+pub fn encode(location: &Location, palette: Palette) -> String {
+    let mut bytes = Vec::with_capacity(LEN);
+    bytes.push(VERSION);
+    bytes.extend_from_slice(&location.view.center.0.to_le_bytes());
+    bytes.extend_from_slice(&location.view.center.1.to_le_bytes());
+    bytes.extend_from_slice(&location.view.scale.to_le_bytes());
+    let max_iter = u32::try_from(location.max_iter).unwrap_or(u32::MAX);
+    bytes.extend_from_slice(&max_iter.to_le_bytes());
+    bytes.push(palette.index());
+    if let Palette::Random { seed } = palette {
+        bytes.extend_from_slice(&seed.to_le_bytes());
+    }
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+pub fn decode(text: &str) -> io::Result<(Location, Palette)> {
+    let bytes = URL_SAFE_NO_PAD
+        .decode(text.trim())
+        .map_err(|err| invalid_data(format!("not a permalink: {err}")))?;
+    if bytes.first() != Some(&VERSION) {
+        return Err(invalid_data("unsupported permalink version"));
+    }
+    let seeded = bytes.get(29) == Some(&Palette::RANDOM_INDEX);
+    if bytes.len() != LEN + if seeded { SEED_LEN } else { 0 } {
+        return Err(invalid_data("permalink has the wrong length"));
+    }
+    let f64_at = |i: usize| f64::from_le_bytes(bytes[i..i + 8].try_into().unwrap());
+    let center = (f64_at(1), f64_at(9));
+    let scale = f64_at(17);
+    let max_iter = u32::from_le_bytes(bytes[25..29].try_into().unwrap()) as usize;
+    let palette = if seeded {
+        let seed = u32::from_le_bytes(bytes[LEN..].try_into().unwrap());
+        Palette::Random { seed }
+    } else {
+        Palette::from_index(bytes[29])
+            .ok_or_else(|| invalid_data("unknown palette in permalink"))?
+    };
+    if !(center.0.is_finite() && center.1.is_finite() && scale.is_finite() && scale > 0.0) {
+        return Err(invalid_data("permalink has an invalid view"));
+    }
+    if max_iter == 0 {
+        return Err(invalid_data("permalink has an iteration limit of 0"));
+    }
+    Ok((
+        Location {
+            view: View { center, scale },
+            max_iter,
+        },
+        palette,
+    ))
+}
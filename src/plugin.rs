@@ -0,0 +1,110 @@
+//! Coloring functions loaded from shared libraries at startup.
+//!
+//! A plugin is a `.so`/`.dylib`/`.dll` in the plugin folder exporting these C
+//! functions:
+//!
+//! ```c
+//! uint32_t mandelbrot_plugin_abi(void);        // must return ABI_VERSION
+//! const char *mandelbrot_coloring_name(void);  // static, NUL-terminated
+//! void mandelbrot_coloring(const uint32_t *iters, size_t len,
+//!                          uint32_t max_iter, uint8_t *rgba);
+//! ```
+//!
+//! `mandelbrot_coloring` gets the escape time of every pixel, row by row from
+//! the top-left, with `max_iter` marking points in the set, and fills `rgba`
+//! with `4 * len` bytes. For formulas whose orbits can converge instead of
+//! escaping, such orbits have the top bit set over the iterations they took
+//! (see [`CONVERGED`](mandelbrot_core::render::CONVERGED)).
+
+use std::{
+    ffi::{CStr, c_char},
+    fs, io,
+    path::Path,
+};
+
+use libloading::Library;
+use mandelbrot_core::{Image, IterationBuffer};
+
+use crate::invalid_data;
+
+/// Version of the plugin interface described above.
+pub const ABI_VERSION: u32 = 1;
+
+type ColoringFn = unsafe extern "C" fn(*const u32, usize, u32, *mut u8);
+
+pub struct ColoringPlugin {
+    pub name: String,
+    coloring: ColoringFn,
+    _library: Library, // keeps `coloring` loaded
+}
+
+impl ColoringPlugin {
+    /// Loads the plugin at `path`. This runs the library's initialization
+    /// code, so only trusted plugins should be put in the plugin folder.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        unsafe {
+            let library = Library::new(path).map_err(invalid_data)?;
+            let abi = library
+                .get::<unsafe extern "C" fn() -> u32>(b"mandelbrot_plugin_abi")
+                .map_err(invalid_data)?();
+            if abi != ABI_VERSION {
+                return Err(invalid_data(format!(
+                    "plugin interface version {abi}, expected {ABI_VERSION}"
+                )));
+            }
+            let name = library
+                .get::<unsafe extern "C" fn() -> *const c_char>(b"mandelbrot_coloring_name")
+                .map_err(invalid_data)?();
+            if name.is_null() {
+                return Err(invalid_data("plugin has no name"));
+            }
+            let name = CStr::from_ptr(name).to_string_lossy().into_owned();
+            let coloring = *library
+                .get::<ColoringFn>(b"mandelbrot_coloring")
+                .map_err(invalid_data)?;
+            Ok(Self {
+                name,
+                coloring,
+                _library: library,
+            })
+        }
+    }
+
+    pub fn colorize(&self, buffer: &IterationBuffer) -> Image {
+        let mut image = Image::new(buffer.width, buffer.height, [0; 4]);
+        unsafe {
+            (self.coloring)(
+                buffer.data.as_ptr(),
+                buffer.data.len(),
+                buffer.max_iter as u32,
+                image.pixels.as_mut_ptr().cast(),
+            );
+        }
+        image
+    }
+}
+
+/// Loads every shared library in `dir`. Returns the plugins that loaded and a
+/// message for each one that didn't. A missing folder just means no plugins.
+pub fn discover(dir: &Path) -> (Vec<ColoringPlugin>, Vec<String>) {
+    let mut plugins = Vec::new();
+    let mut errors = Vec::new();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return (plugins, errors);
+    };
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext == std::env::consts::DLL_EXTENSION)
+        })
+        .collect();
+    paths.sort();
+    for path in paths {
+        match ColoringPlugin::load(&path) {
+            Ok(plugin) => plugins.push(plugin),
+            Err(err) => errors.push(format!("{}: {err}", path.display())),
+        }
+    }
+    (plugins, errors)
+}
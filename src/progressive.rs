@@ -0,0 +1,157 @@
+//! Rendering the view on background threads in Adam7 order, for renders slow
+//! enough to notice: the whole image appears blocky within a fraction of the
+//! render time and sharpens evenly, instead of the window freezing until it's
+//! done.
+
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    thread::{self, JoinHandle},
+};
+
+use eframe::egui;
+use mandelbrot_core::{
+    IterationBuffer, ParallelRenderer, RenderSettings, View,
+    interlace::{ADAM7, fill_blocks},
+};
+use tracing::trace_span;
+
+use crate::scheduler::{Priority, Scheduler};
+
+/// A render running in the background. Dropping it cancels it.
+pub struct ProgressiveRender {
+    settings: RenderSettings,
+    shared: Arc<Shared>,
+    handle: Option<JoinHandle<()>>,
+    /// Passes included in the last image returned by `poll`.
+    shown_passes: usize,
+}
+
+/// State shared with the rendering threads.
+struct Shared {
+    /// Escape times of the pixels in the finished passes and some of the
+    /// current one.
+    data: Mutex<Vec<u32>>,
+    pixels_done: AtomicUsize,
+    passes_done: AtomicUsize,
+    cancel: AtomicBool,
+}
+
+impl ProgressiveRender {
+    /// Starts rendering `view` on the threads of `parallel`. Escape times are
+    /// the same as the reference renderers'.
+    pub fn start(
+        ctx: &egui::Context,
+        view: View,
+        settings: RenderSettings,
+        parallel: ParallelRenderer,
+        scheduler: Arc<Scheduler>,
+    ) -> Self {
+        let shared = Arc::new(Shared {
+            data: Mutex::new(vec![0; settings.width * settings.height]),
+            pixels_done: AtomicUsize::new(0),
+            passes_done: AtomicUsize::new(0),
+            cancel: AtomicBool::new(false),
+        });
+        let handle = {
+            let shared = shared.clone();
+            let ctx = ctx.clone();
+            thread::spawn(move || {
+                scheduler.run(Priority::View, || {
+                    render_passes(&view, &settings, parallel, &shared, &ctx);
+                });
+            })
+        };
+        Self {
+            settings,
+            shared,
+            handle: Some(handle),
+            shown_passes: 0,
+        }
+    }
+
+    /// The image with the passes finished so far, each computed pixel
+    /// filling its block, if more passes have finished since the last call.
+    pub fn poll(&mut self) -> Option<IterationBuffer> {
+        let passes = self.shared.passes_done.load(Ordering::Acquire);
+        if passes == self.shown_passes {
+            return None;
+        }
+        self.shown_passes = passes;
+        let mut data = self.shared.data.lock().unwrap().clone();
+        fill_blocks(&mut data, self.settings.width, passes);
+        Some(IterationBuffer {
+            width: self.settings.width,
+            height: self.settings.height,
+            max_iter: self.settings.max_iter,
+            data,
+        })
+    }
+
+    /// Fraction of the pixels computed so far.
+    pub fn progress(&self) -> f32 {
+        let total = self.settings.width * self.settings.height;
+        self.shared.pixels_done.load(Ordering::Relaxed) as f32 / total.max(1) as f32
+    }
+
+    /// Whether the last image returned by `poll` was the finished render.
+    pub fn is_done(&self) -> bool {
+        self.shown_passes == ADAM7.len()
+    }
+}
+
+impl Drop for ProgressiveRender {
+    fn drop(&mut self) {
+        self.shared.cancel.store(true, Ordering::Relaxed);
+        // Not joined, so a cancelled render doesn't hold up the window; it
+        // stops after the rows in progress
+        drop(self.handle.take());
+    }
+}
+
+/// Computes the passes in order, their rows spread over the threads of
+/// `parallel`, until done or cancelled.
+fn render_passes(
+    view: &View,
+    settings: &RenderSettings,
+    parallel: ParallelRenderer,
+    shared: &Shared,
+    ctx: &egui::Context,
+) {
+    let _span = trace_span!("progressive render").entered();
+    let (width, height) = (settings.width, settings.height);
+    for (index, pass) in ADAM7.iter().enumerate() {
+        let rows: Vec<usize> = pass.rows(height).collect();
+        let next_row = AtomicUsize::new(0);
+        thread::scope(|scope| {
+            for _ in 0..parallel.threads.get() {
+                scope.spawn(|| {
+                    while !shared.cancel.load(Ordering::Relaxed) {
+                        let Some(&y) = rows.get(next_row.fetch_add(1, Ordering::Relaxed)) else {
+                            break;
+                        };
+                        let row: Vec<(usize, u32)> = pass
+                            .columns(width)
+                            .map(|x| {
+                                let c = view.pixel_to_complex(x as f64, y as f64, width, height);
+                                (x, settings.formula.buffer_value(c, settings.max_iter))
+                            })
+                            .collect();
+                        shared.pixels_done.fetch_add(row.len(), Ordering::Relaxed);
+                        let mut data = shared.data.lock().unwrap();
+                        for (x, iter) in row {
+                            data[y * width + x] = iter;
+                        }
+                    }
+                });
+            }
+        });
+        if shared.cancel.load(Ordering::Relaxed) {
+            return;
+        }
+        shared.passes_done.store(index + 1, Ordering::Release);
+        ctx.request_repaint();
+    }
+}
@@ -0,0 +1,263 @@
+//! The session recorder: every change of the view, iteration limit, or
+//! formula while recording, timestamped, so the exploration can be replayed
+//! exactly as it happened or, on desktop, saved and exported as a smooth
+//! video.
+
+use std::sync::Arc;
+#[cfg(not(target_arch = "wasm32"))]
+use std::{fs, io, path::Path};
+
+use eframe::egui;
+use mandelbrot_core::{Palette, timeline::Keyframe};
+use web_time::Instant;
+
+use crate::{animation::Clock, i18n::tr, scheduler::Scheduler, settings::Settings};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{
+    display_name, invalid_data, timeline,
+    video::{ExportOptions, VideoJob},
+};
+
+pub struct RecorderWindow {
+    pub open: bool,
+    /// When recording started, while recording.
+    started: Option<Instant>,
+    /// What was shown after each change, timed in seconds since recording
+    /// started.
+    steps: Vec<Keyframe>,
+    /// Time into the recording, while replaying it.
+    replay: Option<Clock>,
+    #[cfg(not(target_arch = "wasm32"))]
+    export: ExportOptions,
+    #[cfg(not(target_arch = "wasm32"))]
+    job: Option<VideoJob>,
+    #[cfg(not(target_arch = "wasm32"))]
+    message: Option<String>,
+}
+
+// Only the export options differ from their defaults
+#[cfg_attr(target_arch = "wasm32", allow(clippy::derivable_impls))]
+impl Default for RecorderWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            started: None,
+            steps: Vec::new(),
+            replay: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            export: ExportOptions::new("recording"),
+            #[cfg(not(target_arch = "wasm32"))]
+            job: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            message: None,
+        }
+    }
+}
+
+impl RecorderWindow {
+    /// Records `current`, what the view shows now, if recording and it
+    /// changed since the last step.
+    pub fn record(&mut self, current: Keyframe) {
+        let Some(started) = self.started else {
+            return;
+        };
+        let same = |last: &Keyframe| {
+            (last.view, last.max_iter, last.formula)
+                == (current.view, current.max_iter, current.formula)
+        };
+        if !self.steps.last().is_some_and(same) {
+            let time = started.elapsed().as_secs_f64();
+            self.steps.push(Keyframe { time, ..current });
+        }
+    }
+
+    /// Shows the window, advancing a replay. Returns the step to show in the
+    /// view while replaying.
+    ///
+    /// `settings` and `scheduler` are for exports, which the web version
+    /// doesn't have.
+    #[cfg_attr(target_arch = "wasm32", allow(unused_variables))]
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        palette: Palette,
+        settings: &Settings,
+        scheduler: &Arc<Scheduler>,
+    ) -> Option<Keyframe> {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(message) = VideoJob::poll(&mut self.job) {
+            self.message = Some(message);
+        }
+        let mut shown = None;
+        if let Some(clock) = &self.replay {
+            // Steps hold until the next one, exactly as recorded
+            let time = clock.time();
+            let next = self.steps.partition_point(|step| step.time <= time);
+            shown = self.steps.get(next.saturating_sub(1)).copied();
+            if next == self.steps.len() {
+                self.replay = None;
+            }
+            ctx.request_repaint();
+        }
+        if self.started.is_some() {
+            // Keeps the elapsed time shown current
+            ctx.request_repaint_after(std::time::Duration::from_secs(1));
+        }
+        if !self.open {
+            return shown;
+        }
+        let mut open = self.open;
+        egui::Window::new(tr!("recorder-title"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if let Some(started) = self.started {
+                        if ui.button(tr!("recorder-stop")).clicked() {
+                            self.started = None;
+                        }
+                        ui.colored_label(
+                            egui::Color32::RED,
+                            tr!(
+                                "recorder-recording",
+                                seconds = started.elapsed().as_secs(),
+                                steps = self.steps.len(),
+                            ),
+                        );
+                    } else {
+                        if ui
+                            .button(tr!("recorder-record"))
+                            .on_hover_text(tr!("recorder-record-hover"))
+                            .clicked()
+                        {
+                            (self.started, self.replay) = (Some(Instant::now()), None);
+                            self.steps.clear();
+                        }
+                        let replayable = self.steps.len() > 1;
+                        if self.replay.is_some() {
+                            if ui.button(tr!("recorder-stop-replay")).clicked() {
+                                self.replay = None;
+                            }
+                        } else if ui
+                            .add_enabled(replayable, egui::Button::new(tr!("recorder-replay")))
+                            .on_hover_text(tr!("recorder-replay-hover"))
+                            .clicked()
+                        {
+                            self.replay = Some(Clock::playing());
+                        }
+                        if !self.steps.is_empty() {
+                            ui.label(tr!(
+                                "recorder-recorded",
+                                seconds = self.steps.last().map_or(0, |step| step.time as u64),
+                                steps = self.steps.len(),
+                            ));
+                        }
+                    }
+                });
+                if self.steps.is_empty() && self.started.is_none() {
+                    ui.weak(tr!("recorder-none"));
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                self.show_files(ui, palette, settings, scheduler);
+            });
+        self.open = open;
+        shown
+    }
+
+    /// Saving and opening recordings, and exporting them.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn show_files(
+        &mut self,
+        ui: &mut egui::Ui,
+        palette: Palette,
+        settings: &Settings,
+        scheduler: &Arc<Scheduler>,
+    ) {
+        let recording = self.started.is_some();
+        ui.add_enabled_ui(!recording, |ui| {
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(
+                        !self.steps.is_empty(),
+                        egui::Button::new(tr!("recorder-save")),
+                    )
+                    .clicked()
+                    && let Some(path) = rfd::FileDialog::new()
+                        .add_filter(tr!("filter-recording"), &["ron"])
+                        .set_file_name("recording.ron")
+                        .save_file()
+                {
+                    self.message = Some(match save(&path, &self.steps) {
+                        Ok(()) => tr!("status-saved", name = display_name(&path)),
+                        Err(err) => tr!(
+                            "status-save-failed",
+                            name = display_name(&path),
+                            error = err.to_string()
+                        ),
+                    });
+                }
+                if ui.button(tr!("recorder-open")).clicked()
+                    && let Some(path) = rfd::FileDialog::new()
+                        .add_filter(tr!("filter-recording"), &["ron"])
+                        .pick_file()
+                {
+                    self.message = Some(match load(&path) {
+                        Ok(steps) => {
+                            (self.steps, self.replay) = (steps, None);
+                            tr!("status-opened", name = display_name(&path))
+                        }
+                        Err(err) => tr!(
+                            "status-open-failed",
+                            name = display_name(&path),
+                            error = err.to_string()
+                        ),
+                    });
+                }
+            });
+        });
+        ui.separator();
+        let running = self.job.is_some();
+        ui.add_enabled_ui(!running, |ui| self.export.show(ui));
+        if let Some(job) = &self.job {
+            job.show_progress(ui, scheduler);
+        } else if ui
+            .add_enabled(
+                !recording && self.steps.len() > 1,
+                egui::Button::new(tr!("recorder-export")),
+            )
+            .on_hover_text(tr!("recorder-export-hover"))
+            .clicked()
+        {
+            self.job = Some(timeline::export(
+                ui.ctx(),
+                self.steps.clone(),
+                palette,
+                &self.export,
+                settings,
+                scheduler,
+            ));
+            self.message = None;
+        }
+        if let Some(message) = &self.message {
+            ui.label(message);
+        }
+    }
+}
+
+/// Writes `steps` to `path` as RON.
+#[cfg(not(target_arch = "wasm32"))]
+fn save(path: &Path, steps: &[Keyframe]) -> io::Result<()> {
+    let text = ron::ser::to_string_pretty(steps, ron::ser::PrettyConfig::default())
+        .map_err(invalid_data)?;
+    fs::write(path, text)
+}
+
+/// Reads the steps of a recording written by [`save`].
+#[cfg(not(target_arch = "wasm32"))]
+fn load(path: &Path) -> io::Result<Vec<Keyframe>> {
+    let steps: Vec<Keyframe> = ron::from_str(&fs::read_to_string(path)?).map_err(invalid_data)?;
+    if !steps.is_sorted_by(|a, b| a.time <= b.time) {
+        return Err(invalid_data(tr!("recorder-unsorted")));
+    }
+    Ok(steps)
+}
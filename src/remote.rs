@@ -0,0 +1,207 @@
+//! Remote control of the running explorer over WebSocket.
+//!
+//! When enabled in the settings, the app listens on `127.0.0.1`. Handshakes
+//! with an `Origin` header are refused, so web pages open in a browser can't
+//! connect; other programs on the computer can. Each text
+//! message is a JSON object with a `cmd` and its arguments, plus an optional
+//! `id` echoed in the reply:
+//!
+//! - `{"cmd": "get_view"}` → center, scale, iteration limit, and palette
+//! - `{"cmd": "set_view", "center": [re, im], "scale": s, "max_iter": n,
+//!   "palette": "Fire"}`, all arguments optional
+//! - `{"cmd": "query_pixel", "x": 10, "y": 20}` → the point under a pixel of
+//!   the shown image and its escape time
+//! - `{"cmd": "query_point", "re": -0.5, "im": 0.1}` → escape time of a point
+//! - `{"cmd": "render", "size": 512}` → the view as a base64 PNG, at most
+//!   2048 pixels on a side
+//! - `{"cmd": "export", "path": "frame.png"}` → saves the shown frame under
+//!   that file name in the screenshot folder, never over an existing file
+//! - `{"cmd": "permalink"}` → the current permalink
+//!
+//! Replies are `{"ok": true, ...}` or `{"ok": false, "error": "..."}`.
+
+use std::{
+    io,
+    net::{Ipv4Addr, TcpListener, TcpStream},
+    path::PathBuf,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, Sender},
+    },
+    thread,
+    time::Duration,
+};
+
+use eframe::egui;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use tracing::{info, info_span, warn};
+use tungstenite::{
+    Message,
+    handshake::server::{ErrorResponse, Request as Handshake, Response},
+    http::{StatusCode, header},
+};
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+pub enum Command {
+    GetView,
+    SetView {
+        center: Option<(f64, f64)>,
+        scale: Option<f64>,
+        max_iter: Option<usize>,
+        palette: Option<String>,
+    },
+    QueryPixel {
+        x: f64,
+        y: f64,
+    },
+    QueryPoint {
+        re: f64,
+        im: f64,
+    },
+    Render {
+        size: usize,
+    },
+    Export {
+        path: PathBuf,
+    },
+    Permalink,
+}
+
+#[derive(Deserialize)]
+struct Envelope {
+    id: Option<Value>,
+    #[serde(flatten)]
+    command: Command,
+}
+
+/// A command waiting for the app, which sends its reply back.
+pub struct Request {
+    pub command: Command,
+    reply: Sender<Result<Value, String>>,
+}
+
+impl Request {
+    pub fn reply(self, result: Result<Value, String>) {
+        // The connection may have closed meanwhile
+        let _ = self.reply.send(result);
+    }
+}
+
+/// The listening server. Dropping it stops accepting connections; open
+/// connections end when their client disconnects.
+pub struct RemoteServer {
+    pub port: u16,
+    requests: Receiver<Request>,
+    stop: Arc<AtomicBool>,
+}
+
+impl RemoteServer {
+    /// Starts listening on `127.0.0.1:port`. `ctx` is woken up when a command
+    /// arrives.
+    pub fn start(port: u16, ctx: egui::Context) -> io::Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+        // Polled so the thread notices `stop`
+        listener.set_nonblocking(true)?;
+        let (sender, requests) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let server_stop = stop.clone();
+        thread::spawn(move || {
+            while !server_stop.load(Ordering::Relaxed) {
+                match listener.accept() {
+                    Ok((stream, peer)) => {
+                        let (sender, ctx) = (sender.clone(), ctx.clone());
+                        thread::spawn(move || {
+                            let _span = info_span!("remote", %peer).entered();
+                            info!("connected");
+                            match serve_client(stream, &sender, &ctx) {
+                                Ok(()) => info!("disconnected"),
+                                Err(err) => warn!("connection failed: {err}"),
+                            }
+                        });
+                    }
+                    Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                        thread::sleep(Duration::from_millis(100));
+                    }
+                    Err(err) => warn!("could not accept connection: {err}"),
+                }
+            }
+        });
+        info!(port, "remote control listening");
+        Ok(Self {
+            port,
+            requests,
+            stop,
+        })
+    }
+
+    pub fn try_recv(&self) -> Option<Request> {
+        self.requests.try_recv().ok()
+    }
+}
+
+impl Drop for RemoteServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Refuses handshakes from web pages, which browsers always send with an
+/// `Origin` header, so that no page can drive the app.
+#[allow(clippy::result_large_err)] // the signature tungstenite calls back with
+fn refuse_browsers(request: &Handshake, response: Response) -> Result<Response, ErrorResponse> {
+    if !request.headers().contains_key(header::ORIGIN) {
+        return Ok(response);
+    }
+    warn!("refused a connection from a web page");
+    let mut refusal = ErrorResponse::new(Some("connections from web pages are refused".to_owned()));
+    *refusal.status_mut() = StatusCode::FORBIDDEN;
+    Err(refusal)
+}
+
+fn serve_client(
+    stream: TcpStream,
+    sender: &Sender<Request>,
+    ctx: &egui::Context,
+) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    let mut socket = tungstenite::accept_hdr(stream, refuse_browsers).map_err(io::Error::other)?;
+    loop {
+        let text = match socket.read() {
+            Ok(Message::Text(text)) => text,
+            Ok(Message::Close(_)) => return Ok(()),
+            Ok(_) => continue,
+            Err(tungstenite::Error::ConnectionClosed) => return Ok(()),
+            Err(err) => return Err(io::Error::other(err)),
+        };
+        let reply = match serde_json::from_str::<Envelope>(&text) {
+            Ok(Envelope { id, command }) => {
+                let (reply, result) = mpsc::channel();
+                if sender.send(Request { command, reply }).is_err() {
+                    // The app has closed
+                    return Ok(());
+                }
+                ctx.request_repaint();
+                let mut reply = match result.recv() {
+                    Ok(Ok(Value::Object(mut fields))) => {
+                        fields.insert("ok".to_owned(), true.into());
+                        Value::Object(fields)
+                    }
+                    Ok(Ok(_)) => json!({ "ok": true }),
+                    Ok(Err(error)) => json!({ "ok": false, "error": error }),
+                    Err(_) => return Ok(()),
+                };
+                if let Some(id) = id {
+                    reply["id"] = id;
+                }
+                reply
+            }
+            Err(err) => json!({ "ok": false, "error": err.to_string() }),
+        };
+        socket
+            .send(Message::text(reply.to_string()))
+            .map_err(io::Error::other)?;
+    }
+}
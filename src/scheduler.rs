@@ -0,0 +1,130 @@
+//! Ordering render work between the window and background jobs.
+//!
+//! Every consumer runs its render work through the shared [`Scheduler`] with a
+//! [`Priority`]. Work waits while more urgent work is running, and exports
+//! also wait until the user has left the view alone for [`IDLE_DELAY`], so
+//! background jobs don't make zooming and panning stutter. Waiting happens
+//! between units of work (a whole image for a batch), not in the middle of
+//! one.
+
+use std::{
+    sync::{Condvar, Mutex},
+    time::Duration,
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+use mandelbrot_core::{IterationBuffer, RenderSettings, Renderer, View};
+use web_time::Instant;
+
+/// How long after the last interaction exports stay paused.
+pub const IDLE_DELAY: Duration = Duration::from_millis(500);
+/// Niceness of low-priority threads, from 0 (normal) to 19 (lowest).
+#[cfg(target_os = "linux")]
+const LOW_PRIORITY_NICENESS: i32 = 10;
+
+/// How urgent render work is, most urgent first. Previews such as thumbnails
+/// belong between the two.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    /// The image in the window. Never waits.
+    View,
+    /// Files rendered in the background, such as batch renders.
+    Export,
+}
+
+impl Priority {
+    const COUNT: usize = 2;
+}
+
+#[derive(Default)]
+pub struct Scheduler {
+    state: Mutex<State>,
+    finished: Condvar,
+}
+
+#[derive(Default)]
+struct State {
+    running: [usize; Priority::COUNT],
+    last_interaction: Option<Instant>,
+}
+
+impl State {
+    /// How long work of `priority` should wait before checking again, or
+    /// `None` if it may run now.
+    fn wait_time(&self, priority: Priority) -> Option<Duration> {
+        if self.running[..priority as usize].iter().any(|&n| n > 0) {
+            // Woken when that work finishes
+            return Some(IDLE_DELAY);
+        }
+        let since_interaction = self.last_interaction?.elapsed();
+        (priority == Priority::Export && since_interaction < IDLE_DELAY)
+            .then(|| IDLE_DELAY - since_interaction)
+    }
+}
+
+impl Scheduler {
+    /// Runs `work` once nothing more urgent is going on.
+    pub fn run<T>(&self, priority: Priority, work: impl FnOnce() -> T) -> T {
+        let mut state = self.state.lock().unwrap();
+        if priority != Priority::View {
+            while let Some(wait) = state.wait_time(priority) {
+                state = self.finished.wait_timeout(state, wait).unwrap().0;
+            }
+        }
+        state.running[priority as usize] += 1;
+        drop(state);
+        let result = work();
+        self.state.lock().unwrap().running[priority as usize] -= 1;
+        self.finished.notify_all();
+        result
+    }
+
+    /// Records that the user is interacting with the view, pausing exports.
+    pub fn interacting(&self) {
+        self.state.lock().unwrap().last_interaction = Some(Instant::now());
+    }
+
+    /// Whether work of `priority` would have to wait right now.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn is_paused(&self, priority: Priority) -> bool {
+        self.state.lock().unwrap().wait_time(priority).is_some()
+    }
+}
+
+/// A renderer whose renders go through a scheduler.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct Scheduled<'a, R> {
+    pub renderer: R,
+    pub scheduler: &'a Scheduler,
+    pub priority: Priority,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<R: Renderer> Renderer for Scheduled<'_, R> {
+    fn name(&self) -> &'static str {
+        self.renderer.name()
+    }
+
+    fn render(&self, view: &View, settings: &RenderSettings) -> IterationBuffer {
+        self.scheduler
+            .run(self.priority, || self.renderer.render(view, settings))
+    }
+}
+
+/// Makes the calling thread, and threads it starts afterwards, give way to
+/// other programs' threads, for work that can take its time.
+#[cfg(target_os = "linux")]
+pub fn lower_thread_priority() -> std::io::Result<()> {
+    // Niceness is per thread on Linux, and new threads inherit it
+    let thread = unsafe { libc::gettid() } as libc::id_t;
+    if unsafe { libc::setpriority(libc::PRIO_PROCESS, thread, LOW_PRIORITY_NICENESS) } == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+#[cfg(all(not(target_os = "linux"), not(target_arch = "wasm32")))]
+pub fn lower_thread_priority() -> std::io::Result<()> {
+    Err(std::io::ErrorKind::Unsupported.into())
+}
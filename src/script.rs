@@ -0,0 +1,141 @@
+//! Rhai scripts that drive the explorer: moving the view, querying orbits,
+//! and rendering frames to PNG files.
+//!
+//! Scripts see these functions (numbers are floats unless noted):
+//!
+//! - `center()` → `[re, im]`, `set_center(re, im)`
+//! - `scale()`, `set_scale(scale)`, `zoom(factor)` (divides the scale)
+//! - `max_iter()` → int, `set_max_iter(n)`
+//! - `palette()` → name, `set_palette(name)`
+//! - `escape_time(re, im)` → int, `orbit(re, im)` → array of `[re, im]`
+//! - `permalink()` → the current location as a permalink
+//! - `render(path, size)` or `render(path, width, height)` writes the current
+//!   view to a PNG file
+//!
+//! The view a script leaves behind is shown when it was run from the GUI.
+
+use std::{cell::RefCell, io, path::Path, rc::Rc};
+
+use mandelbrot_core::{Palette, escape_time, orbit, render};
+use rhai::{Array, Dynamic, Engine, EvalAltResult};
+
+use crate::{export, invalid_data, location::Location, permalink};
+
+/// What a script can change.
+#[derive(Debug, Clone, Copy)]
+pub struct State {
+    pub location: Location,
+    pub palette: Palette,
+}
+
+type Result<T> = std::result::Result<T, Box<EvalAltResult>>;
+
+pub fn run_file(path: &Path, state: State, print: impl Fn(&str) + 'static) -> io::Result<State> {
+    run(&std::fs::read_to_string(path)?, state, print)
+}
+
+/// Runs `source` starting from `state` and returns the state it ends in.
+/// `print` receives the script's `print` and `debug` output.
+pub fn run(source: &str, state: State, print: impl Fn(&str) + 'static) -> io::Result<State> {
+    let state = Rc::new(RefCell::new(state));
+    let mut engine = Engine::new();
+    let print = Rc::new(print);
+    engine.on_print({
+        let print = print.clone();
+        move |text| print(text)
+    });
+    engine.on_debug(move |text, _, _| print(text));
+
+    let s = state.clone();
+    engine.register_fn("center", move || -> Array {
+        let (re, im) = s.borrow().location.view.center;
+        vec![re.into(), im.into()]
+    });
+    let s = state.clone();
+    engine.register_fn("set_center", move |re: f64, im: f64| {
+        s.borrow_mut().location.view.center = (re, im);
+    });
+    let s = state.clone();
+    engine.register_fn("scale", move || s.borrow().location.view.scale);
+    let s = state.clone();
+    engine.register_fn("set_scale", move |scale: f64| -> Result<()> {
+        if !(scale.is_finite() && scale > 0.0) {
+            return Err(format!("invalid scale {scale}").into());
+        }
+        s.borrow_mut().location.view.scale = scale;
+        Ok(())
+    });
+    let s = state.clone();
+    engine.register_fn("zoom", move |factor: f64| -> Result<()> {
+        if !(factor.is_finite() && factor > 0.0) {
+            return Err(format!("invalid zoom factor {factor}").into());
+        }
+        s.borrow_mut().location.view.scale /= factor;
+        Ok(())
+    });
+    let s = state.clone();
+    engine.register_fn("max_iter", move || s.borrow().location.max_iter as i64);
+    let s = state.clone();
+    engine.register_fn("set_max_iter", move |max_iter: i64| -> Result<()> {
+        if max_iter < 1 {
+            return Err(format!("invalid iteration limit {max_iter}").into());
+        }
+        s.borrow_mut().location.max_iter = max_iter as usize;
+        Ok(())
+    });
+    let s = state.clone();
+    engine.register_fn("palette", move || s.borrow().palette.name().to_owned());
+    let s = state.clone();
+    engine.register_fn("set_palette", move |name: &str| -> Result<()> {
+        let palette = Palette::ALL
+            .into_iter()
+            .find(|palette| palette.name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| format!("unknown palette {name:?}"))?;
+        s.borrow_mut().palette = palette;
+        Ok(())
+    });
+    let s = state.clone();
+    engine.register_fn("escape_time", move |re: f64, im: f64| {
+        escape_time((re, im), s.borrow().location.max_iter) as i64
+    });
+    let s = state.clone();
+    engine.register_fn("orbit", move |re: f64, im: f64| -> Array {
+        orbit((re, im), s.borrow().location.max_iter)
+            .into_iter()
+            .map(|(re, im)| Dynamic::from_array(vec![re.into(), im.into()]))
+            .collect()
+    });
+    let s = state.clone();
+    engine.register_fn("permalink", move || {
+        let state = s.borrow();
+        permalink::encode(&state.location, state.palette)
+    });
+    let s = state.clone();
+    engine.register_fn("render", move |path: &str, size: i64| {
+        render_to(&s.borrow(), path, size, size)
+    });
+    let s = state.clone();
+    engine.register_fn("render", move |path: &str, width: i64, height: i64| {
+        render_to(&s.borrow(), path, width, height)
+    });
+
+    engine.run(source).map_err(invalid_data)?;
+    Ok(*state.borrow())
+}
+
+fn render_to(state: &State, path: &str, width: i64, height: i64) -> Result<()> {
+    if width < 1 || height < 1 {
+        return Err(format!("invalid image size {width}x{height}").into());
+    }
+    let Location { view, max_iter } = state.location;
+    let image = render(
+        &view,
+        width as usize,
+        height as usize,
+        max_iter,
+        state.palette,
+    );
+    let link = permalink::encode(&state.location, state.palette);
+    export::write_png(Path::new(path), &image, Some(&link))
+        .map_err(|err| format!("could not write {path}: {err}").into())
+}
@@ -0,0 +1,110 @@
+//! Session files: a snapshot of everything needed to resume an exploration.
+//!
+//! Sessions are written as RON by default; files with a `.json` extension are
+//! read and written as JSON instead.
+
+use std::{fs, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use mandelbrot_core::{
+    DEFAULT_MAX_ITER, Formula, Palette, View, layers::Layer, timeline::Keyframe, trap::Trap,
+};
+
+use crate::{annotations::Annotation, bookmarks::Bookmark, invalid_data, traps};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    pub center: (f64, f64), // center of view in Mandelbrot space
+    pub scale: f64,         // Mandelbrot units per image width
+    pub max_iter: usize,
+    pub palette: Palette,
+    /// Parameter c of the orbit shown as the yellow path, if any.
+    pub orbit: Option<(f64, f64)>,
+    /// Iteration limit of the path, if it has its own.
+    pub orbit_max_iter: Option<usize>,
+    /// The map iterated.
+    pub formula: Formula,
+    /// Named views to come back to.
+    pub bookmarks: Vec<Bookmark>,
+    /// Labels, arrows, and markers drawn over the view.
+    pub annotations: Vec<Annotation>,
+    /// Orbit traps the exterior is tinted by, in complex coordinates.
+    pub traps: Vec<Trap>,
+    /// Distance over which the tint of a trap fades.
+    pub trap_width: f64,
+    /// Coloring layers from the bottom up, used instead of the palette while
+    /// any is visible.
+    pub layers: Vec<Layer>,
+    /// Keyframes of the timeline, sorted by time.
+    pub keyframes: Vec<Keyframe>,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self {
+            center: View::default().center,
+            scale: View::default().scale,
+            max_iter: DEFAULT_MAX_ITER,
+            palette: Palette::default(),
+            orbit: None,
+            orbit_max_iter: None,
+            formula: Formula::Mandelbrot,
+            bookmarks: Vec::new(),
+            annotations: Vec::new(),
+            traps: Vec::new(),
+            trap_width: traps::DEFAULT_WIDTH,
+            layers: Vec::new(),
+            keyframes: Vec::new(),
+        }
+    }
+}
+
+pub fn save(path: &Path, session: &Session) -> io::Result<()> {
+    fs::write(path, to_string(path, session)?)
+}
+
+/// `session` as the file at `path` would hold it.
+pub fn to_string(path: &Path, session: &Session) -> io::Result<String> {
+    if is_json(path) {
+        serde_json::to_string_pretty(session).map_err(invalid_data)
+    } else {
+        ron::ser::to_string_pretty(session, ron::ser::PrettyConfig::default()).map_err(invalid_data)
+    }
+}
+
+pub fn load(path: &Path) -> io::Result<Session> {
+    let text = fs::read_to_string(path)?;
+    let session: Session = if is_json(path) {
+        serde_json::from_str(&text).map_err(invalid_data)?
+    } else {
+        ron::from_str(&text).map_err(invalid_data)?
+    };
+    validate(&session)?;
+    Ok(session)
+}
+
+/// Rejects views and iteration limits no render could come from, as
+/// location files and batch lists do.
+fn validate(session: &Session) -> io::Result<()> {
+    let finite = |(x, y): (f64, f64)| x.is_finite() && y.is_finite();
+    if !(session.scale.is_finite() && session.scale > 0.0) {
+        return Err(invalid_data("scale must be positive"));
+    }
+    if !finite(session.center) {
+        return Err(invalid_data("center must be finite"));
+    }
+    if session.max_iter == 0 || session.orbit_max_iter == Some(0) {
+        return Err(invalid_data("iteration limits must be at least 1"));
+    }
+    if session.orbit.is_some_and(|orbit| !finite(orbit)) {
+        return Err(invalid_data("orbit must be finite"));
+    }
+    Ok(())
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+}
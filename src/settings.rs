@@ -0,0 +1,205 @@
+//! User preferences, persisted between runs through eframe's storage.
+
+use std::{num::NonZeroUsize, path::PathBuf};
+
+use mandelbrot_core::{FastRenderer, ParallelRenderer};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cache,
+    i18n::{Language, tr},
+    memory,
+};
+
+pub const STORAGE_KEY: &str = "settings";
+
+/// Which renderer computes the image shown in the window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Backend {
+    /// Whichever of the others was fastest in a benchmark at startup.
+    #[default]
+    Auto,
+    Scalar,
+    Parallel,
+    /// Single precision at shallow zooms, see [`mandelbrot_core::FastRenderer`].
+    Fast,
+    Gpu,
+}
+
+impl Backend {
+    pub const ALL: [Backend; 5] = [
+        Backend::Auto,
+        Backend::Scalar,
+        Backend::Parallel,
+        Backend::Fast,
+        Backend::Gpu,
+    ];
+}
+
+/// How much work goes into rendering the view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Quality {
+    Draft,
+    Normal,
+    High,
+    Ultra,
+}
+
+impl Quality {
+    pub const ALL: [Quality; 4] = [
+        Quality::Draft,
+        Quality::Normal,
+        Quality::High,
+        Quality::Ultra,
+    ];
+
+    pub fn name(self) -> String {
+        match self {
+            Quality::Draft => tr!("quality-draft"),
+            Quality::Normal => tr!("quality-normal"),
+            Quality::High => tr!("quality-high"),
+            Quality::Ultra => tr!("quality-ultra"),
+        }
+    }
+
+    /// Rendered pixels per pixel of the window in each direction, before
+    /// supersampling.
+    pub fn resolution_scale(self) -> f64 {
+        match self {
+            Quality::Draft => 0.5,
+            Quality::Normal | Quality::High | Quality::Ultra => 1.0,
+        }
+    }
+
+    /// Samples averaged into each pixel in each direction, for antialiasing:
+    /// 2 means 2×2 samples.
+    pub fn supersampling(self) -> usize {
+        match self {
+            Quality::Draft | Quality::Normal => 1,
+            Quality::High => 2,
+            Quality::Ultra => 3,
+        }
+    }
+
+    /// The iteration limit to render with when `max_iter` is set.
+    pub fn max_iter(self, max_iter: usize) -> usize {
+        match self {
+            Quality::Draft => (max_iter / 2).max(10),
+            Quality::Normal | Quality::High => max_iter,
+            Quality::Ultra => max_iter * 2,
+        }
+    }
+}
+
+/// The quality setting: a fixed preset, or one depending on what the user is
+/// doing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QualityMode {
+    Fixed(Quality),
+    /// Draft while zooming, panning, or on autopilot, High once idle.
+    Auto,
+}
+
+impl Default for QualityMode {
+    fn default() -> Self {
+        QualityMode::Fixed(Quality::Normal)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Directory F12 screenshots are saved into.
+    pub screenshot_dir: PathBuf,
+    /// Renderer for the interactive view. Falls back to the parallel CPU
+    /// renderer when the GPU isn't available.
+    pub backend: Backend,
+    /// Directory coloring plugins are loaded from at startup.
+    pub plugin_dir: PathBuf,
+    /// Whether to accept remote control connections on `remote_port`.
+    pub remote_enabled: bool,
+    pub remote_port: u16,
+    /// Memory budget for cached renders, in megabytes.
+    pub cache_mb: usize,
+    /// Cap on the memory of the frame shown, cached frames and exports
+    /// together, in megabytes.
+    pub memory_cap_mb: usize,
+    /// Whether to draw the view on the GPU while zooming, see
+    /// [`crate::gpu::ScreenRenderer`].
+    pub live_shader: bool,
+    /// Whether the view is rendered on the UI thread a slice per frame, each
+    /// taking at most `frame_budget_ms`, rather than all at once or in the
+    /// background. On by default on the web, which has no threads.
+    pub cooperative: bool,
+    pub frame_budget_ms: u32,
+    /// Threads for rendering on the CPU, or `None` for one per core.
+    pub threads: Option<NonZeroUsize>,
+    /// Whether exports run at lower priority than other programs.
+    pub low_priority_exports: bool,
+    pub quality: QualityMode,
+    /// Whether the orbit panel is shown in a window of its own rather than
+    /// beside the view.
+    pub orbit_window: bool,
+    pub language: Language,
+    /// Factor a notch of the mouse wheel, or a press of plus or minus, zooms
+    /// in or out by.
+    pub zoom_step: f64,
+    /// Whether turning the wheel towards you, rather than away, zooms in.
+    pub invert_wheel: bool,
+    /// Whether the wheel zooms about the point under the cursor, rather than
+    /// the center of the view.
+    pub zoom_about_cursor: bool,
+    /// Whether the wheel zooms in proportion to how far it turns, easing
+    /// into each step, rather than by a whole step at once for every turn.
+    pub smooth_zoom: bool,
+    /// Whether holding the primary button on the view, or Z or X, zooms
+    /// continuously, by `hold_zoom_rate` a second, rather than selecting
+    /// points.
+    pub hold_zoom: bool,
+    /// Factor the view zooms in or out by for every second of holding.
+    pub hold_zoom_rate: f64,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            screenshot_dir: PathBuf::from("screenshots"),
+            backend: Backend::default(),
+            plugin_dir: PathBuf::from("plugins"),
+            remote_enabled: false,
+            remote_port: 9001,
+            cache_mb: cache::DEFAULT_BUDGET_MB,
+            memory_cap_mb: memory::DEFAULT_CAP_MB,
+            live_shader: false,
+            cooperative: cfg!(target_arch = "wasm32"),
+            frame_budget_ms: 8,
+            threads: None,
+            low_priority_exports: true,
+            quality: QualityMode::default(),
+            orbit_window: false,
+            language: Language::default(),
+            zoom_step: 1.25,
+            invert_wheel: false,
+            zoom_about_cursor: true,
+            smooth_zoom: false,
+            hold_zoom: false,
+            hold_zoom_rate: 2.0,
+        }
+    }
+}
+
+impl Settings {
+    /// The parallel CPU renderer with the configured number of threads.
+    pub fn parallel_renderer(&self) -> ParallelRenderer {
+        self.threads
+            .map_or_else(ParallelRenderer::default, |threads| ParallelRenderer {
+                threads,
+            })
+    }
+
+    pub fn fast_renderer(&self) -> FastRenderer {
+        FastRenderer {
+            parallel: self.parallel_renderer(),
+        }
+    }
+}
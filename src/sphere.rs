@@ -0,0 +1,162 @@
+//! The Riemann sphere window: the whole plane wrapped around a globe that can
+//! be turned by dragging, with 0 at the south pole and infinity at the north
+//! pole.
+
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use eframe::egui;
+use mandelbrot_core::{
+    Formula, Palette, ParallelRenderer, colorize,
+    sphere::{from_angles, render_sphere, to_sphere},
+};
+
+use crate::{i18n::tr, to_color_image};
+
+/// Width of the map of the sphere wrapped around the globe, in pixels.
+const TEXTURE_WIDTH: usize = 1024;
+/// Diameter of the globe in points.
+const GLOBE_SIZE: f32 = 320.0;
+/// Segments of the globe's mesh around the equator; half as many from pole
+/// to pole.
+const SEGMENTS: usize = 96;
+/// Radians turned per point dragged.
+const DRAG_SPEED: f64 = 0.01;
+
+pub struct SphereWindow {
+    pub open: bool,
+    /// Rotation about the axis through 0 and infinity, in radians.
+    yaw: f64,
+    /// Tilt towards the viewer, in radians; negative shows more of the south
+    /// pole.
+    pitch: f64,
+    texture: Option<egui::TextureHandle>,
+    /// What `texture` was rendered with.
+    shown: Option<(Formula, usize, Palette)>,
+}
+
+impl Default for SphereWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            yaw: 0.0,
+            pitch: -0.6,
+            texture: None,
+            shown: None,
+        }
+    }
+}
+
+impl SphereWindow {
+    /// Shows the window, rendering the sphere again when the formula,
+    /// iteration limit, or palette changed. `selected` is the point whose
+    /// path is shown, marked on the globe.
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        renderer: &ParallelRenderer,
+        formula: Formula,
+        max_iter: usize,
+        palette: Palette,
+        selected: Option<(f64, f64)>,
+    ) {
+        if !self.open {
+            return;
+        }
+        let key = (formula, max_iter, palette);
+        if self.shown != Some(key) {
+            let buffer = render_sphere(renderer, formula, TEXTURE_WIDTH, max_iter);
+            let image = to_color_image(&colorize(&buffer, palette));
+            match &mut self.texture {
+                Some(texture) => texture.set(image, egui::TextureOptions::LINEAR),
+                None => {
+                    self.texture =
+                        Some(ctx.load_texture("sphere", image, egui::TextureOptions::LINEAR));
+                }
+            }
+            self.shown = Some(key);
+        }
+        let mut open = self.open;
+        egui::Window::new(tr!("sphere-title"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let (rect, response) =
+                    ui.allocate_exact_size(egui::Vec2::splat(GLOBE_SIZE), egui::Sense::drag());
+                let delta = response.drag_delta();
+                self.yaw += f64::from(delta.x) * DRAG_SPEED;
+                self.pitch =
+                    (self.pitch - f64::from(delta.y) * DRAG_SPEED).clamp(-FRAC_PI_2, FRAC_PI_2);
+                self.draw(&ui.painter_at(rect), rect, selected);
+                ui.horizontal(|ui| {
+                    ui.label(tr!("sphere-hint"));
+                    if ui.button(tr!("sphere-reset")).clicked() {
+                        let Self { yaw, pitch, .. } = Self::default();
+                        (self.yaw, self.pitch) = (yaw, pitch);
+                    }
+                });
+            });
+        self.open = open;
+    }
+
+    /// Where point `p` of the unit sphere appears in `rect`, and whether it's
+    /// on the side facing the viewer.
+    fn project(&self, rect: egui::Rect, p: [f64; 3]) -> (egui::Pos2, bool) {
+        let [x, y, z] = p;
+        let (sin_yaw, cos_yaw) = self.yaw.sin_cos();
+        let (x, y) = (x * cos_yaw - y * sin_yaw, x * sin_yaw + y * cos_yaw);
+        let (sin_pitch, cos_pitch) = self.pitch.sin_cos();
+        let (depth, up) = (z * sin_pitch - y * cos_pitch, y * sin_pitch + z * cos_pitch);
+        let radius = rect.width() / 2.0;
+        let pos = rect.center() + radius * egui::vec2(x as f32, -up as f32);
+        (pos, depth > 0.0)
+    }
+
+    fn draw(&self, painter: &egui::Painter, rect: egui::Rect, selected: Option<(f64, f64)>) {
+        let Some(texture) = &self.texture else {
+            return;
+        };
+        let (columns, rows) = (SEGMENTS, SEGMENTS / 2);
+        let mut mesh = egui::Mesh::with_texture(texture.id());
+        let mut facing = Vec::with_capacity((columns + 1) * (rows + 1));
+        for row in 0..=rows {
+            let v = row as f64 / rows as f64;
+            for column in 0..=columns {
+                let u = column as f64 / columns as f64;
+                let p = from_angles(u * 2.0 * PI - PI, FRAC_PI_2 - v * PI);
+                let (pos, front) = self.project(rect, p);
+                facing.push(front);
+                mesh.vertices.push(egui::epaint::Vertex {
+                    pos,
+                    uv: egui::pos2(u as f32, v as f32),
+                    color: egui::Color32::WHITE,
+                });
+            }
+        }
+        let index = |row: usize, column: usize| (row * (columns + 1) + column) as u32;
+        for row in 0..rows {
+            for column in 0..columns {
+                let quad = [
+                    index(row, column),
+                    index(row, column + 1),
+                    index(row + 1, column + 1),
+                    index(row + 1, column),
+                ];
+                // Quads on the limb are kept if any corner faces the viewer,
+                // so the outline isn't frayed; their back parts are edge-on
+                // and barely show
+                if quad.iter().any(|&i| facing[i as usize]) {
+                    mesh.add_triangle(quad[0], quad[1], quad[2]);
+                    mesh.add_triangle(quad[0], quad[2], quad[3]);
+                }
+            }
+        }
+        painter.circle_filled(rect.center(), rect.width() / 2.0, egui::Color32::BLACK);
+        painter.add(mesh);
+        if let Some(c) = selected
+            && let (pos, true) = self.project(rect, to_sphere(c))
+        {
+            painter.circle_filled(pos, 4.0, egui::Color32::YELLOW);
+            painter.circle_stroke(pos, 4.0, egui::Stroke::new(1.0, egui::Color32::BLACK));
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! Surprise Me: a random place on the boundary of the set at a random depth,
+//! checked by the same entropy measure as Find Detail so that frames with
+//! little structure are passed over.
+
+use mandelbrot_core::{
+    Formula, RenderSettings, Renderer, Tile, View, area::SET_BOUNDS, detail::entropy, escape_time,
+    random::SplitMix64,
+};
+
+use crate::autopilot::max_iter_for;
+
+/// Range of depths chosen from, in doublings of the default zoom. Deeper
+/// than that, `f64` can't tell the pixels of a view apart.
+const DOUBLINGS: (f64, f64) = (3.0, 36.0);
+/// Side in pixels of the preview a location is checked with.
+const PREVIEW_SIZE: usize = 64;
+/// A preview with less entropy in bits is too plain to show.
+const MIN_ENTROPY: f64 = 3.0;
+/// Locations tried before giving up.
+const TRIES: usize = 20;
+
+/// A location found by [`surprise`].
+pub struct Surprise {
+    pub view: View,
+    pub max_iter: usize,
+    /// Entropy in bits of the escape times of a preview of the view.
+    pub entropy: f64,
+}
+
+/// A random location of z → z² + c showing part of the boundary, with an
+/// iteration limit for its depth, checked with a preview rendered by
+/// `renderer`. `None` if no try was detailed enough.
+pub fn surprise(rng: &mut SplitMix64, renderer: &impl Renderer) -> Option<Surprise> {
+    (0..TRIES).find_map(|_| {
+        let doublings = DOUBLINGS.0 + (DOUBLINGS.1 - DOUBLINGS.0) * rng.next_f64();
+        let mut view = View {
+            scale: View::default().scale / doublings.exp2(),
+            ..View::default()
+        };
+        let max_iter = max_iter_for(&view);
+        view.center = boundary_point(rng, max_iter, view.pixel_size(PREVIEW_SIZE))?;
+        let settings = RenderSettings {
+            width: PREVIEW_SIZE,
+            height: PREVIEW_SIZE,
+            max_iter,
+            formula: Formula::Mandelbrot,
+        };
+        let preview = renderer.render(&view, &settings);
+        let whole = Tile {
+            x: 0,
+            y: 0,
+            width: PREVIEW_SIZE,
+            height: PREVIEW_SIZE,
+        };
+        let entropy = entropy(&preview, &whole);
+        (entropy >= MIN_ENTROPY).then_some(Surprise {
+            view,
+            max_iter,
+            entropy,
+        })
+    })
+}
+
+/// A point within `precision` of the boundary of the set at `max_iter`
+/// iterations: where the segment between a random point outside the set and
+/// a random point inside crosses it, found by bisection.
+fn boundary_point(rng: &mut SplitMix64, max_iter: usize, precision: f64) -> Option<(f64, f64)> {
+    let inside = |c| escape_time(c, max_iter) >= max_iter;
+    let mut random_point = |wanted_inside: bool| {
+        let (re_min, re_max, im_min, im_max) = SET_BOUNDS;
+        (0..1000)
+            .map(|_| {
+                let re = re_min + (re_max - re_min) * rng.next_f64();
+                let im = im_min + (im_max - im_min) * rng.next_f64();
+                (re, im)
+            })
+            .find(|&c| inside(c) == wanted_inside)
+    };
+    let (mut outside, mut inner) = (random_point(false)?, random_point(true)?);
+    while (outside.0 - inner.0).hypot(outside.1 - inner.1) > precision {
+        let middle = ((outside.0 + inner.0) / 2.0, (outside.1 + inner.1) / 2.0);
+        if inside(middle) {
+            inner = middle;
+        } else {
+            outside = middle;
+        }
+    }
+    Some(outside)
+}
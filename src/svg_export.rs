@@ -0,0 +1,185 @@
+//! Exporting what is drawn over the view — the path, annotations, axes, a
+//! grid, and the outlines of the main cardioid and period 2 disk — as an SVG
+//! figure lined up with a render of the view at a chosen size, to be
+//! finished in a vector editor such as Inkscape.
+
+use std::{fs, path::Path};
+
+use eframe::egui;
+use mandelbrot_core::{
+    Formula, View,
+    interior::{main_cardioid, period_two_disk},
+    svg::{Stroke, Svg},
+};
+
+use crate::{
+    OTHER_PATH_COLOR,
+    annotations::{self, Annotation},
+    display_name, export,
+    i18n::tr,
+};
+
+/// Points along each outline of the main cardioid and period 2 disk.
+const OUTLINE_SAMPLES: usize = 720;
+const PATH_COLOR: egui::Color32 = egui::Color32::YELLOW;
+const AXES_COLOR: [u8; 4] = [255, 255, 255, 200];
+const GRID_COLOR: [u8; 4] = [255, 255, 255, 80];
+const OUTLINE_COLOR: [u8; 4] = [255, 80, 80, 255];
+/// Font size of annotation labels in pixels, as in the view.
+const LABEL_SIZE: f64 = 14.0;
+
+/// What the view has drawn over it.
+pub struct Layers<'a> {
+    /// The path of the selected point.
+    pub path: &'a [(f64, f64)],
+    /// The path of the second critical point, for formulas with one.
+    pub other_path: &'a [(f64, f64)],
+    pub annotations: &'a [Annotation],
+}
+
+/// The File → Export Overlays as SVG window.
+pub struct SvgExportWindow {
+    pub open: bool,
+    /// Size of the render the figure lines up with, in pixels.
+    pub size: [usize; 2],
+    path: bool,
+    annotations: bool,
+    axes: bool,
+    grid: bool,
+    /// Outlines of the main cardioid and period 2 disk.
+    outlines: bool,
+    message: Option<String>,
+}
+
+impl Default for SvgExportWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            size: [1024, 1024],
+            path: true,
+            annotations: true,
+            axes: false,
+            grid: false,
+            outlines: false,
+            message: None,
+        }
+    }
+}
+
+impl SvgExportWindow {
+    pub fn show(&mut self, ctx: &egui::Context, view: &View, formula: Formula, layers: Layers) {
+        if !self.open {
+            return;
+        }
+        let mut open = self.open;
+        egui::Window::new(tr!("svg-export-title"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr!("batch-size"));
+                    for side in &mut self.size {
+                        ui.add(egui::DragValue::new(side).range(16..=16384).suffix(" px"));
+                    }
+                });
+                ui.weak(tr!("svg-export-size-hint"));
+                ui.checkbox(&mut self.path, tr!("svg-export-path"));
+                ui.checkbox(&mut self.annotations, tr!("svg-export-annotations"));
+                ui.checkbox(&mut self.axes, tr!("svg-export-axes"));
+                ui.checkbox(&mut self.grid, tr!("svg-export-grid"));
+                // The outlines are of the components of z → z² + c
+                let mandelbrot = formula == Formula::Mandelbrot;
+                ui.add_enabled(
+                    mandelbrot,
+                    egui::Checkbox::new(&mut self.outlines, tr!("svg-export-outlines")),
+                );
+                if ui.button(tr!("svg-export-save")).clicked() {
+                    let name = export::screenshot_name(view).replace(".png", ".svg");
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("SVG", &["svg"])
+                        .set_file_name(name)
+                        .save_file()
+                    {
+                        let text = self.figure(view, mandelbrot, &layers);
+                        self.message = Some(save(&path, &text));
+                    }
+                }
+                if let Some(message) = &self.message {
+                    ui.label(message);
+                }
+            });
+        self.open = open;
+    }
+
+    /// The chosen layers over `view` as an SVG document, each a layer of its
+    /// own, from the bottom up.
+    fn figure(&self, view: &View, mandelbrot: bool, layers: &Layers) -> String {
+        let [width, height] = self.size;
+        let mut svg = Svg::new(view, width, height);
+        if self.grid {
+            svg.begin_group(&tr!("svg-export-grid"));
+            svg.grid(Stroke::new(1.0, GRID_COLOR));
+            svg.end_group();
+        }
+        if self.axes {
+            svg.begin_group(&tr!("svg-export-axes"));
+            svg.axes(Stroke::new(1.5, AXES_COLOR));
+            svg.end_group();
+        }
+        if self.outlines && mandelbrot {
+            svg.begin_group(&tr!("svg-export-outlines"));
+            let stroke = Stroke::new(1.5, OUTLINE_COLOR);
+            svg.polyline(&main_cardioid(OUTLINE_SAMPLES), stroke);
+            svg.polyline(&period_two_disk(OUTLINE_SAMPLES), stroke);
+            svg.end_group();
+        }
+        if self.path && !layers.path.is_empty() {
+            svg.begin_group(&tr!("svg-export-path"));
+            svg.polyline(
+                layers.other_path,
+                Stroke::new(2.0, OTHER_PATH_COLOR.to_array()),
+            );
+            svg.polyline(layers.path, Stroke::new(2.0, PATH_COLOR.to_array()));
+            svg.end_group();
+        }
+        if self.annotations && !layers.annotations.is_empty() {
+            svg.begin_group(&tr!("svg-export-annotations"));
+            draw_annotations(&mut svg, layers.annotations);
+            svg.end_group();
+        }
+        svg.finish()
+    }
+}
+
+/// Draws `annotations` as the view does, outlined in a shadow.
+fn draw_annotations(svg: &mut Svg, list: &[Annotation]) {
+    let color = annotations::COLOR.to_array();
+    let shadow = Stroke::new(4.0, annotations::SHADOW.to_array());
+    let stroke = Stroke::new(2.0, color);
+    let radius = f64::from(annotations::MARKER_RADIUS);
+    for annotation in list {
+        match annotation {
+            Annotation::Label { at, text } => svg.label(*at, text, LABEL_SIZE, color, shadow),
+            Annotation::Arrow { from, to } => {
+                svg.arrow(*from, *to, shadow);
+                svg.arrow(*from, *to, stroke);
+            }
+            Annotation::Marker { at } => {
+                svg.circle(*at, radius, shadow);
+                svg.circle(*at, radius, stroke);
+            }
+        }
+    }
+}
+
+/// Writes `text` to `path`, describing how it went.
+fn save(path: &Path, text: &str) -> String {
+    match fs::write(path, text) {
+        Ok(()) => tr!("status-exported", name = display_name(path)),
+        Err(err) => tr!(
+            "status-export-failed",
+            name = display_name(path),
+            error = err.to_string()
+        ),
+    }
+}
@@ -0,0 +1,252 @@
+//! The Analysis → Tile Telemetry window: the frame shown rendered again tile
+//! by tile, timing each, with a heat map of the times over the view and a
+//! list of the slowest tiles, to see where rendering time goes.
+
+use std::{num::NonZeroUsize, sync::Mutex, thread, time::Duration};
+
+use eframe::egui;
+use mandelbrot_core::{ParallelRenderer, RenderSettings, Tile, View};
+use tracing::info;
+use web_time::Instant;
+
+use crate::i18n::tr;
+
+/// Tile sizes to choose from, in pixels.
+const TILE_SIZES: [usize; 3] = [16, 32, 64];
+/// Tiles listed and outlined as the slowest.
+const SLOWEST: usize = 10;
+/// Opacity of the heat map over the view.
+const HEAT_ALPHA: f32 = 0.55;
+
+/// How long each tile of a render took.
+struct Telemetry {
+    view: View,
+    settings: RenderSettings,
+    /// The tiles and their times, row by row from the top-left.
+    tiles: Vec<(Tile, Duration)>,
+    /// Time from the first tile started to the last finished.
+    elapsed: Duration,
+}
+
+impl Telemetry {
+    /// Renders `view` as `settings` say in `size`×`size` tiles, each on one of
+    /// `renderer`'s threads, timing them.
+    fn measure(
+        renderer: &ParallelRenderer,
+        view: &View,
+        settings: &RenderSettings,
+        size: usize,
+    ) -> Self {
+        let mut tiles: Vec<(Tile, Duration)> = (0..settings.height)
+            .step_by(size)
+            .flat_map(|y| {
+                (0..settings.width).step_by(size).map(move |x| Tile {
+                    x,
+                    y,
+                    width: size.min(settings.width - x),
+                    height: size.min(settings.height - y),
+                })
+            })
+            .map(|tile| (tile, Duration::ZERO))
+            .collect();
+        // Each tile on a single thread, so its time is what it costs
+        let single = ParallelRenderer {
+            threads: NonZeroUsize::MIN,
+        };
+        let time = |(tile, time): &mut (Tile, Duration)| {
+            let started = Instant::now();
+            single.render_tile(view, settings, tile);
+            *time = started.elapsed();
+        };
+        let started = Instant::now();
+        if renderer.threads.get() == 1 {
+            tiles.iter_mut().for_each(time);
+        } else {
+            let queue = Mutex::new(tiles.iter_mut());
+            thread::scope(|scope| {
+                for _ in 0..renderer.threads.get() {
+                    scope.spawn(|| {
+                        loop {
+                            let Some(tile) = queue.lock().unwrap().next() else {
+                                break;
+                            };
+                            time(tile);
+                        }
+                    });
+                }
+            });
+        }
+        let elapsed = started.elapsed();
+        info!(
+            tiles = tiles.len(),
+            ms = elapsed.as_secs_f64() * 1000.0,
+            "measured tile times"
+        );
+        Self {
+            view: *view,
+            settings: *settings,
+            tiles,
+            elapsed,
+        }
+    }
+
+    /// Indices of the slowest tiles, slowest first.
+    fn slowest(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.tiles.len()).collect();
+        order.sort_by(|&a, &b| self.tiles[b].1.cmp(&self.tiles[a].1));
+        order.truncate(SLOWEST);
+        order
+    }
+
+    fn max_time(&self) -> Duration {
+        (self.tiles.iter().map(|(_, time)| *time))
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Where `tile` appears over a rendering of the view in `image_rect`.
+    fn screen_rect(&self, tile: &Tile, image_rect: egui::Rect) -> egui::Rect {
+        let scale = egui::vec2(
+            image_rect.width() / self.settings.width as f32,
+            image_rect.height() / self.settings.height as f32,
+        );
+        let min = egui::vec2(tile.x as f32, tile.y as f32) * scale;
+        let size = egui::vec2(tile.width as f32, tile.height as f32) * scale;
+        egui::Rect::from_min_size(image_rect.min + min, size)
+    }
+}
+
+/// `t` from 0 to 1 as a color from dark blue through red to yellow.
+fn heat(t: f32) -> egui::Color32 {
+    let t = t.clamp(0.0, 1.0);
+    let channel = |v: f32| (v.clamp(0.0, 1.0) * 255.0) as u8;
+    let color = egui::Color32::from_rgb(channel(t * 2.0), channel(t * 2.0 - 1.0), channel(0.5 - t));
+    color.gamma_multiply(HEAT_ALPHA)
+}
+
+pub struct TelemetryWindow {
+    pub open: bool,
+    tile_size: usize,
+    heat_map: bool,
+    result: Option<Telemetry>,
+}
+
+impl Default for TelemetryWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            tile_size: 32,
+            heat_map: true,
+            result: None,
+        }
+    }
+}
+
+impl TelemetryWindow {
+    /// Shows the window. `settings` are those of the frame shown, of `view`,
+    /// if there is one.
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        renderer: &ParallelRenderer,
+        view: &View,
+        settings: Option<RenderSettings>,
+    ) {
+        let mut open = self.open;
+        egui::Window::new(tr!("telemetry-title"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr!("telemetry-tile-size"));
+                    for size in TILE_SIZES {
+                        ui.selectable_value(&mut self.tile_size, size, format!("{size} px"));
+                    }
+                });
+                ui.checkbox(&mut self.heat_map, tr!("telemetry-heat-map"))
+                    .on_hover_text(tr!("telemetry-heat-map-hover"));
+                if ui
+                    .add_enabled(
+                        settings.is_some(),
+                        egui::Button::new(tr!("telemetry-measure")),
+                    )
+                    .on_hover_text(tr!("telemetry-measure-hover"))
+                    .clicked()
+                    && let Some(settings) = settings
+                {
+                    self.result = Some(Telemetry::measure(
+                        renderer,
+                        view,
+                        &settings,
+                        self.tile_size,
+                    ));
+                }
+                let Some(result) = &self.result else {
+                    ui.weak(tr!("telemetry-hint"));
+                    return;
+                };
+                if result.view != *view {
+                    ui.weak(tr!("telemetry-stale"));
+                }
+                let total: Duration = result.tiles.iter().map(|(_, time)| *time).sum();
+                ui.label(tr!(
+                    "telemetry-summary",
+                    tiles = result.tiles.len(),
+                    total = format!("{:.1}", total.as_secs_f64() * 1000.0),
+                    elapsed = format!("{:.1}", result.elapsed.as_secs_f64() * 1000.0),
+                    max_iter = result.settings.max_iter,
+                ));
+                ui.separator();
+                ui.label(tr!("telemetry-slowest"));
+                egui::Grid::new("slowest_tiles")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for i in result.slowest() {
+                            let (tile, time) = &result.tiles[i];
+                            let (re, im) = result.view.pixel_to_complex(
+                                (tile.x + tile.width / 2) as f64,
+                                (tile.y + tile.height / 2) as f64,
+                                result.settings.width,
+                                result.settings.height,
+                            );
+                            ui.label(format!("{:.2} ms", time.as_secs_f64() * 1000.0));
+                            ui.label(format!(
+                                "{:.1}%",
+                                time.as_secs_f64() / total.as_secs_f64().max(f64::MIN_POSITIVE)
+                                    * 100.0
+                            ));
+                            ui.label(tr!("telemetry-tile", x = tile.x, y = tile.y));
+                            ui.weak(format!("{re:.6} {im:+.6}i"));
+                            ui.end_row();
+                        }
+                    });
+            });
+        self.open = open;
+    }
+
+    /// Draws the heat map of the tile times over `image_rect`, the view as
+    /// shown, while the window is open and the times are of `view`, with the
+    /// slowest tiles outlined.
+    pub fn draw(&self, painter: &egui::Painter, image_rect: egui::Rect, view: &View) {
+        let Some(result) = &self.result else {
+            return;
+        };
+        if !self.open || !self.heat_map || result.view != *view {
+            return;
+        }
+        let max = result.max_time().as_secs_f32().max(f32::MIN_POSITIVE);
+        for (tile, time) in &result.tiles {
+            let rect = result.screen_rect(tile, image_rect);
+            painter.rect_filled(rect, 0.0, heat(time.as_secs_f32() / max));
+        }
+        for i in result.slowest() {
+            let rect = result.screen_rect(&result.tiles[i].0, image_rect);
+            painter.rect_stroke(
+                rect,
+                0.0,
+                egui::Stroke::new(1.5, egui::Color32::WHITE),
+                egui::StrokeKind::Inside,
+            );
+        }
+    }
+}
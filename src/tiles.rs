@@ -0,0 +1,209 @@
+//! Serving the set as slippy-map tiles over HTTP.
+//!
+//! `GET /{z}/{x}/{y}.png` returns a 256×256 tile in the usual XYZ scheme: zoom
+//! level 0 is a single tile covering re ∈ [-2.5, 1.5], im ∈ [-2, 2], and each
+//! level splits every tile into four, with x growing to the right and y
+//! downwards. `GET /` serves a Leaflet page for browsing the tiles.
+
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use mandelbrot_core::{Formula, Palette, RenderSettings, Renderer, ScalarRenderer, View, colorize};
+use tracing::{debug, info_span, warn};
+
+use crate::{cache::LruCache, export};
+
+/// Width and height of a tile in pixels.
+pub const TILE_SIZE: usize = 256;
+/// Deepest zoom level served; beyond it `f64` can't tell pixels apart.
+pub const MAX_ZOOM: u32 = 40;
+/// Most bytes of request line and headers read, so that a client can't make a
+/// connection buffer without end.
+const MAX_HEAD_LEN: u64 = 8 << 10;
+/// The area covered by the zoom 0 tile.
+const WORLD: View = View {
+    center: (-0.5, 0.0),
+    scale: 4.0,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct TileServer {
+    /// Iteration limit at zoom level 0.
+    pub max_iter: usize,
+    /// Added to the iteration limit for each zoom level.
+    pub iter_per_zoom: usize,
+    pub palette: Palette,
+    /// Memory budget for rendered tiles, in megabytes.
+    pub cache_mb: usize,
+}
+
+/// Encoded tiles by zoom level and position, shared by all connections.
+type TileCache = Mutex<LruCache<(u32, u64, u64), Arc<Vec<u8>>>>;
+
+impl TileServer {
+    /// Serves tiles on `addr` until the process is stopped.
+    pub fn serve(self, addr: impl ToSocketAddrs) -> io::Result<()> {
+        let listener = TcpListener::bind(addr)?;
+        eprintln!("serving tiles on http://{}/", listener.local_addr()?);
+        let cache: Arc<TileCache> = Arc::new(Mutex::new(LruCache::new(self.cache_mb << 20)));
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    let cache = cache.clone();
+                    thread::spawn(move || {
+                        if let Err(err) = self.handle(stream, &cache) {
+                            warn!("request failed: {err}");
+                        }
+                    });
+                }
+                Err(err) => warn!("could not accept connection: {err}"),
+            }
+        }
+        Ok(())
+    }
+
+    fn handle(&self, stream: TcpStream, cache: &TileCache) -> io::Result<()> {
+        let mut reader = BufReader::new((&stream).take(MAX_HEAD_LEN));
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line)?;
+        // Skip the headers, up to the empty line that ends them
+        let mut line = String::new();
+        let mut ended = false;
+        while reader.read_line(&mut line)? > 0 {
+            if line.trim_end().is_empty() {
+                ended = true;
+                break;
+            }
+            line.clear();
+        }
+        if !ended && reader.get_ref().limit() == 0 {
+            return respond(
+                &mut &stream,
+                "431 Request Header Fields Too Large",
+                "text/plain",
+                b"request too long",
+            );
+        }
+        let mut parts = request_line.split_whitespace();
+        let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+        let _span = info_span!("tile request", path).entered();
+        // A query, such as a cache buster, doesn't change what's asked for
+        let path = path.split_once('?').map_or(path, |(path, _)| path);
+        let mut stream = &stream;
+        if method != "GET" {
+            return respond(
+                &mut stream,
+                "405 Method Not Allowed",
+                "text/plain",
+                b"GET only",
+            );
+        }
+        if path == "/" {
+            return respond(&mut stream, "200 OK", "text/html", INDEX_HTML.as_bytes());
+        }
+        let Some((z, x, y)) = parse_tile_path(path) else {
+            return respond(&mut stream, "404 Not Found", "text/plain", b"not a tile");
+        };
+        // Not held while rendering, so two requests for the same new tile may
+        // both render it
+        let cached = cache.lock().unwrap().get(&(z, x, y)).cloned();
+        let hit = cached.is_some();
+        let png = match cached {
+            Some(png) => png,
+            None => {
+                let png = Arc::new(self.render_tile(z, x, y)?);
+                cache.lock().unwrap().insert((z, x, y), png.clone());
+                png
+            }
+        };
+        let stats = cache.lock().unwrap().stats();
+        debug!(
+            bytes = png.len(),
+            cached = hit,
+            hits = stats.hits,
+            misses = stats.misses,
+            evictions = stats.evictions,
+            "served tile"
+        );
+        respond(&mut stream, "200 OK", "image/png", &png)
+    }
+
+    /// Renders tile `x`, `y` of zoom level `z` as a PNG.
+    pub fn render_tile(&self, z: u32, x: u64, y: u64) -> io::Result<Vec<u8>> {
+        let tiles = (1u64 << z) as f64;
+        let scale = WORLD.scale / tiles;
+        let left = WORLD.center.0 - WORLD.scale / 2.0;
+        let top = WORLD.center.1 - WORLD.scale / 2.0;
+        let view = View {
+            center: (
+                left + (x as f64 + 0.5) * scale,
+                top + (y as f64 + 0.5) * scale,
+            ),
+            scale,
+        };
+        let settings = RenderSettings {
+            width: TILE_SIZE,
+            height: TILE_SIZE,
+            max_iter: self.max_iter + self.iter_per_zoom * z as usize,
+            formula: Formula::Mandelbrot,
+        };
+        // Each connection has its own thread already
+        let image = colorize(&ScalarRenderer.render(&view, &settings), self.palette);
+        let mut png = Vec::new();
+        export::encode_png(&mut png, &image, None)?;
+        Ok(png)
+    }
+}
+
+/// Parses `/{z}/{x}/{y}.png`, rejecting tiles outside the zoom level.
+fn parse_tile_path(path: &str) -> Option<(u32, u64, u64)> {
+    let mut parts = path.strip_prefix('/')?.strip_suffix(".png")?.split('/');
+    let z: u32 = parts.next()?.parse().ok()?;
+    let x: u64 = parts.next()?.parse().ok()?;
+    let y: u64 = parts.next()?.parse().ok()?;
+    if parts.next().is_some() || z > MAX_ZOOM || x >> z != 0 || y >> z != 0 {
+        return None;
+    }
+    Some((z, x, y))
+}
+
+fn respond(
+    stream: &mut impl Write,
+    status: &str,
+    content_type: &str,
+    body: &[u8],
+) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\n\
+         Cache-Control: public, max-age=86400\r\nAccess-Control-Allow-Origin: *\r\n\
+         Connection: close\r\n\r\n",
+        body.len()
+    )?;
+    stream.write_all(body)?;
+    stream.flush()
+}
+
+const INDEX_HTML: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Mandelbrot Tiles</title>
+<link rel="stylesheet" href="https://unpkg.com/leaflet@1.9.4/dist/leaflet.css">
+<script src="https://unpkg.com/leaflet@1.9.4/dist/leaflet.js"></script>
+<style>html, body, #map { height: 100%; margin: 0; background: #000; }</style>
+</head>
+<body>
+<div id="map"></div>
+<script>
+const map = L.map("map", { crs: L.CRS.Simple, maxZoom: 40 }).setView([-128, 128], 1);
+L.tileLayer("/{z}/{x}/{y}.png", { noWrap: true, maxZoom: 40, maxNativeZoom: 40,
+    bounds: [[0, 0], [-256, 256]] }).addTo(map);
+</script>
+</body>
+</html>
+"#;
@@ -0,0 +1,253 @@
+//! The timeline window: keyframes of the view, iteration limit, and formula
+//! parameters, played back in the view and, on desktop, exported as frames.
+//! Keyframes are saved in session files.
+
+use std::sync::Arc;
+
+use eframe::egui;
+use mandelbrot_core::{
+    Palette,
+    timeline::{Keyframe, duration, frame_at},
+};
+#[cfg(not(target_arch = "wasm32"))]
+use mandelbrot_core::{RenderSettings, Renderer, colorize};
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::video::{ExportOptions, VideoJob};
+use crate::{animation::Clock, i18n::tr, scheduler::Scheduler, settings::Settings};
+
+/// Seconds after the last keyframe that a new one is added at.
+const KEYFRAME_SPACING: f64 = 2.0;
+
+pub struct TimelineWindow {
+    pub open: bool,
+    /// Keyframes sorted by time.
+    pub keyframes: Vec<Keyframe>,
+    /// Seconds from the start of the animation shown.
+    clock: Clock,
+    looping: bool,
+    #[cfg(not(target_arch = "wasm32"))]
+    export: ExportOptions,
+    #[cfg(not(target_arch = "wasm32"))]
+    job: Option<VideoJob>,
+    #[cfg(not(target_arch = "wasm32"))]
+    message: Option<String>,
+}
+
+// Only the export options differ from their defaults
+#[cfg_attr(target_arch = "wasm32", allow(clippy::derivable_impls))]
+impl Default for TimelineWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            keyframes: Vec::new(),
+            clock: Clock::default(),
+            looping: false,
+            #[cfg(not(target_arch = "wasm32"))]
+            export: ExportOptions::new("timeline"),
+            #[cfg(not(target_arch = "wasm32"))]
+            job: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            message: None,
+        }
+    }
+}
+
+impl TimelineWindow {
+    /// Shows the window, advancing playback. `current` is what the view
+    /// shows now, which new keyframes take. Returns the frame to show in the
+    /// view, while playing, scrubbing, or going to a keyframe.
+    ///
+    /// `settings` and `scheduler` are for exports, which the web version
+    /// doesn't have.
+    #[cfg_attr(target_arch = "wasm32", allow(unused_variables))]
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        current: Keyframe,
+        palette: Palette,
+        settings: &Settings,
+        scheduler: &Arc<Scheduler>,
+    ) -> Option<Keyframe> {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(message) = VideoJob::poll(&mut self.job) {
+            self.message = Some(message);
+        }
+        let mut shown = None;
+        let end = duration(&self.keyframes);
+        if self.clock.is_playing() {
+            self.clock.keep_within(end, self.looping);
+            shown = frame_at(&self.keyframes, self.clock.time());
+            ctx.request_repaint();
+        }
+        if !self.open {
+            return shown;
+        }
+        let mut open = self.open;
+        egui::Window::new(tr!("timeline-title"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                if ui
+                    .button(tr!("timeline-add"))
+                    .on_hover_text(tr!("timeline-add-hover"))
+                    .clicked()
+                {
+                    let time = self
+                        .keyframes
+                        .last()
+                        .map_or(0.0, |k| k.time + KEYFRAME_SPACING);
+                    self.keyframes.push(Keyframe { time, ..current });
+                }
+                let mut remove = None;
+                let mut sort = false;
+                egui::ScrollArea::vertical()
+                    .max_height(200.0)
+                    .show(ui, |ui| {
+                        for (i, keyframe) in self.keyframes.iter_mut().enumerate() {
+                            ui.horizontal(|ui| {
+                                if ui
+                                    .small_button("🗑")
+                                    .on_hover_text(tr!("timeline-remove"))
+                                    .clicked()
+                                {
+                                    remove = Some(i);
+                                }
+                                let time = ui.add(
+                                    egui::DragValue::new(&mut keyframe.time)
+                                        .range(0.0..=3600.0)
+                                        .speed(0.05)
+                                        .suffix(" s"),
+                                );
+                                sort |= time.changed();
+                                if ui
+                                    .small_button(tr!("timeline-go-to"))
+                                    .on_hover_text(tr!("timeline-go-to-hover"))
+                                    .clicked()
+                                {
+                                    self.clock.pause();
+                                    self.clock.seek(keyframe.time);
+                                    shown = Some(*keyframe);
+                                }
+                                ui.label(tr!(
+                                    "timeline-keyframe",
+                                    formula = keyframe.formula.name(),
+                                    zoom = format!("{:.3e}", keyframe.view.zoom()),
+                                    max_iter = keyframe.max_iter,
+                                ));
+                            });
+                        }
+                    });
+                if let Some(i) = remove {
+                    self.keyframes.remove(i);
+                }
+                if sort {
+                    self.keyframes.sort_by(|a, b| a.time.total_cmp(&b.time));
+                }
+                if self.keyframes.is_empty() {
+                    ui.weak(tr!("timeline-none"));
+                }
+                let end = duration(&self.keyframes);
+                ui.add_enabled_ui(self.keyframes.len() > 1, |ui| {
+                    ui.horizontal(|ui| {
+                        let (icon, hover) = if self.clock.is_playing() {
+                            ("⏸", tr!("playback-pause"))
+                        } else {
+                            ("▶", tr!("playback-play"))
+                        };
+                        if ui.button(icon).on_hover_text(hover).clicked() {
+                            self.clock.toggle(end);
+                        }
+                        let mut time = self.clock.time().min(end);
+                        let slider = ui.add(
+                            egui::Slider::new(&mut time, 0.0..=end)
+                                .suffix(" s")
+                                .fixed_decimals(1),
+                        );
+                        if slider.changed() {
+                            self.clock.seek(time);
+                            shown = frame_at(&self.keyframes, time);
+                        }
+                        ui.checkbox(&mut self.looping, tr!("playback-loop"));
+                    });
+                });
+                #[cfg(not(target_arch = "wasm32"))]
+                self.show_export(ui, palette, settings, scheduler);
+            });
+        self.open = open;
+        shown
+    }
+
+    /// The export controls: where to, at what frame rate and size, and the
+    /// progress of a running export.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn show_export(
+        &mut self,
+        ui: &mut egui::Ui,
+        palette: Palette,
+        settings: &Settings,
+        scheduler: &Arc<Scheduler>,
+    ) {
+        ui.separator();
+        let running = self.job.is_some();
+        ui.add_enabled_ui(!running, |ui| self.export.show(ui));
+        if let Some(job) = &self.job {
+            job.show_progress(ui, scheduler);
+        } else if ui
+            .add_enabled(
+                self.keyframes.len() > 1,
+                egui::Button::new(tr!("timeline-export")),
+            )
+            .on_hover_text(tr!("video-export-hover"))
+            .clicked()
+        {
+            self.job = Some(export(
+                ui.ctx(),
+                self.keyframes.clone(),
+                palette,
+                &self.export,
+                settings,
+                scheduler,
+            ));
+            self.message = None;
+        }
+        if let Some(message) = &self.message {
+            ui.label(message);
+        }
+    }
+}
+
+/// Starts exporting the animation through `keyframes`, sorted by time, from
+/// the first to the last, as `options` say.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn export(
+    ctx: &egui::Context,
+    keyframes: Vec<Keyframe>,
+    palette: Palette,
+    options: &ExportOptions,
+    settings: &Settings,
+    scheduler: &Arc<Scheduler>,
+) -> VideoJob {
+    let (size, fps) = (options.size, options.fps);
+    let start = keyframes.first().map_or(0.0, |k| k.time);
+    let total = ((duration(&keyframes) - start) * f64::from(fps)).round() as usize + 1;
+    let renderer = settings.parallel_renderer();
+    VideoJob::start(
+        ctx,
+        scheduler.clone(),
+        settings.low_priority_exports,
+        options,
+        total,
+        move |i| {
+            let time = start + i as f64 / f64::from(fps);
+            let frame = frame_at(&keyframes, time).expect("an animation has keyframes");
+            let settings = RenderSettings {
+                width: size,
+                height: size,
+                max_iter: frame.max_iter,
+                formula: frame.formula,
+            };
+            colorize(&renderer.render(&frame.view, &settings), palette)
+        },
+    )
+}
@@ -0,0 +1,229 @@
+//! The guided tour: famous places of the set, visited in turn with a caption
+//! for each. The tour is a timeline like the one of the timeline window,
+//! pulling back between stops far enough to take in both.
+
+use eframe::egui;
+use mandelbrot_core::{
+    Formula, View,
+    timeline::{Keyframe, duration, frame_at},
+};
+
+use crate::{animation::Clock, i18n::tr};
+
+/// Seconds the tour stays at each stop.
+const HOLD_SECONDS: f64 = 5.0;
+/// Seconds it takes from one stop to the next.
+const TRAVEL_SECONDS: f64 = 6.0;
+/// How much wider than the distance between two stops the view gets on the
+/// way, so that both are in it.
+const OVERVIEW_MARGIN: f64 = 1.5;
+
+/// A place the tour stops at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stop {
+    WholeSet,
+    SeahorseValley,
+    ElephantValley,
+    /// The largest minibrot on the real axis, of period 3.
+    Minibrot,
+    Feigenbaum,
+    Spiral,
+}
+
+impl Stop {
+    /// The stops in the order the tour visits them.
+    const ALL: [Stop; 6] = [
+        Stop::WholeSet,
+        Stop::SeahorseValley,
+        Stop::ElephantValley,
+        Stop::Minibrot,
+        Stop::Feigenbaum,
+        Stop::Spiral,
+    ];
+
+    fn name(self) -> String {
+        match self {
+            Stop::WholeSet => tr!("tour-whole-set"),
+            Stop::SeahorseValley => tr!("tour-seahorse-valley"),
+            Stop::ElephantValley => tr!("tour-elephant-valley"),
+            Stop::Minibrot => tr!("tour-minibrot"),
+            Stop::Feigenbaum => tr!("tour-feigenbaum"),
+            Stop::Spiral => tr!("tour-spiral"),
+        }
+    }
+
+    fn caption(self) -> String {
+        match self {
+            Stop::WholeSet => tr!("tour-whole-set-caption"),
+            Stop::SeahorseValley => tr!("tour-seahorse-valley-caption"),
+            Stop::ElephantValley => tr!("tour-elephant-valley-caption"),
+            Stop::Minibrot => tr!("tour-minibrot-caption"),
+            Stop::Feigenbaum => tr!("tour-feigenbaum-caption"),
+            Stop::Spiral => tr!("tour-spiral-caption"),
+        }
+    }
+
+    /// The view of the stop and the iteration limit it is shown with.
+    fn location(self) -> (View, usize) {
+        let (center, scale, max_iter) = match self {
+            Stop::WholeSet => ((-0.5, 0.0), 3.0, 100),
+            Stop::SeahorseValley => ((-0.7463, 0.1102), 0.01, 500),
+            Stop::ElephantValley => ((0.285, 0.012), 0.03, 1000),
+            Stop::Minibrot => ((-1.754_877_666_246_693, 0.0), 0.06, 400),
+            Stop::Feigenbaum => ((-1.401_155_189_092_051, 0.0), 0.003, 1000),
+            Stop::Spiral => ((-0.761_574, -0.084_759_6), 0.0002, 1500),
+        };
+        (View { center, scale }, max_iter)
+    }
+}
+
+/// Seconds from the start of the tour to the arrival at stop `i`.
+fn arrival(i: usize) -> f64 {
+    i as f64 * (HOLD_SECONDS + TRAVEL_SECONDS)
+}
+
+/// The keyframes of the tour: each stop held for a while, and halfway
+/// between two stops a view wide enough for both.
+fn keyframes() -> Vec<Keyframe> {
+    let keyframe = |time, view, max_iter| Keyframe {
+        time,
+        view,
+        max_iter,
+        formula: Formula::Mandelbrot,
+    };
+    let mut keyframes = Vec::new();
+    for (i, stop) in Stop::ALL.into_iter().enumerate() {
+        let (view, max_iter) = stop.location();
+        let (arrive, leave) = (arrival(i), arrival(i) + HOLD_SECONDS);
+        keyframes.push(keyframe(arrive, view, max_iter));
+        keyframes.push(keyframe(leave, view, max_iter));
+        if let Some(next) = Stop::ALL.get(i + 1) {
+            let (next_view, next_max_iter) = next.location();
+            let (a, b) = (view.center, next_view.center);
+            let distance = (a.0 - b.0).hypot(a.1 - b.1) * OVERVIEW_MARGIN;
+            let overview = View {
+                center: ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0),
+                scale: distance.max(view.scale).max(next_view.scale),
+            };
+            let max_iter = max_iter.min(next_max_iter);
+            keyframes.push(keyframe(leave + TRAVEL_SECONDS / 2.0, overview, max_iter));
+        }
+    }
+    keyframes
+}
+
+/// The View → Guided Tour window, which also shows the caption of the stop
+/// over the view while the tour plays.
+pub struct TourWindow {
+    pub open: bool,
+    keyframes: Vec<Keyframe>,
+    clock: Clock,
+}
+
+impl Default for TourWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            keyframes: keyframes(),
+            clock: Clock::default(),
+        }
+    }
+}
+
+impl TourWindow {
+    /// Shows the window and the caption, advancing the tour. Returns the
+    /// frame to show in the view while playing or going to a stop.
+    pub fn show(&mut self, ctx: &egui::Context) -> Option<Keyframe> {
+        let mut shown = None;
+        let end = duration(&self.keyframes);
+        if self.clock.is_playing() {
+            self.clock.keep_within(end, false);
+            shown = frame_at(&self.keyframes, self.clock.time());
+            ctx.request_repaint();
+        }
+        if !self.open {
+            // Closing the window ends the tour
+            self.clock.pause();
+            return shown;
+        }
+        let time = self.clock.time();
+        // The stop last arrived at, and whether the tour is still there
+        let current = (0..Stop::ALL.len())
+            .rev()
+            .find(|&i| arrival(i) <= time)
+            .unwrap_or(0);
+        let at_stop = time - arrival(current) <= HOLD_SECONDS;
+        if self.clock.is_playing() {
+            self.show_caption(ctx, current, at_stop);
+        }
+        let mut open = self.open;
+        egui::Window::new(tr!("tour-title"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let mut go_to = None;
+                    if ui
+                        .add_enabled(current > 0 || !at_stop, egui::Button::new("⏮"))
+                        .on_hover_text(tr!("tour-previous"))
+                        .clicked()
+                    {
+                        go_to = Some(if at_stop { current - 1 } else { current });
+                    }
+                    let (icon, hover) = if self.clock.is_playing() {
+                        ("⏸", tr!("playback-pause"))
+                    } else {
+                        ("▶", tr!("playback-play"))
+                    };
+                    if ui.button(icon).on_hover_text(hover).clicked() {
+                        self.clock.toggle(end);
+                    }
+                    if ui
+                        .add_enabled(current + 1 < Stop::ALL.len(), egui::Button::new("⏭"))
+                        .on_hover_text(tr!("tour-next"))
+                        .clicked()
+                    {
+                        go_to = Some(current + 1);
+                    }
+                    if let Some(i) = go_to {
+                        self.clock.seek(arrival(i));
+                        shown = frame_at(&self.keyframes, arrival(i));
+                    }
+                });
+                ui.separator();
+                for (i, stop) in Stop::ALL.into_iter().enumerate() {
+                    let here = i == current && at_stop;
+                    if ui.selectable_label(here, stop.name()).clicked() {
+                        self.clock.seek(arrival(i));
+                        shown = frame_at(&self.keyframes, arrival(i));
+                    }
+                    if here {
+                        ui.weak(stop.caption());
+                    }
+                }
+            });
+        self.open = open;
+        shown
+    }
+
+    /// The name and caption of the stop the tour is at, or the name of the
+    /// one it is on the way to, at the bottom of the screen.
+    fn show_caption(&self, ctx: &egui::Context, current: usize, at_stop: bool) {
+        egui::Area::new(egui::Id::new("tour_caption"))
+            .anchor(egui::Align2::CENTER_BOTTOM, [0.0, -48.0])
+            .interactable(false)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.set_max_width(480.0);
+                    if at_stop {
+                        let stop = Stop::ALL[current];
+                        ui.heading(stop.name());
+                        ui.label(stop.caption());
+                    } else {
+                        let next = Stop::ALL[current + 1].name();
+                        ui.label(tr!("tour-on-the-way", name = next));
+                    }
+                });
+            });
+    }
+}
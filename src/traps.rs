@@ -0,0 +1,383 @@
+//! The orbit trap editor: points, lines, and circles placed and dragged on
+//! the view, and lattices of points added from a preset, with the pixels
+//! outside the set tinted by how close their orbit passes to them (see
+//! [`mandelbrot_core::trap`]). Traps are kept in complex coordinates and
+//! saved in session files.
+
+use eframe::egui;
+use mandelbrot_core::{
+    View,
+    trap::{Trap, trap_color},
+};
+
+use crate::i18n::tr;
+
+/// Distance over which the tint of a trap fades, by default.
+pub const DEFAULT_WIDTH: f64 = 0.05;
+/// How close to a handle, in points, a drag has to start to move it.
+const HANDLE_RADIUS: f32 = 8.0;
+/// Smallest spacing of a lattice trap.
+const MIN_SPACING: f64 = 1e-9;
+/// Lattice points closer together than this on screen, in points, aren't
+/// drawn.
+const MIN_DRAWN_SPACING: f32 = 12.0;
+
+/// What clicking or dragging on the view places.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tool {
+    Point,
+    Line,
+    Circle,
+}
+
+impl Tool {
+    const ALL: [Tool; 3] = [Tool::Point, Tool::Line, Tool::Circle];
+
+    fn name(self) -> String {
+        match self {
+            Tool::Point => tr!("traps-tool-point"),
+            Tool::Line => tr!("traps-tool-line"),
+            Tool::Circle => tr!("traps-tool-circle"),
+        }
+    }
+
+    /// The trap placed by dragging from `start` to `end`.
+    fn trap(self, start: (f64, f64), end: (f64, f64)) -> Trap {
+        match self {
+            Tool::Point => Trap::Point { at: end },
+            Tool::Line => Trap::Line {
+                from: start,
+                to: end,
+            },
+            Tool::Circle => Trap::Circle {
+                center: start,
+                radius: (end.0 - start.0).hypot(end.1 - start.1),
+            },
+        }
+    }
+}
+
+/// A draggable point of a trap: its point, either end of its line, the
+/// center or a point on the outline of its circle, or the lattice point
+/// next to the origin on the real axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Handle {
+    Start,
+    End,
+}
+
+fn handles(trap: &Trap) -> Vec<(Handle, (f64, f64))> {
+    match *trap {
+        Trap::Point { at } => vec![(Handle::Start, at)],
+        Trap::Line { from, to } => vec![(Handle::Start, from), (Handle::End, to)],
+        Trap::Circle { center, radius } => vec![
+            (Handle::Start, center),
+            (Handle::End, (center.0 + radius, center.1)),
+        ],
+        Trap::Lattice { spacing } => vec![(Handle::End, (spacing, 0.0))],
+    }
+}
+
+fn move_handle(trap: &mut Trap, handle: Handle, to: (f64, f64)) {
+    match (trap, handle) {
+        (Trap::Point { at }, _) => *at = to,
+        (Trap::Line { from, .. }, Handle::Start) => *from = to,
+        (Trap::Line { to: end, .. }, Handle::End) => *end = to,
+        (Trap::Circle { center, .. }, Handle::Start) => *center = to,
+        (Trap::Circle { center, radius }, Handle::End) => {
+            *radius = (to.0 - center.0).hypot(to.1 - center.1);
+        }
+        (Trap::Lattice { spacing }, _) => *spacing = to.0.hypot(to.1).max(MIN_SPACING),
+    }
+}
+
+fn describe(trap: &Trap) -> String {
+    match *trap {
+        Trap::Point { at } => tr!("trap-point", at = format_point(at)),
+        Trap::Line { from, to } => tr!(
+            "trap-line",
+            from = format_point(from),
+            to = format_point(to)
+        ),
+        Trap::Circle { center, radius } => tr!(
+            "trap-circle",
+            center = format_point(center),
+            radius = format!("{radius:.6}")
+        ),
+        Trap::Lattice { spacing } => tr!("trap-lattice", spacing = format!("{spacing:.6}")),
+    }
+}
+
+fn format_point((re, im): (f64, f64)) -> String {
+    format!("{re:.6} {im:+.6}i")
+}
+
+/// The View → Orbit Traps window, which also places and moves traps on the
+/// view while open.
+pub struct TrapsWindow {
+    pub open: bool,
+    pub traps: Vec<Trap>,
+    /// Distance from a trap over which its tint fades out.
+    pub width: f64,
+    tool: Option<Tool>,
+    /// Where the trap being dragged out starts.
+    start: Option<(f64, f64)>,
+    /// The trap, by index, and handle being dragged.
+    dragging: Option<(usize, Handle)>,
+}
+
+impl Default for TrapsWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            traps: Vec::new(),
+            width: DEFAULT_WIDTH,
+            tool: None,
+            start: None,
+            dragging: None,
+        }
+    }
+}
+
+impl TrapsWindow {
+    pub fn show(&mut self, ctx: &egui::Context) {
+        let mut open = self.open;
+        egui::Window::new(tr!("traps-title"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr!("traps-place"));
+                    ui.selectable_value(&mut self.tool, None, tr!("traps-tool-none"));
+                    for tool in Tool::ALL {
+                        ui.selectable_value(&mut self.tool, Some(tool), tool.name());
+                    }
+                });
+                ui.weak(match self.tool {
+                    None => tr!("traps-move-hint"),
+                    Some(Tool::Point) => tr!("traps-point-hint"),
+                    Some(Tool::Line) => tr!("traps-line-hint"),
+                    Some(Tool::Circle) => tr!("traps-circle-hint"),
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr!("traps-presets"));
+                    if ui.button(tr!("traps-preset-unit-circle")).clicked() {
+                        self.traps.push(Trap::Circle {
+                            center: (0.0, 0.0),
+                            radius: 1.0,
+                        });
+                    }
+                    if ui.button(tr!("traps-preset-axes")).clicked() {
+                        self.traps.push(Trap::Line {
+                            from: (-2.0, 0.0),
+                            to: (2.0, 0.0),
+                        });
+                        self.traps.push(Trap::Line {
+                            from: (0.0, -2.0),
+                            to: (0.0, 2.0),
+                        });
+                    }
+                    if ui.button(tr!("traps-preset-origin")).clicked() {
+                        self.traps.push(Trap::Point { at: (0.0, 0.0) });
+                    }
+                    if ui
+                        .button(tr!("traps-preset-gaussian"))
+                        .on_hover_text(tr!("traps-preset-gaussian-hover"))
+                        .clicked()
+                    {
+                        self.traps.push(Trap::Lattice { spacing: 1.0 });
+                    }
+                });
+                ui.add(
+                    egui::Slider::new(&mut self.width, 0.001..=1.0)
+                        .logarithmic(true)
+                        .text(tr!("traps-width")),
+                )
+                .on_hover_text(tr!("traps-width-hover"));
+                ui.separator();
+                let mut remove = None;
+                egui::ScrollArea::vertical()
+                    .max_height(200.0)
+                    .show(ui, |ui| {
+                        for (i, trap) in self.traps.iter_mut().enumerate() {
+                            ui.horizontal(|ui| {
+                                let button =
+                                    ui.small_button("🗑").on_hover_text(tr!("traps-remove"));
+                                button.widget_info(|| {
+                                    egui::WidgetInfo::labeled(
+                                        egui::WidgetType::Button,
+                                        true,
+                                        tr!("traps-remove-one", trap = describe(trap)),
+                                    )
+                                });
+                                if button.clicked() {
+                                    remove = Some(i);
+                                }
+                                let [r, g, b, _] = trap_color(i);
+                                ui.colored_label(egui::Color32::from_rgb(r, g, b), describe(trap));
+                                if let Trap::Lattice { spacing } = trap {
+                                    ui.add(
+                                        egui::DragValue::new(spacing)
+                                            .range(MIN_SPACING..=100.0)
+                                            .speed(0.005)
+                                            .max_decimals(9),
+                                    )
+                                    .on_hover_text(tr!("traps-spacing-hover"));
+                                }
+                            });
+                        }
+                    });
+                if let Some(i) = remove {
+                    self.traps.remove(i);
+                    self.dragging = None;
+                }
+                if self.traps.is_empty() {
+                    ui.weak(tr!("traps-none"));
+                } else if ui.button(tr!("traps-remove-all")).clicked() {
+                    self.traps.clear();
+                    self.dragging = None;
+                }
+            });
+        self.open = open;
+    }
+
+    /// Places traps with the selected tool, or moves the handle a drag
+    /// starts on, as `response`, the view's, is clicked or dragged.
+    /// `to_complex` and `to_screen` map between screen positions and points.
+    /// Returns whether the pointer was used, so it doesn't also select a
+    /// path.
+    pub fn interact(
+        &mut self,
+        response: &egui::Response,
+        to_complex: impl Fn(egui::Pos2) -> (f64, f64),
+        to_screen: impl Fn((f64, f64)) -> egui::Pos2,
+    ) -> bool {
+        if !self.open {
+            return false;
+        }
+        // Drags end with the button released, when there's no interaction
+        let pos = (response.interact_pointer_pos())
+            .or_else(|| response.ctx.input(|i| i.pointer.latest_pos()));
+        let origin = response.ctx.input(|i| i.pointer.press_origin());
+        if let Some(tool) = self.tool {
+            let Some(pos) = pos else {
+                return true;
+            };
+            if tool == Tool::Point && response.clicked() {
+                self.traps.push(tool.trap(to_complex(pos), to_complex(pos)));
+            }
+            if tool != Tool::Point {
+                if response.drag_started() {
+                    self.start = Some(to_complex(origin.unwrap_or(pos)));
+                }
+                if response.drag_stopped()
+                    && let Some(start) = self.start.take()
+                {
+                    self.traps.push(tool.trap(start, to_complex(pos)));
+                }
+            }
+            return true;
+        }
+        if response.drag_started()
+            && let Some(origin) = origin
+        {
+            self.dragging = self.handle_at(origin, &to_screen);
+        }
+        let Some((index, handle)) = self.dragging else {
+            return false;
+        };
+        if let Some(pos) = pos {
+            move_handle(&mut self.traps[index], handle, to_complex(pos));
+        }
+        if response.drag_stopped() {
+            self.dragging = None;
+        }
+        true
+    }
+
+    /// The handle closest to screen position `pos`, if any is close enough
+    /// to grab.
+    fn handle_at(
+        &self,
+        pos: egui::Pos2,
+        to_screen: impl Fn((f64, f64)) -> egui::Pos2,
+    ) -> Option<(usize, Handle)> {
+        (self.traps.iter().enumerate())
+            .flat_map(|(i, trap)| {
+                handles(trap)
+                    .into_iter()
+                    .map(move |(handle, at)| ((i, handle), at))
+            })
+            .map(|(handle, at)| (handle, to_screen(at).distance(pos)))
+            .filter(|&(_, distance)| distance <= HANDLE_RADIUS)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(handle, _)| handle)
+    }
+
+    /// Draws the traps and their handles over `image_rect`, which shows
+    /// `view`, while the window is open. `pointer` is where the trap being
+    /// dragged out ends.
+    pub fn draw(
+        &self,
+        painter: &egui::Painter,
+        image_rect: egui::Rect,
+        view: &View,
+        pointer: Option<egui::Pos2>,
+    ) {
+        if !self.open {
+            return;
+        }
+        let side = image_rect.width() as usize;
+        let to_screen = |(re, im): (f64, f64)| {
+            let (x, y) = view.complex_to_pixel(re, im, side, side);
+            image_rect.min + egui::vec2(x as f32, y as f32)
+        };
+        let pixel = view.pixel_size(side);
+        let shadow = egui::Stroke::new(3.0, egui::Color32::from_black_alpha(160));
+        let mut traps: Vec<_> = self.traps.iter().copied().enumerate().collect();
+        if let (Some(tool), Some(start), Some(pointer)) = (self.tool, self.start, pointer) {
+            let end = view.pixel_to_complex(
+                f64::from(pointer.x - image_rect.left()),
+                f64::from(pointer.y - image_rect.top()),
+                side,
+                side,
+            );
+            traps.push((self.traps.len(), tool.trap(start, end)));
+        }
+        for (i, trap) in traps {
+            let [r, g, b, _] = trap_color(i);
+            let stroke = egui::Stroke::new(1.5, egui::Color32::from_rgb(r, g, b));
+            match trap {
+                Trap::Point { .. } => {}
+                Trap::Line { from, to } => {
+                    painter.line_segment([to_screen(from), to_screen(to)], shadow);
+                    painter.line_segment([to_screen(from), to_screen(to)], stroke);
+                }
+                Trap::Circle { center, radius } => {
+                    let radius = (radius / pixel) as f32;
+                    painter.circle_stroke(to_screen(center), radius, shadow);
+                    painter.circle_stroke(to_screen(center), radius, stroke);
+                }
+                Trap::Lattice { spacing } if (spacing / pixel) as f32 >= MIN_DRAWN_SPACING => {
+                    // The lattice points in the view
+                    let corner = |x, y| view.pixel_to_complex(x, y, side, side);
+                    let (min, max) = (corner(0.0, 0.0), corner(side as f64, side as f64));
+                    let range = |a: f64, b: f64| {
+                        (a.min(b) / spacing).ceil() as i64..=(a.max(b) / spacing).floor() as i64
+                    };
+                    for m in range(min.0, max.0) {
+                        for n in range(min.1, max.1) {
+                            let at = to_screen((m as f64 * spacing, n as f64 * spacing));
+                            painter.circle_filled(at, 2.0, stroke.color);
+                        }
+                    }
+                }
+                Trap::Lattice { .. } => {}
+            }
+            for (_, at) in handles(&trap) {
+                painter.circle_filled(to_screen(at), 4.0, stroke.color);
+                painter.circle_stroke(to_screen(at), 4.0, shadow);
+            }
+        }
+    }
+}
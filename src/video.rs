@@ -0,0 +1,222 @@
+//! Writing animations to video on a background thread: every frame is saved
+//! as a numbered PNG, `frame_00000.png` onwards, and the frames are joined
+//! into an MP4 with `ffmpeg` when it's installed. Without it the frames are
+//! left for another encoder.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    thread::{self, JoinHandle},
+    time::Instant,
+};
+
+use eframe::egui;
+use mandelbrot_core::Image;
+use tracing::{info, info_span, warn};
+
+use crate::{
+    PROGRESS_REPAINT_INTERVAL, display_name, export,
+    i18n::tr,
+    progress_text,
+    scheduler::{Priority, Scheduler, lower_thread_priority},
+};
+
+/// Name of the video written next to the frames.
+const VIDEO_NAME: &str = "video.mp4";
+
+/// Where an animation is exported to, and at what frame rate and size.
+pub struct ExportOptions {
+    pub out_dir: PathBuf,
+    pub fps: u32,
+    /// Width and height of the frames in pixels.
+    pub size: usize,
+}
+
+impl ExportOptions {
+    /// Options writing to the folder `out_dir`, at 30 frames per second and
+    /// 720 pixels square.
+    pub fn new(out_dir: &str) -> Self {
+        Self {
+            out_dir: PathBuf::from(out_dir),
+            fps: 30,
+            size: 720,
+        }
+    }
+
+    /// Shows the options for editing.
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(tr!("batch-output-folder"));
+            let mut dir = self.out_dir.display().to_string();
+            if ui.text_edit_singleline(&mut dir).changed() {
+                self.out_dir = dir.into();
+            }
+            if ui.button(tr!("button-browse")).clicked()
+                && let Some(dir) = rfd::FileDialog::new().pick_folder()
+            {
+                self.out_dir = dir;
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr!("batch-size"));
+            ui.add(
+                egui::DragValue::new(&mut self.size)
+                    .range(16..=4096)
+                    .suffix(" px"),
+            );
+            ui.label(tr!("video-fps"));
+            ui.add(egui::DragValue::new(&mut self.fps).range(1..=120));
+        });
+    }
+}
+
+/// How a finished video job went.
+enum Outcome {
+    /// The frames were joined into the video at this path.
+    Video(PathBuf),
+    /// `ffmpeg` isn't installed, so only the frames were written.
+    Frames,
+    /// Cancelled after this many frames.
+    Cancelled(usize),
+}
+
+/// A video being written on a background thread.
+pub struct VideoJob {
+    total: usize,
+    out_dir: PathBuf,
+    done: Arc<AtomicUsize>,
+    cancel: Arc<AtomicBool>,
+    handle: JoinHandle<io::Result<Outcome>>,
+    started: Instant,
+}
+
+impl VideoJob {
+    /// Starts writing `total` frames as `options` say, rendering the `i`th
+    /// with `frame(i)` as export work of the scheduler. With `low_priority`
+    /// the thread's priority is lowered first.
+    pub fn start(
+        ctx: &egui::Context,
+        scheduler: Arc<Scheduler>,
+        low_priority: bool,
+        options: &ExportOptions,
+        total: usize,
+        frame: impl Fn(usize) -> Image + Send + 'static,
+    ) -> Self {
+        let (out_dir, fps) = (options.out_dir.clone(), options.fps);
+        let done = Arc::new(AtomicUsize::new(0));
+        let cancel = Arc::new(AtomicBool::new(false));
+        let handle = {
+            let out_dir = out_dir.clone();
+            let done = done.clone();
+            let cancel = cancel.clone();
+            let ctx = ctx.clone();
+            thread::spawn(move || {
+                // Before any renderer starts its threads, so they inherit it
+                if low_priority && let Err(err) = lower_thread_priority() {
+                    warn!("could not lower the video export's priority: {err}");
+                }
+                let _span = info_span!("video", frames = total, fps).entered();
+                fs::create_dir_all(&out_dir)?;
+                for i in 0..total {
+                    if cancel.load(Ordering::Relaxed) {
+                        info!(written = i, "video cancelled");
+                        ctx.request_repaint();
+                        return Ok(Outcome::Cancelled(i));
+                    }
+                    let image = scheduler.run(Priority::Export, || frame(i));
+                    export::write_png(&frame_path(&out_dir, i), &image, None)?;
+                    done.store(i + 1, Ordering::Relaxed);
+                    ctx.request_repaint();
+                }
+                let outcome = encode(&out_dir, fps);
+                ctx.request_repaint();
+                outcome
+            })
+        };
+        Self {
+            total,
+            out_dir,
+            done,
+            cancel,
+            handle,
+            started: Instant::now(),
+        }
+    }
+
+    /// Shows the job's progress and a button to cancel it.
+    pub fn show_progress(&self, ui: &mut egui::Ui, scheduler: &Scheduler) {
+        let done = self.done.load(Ordering::Relaxed);
+        let fraction = done as f32 / self.total.max(1) as f32;
+        ui.add(egui::ProgressBar::new(fraction).text(tr!(
+            "video-progress",
+            done = done,
+            total = self.total,
+            progress = progress_text(fraction, self.started.elapsed()),
+        )));
+        ui.ctx().request_repaint_after(PROGRESS_REPAINT_INTERVAL);
+        if scheduler.is_paused(Priority::Export) {
+            ui.weak(tr!("video-paused"));
+        }
+        if ui.button(tr!("button-cancel")).clicked() {
+            self.cancel.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Takes the job out of `job` once it has finished, describing how it
+    /// went.
+    pub fn poll(job: &mut Option<VideoJob>) -> Option<String> {
+        if !job.as_ref().is_some_and(|job| job.handle.is_finished()) {
+            return None;
+        }
+        let job = job.take()?;
+        let folder = job.out_dir.display().to_string();
+        let outcome = job
+            .handle
+            .join()
+            .unwrap_or_else(|_| Err(io::Error::other("the export thread panicked")));
+        Some(match outcome {
+            Ok(Outcome::Video(path)) => tr!("video-done", name = display_name(&path)),
+            Ok(Outcome::Frames) => tr!("video-frames-only", total = job.total, folder = folder),
+            Ok(Outcome::Cancelled(done)) => tr!("video-cancelled", done = done, folder = folder),
+            Err(err) => tr!("video-failed", error = err.to_string()),
+        })
+    }
+}
+
+/// Path of the `i`th frame in `dir`.
+fn frame_path(dir: &Path, i: usize) -> PathBuf {
+    dir.join(format!("frame_{i:05}.png"))
+}
+
+/// Joins the frames in `dir` into an MP4 with `ffmpeg`, if it's installed.
+fn encode(dir: &Path, fps: u32) -> io::Result<Outcome> {
+    let video = dir.join(VIDEO_NAME);
+    let status = Command::new("ffmpeg")
+        .args(["-y", "-loglevel", "error", "-framerate"])
+        .arg(fps.to_string())
+        .arg("-i")
+        .arg(dir.join("frame_%05d.png"))
+        // Most players only take 4:2:0, which needs even sizes
+        .args([
+            "-vf",
+            "pad=ceil(iw/2)*2:ceil(ih/2)*2",
+            "-pix_fmt",
+            "yuv420p",
+        ])
+        .arg(&video)
+        .status();
+    match status {
+        Ok(status) if status.success() => {
+            info!("wrote {}", video.display());
+            Ok(Outcome::Video(video))
+        }
+        Ok(status) => Err(io::Error::other(format!("ffmpeg failed: {status}"))),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Outcome::Frames),
+        Err(err) => Err(err),
+    }
+}